@@ -9,12 +9,16 @@ use syn::parse::Parser;
 // syn::AttributeArgs does not implement syn::Parse
 type AttributeArgs = syn::punctuated::Punctuated<syn::Meta, syn::Token![,]>;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct FinalConfig {
     entries: Option<u32>,
     timer_enabled: Option<bool>,
     threads: Option<u32>,
     driver: DriverType,
+    // Tried in order if `driver` is not enabled in monoio or fails to build
+    fallbacks: Vec<DriverType>,
+    // Variants not generated by `test_all`
+    exclude: Vec<TestDriver>,
 }
 
 /// Config used in case of the attribute not being able to build a valid config
@@ -23,6 +27,8 @@ const DEFAULT_ERROR_CONFIG: FinalConfig = FinalConfig {
     timer_enabled: None,
     threads: None,
     driver: DriverType::Fusion,
+    fallbacks: Vec::new(),
+    exclude: Vec::new(),
 };
 
 struct Configuration {
@@ -30,6 +36,8 @@ struct Configuration {
     timer_enabled: Option<(bool, Span)>,
     threads: Option<(u32, Span)>,
     driver: Option<(DriverType, Span)>,
+    fallbacks: Vec<DriverType>,
+    exclude: Option<(Vec<TestDriver>, Span)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +47,60 @@ enum DriverType {
     Fusion,
 }
 
+/// The variants generated by `test_all`, one per driver and platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TestDriver {
+    Uring,
+    Legacy,
+    Iocp,
+}
+
+impl TestDriver {
+    const ALL: [TestDriver; 3] = [TestDriver::Uring, TestDriver::Legacy, TestDriver::Iocp];
+
+    fn name(self) -> &'static str {
+        match self {
+            TestDriver::Uring => "uring",
+            TestDriver::Legacy => "legacy",
+            TestDriver::Iocp => "iocp",
+        }
+    }
+
+    fn driver(self) -> DriverType {
+        match self {
+            TestDriver::Uring => DriverType::Uring,
+            TestDriver::Legacy | TestDriver::Iocp => DriverType::Legacy,
+        }
+    }
+
+    // Keeps the test only on the platform of the variant, and if monoio is
+    // built with its driver.
+    fn gate(self, test: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            TestDriver::Uring => quote! {
+                monoio::__if_uring_driver! {{ #test } else {}}
+            },
+            TestDriver::Legacy => quote! {
+                #[cfg(unix)]
+                monoio::__if_legacy_driver! {{ #test } else {}}
+            },
+            TestDriver::Iocp => quote! {
+                #[cfg(windows)]
+                monoio::__if_legacy_driver! {{ #test } else {}}
+            },
+        }
+    }
+}
+
+/// How the function is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Main,
+    Test,
+    // One of the variants of `test_all`, gated by the caller
+    TestVariant,
+}
+
 impl Configuration {
     fn new() -> Self {
         Configuration {
@@ -46,6 +108,8 @@ impl Configuration {
             timer_enabled: None,
             threads: None,
             driver: None,
+            fallbacks: Vec::new(),
+            exclude: None,
         }
     }
 
@@ -59,6 +123,67 @@ impl Configuration {
         Ok(())
     }
 
+    fn set_driver_list(&mut self, list: &syn::ExprArray, span: Span) -> Result<(), syn::Error> {
+        if self.driver.is_some() {
+            return Err(syn::Error::new(span, "`driver` set multiple times."));
+        }
+
+        let mut drivers = Vec::new();
+        for elem in &list.elems {
+            let lit = match elem {
+                syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit,
+                expr => return Err(syn::Error::new_spanned(expr, "Must be a literal")),
+            };
+            let lit_span = syn::spanned::Spanned::span(lit);
+            let driver = parse_driver(lit.clone(), lit_span, "driver")?;
+            if drivers.contains(&driver) {
+                return Err(syn::Error::new(lit_span, "driver listed multiple times."));
+            }
+            drivers.push(driver);
+        }
+        if drivers.is_empty() {
+            return Err(syn::Error::new(span, "`driver` may not be an empty list."));
+        }
+        self.driver = Some((drivers.remove(0), span));
+        self.fallbacks = drivers;
+        Ok(())
+    }
+
+    fn set_exclude(&mut self, value: &syn::Expr, span: Span) -> Result<(), syn::Error> {
+        if self.exclude.is_some() {
+            return Err(syn::Error::new(span, "`exclude` set multiple times."));
+        }
+
+        let elems: Vec<&syn::Expr> = match value {
+            syn::Expr::Array(list) => list.elems.iter().collect(),
+            expr => vec![expr],
+        };
+        let mut exclude = Vec::new();
+        for elem in elems {
+            let lit = match elem {
+                syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit,
+                expr => return Err(syn::Error::new_spanned(expr, "Must be a literal")),
+            };
+            let lit_span = syn::spanned::Spanned::span(lit);
+            let val = parse_string(lit.clone(), lit_span, "exclude")?;
+            let driver = match val.as_str() {
+                "uring" | "io_uring" | "iouring" => TestDriver::Uring,
+                "legacy" => TestDriver::Legacy,
+                "iocp" => TestDriver::Iocp,
+                _ => {
+                    return Err(syn::Error::new(
+                        lit_span,
+                        "Failed to parse value of `exclude`; expected one of: `uring`, \
+                         `legacy`, `iocp`.",
+                    ))
+                }
+            };
+            exclude.push(driver);
+        }
+        self.exclude = Some((exclude, span));
+        Ok(())
+    }
+
     fn set_threads(&mut self, threads: syn::Lit, span: Span) -> Result<(), syn::Error> {
         if self.threads.is_some() {
             return Err(syn::Error::new(span, "`threads` set multiple times."));
@@ -98,6 +223,12 @@ impl Configuration {
             timer_enabled: self.timer_enabled.map(|(t, _)| t),
             threads: self.threads.map(|(t, _)| t),
             driver: self.driver.map(|(d, _)| d).unwrap_or(DriverType::Fusion),
+            fallbacks: self.fallbacks.clone(),
+            exclude: self
+                .exclude
+                .as_ref()
+                .map(|(e, _)| e.clone())
+                .unwrap_or_default(),
         })
     }
 }
@@ -156,7 +287,11 @@ fn parse_bool(bool: syn::Lit, span: Span, field: &str) -> Result<bool, syn::Erro
     }
 }
 
-fn build_config(input: syn::ItemFn, args: AttributeArgs) -> Result<FinalConfig, syn::Error> {
+fn build_config(
+    input: syn::ItemFn,
+    args: AttributeArgs,
+    test_all: bool,
+) -> Result<FinalConfig, syn::Error> {
     if input.sig.asyncness.is_none() {
         let msg = "the `async` keyword is missing from the function declaration";
         return Err(syn::Error::new_spanned(input.sig.fn_token, msg));
@@ -175,6 +310,15 @@ fn build_config(input: syn::ItemFn, args: AttributeArgs) -> Result<FinalConfig,
                     })?
                     .to_string()
                     .to_lowercase();
+                if let ("driver", syn::Expr::Array(list)) = (ident.as_str(), &namevalue.value) {
+                    config.set_driver_list(list, syn::spanned::Spanned::span(list))?;
+                    continue;
+                }
+                if ident == "exclude" {
+                    config
+                        .set_exclude(&namevalue.value, syn::spanned::Spanned::span(&namevalue))?;
+                    continue;
+                }
                 let lit = match &namevalue.value {
                     syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit,
                     expr => return Err(syn::Error::new_spanned(expr, "Must be a literal")),
@@ -229,10 +373,23 @@ fn build_config(input: syn::ItemFn, args: AttributeArgs) -> Result<FinalConfig,
         }
     }
 
+    if let (false, Some((_, span))) = (test_all, &config.exclude) {
+        return Err(syn::Error::new(
+            *span,
+            "`exclude` is only supported by `test_all`.",
+        ));
+    }
+    if let (true, Some((_, span))) = (test_all, &config.driver) {
+        return Err(syn::Error::new(
+            *span,
+            "`test_all` runs the test on every driver; use `exclude` to skip some of them.",
+        ));
+    }
+
     config.build()
 }
 
-fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> TokenStream {
+fn parse_knobs(mut input: syn::ItemFn, entry: Entry, config: FinalConfig) -> TokenStream {
     input.sig.asyncness = None;
 
     // If type mismatch occurs, the current rustc points to the last statement.
@@ -253,24 +410,72 @@ fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> To
         (start, end)
     };
 
-    let mut rt = match config.driver {
-        DriverType::Legacy => {
-            quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()}
-        }
-        DriverType::Uring => {
-            quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()}
+    let builder = |driver: DriverType| {
+        let mut rt = match driver {
+            DriverType::Legacy => {
+                quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()}
+            }
+            DriverType::Uring => {
+                quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()}
+            }
+            DriverType::Fusion => {
+                quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::FusionDriver>::new()}
+            }
+        };
+
+        if let Some(entries) = config.entries {
+            rt = quote! { #rt.with_entries(#entries) }
         }
-        DriverType::Fusion => {
-            quote_spanned! {last_stmt_start_span=>monoio::RuntimeBuilder::<monoio::FusionDriver>::new()}
+        if Some(true) == config.timer_enabled {
+            rt = quote! { #rt.enable_timer() }
         }
+        rt
     };
 
-    if let Some(entries) = config.entries {
-        rt = quote! { #rt.with_entries(#entries) }
-    }
-    if Some(true) == config.timer_enabled {
-        rt = quote! { #rt.enable_timer() }
-    }
+    // Builds the runtime and blocks on `body` with it.
+    let run = |body: proc_macro2::TokenStream| {
+        if config.fallbacks.is_empty() {
+            let rt = builder(config.driver);
+            return quote! {
+                #rt.build()
+                    .expect("Failed building the Runtime")
+                    .block_on(#body)
+            };
+        }
+
+        // Try the drivers in order, skipping the ones monoio is built without
+        let attempts = std::iter::once(config.driver)
+            .chain(config.fallbacks.iter().copied())
+            .map(|driver| {
+                let rt = builder(driver);
+                let enabled = match driver {
+                    DriverType::Legacy => quote!(monoio::__if_legacy_driver),
+                    DriverType::Uring => quote!(monoio::__if_uring_driver),
+                    DriverType::Fusion => quote!(monoio::__if_fusion_driver),
+                };
+                quote! {
+                    #enabled! {{
+                        match #rt.build() {
+                            ::std::result::Result::Ok(mut rt) => break 'run rt.block_on(#body),
+                            ::std::result::Result::Err(e) => {
+                                error.get_or_insert(e);
+                            }
+                        }
+                    } else {}}
+                }
+            });
+        quote! {
+            'run: {
+                #[allow(unused_mut)]
+                let mut error: ::std::option::Option<::std::io::Error> = ::std::option::Option::None;
+                #(#attempts)*
+                match error {
+                    ::std::option::Option::Some(e) => panic!("Failed building the Runtime: {}", e),
+                    ::std::option::Option::None => panic!("None of the listed drivers is enabled in monoio"),
+                }
+            }
+        }
+    };
 
     let body = &input.block;
     let brace_token = input.block.brace_token;
@@ -288,14 +493,12 @@ fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> To
     };
 
     if matches!(config.threads, None | Some(1)) {
+        let run_body = run(quote!(body));
         input.block = syn::parse2(quote_spanned! {last_stmt_end_span=>
             {
                 let body = async #body;
                 #[allow(clippy::expect_used)]
-                #tail_return #rt
-                    .build()
-                    .expect("Failed building the Runtime")
-                    .block_on(body)#tail_semicolon
+                #tail_return #run_body #tail_semicolon
             }
         })
         .expect("Parsing failure");
@@ -312,6 +515,8 @@ fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> To
         } else {
             quote!(#threads)
         };
+        let run_worker = run(quote!(async #body));
+        let run_body = run(quote!(body));
         input.block = syn::parse2(quote_spanned! {last_stmt_end_span=>
             {
                 let body = async #body;
@@ -320,16 +525,12 @@ fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> To
                 let threads: Vec<_> = (1 .. #threads_expr)
                     .map(|_| {
                         ::std::thread::spawn(|| {
-                            #rt.build()
-                                .expect("Failed building the Runtime")
-                                .block_on(async #body);
+                            #run_worker;
                         })
                     })
                     .collect();
                 // Run on main threads
-                #rt.build()
-                    .expect("Failed building the Runtime")
-                    .block_on(body);
+                #run_body;
 
                 // Wait for other threads
                 threads.into_iter().for_each(|t| {
@@ -342,24 +543,23 @@ fn parse_knobs(mut input: syn::ItemFn, is_test: bool, config: FinalConfig) -> To
 
     input.block.brace_token = brace_token;
 
-    let header = if is_test {
+    let header = if entry != Entry::Main {
         quote! {
             #[::core::prelude::v1::test]
         }
     } else {
         quote! {}
     };
-    let cfg_attr = if is_test {
-        match config.driver {
-            DriverType::Legacy => quote! {
-                #[cfg(feature = "legacy")]
-            },
-            DriverType::Uring => quote! {
-                #[cfg(all(target_os = "linux", feature = "iouring"))]
-            },
-            DriverType::Fusion => quote! {
-                #[cfg(any(feature = "legacy", feature = "iouring"))]
-            },
+    let cfg_attr = if entry == Entry::Test {
+        let cfgs = std::iter::once(config.driver)
+            .chain(config.fallbacks.iter().copied())
+            .map(|driver| match driver {
+                DriverType::Legacy => quote!(feature = "legacy"),
+                DriverType::Uring => quote!(all(target_os = "linux", feature = "iouring")),
+                DriverType::Fusion => quote!(any(feature = "legacy", feature = "iouring")),
+            });
+        quote! {
+            #[cfg(any(#(#cfgs),*))]
         }
     } else {
         quote! {}
@@ -392,12 +592,12 @@ pub(crate) fn main(args: TokenStream, item: TokenStream) -> TokenStream {
     } else {
         AttributeArgs::parse_terminated
             .parse(args)
-            .and_then(|args| build_config(input.clone(), args))
+            .and_then(|args| build_config(input.clone(), args, false))
     };
 
     match config {
-        Ok(config) => parse_knobs(input, false, config),
-        Err(e) => token_stream_with_error(parse_knobs(input, false, DEFAULT_ERROR_CONFIG), e),
+        Ok(config) => parse_knobs(input, Entry::Main, config),
+        Err(e) => token_stream_with_error(parse_knobs(input, Entry::Main, DEFAULT_ERROR_CONFIG), e),
     }
 }
 
@@ -419,12 +619,12 @@ pub(crate) fn test(args: TokenStream, item: TokenStream) -> TokenStream {
     } else {
         AttributeArgs::parse_terminated
             .parse(args)
-            .and_then(|args| build_config(input.clone(), args))
+            .and_then(|args| build_config(input.clone(), args, false))
     };
 
     match config {
-        Ok(config) => parse_knobs(input, true, config),
-        Err(e) => token_stream_with_error(parse_knobs(input, true, DEFAULT_ERROR_CONFIG), e),
+        Ok(config) => parse_knobs(input, Entry::Test, config),
+        Err(e) => token_stream_with_error(parse_knobs(input, Entry::Test, DEFAULT_ERROR_CONFIG), e),
     }
 }
 
@@ -446,34 +646,32 @@ pub(crate) fn test_all(args: TokenStream, item: TokenStream) -> TokenStream {
     } else {
         AttributeArgs::parse_terminated
             .parse(args)
-            .and_then(|args| build_config(input.clone(), args))
+            .and_then(|args| build_config(input.clone(), args, true))
     };
-    let mut config = match config {
+    let config = match config {
         Ok(config) => config,
         Err(e) => {
-            return token_stream_with_error(parse_knobs(input, true, DEFAULT_ERROR_CONFIG), e)
+            return token_stream_with_error(
+                parse_knobs(input, Entry::Test, DEFAULT_ERROR_CONFIG),
+                e,
+            )
         }
     };
 
-    let mut output = TokenStream::new();
-
-    let mut input_uring = input.clone();
-    input_uring.sig.ident = proc_macro2::Ident::new(
-        &format!("uring_{}", input_uring.sig.ident),
-        input_uring.sig.ident.span(),
-    );
-    config.driver = DriverType::Uring;
-    let token_uring = parse_knobs(input_uring, true, config);
-    output.extend(token_uring);
-
-    let mut input_legacy = input;
-    input_legacy.sig.ident = proc_macro2::Ident::new(
-        &format!("legacy_{}", input_legacy.sig.ident),
-        input_legacy.sig.ident.span(),
-    );
-    config.driver = DriverType::Legacy;
-    let token_legacy = parse_knobs(input_legacy, true, config);
-    output.extend(token_legacy);
-
-    output
+    let mut output = proc_macro2::TokenStream::new();
+    for variant in TestDriver::ALL {
+        if config.exclude.contains(&variant) {
+            continue;
+        }
+        let mut input = input.clone();
+        input.sig.ident = proc_macro2::Ident::new(
+            &format!("{}_{}", variant.name(), input.sig.ident),
+            input.sig.ident.span(),
+        );
+        let mut config = config.clone();
+        config.driver = variant.driver();
+        let test = parse_knobs(input, Entry::TestVariant, config);
+        output.extend(variant.gate(test.into()));
+    }
+    output.into()
 }
@@ -21,6 +21,12 @@ pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
     entry::test(args, item)
 }
 
+/// Generates one test per driver monoio is built with: `uring_<name>` on
+/// Linux, `legacy_<name>` on other unix platforms and `iocp_<name>` on
+/// Windows, the legacy driver being based on IOCP there.
+///
+/// Some of them are skipped with `exclude`, e.g.
+/// `#[monoio::test_all(exclude = "iocp")]` or `exclude = ["uring", "iocp"]`.
 #[proc_macro_attribute]
 pub fn test_all(args: TokenStream, item: TokenStream) -> TokenStream {
     entry::test_all(args, item)
@@ -0,0 +1,136 @@
+use std::io;
+
+// Capacities step by 16 bytes up to 512 bytes, then double.
+const SMALL_STEP: usize = 16;
+const SMALL_LEN: usize = 31;
+const MAX_INDEX: usize = SMALL_LEN + (usize::BITS - 10) as usize;
+
+const INDEX_INCREMENT: usize = 4;
+const INDEX_DECREMENT: usize = 1;
+
+fn size_at(index: usize) -> usize {
+    if index < SMALL_LEN {
+        (index + 1) * SMALL_STEP
+    } else {
+        512 << (index - SMALL_LEN)
+    }
+}
+
+// The index of the smallest capacity not below `size`.
+fn index_of(size: usize) -> usize {
+    (0..MAX_INDEX)
+        .find(|&index| size_at(index) >= size)
+        .unwrap_or(MAX_INDEX)
+}
+
+/// Suggests the capacity of the next read buffer from the sizes of the last
+/// reads, like netty's `AdaptiveRecvByteBufAllocator`.
+///
+/// The capacity grows quickly when reads fill the buffer, and shrinks slowly
+/// when two reads in a row would have fit in a smaller one. Busy connections
+/// get large buffers and few syscalls, while idle ones hold small buffers.
+///
+/// Capacities are picked from a table stepping by 16 bytes up to 512 bytes
+/// and doubling above, within the bounds given at construction.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{buf::AdaptiveReadSize, io::AsyncReadRent, net::TcpStream};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut sizer = AdaptiveReadSize::new();
+///     loop {
+///         let buf = Vec::with_capacity(sizer.next_capacity());
+///         let (res, buf) = stream.read(buf).await;
+///         let n = res?;
+///         if n == 0 {
+///             return Ok(());
+///         }
+///         sizer.record(n);
+///         println!("read {:?}", &buf[..]);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveReadSize {
+    min_index: usize,
+    max_index: usize,
+    index: usize,
+    next: usize,
+    decrease_now: bool,
+}
+
+impl AdaptiveReadSize {
+    /// Default lower bound of the capacity.
+    pub const DEFAULT_MIN: usize = 64;
+    /// Default initial capacity.
+    pub const DEFAULT_INITIAL: usize = 2048;
+    /// Default upper bound of the capacity.
+    pub const DEFAULT_MAX: usize = 64 * 1024;
+
+    /// Create a sizer with the default bounds, starting at 2KiB and
+    /// adapting between 64B and 64KiB.
+    pub fn new() -> Self {
+        Self::with_bounds(Self::DEFAULT_MIN, Self::DEFAULT_INITIAL, Self::DEFAULT_MAX)
+            .expect("default bounds are valid")
+    }
+
+    /// Create a sizer starting at `initial` and adapting between `min` and
+    /// `max`. The bounds are rounded up to capacities of the table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`](io::ErrorKind::InvalidInput) error if
+    /// `min` is zero or `initial` is not between `min` and `max`.
+    pub fn with_bounds(min: usize, initial: usize, max: usize) -> io::Result<Self> {
+        if min == 0 || initial < min || max < initial {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid adaptive read size bounds",
+            ));
+        }
+        let index = index_of(initial);
+        Ok(Self {
+            min_index: index_of(min),
+            max_index: index_of(max),
+            index,
+            next: size_at(index),
+            decrease_now: false,
+        })
+    }
+
+    /// Returns the capacity to use for the next read.
+    #[inline]
+    pub fn next_capacity(&self) -> usize {
+        self.next
+    }
+
+    /// Records that a read returned `n` bytes, adapting the next capacity.
+    pub fn record(&mut self, n: usize) {
+        if n <= size_at(self.index.saturating_sub(INDEX_DECREMENT)) {
+            if self.decrease_now {
+                self.index = self
+                    .index
+                    .saturating_sub(INDEX_DECREMENT)
+                    .max(self.min_index);
+                self.next = size_at(self.index);
+                self.decrease_now = false;
+            } else {
+                self.decrease_now = true;
+            }
+        } else if n >= self.next {
+            self.index = (self.index + INDEX_INCREMENT).min(self.max_index);
+            self.next = size_at(self.index);
+            self.decrease_now = false;
+        }
+    }
+}
+
+impl Default for AdaptiveReadSize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
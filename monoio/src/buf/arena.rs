@@ -0,0 +1,381 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt, io,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    rc::Rc,
+};
+
+use super::{IoBuf, IoBufMut};
+use crate::driver::{Inner, CURRENT};
+
+#[cfg(target_os = "linux")]
+const HUGE_PAGE_SIZE: usize = 2 << 20;
+
+/// A fixed number of equally sized IO buffers carved out of one mapping.
+///
+/// The mapping is backed by huge pages if the system has some reserved
+/// (`MAP_HUGETLB`), which keeps the TLB footprint of large buffer pools
+/// small. Otherwise it falls back to regular pages, hinting the kernel to use
+/// transparent huge pages.
+///
+/// All buffers can be registered with the io_uring of the current runtime at
+/// once with [`register`](BufArena::register), each of them is then the
+/// fixed buffer at its [`buf_index`](ArenaBuf::buf_index).
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{buf::BufArena, io::AsyncReadRent, net::TcpStream};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let arena = BufArena::new(16 * 1024, 1024)?;
+///     arena.register()?;
+///
+///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let buf = arena.get().expect("arena exhausted");
+///     let (res, buf) = stream.read(buf).await;
+///     println!("read {} bytes: {:?}", res?, &buf[..]);
+///     // Dropping the buffer returns it to the arena.
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BufArena {
+    inner: Rc<ArenaInner>,
+}
+
+struct ArenaInner {
+    ptr: NonNull<u8>,
+    map_len: usize,
+    buf_size: usize,
+    count: usize,
+    huge_pages: bool,
+    free: RefCell<Vec<u16>>,
+    // The ring the buffers are registered with.
+    registered: RefCell<Option<Inner>>,
+    available: Cell<usize>,
+}
+
+impl BufArena {
+    /// Maps an arena of `count` buffers of `buf_size` bytes each.
+    ///
+    /// Inside a runtime built with
+    /// [`numa_node`](crate::RuntimeBuilder::numa_node), the arena is bound
+    /// to that node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`](io::ErrorKind::InvalidInput) error if
+    /// `buf_size` or `count` is zero, or `count` is larger than
+    /// `u16::MAX`, the number of fixed buffers a ring can address. Mapping
+    /// errors are returned as is.
+    pub fn new(buf_size: usize, count: usize) -> io::Result<BufArena> {
+        #[cfg(target_os = "linux")]
+        if let Some(node) =
+            crate::runtime::CURRENT.try_with(|ctx| ctx.and_then(|ctx| ctx.numa_node))
+        {
+            return Self::new_on_node(buf_size, count, node);
+        }
+        Self::map_arena(buf_size, count, None)
+    }
+
+    /// Maps an arena of `count` buffers of `buf_size` bytes each, preferring
+    /// to allocate its pages on the NUMA node `node`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`new`](BufArena::new), and the error of the
+    /// kernel if the node does not exist.
+    #[cfg(target_os = "linux")]
+    pub fn new_on_node(buf_size: usize, count: usize, node: usize) -> io::Result<BufArena> {
+        Self::map_arena(buf_size, count, Some(node))
+    }
+
+    pub(crate) fn map_arena(
+        buf_size: usize,
+        count: usize,
+        node: Option<usize>,
+    ) -> io::Result<BufArena> {
+        if buf_size == 0 || count == 0 || count > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid buffer arena size",
+            ));
+        }
+        let len = buf_size.checked_mul(count).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "buffer arena is too large")
+        })?;
+        let (ptr, map_len, huge_pages) = map(len)?;
+        let inner = ArenaInner {
+            ptr,
+            map_len,
+            buf_size,
+            count,
+            huge_pages,
+            // Hand out the lowest indexes first.
+            free: RefCell::new((0..count as u16).rev().collect()),
+            registered: RefCell::new(None),
+            available: Cell::new(count),
+        };
+        // Bind before anything faults the pages in, unmapping on error.
+        #[cfg(target_os = "linux")]
+        if let Some(node) = node {
+            crate::utils::numa::bind_to_node(ptr.as_ptr(), map_len, node)?;
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = node;
+
+        Ok(BufArena {
+            inner: Rc::new(inner),
+        })
+    }
+
+    /// Takes a free buffer out of the arena, or returns `None` if all of them
+    /// are in use. The buffer goes back to the arena when dropped.
+    pub fn get(&self) -> Option<ArenaBuf> {
+        let index = self.inner.free.borrow_mut().pop()?;
+        self.inner.available.set(self.inner.available.get() - 1);
+        Some(ArenaBuf {
+            arena: self.inner.clone(),
+            index,
+            len: 0,
+        })
+    }
+
+    /// Registers all the buffers with the io_uring of the current runtime as
+    /// its fixed buffers. They are unregistered when the arena is dropped.
+    ///
+    /// Registration pins the memory, which counts against `RLIMIT_MEMLOCK`
+    /// on older kernels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AlreadyExists`](io::ErrorKind::AlreadyExists) error if
+    /// the arena is registered already, an
+    /// [`Unsupported`](io::ErrorKind::Unsupported) error on the legacy
+    /// driver, and the error of the kernel if the ring already has fixed
+    /// buffers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn register(&self) -> io::Result<()> {
+        if self.inner.registered.borrow().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "buffer arena is already registered",
+            ));
+        }
+        let iovecs: Vec<libc::iovec> = (0..self.inner.count)
+            .map(|i| libc::iovec {
+                iov_base: self.inner.buf_ptr(i) as _,
+                iov_len: self.inner.buf_size,
+            })
+            .collect();
+        CURRENT.with(|driver| {
+            // Safety: the mapping lives until the buffers are unregistered, the
+            // arena keeps the ring alive until then.
+            unsafe { driver.register_buffers(&iovecs)? };
+            *self.inner.registered.borrow_mut() = Some(driver.clone());
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if the buffers are registered with a ring.
+    pub fn is_registered(&self) -> bool {
+        self.inner.registered.borrow().is_some()
+    }
+
+    /// Returns `true` if the arena is backed by huge pages reserved with
+    /// `MAP_HUGETLB`.
+    pub fn is_huge_pages(&self) -> bool {
+        self.inner.huge_pages
+    }
+
+    /// Returns the size of each buffer.
+    pub fn buf_size(&self) -> usize {
+        self.inner.buf_size
+    }
+
+    /// Returns the number of buffers of the arena.
+    pub fn count(&self) -> usize {
+        self.inner.count
+    }
+
+    /// Returns the number of buffers not in use.
+    pub fn available(&self) -> usize {
+        self.inner.available.get()
+    }
+}
+
+impl fmt::Debug for BufArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufArena")
+            .field("buf_size", &self.inner.buf_size)
+            .field("count", &self.inner.count)
+            .field("available", &self.inner.available.get())
+            .field("huge_pages", &self.inner.huge_pages)
+            .field("registered", &self.is_registered())
+            .finish()
+    }
+}
+
+impl ArenaInner {
+    #[inline]
+    fn buf_ptr(&self, index: usize) -> *mut u8 {
+        unsafe { self.ptr.as_ptr().add(index * self.buf_size) }
+    }
+}
+
+impl Drop for ArenaInner {
+    fn drop(&mut self) {
+        if let Some(driver) = self.registered.get_mut().take() {
+            let _ = driver.unregister_buffers();
+        }
+        unsafe { libc::munmap(self.ptr.as_ptr() as _, self.map_len) };
+    }
+}
+
+// Maps `len` bytes, with huge pages if possible.
+pub(super) fn map(len: usize) -> io::Result<(NonNull<u8>, usize, bool)> {
+    #[cfg(target_os = "linux")]
+    {
+        let huge_len = len.div_ceil(HUGE_PAGE_SIZE) * HUGE_PAGE_SIZE;
+        if let Ok(ptr) = mmap(huge_len, libc::MAP_HUGETLB) {
+            return Ok((ptr, huge_len, true));
+        }
+    }
+
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let len = len.div_ceil(page_size) * page_size;
+    let ptr = mmap(len, 0)?;
+    #[cfg(target_os = "linux")]
+    unsafe {
+        libc::madvise(ptr.as_ptr() as _, len, libc::MADV_HUGEPAGE)
+    };
+    Ok((ptr, len, false))
+}
+
+pub(super) fn mmap(len: usize, flags: libc::c_int) -> io::Result<NonNull<u8>> {
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | flags,
+            -1,
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { NonNull::new_unchecked(ptr as *mut u8) })
+}
+
+/// A buffer taken from a [`BufArena`].
+///
+/// Its capacity is the buffer size of the arena. It goes back to the arena
+/// when dropped.
+pub struct ArenaBuf {
+    arena: Rc<ArenaInner>,
+    index: u16,
+    len: usize,
+}
+
+impl ArenaBuf {
+    /// Returns the index of the buffer in the arena, which is its fixed
+    /// buffer index once the arena is registered.
+    pub fn buf_index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.arena.buf_size
+    }
+
+    /// Sets the initialized length of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than the capacity.
+    pub fn set_len(&mut self, len: usize) {
+        assert!(len <= self.capacity(), "length exceeds the buffer capacity");
+        self.len = len;
+    }
+
+    /// Returns `true` if the buffer is registered with a ring.
+    pub fn is_registered(&self) -> bool {
+        self.arena.registered.borrow().is_some()
+    }
+
+    #[inline]
+    fn ptr(&self) -> *mut u8 {
+        self.arena.buf_ptr(self.index as usize)
+    }
+}
+
+impl Drop for ArenaBuf {
+    fn drop(&mut self) {
+        self.arena.free.borrow_mut().push(self.index);
+        self.arena.available.set(self.arena.available.get() + 1);
+    }
+}
+
+impl Deref for ArenaBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr(), self.len) }
+    }
+}
+
+impl DerefMut for ArenaBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr(), self.len) }
+    }
+}
+
+impl fmt::Debug for ArenaBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaBuf")
+            .field("buf_index", &self.index)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+unsafe impl IoBuf for ArenaBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+}
+
+unsafe impl IoBufMut for ArenaBuf {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.ptr()
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, init_len: usize) {
+        self.len = init_len;
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fmt, io,
+    ops::{Deref, DerefMut},
+};
+
+use super::{ArenaBuf, IoBuf, IoBufMut};
+
+/// A buffer registered with the io_uring of the runtime.
+///
+/// IO on a fixed buffer through [`File::read_fixed_at`] or
+/// [`TcpStream::read_fixed`] and their write counterparts uses the
+/// `READ_FIXED` and `WRITE_FIXED` opcodes, which skip mapping the buffer
+/// into the kernel on every operation.
+///
+/// Buffers come from the ones registered when building the runtime with
+/// [`with_registered_buffers`](crate::RuntimeBuilder::with_registered_buffers),
+/// see [`get`](FixedBuf::get), or from a registered
+/// [`BufArena`](super::BufArena). A fixed buffer goes back to where it came
+/// from when dropped.
+///
+/// [`File::read_fixed_at`]: crate::fs::File::read_fixed_at
+/// [`TcpStream::read_fixed`]: crate::net::TcpStream::read_fixed
+///
+/// # Examples
+///
+#[cfg_attr(all(target_os = "linux", feature = "iouring"), doc = "```no_run")]
+#[cfg_attr(not(all(target_os = "linux", feature = "iouring")), doc = "```ignore")]
+/// use monoio::{buf::FixedBuf, fs::File};
+///
+/// fn main() -> std::io::Result<()> {
+///     monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+///         .with_registered_buffers(64, 4096)
+///         .build()?
+///         .block_on(async {
+///             let file = File::open("foo.txt").await?;
+///             let buf = FixedBuf::get().expect("no free fixed buffer");
+///             let (res, buf) = file.read_fixed_at(buf, 0).await;
+///             println!("read {} bytes: {:?}", res?, &buf[..]);
+///             Ok(())
+///         })
+/// }
+/// ```
+pub struct FixedBuf {
+    buf: ArenaBuf,
+}
+
+impl FixedBuf {
+    /// Takes a free buffer out of the registered buffers of the current
+    /// runtime.
+    ///
+    /// Returns `None` if all of them are in use, or the runtime has none:
+    /// it was not built with
+    /// [`with_registered_buffers`](crate::RuntimeBuilder::with_registered_buffers)
+    /// or does not run on io_uring.
+    pub fn get() -> Option<FixedBuf> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let buf = crate::runtime::CURRENT.try_with(|ctx| ctx?.fixed_bufs.as_ref()?.get());
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let buf = None;
+        buf.map(|buf| FixedBuf { buf })
+    }
+
+    /// Returns the fixed buffer index of the buffer.
+    pub fn buf_index(&self) -> u16 {
+        self.buf.buf_index()
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Sets the initialized length of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than the capacity.
+    pub fn set_len(&mut self, len: usize) {
+        self.buf.set_len(len);
+    }
+
+    /// Returns the underlying arena buffer.
+    pub fn into_inner(self) -> ArenaBuf {
+        self.buf
+    }
+}
+
+impl TryFrom<ArenaBuf> for FixedBuf {
+    type Error = io::Error;
+
+    /// Fails with an [`InvalidInput`](io::ErrorKind::InvalidInput) error if
+    /// the arena of the buffer is not registered.
+    fn try_from(buf: ArenaBuf) -> io::Result<Self> {
+        if !buf.is_registered() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buffer arena is not registered",
+            ));
+        }
+        Ok(FixedBuf { buf })
+    }
+}
+
+impl Deref for FixedBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl DerefMut for FixedBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl fmt::Debug for FixedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBuf")
+            .field("buf_index", &self.buf_index())
+            .field("len", &self.buf.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+unsafe impl IoBuf for FixedBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.buf.read_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.buf.bytes_init()
+    }
+}
+
+unsafe impl IoBufMut for FixedBuf {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.buf.write_ptr()
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.buf.bytes_total()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, init_len: usize) {
+        self.buf.set_init(init_len)
+    }
+}
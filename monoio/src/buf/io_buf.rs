@@ -1,4 +1,4 @@
-use std::{ops, rc::Rc, sync::Arc};
+use std::{borrow::Cow, mem::MaybeUninit, ops, rc::Rc, sync::Arc};
 
 use super::Slice;
 use crate::buf::slice::SliceMut;
@@ -154,6 +154,44 @@ unsafe impl IoBuf for &'static str {
     }
 }
 
+unsafe impl IoBuf for String {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+}
+
+// The owned variants keep their data on the heap, so moving the `Cow` does
+// not move the bytes.
+unsafe impl IoBuf for Cow<'static, [u8]> {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl IoBuf for Cow<'static, str> {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+}
+
 #[cfg(feature = "bytes")]
 unsafe impl IoBuf for bytes::Bytes {
     #[inline]
@@ -294,6 +332,57 @@ pub unsafe trait IoBufMut: Unpin + 'static {
         let (begin, end) = parse_range(range, self.bytes_total());
         SliceMut::new_unchecked(self, begin, end)
     }
+
+    /// Returns the uninitialized part of the buffer, from `bytes_init` to
+    /// `bytes_total`.
+    ///
+    /// Data can be written there without initializing the memory first, and
+    /// then exposed with [`assume_init`](IoBufMut::assume_init).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBufMut;
+    ///
+    /// let mut buf = Vec::with_capacity(8);
+    /// buf.extend_from_slice(b"ab");
+    /// let spare = buf.spare_capacity_mut();
+    /// spare[0].write(b'c');
+    /// unsafe { buf.assume_init(1) };
+    /// assert_eq!(buf, b"abc");
+    /// ```
+    #[inline]
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>]
+    where
+        Self: IoBuf,
+    {
+        let init = self.bytes_init();
+        let total = self.bytes_total();
+        // Safety: the buffer owns `bytes_total` bytes from `write_ptr`, and
+        // the returned slice borrows it mutably.
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.write_ptr().add(init) as *mut MaybeUninit<u8>,
+                total - init,
+            )
+        }
+    }
+
+    /// Marks `n` more bytes of the buffer, right after `bytes_init`, as
+    /// initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the first `n` bytes of
+    /// [`spare_capacity_mut`](IoBufMut::spare_capacity_mut) are initialized.
+    #[inline]
+    unsafe fn assume_init(&mut self, n: usize)
+    where
+        Self: IoBuf,
+    {
+        let init = self.bytes_init();
+        self.set_init(init + n);
+    }
 }
 
 unsafe impl IoBufMut for Vec<u8> {
@@ -395,6 +484,26 @@ where
     }
 }
 
+/// Parses `range` within `0..len`, returning `None` if it is out of bounds.
+pub(super) fn try_parse_range(
+    range: impl ops::RangeBounds<usize>,
+    len: usize,
+) -> Option<(usize, usize)> {
+    use core::ops::Bound;
+
+    let begin = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.checked_add(1)?,
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    (begin <= end && end <= len).then_some((begin, end))
+}
+
 fn parse_range(range: impl ops::RangeBounds<usize>, end: usize) -> (usize, usize) {
     use core::ops::Bound;
 
@@ -504,6 +613,52 @@ mod tests {
         assert_eq!(s.bytes_init(), 11);
     }
 
+    #[test]
+    fn io_buf_slice_advance() {
+        let mut slice = b"0123456789".to_vec().slice(2..8);
+        slice.advance(2);
+        assert_eq!(slice.as_slice(), b"4567");
+        assert_eq!(slice.remaining(), 4);
+
+        let slice = slice.try_slice(1..=2).unwrap();
+        assert_eq!(slice.as_slice(), b"56");
+        let slice = slice.try_slice(..=usize::MAX).unwrap_err();
+        let slice = slice.try_slice(3..).unwrap_err();
+        assert_eq!((slice.begin(), slice.end()), (5, 7));
+
+        let mut buf = Vec::with_capacity(8);
+        buf.extend_from_slice(b"01");
+        let mut slice = buf.slice_mut(..);
+        slice.advance(2);
+        // The slice may not begin past the initialized bytes.
+        let slice = slice.try_slice(1..).unwrap_err();
+        assert_eq!(slice.try_slice(..4).unwrap().bytes_total(), 4);
+    }
+
+    #[test]
+    fn io_buf_cow() {
+        let borrowed: Cow<'static, [u8]> = Cow::Borrowed(b"hello");
+        assert_eq!(borrowed.read_ptr(), b"hello".as_ptr());
+        assert_eq!(borrowed.bytes_init(), 5);
+
+        let owned: Cow<'static, [u8]> = Cow::Owned(b"hello world".to_vec());
+        let ptr = owned.as_ptr();
+        let owned = Box::new(owned);
+        assert_eq!(owned.read_ptr(), ptr);
+        assert_eq!(owned.bytes_init(), 11);
+
+        let s: Cow<'static, str> = Cow::Owned("hello".to_owned());
+        assert_eq!(s.as_slice(), b"hello");
+    }
+
+    #[test]
+    fn io_buf_static() {
+        const BUF: crate::buf::StaticBuf = crate::buf::StaticBuf::new_str("hello");
+        assert_eq!(BUF.read_ptr(), "hello".as_ptr());
+        assert_eq!(BUF.bytes_init(), 5);
+        assert_eq!(BUF.slice(1..).as_slice(), b"ello");
+    }
+
     #[test]
     fn io_buf_slice_ref() {
         let s: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -582,4 +737,24 @@ mod tests {
         assert_eq!(slice.bytes_init(), 5);
         assert_eq!(slice.into_inner().len(), 6);
     }
+
+    #[test]
+    fn io_buf_spare_capacity() {
+        let mut buf = Vec::with_capacity(10);
+        buf.extend_from_slice(b"0123");
+        let ptr = buf.as_ptr();
+
+        let spare = buf.spare_capacity_mut();
+        assert_eq!(spare.len(), 6);
+        assert_eq!(spare.as_ptr() as *const u8, unsafe { ptr.add(4) });
+        spare[0].write(b'4');
+        spare[1].write(b'5');
+        unsafe { buf.assume_init(2) };
+        assert_eq!(buf, b"012345");
+
+        let mut slice = buf.slice_mut(2..8);
+        assert_eq!(slice.spare_capacity_mut().len(), 2);
+        let mut boxed = vec![0u8; 4].into_boxed_slice();
+        assert!(boxed.spare_capacity_mut().is_empty());
+    }
 }
@@ -18,14 +18,65 @@ pub use slice::{IoVecWrapper, IoVecWrapperMut, Slice, SliceMut};
 mod raw_buf;
 pub use raw_buf::{RawBuf, RawBufVectored};
 
+mod static_buf;
+pub use static_buf::StaticBuf;
+
 mod vec_wrapper;
 pub(crate) use vec_wrapper::{read_vec_meta, write_vec_meta, IoVecMeta};
 
 mod msg;
 pub use msg::{MsgBuf, MsgBufMut, MsgMeta};
 
+mod adaptive;
+pub use adaptive::AdaptiveReadSize;
+
+#[cfg(unix)]
+mod arena;
+#[cfg(unix)]
+pub use arena::{ArenaBuf, BufArena};
+
+#[cfg(unix)]
+mod fixed;
+#[cfg(unix)]
+pub use fixed::FixedBuf;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod ring;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use ring::{BufRing, RingBuf};
+
 pub(crate) fn deref(buf: &impl IoBuf) -> &[u8] {
     // Safety: the `IoBuf` trait is marked as unsafe and is expected to be
     // implemented correctly.
     unsafe { std::slice::from_raw_parts(buf.read_ptr(), buf.bytes_init()) }
 }
+
+// A stream read into a buffer with no capacity completes at once with `Ok(0)`
+// without any IO, see `AsyncReadRent::read`. That is easily mistaken for EOF,
+// so it is logged with the `debug` feature and panics with the
+// `lint-zero-len-io` feature.
+#[inline]
+pub(crate) fn is_zero_len_read(buf: &mut impl IoBufMut) -> bool {
+    let zero = buf.bytes_total() == 0;
+    if zero {
+        lint_zero_len_io("read");
+    }
+    zero
+}
+
+// Zero-length stream writes are not skipped, but flagged the same way.
+#[inline]
+pub(crate) fn lint_zero_len_write(buf: &impl IoBuf) {
+    if buf.bytes_init() == 0 {
+        lint_zero_len_io("write");
+    }
+}
+
+#[cold]
+#[allow(unused_variables)]
+fn lint_zero_len_io(op: &str) {
+    #[cfg(feature = "lint-zero-len-io")]
+    panic!("{op} with a zero-length buffer");
+    #[cfg(not(feature = "lint-zero-len-io"))]
+    warn!("monoio: {} with a zero-length buffer", op);
+}
@@ -0,0 +1,276 @@
+use std::{
+    cell::Cell,
+    fmt, io,
+    ops::Deref,
+    ptr::NonNull,
+    rc::Rc,
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+use io_uring::types::BufRingEntry;
+
+use super::{
+    arena::{map, mmap},
+    IoBuf,
+};
+use crate::driver::{Inner, CURRENT};
+
+thread_local! {
+    static NEXT_BGID: Cell<u16> = const { Cell::new(0) };
+}
+
+/// A ring of equally sized buffers provided to the io_uring of the current
+/// runtime, which picks one of them for every receive of a
+/// [`recv_multishot`](crate::net::TcpStream::recv_multishot).
+///
+/// Received data is handed out as [`RingBuf`]s, each buffer goes back to
+/// the ring when its `RingBuf` is dropped. The kernel stops receiving once
+/// all of them are in use.
+///
+/// Provided buffer rings require kernel 5.19+.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{buf::BufRing, io::stream::Stream, net::TcpStream};
+///
+/// fn main() -> std::io::Result<()> {
+///     monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+///         .build()?
+///         .block_on(async {
+///             let ring = BufRing::new(64, 4096)?;
+///             let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+///             let mut received = stream.recv_multishot(&ring);
+///             while let Some(buf) = received.next().await {
+///                 println!("received {:?}", &buf?[..]);
+///             }
+///             Ok(())
+///         })
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BufRing {
+    inner: Rc<RingInner>,
+}
+
+struct RingInner {
+    ring: NonNull<BufRingEntry>,
+    ring_len: usize,
+    bufs: NonNull<u8>,
+    bufs_len: usize,
+    buf_size: usize,
+    entries: u16,
+    bgid: u16,
+    tail: Cell<u16>,
+    // Buffers given to the kernel.
+    available: Cell<u16>,
+    driver: Inner,
+}
+
+impl BufRing {
+    /// Maps a ring of `entries` buffers of `buf_size` bytes each and
+    /// registers it with the io_uring of the current runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`](io::ErrorKind::InvalidInput) error if
+    /// `buf_size` is zero or larger than `u32::MAX`, or `entries` is not a
+    /// power of two up to 32768. Returns an
+    /// [`Unsupported`](io::ErrorKind::Unsupported) error on the legacy
+    /// driver, and the error of the kernel if it does not support buffer
+    /// rings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn new(entries: u16, buf_size: usize) -> io::Result<BufRing> {
+        if !entries.is_power_of_two() || entries > 1 << 15 || buf_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid buffer ring size",
+            ));
+        }
+        if u32::try_from(buf_size).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buffer ring buffers are too large",
+            ));
+        }
+        let bufs_len = buf_size.checked_mul(entries as usize).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "buffer ring is too large")
+        })?;
+        let driver = CURRENT.with(|inner| inner.clone());
+
+        // The ring must be page aligned, which the mapping is.
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let ring_len = (entries as usize * std::mem::size_of::<BufRingEntry>()).div_ceil(page_size)
+            * page_size;
+        let ring = mmap(ring_len, 0)?.cast::<BufRingEntry>();
+        let (bufs, bufs_len, _) = match map(bufs_len) {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                unsafe { libc::munmap(ring.as_ptr() as _, ring_len) };
+                return Err(e);
+            }
+        };
+        let bgid = NEXT_BGID.with(|next| {
+            let bgid = next.get();
+            next.set(bgid.wrapping_add(1));
+            bgid
+        });
+        let inner = RingInner {
+            ring,
+            ring_len,
+            bufs,
+            bufs_len,
+            buf_size,
+            entries,
+            bgid,
+            tail: Cell::new(0),
+            available: Cell::new(0),
+            driver,
+        };
+        for bid in 0..entries {
+            inner.push(bid);
+        }
+        // Safety: the mappings live until the ring is unregistered, see the
+        // drop of `RingInner`. On error, unregistering its unused group id
+        // there fails harmlessly.
+        unsafe {
+            inner
+                .driver
+                .register_buf_ring(ring.as_ptr() as u64, entries, bgid)?
+        };
+        Ok(BufRing {
+            inner: Rc::new(inner),
+        })
+    }
+
+    /// Returns the size of each buffer.
+    pub fn buf_size(&self) -> usize {
+        self.inner.buf_size
+    }
+
+    /// Returns the number of buffers of the ring.
+    pub fn entries(&self) -> u16 {
+        self.inner.entries
+    }
+
+    /// Returns the number of buffers the kernel may receive into, i.e. not
+    /// held by a [`RingBuf`] or by a completion nobody took yet.
+    pub fn available(&self) -> u16 {
+        self.inner.available.get()
+    }
+
+    /// Returns the buffer group id the ring is registered as.
+    pub(crate) fn bgid(&self) -> u16 {
+        self.inner.bgid
+    }
+
+    /// Takes the buffer `bid` the kernel filled with `len` bytes.
+    pub(crate) fn take(&self, bid: u16, len: usize) -> RingBuf {
+        debug_assert!(bid < self.inner.entries && len <= self.inner.buf_size);
+        self.inner.available.set(self.inner.available.get() - 1);
+        RingBuf {
+            ring: self.inner.clone(),
+            bid,
+            len,
+        }
+    }
+}
+
+impl fmt::Debug for BufRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufRing")
+            .field("bgid", &self.inner.bgid)
+            .field("entries", &self.inner.entries)
+            .field("buf_size", &self.inner.buf_size)
+            .finish()
+    }
+}
+
+impl RingInner {
+    #[inline]
+    fn buf_ptr(&self, bid: u16) -> *mut u8 {
+        unsafe { self.bufs.as_ptr().add(bid as usize * self.buf_size) }
+    }
+
+    // Gives the buffer `bid` back to the kernel.
+    fn push(&self, bid: u16) {
+        let tail = self.tail.get();
+        let mask = self.entries - 1;
+        unsafe {
+            let entry = &mut *self.ring.as_ptr().add((tail & mask) as usize);
+            entry.set_addr(self.buf_ptr(bid) as u64);
+            entry.set_len(self.buf_size as u32);
+            entry.set_bid(bid);
+            // Publish the entry, the kernel reads the tail concurrently.
+            let tail_ptr = BufRingEntry::tail(self.ring.as_ptr()) as *const AtomicU16;
+            (*tail_ptr).store(tail.wrapping_add(1), Ordering::Release);
+        }
+        self.tail.set(tail.wrapping_add(1));
+        self.available.set(self.available.get() + 1);
+    }
+}
+
+impl Drop for RingInner {
+    fn drop(&mut self) {
+        let _ = self.driver.unregister_buf_ring(self.bgid);
+        unsafe {
+            libc::munmap(self.ring.as_ptr() as _, self.ring_len);
+            libc::munmap(self.bufs.as_ptr() as _, self.bufs_len);
+        }
+    }
+}
+
+/// A buffer of a [`BufRing`] holding received data.
+///
+/// It goes back to the ring when dropped.
+pub struct RingBuf {
+    ring: Rc<RingInner>,
+    bid: u16,
+    len: usize,
+}
+
+impl RingBuf {
+    /// Returns the id of the buffer in its ring.
+    pub fn bid(&self) -> u16 {
+        self.bid
+    }
+}
+
+impl Drop for RingBuf {
+    fn drop(&mut self) {
+        self.ring.push(self.bid);
+    }
+}
+
+impl Deref for RingBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ring.buf_ptr(self.bid), self.len) }
+    }
+}
+
+impl fmt::Debug for RingBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingBuf")
+            .field("bid", &self.bid)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+unsafe impl IoBuf for RingBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.ring.buf_ptr(self.bid)
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+}
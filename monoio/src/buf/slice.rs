@@ -24,6 +24,7 @@ use crate::buf::{IoBuf, IoBufMut};
 ///
 /// assert_eq!(&slice[..], b"hello");
 /// ```
+#[derive(Debug)]
 pub struct SliceMut<T> {
     buf: T,
     begin: usize,
@@ -39,6 +40,64 @@ impl<T: IoBuf + IoBufMut> SliceMut<T> {
         assert!(begin <= end);
         Self { buf, begin, end }
     }
+
+    /// Returns a view of `range` of this slice, relative to its beginning,
+    /// over the same buffer.
+    ///
+    /// Returns the slice back if the range is out of its bounds, or begins
+    /// past the initialized bytes of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBufMut;
+    ///
+    /// let buf = b"hello world".to_vec();
+    /// let slice = buf.slice_mut(6..);
+    ///
+    /// let slice = slice.try_slice(..3).unwrap();
+    /// assert_eq!((slice.begin(), slice.end()), (6, 9));
+    /// assert!(slice.try_slice(..4).is_err());
+    /// ```
+    pub fn try_slice(self, range: impl ops::RangeBounds<usize>) -> Result<Self, Self> {
+        match super::io_buf::try_parse_range(range, self.remaining()) {
+            Some((begin, end)) if self.begin + begin <= self.buf.bytes_init() => Ok(Self {
+                begin: self.begin + begin,
+                end: self.begin + end,
+                buf: self.buf,
+            }),
+            _ => Err(self),
+        }
+    }
+
+    /// Move the beginning of the slice forward by `n` bytes, e.g. past the
+    /// bytes read into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than the remaining bytes, or if the slice
+    /// would begin past the initialized bytes of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBufMut;
+    ///
+    /// let buf = b"hello world".to_vec();
+    /// let mut slice = buf.slice_mut(..);
+    ///
+    /// slice.advance(6);
+    /// assert_eq!(&slice[..], b"world");
+    /// ```
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        assert!(n <= self.remaining(), "advance past the end of the slice");
+        assert!(
+            self.begin + n <= self.buf.bytes_init(),
+            "advance past the initialized bytes"
+        );
+        self.begin += n;
+    }
 }
 
 impl<T> SliceMut<T> {
@@ -85,6 +144,23 @@ impl<T> SliceMut<T> {
         self.end
     }
 
+    /// Number of bytes of the slice, from its beginning to its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBufMut;
+    ///
+    /// let buf = Vec::with_capacity(16);
+    /// let slice = buf.slice_mut(..);
+    ///
+    /// assert_eq!(16, slice.remaining());
+    /// ```
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        self.end - self.begin
+    }
+
     /// Gets a reference to the underlying buffer.
     ///
     /// This method escapes the slice's view.
@@ -188,6 +264,7 @@ unsafe impl<T: IoBufMut> IoBufMut for SliceMut<T> {
 
 /// An owned view into a contiguous sequence of bytes.
 /// Slice implements IoBuf.
+#[derive(Debug)]
 pub struct Slice<T> {
     buf: T,
     begin: usize,
@@ -202,6 +279,33 @@ impl<T: IoBuf> Slice<T> {
         assert!(begin <= end);
         Self { buf, begin, end }
     }
+
+    /// Returns a view of `range` of this slice, relative to its beginning,
+    /// over the same buffer. Returns the slice back if the range is out of
+    /// its bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBuf;
+    ///
+    /// let buf = b"hello world".to_vec();
+    /// let slice = buf.slice(6..);
+    ///
+    /// let slice = slice.try_slice(1..3).unwrap();
+    /// assert_eq!((slice.begin(), slice.end()), (7, 9));
+    /// assert!(slice.try_slice(..3).is_err());
+    /// ```
+    pub fn try_slice(self, range: impl ops::RangeBounds<usize>) -> Result<Self, Self> {
+        match super::io_buf::try_parse_range(range, self.remaining()) {
+            Some((begin, end)) => Ok(Self {
+                begin: self.begin + begin,
+                end: self.begin + end,
+                buf: self.buf,
+            }),
+            None => Err(self),
+        }
+    }
 }
 
 impl<T> Slice<T> {
@@ -226,6 +330,37 @@ impl<T> Slice<T> {
         self.end
     }
 
+    /// Number of bytes of the slice, from its beginning to its end.
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        self.end - self.begin
+    }
+
+    /// Move the beginning of the slice forward by `n` bytes, e.g. past the
+    /// bytes written from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than the remaining bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::buf::IoBuf;
+    ///
+    /// let buf = b"hello world".to_vec();
+    /// let mut slice = buf.slice(..);
+    ///
+    /// slice.advance(6);
+    /// assert_eq!(slice.remaining(), 5);
+    /// assert_eq!(slice.into_inner(), b"hello world");
+    /// ```
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        assert!(n <= self.remaining(), "advance past the end of the slice");
+        self.begin += n;
+    }
+
     /// Gets a reference to the underlying buffer.
     #[inline]
     pub const fn get_ref(&self) -> &T {
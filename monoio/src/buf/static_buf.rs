@@ -0,0 +1,81 @@
+use std::ops::Deref;
+
+use super::IoBuf;
+
+/// A buffer of static data, e.g. a canned response.
+///
+/// It is `Copy` and may be built in a `const`, so the same bytes can be
+/// written to any number of streams with no allocation nor reference
+/// counting. `&'static [u8]` and `&'static str` are buffers as well, this
+/// gives them a name which may be used as a field or a type parameter.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{buf::StaticBuf, io::AsyncWriteRentExt, net::TcpStream};
+///
+/// const NOT_FOUND: StaticBuf =
+///     StaticBuf::new_str("HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n");
+///
+/// async fn not_found(stream: &mut TcpStream) -> std::io::Result<()> {
+///     stream.write_all(NOT_FOUND).await.0?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticBuf(&'static [u8]);
+
+impl StaticBuf {
+    /// Create a buffer of `bytes`.
+    #[inline]
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self(bytes)
+    }
+
+    /// Create a buffer of the bytes of `s`.
+    #[inline]
+    pub const fn new_str(s: &'static str) -> Self {
+        Self(s.as_bytes())
+    }
+
+    /// Returns the data of the buffer.
+    #[inline]
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+}
+
+impl Deref for StaticBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl From<&'static [u8]> for StaticBuf {
+    #[inline]
+    fn from(bytes: &'static [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&'static str> for StaticBuf {
+    #[inline]
+    fn from(s: &'static str) -> Self {
+        Self(s.as_bytes())
+    }
+}
+
+unsafe impl IoBuf for StaticBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.0.len()
+    }
+}
@@ -1,17 +1,92 @@
 #[cfg(windows)]
 use {std::ops::Add, windows_sys::Win32::Networking::WinSock::WSABUF};
 
+use std::{
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
 use super::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut};
+use crate::driver::op::OpBox;
 
 pub(crate) struct IoVecMeta {
     #[cfg(unix)]
-    data: Vec<libc::iovec>,
+    data: IoVecs<libc::iovec>,
     #[cfg(windows)]
-    data: Vec<WSABUF>,
+    data: IoVecs<WSABUF>,
     offset: usize,
     len: usize,
 }
 
+/// Number of entries [`IoVecs`] keeps in a recycled op slot.
+const SMALL_IOVECS: usize = 4;
+
+/// A vector of iovecs.
+///
+/// The meta is moved along with the ops it is passed to, so entries always
+/// live on the heap. Short vectors use a recycled [`OpBox`] slot instead of
+/// allocating.
+pub(crate) enum IoVecs<T: Copy> {
+    Small(OpBox<[MaybeUninit<T>; SMALL_IOVECS]>, usize),
+    Large(Vec<T>),
+}
+
+impl<T: Copy> IoVecs<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        if capacity > SMALL_IOVECS {
+            IoVecs::Large(Vec::with_capacity(capacity))
+        } else {
+            IoVecs::Small(OpBox::new([MaybeUninit::uninit(); SMALL_IOVECS]), 0)
+        }
+    }
+
+    fn one(item: T) -> Self {
+        let mut this = Self::with_capacity(1);
+        this.push(item);
+        this
+    }
+
+    fn push(&mut self, item: T) {
+        match self {
+            IoVecs::Small(data, len) if *len < SMALL_IOVECS => {
+                data[*len].write(item);
+                *len += 1;
+            }
+            IoVecs::Small(..) => {
+                let mut large = Vec::with_capacity(SMALL_IOVECS + 1);
+                large.extend_from_slice(self);
+                large.push(item);
+                *self = IoVecs::Large(large);
+            }
+            IoVecs::Large(data) => data.push(item),
+        }
+    }
+}
+
+impl<T: Copy> Deref for IoVecs<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            IoVecs::Small(data, len) => unsafe {
+                std::slice::from_raw_parts(data.as_ptr().cast(), *len)
+            },
+            IoVecs::Large(data) => data,
+        }
+    }
+}
+
+impl<T: Copy> DerefMut for IoVecs<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            IoVecs::Small(data, len) => unsafe {
+                std::slice::from_raw_parts_mut(data.as_mut_ptr().cast(), *len)
+            },
+            IoVecs::Large(data) => data,
+        }
+    }
+}
+
 /// Read IoVecBuf meta data into a Vec.
 pub(crate) fn read_vec_meta<T: IoVecBuf>(buf: &T) -> IoVecMeta {
     #[cfg(unix)]
@@ -19,7 +94,7 @@ pub(crate) fn read_vec_meta<T: IoVecBuf>(buf: &T) -> IoVecMeta {
         let ptr = buf.read_iovec_ptr();
         let iovec_len = buf.read_iovec_len();
 
-        let mut data = Vec::with_capacity(iovec_len);
+        let mut data = IoVecs::with_capacity(iovec_len);
         let mut len = 0;
         for i in 0..iovec_len {
             let iovec = unsafe { *ptr.add(i) };
@@ -37,7 +112,7 @@ pub(crate) fn read_vec_meta<T: IoVecBuf>(buf: &T) -> IoVecMeta {
         let ptr = buf.read_wsabuf_ptr();
         let wsabuf_len = buf.read_wsabuf_len();
 
-        let mut data = Vec::with_capacity(wsabuf_len);
+        let mut data = IoVecs::with_capacity(wsabuf_len);
         let mut len = 0;
         for i in 0..wsabuf_len {
             let wsabuf = unsafe { *ptr.add(i) };
@@ -60,7 +135,7 @@ pub(crate) fn write_vec_meta<T: IoVecBufMut>(buf: &mut T) -> IoVecMeta {
         let ptr = buf.write_iovec_ptr();
         let iovec_len = buf.write_iovec_len();
 
-        let mut data = Vec::with_capacity(iovec_len);
+        let mut data = IoVecs::with_capacity(iovec_len);
         let mut len = 0;
         for i in 0..iovec_len {
             let iovec = unsafe { *ptr.add(i) };
@@ -78,7 +153,7 @@ pub(crate) fn write_vec_meta<T: IoVecBufMut>(buf: &mut T) -> IoVecMeta {
         let ptr = buf.write_wsabuf_ptr();
         let wsabuf_len = buf.write_wsabuf_len();
 
-        let mut data = Vec::with_capacity(wsabuf_len);
+        let mut data = IoVecs::with_capacity(wsabuf_len);
         let mut len = 0;
         for i in 0..wsabuf_len {
             let wsabuf = unsafe { *ptr.add(i) };
@@ -218,7 +293,7 @@ impl<'t, T: IoBuf> From<&'t T> for IoVecMeta {
         #[cfg(windows)]
         let item = WSABUF { buf: ptr, len };
         Self {
-            data: vec![item],
+            data: IoVecs::one(item),
             offset: 0,
             len: 1,
         }
@@ -237,7 +312,7 @@ impl<'t, T: IoBufMut> From<&'t mut T> for IoVecMeta {
         #[cfg(windows)]
         let item = WSABUF { buf: ptr, len };
         Self {
-            data: vec![item],
+            data: IoVecs::one(item),
             offset: 0,
             len: 1,
         }
@@ -269,6 +344,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_iovecs_push_spills() {
+        let mut vecs = IoVecs::with_capacity(0);
+        for n in 0..6_usize {
+            vecs.push(n);
+        }
+        assert_eq!(&*vecs, &[0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_vec_meta_spills() {
+        let iovec = VecBuf::from((1..=6).map(|n| vec![0; n]).collect::<Vec<_>>());
+        let meta = read_vec_meta(&iovec);
+        assert_eq!(meta.len(), 21);
+        assert_eq!(meta.data.len(), 6);
+        #[cfg(unix)]
+        assert!(meta.data.iter().map(|i| i.iov_len).eq(1..=6));
+        #[cfg(windows)]
+        assert!(meta.data.iter().map(|i| i.len as usize).eq(1..=6));
+    }
+
     #[test]
     fn test_write_vec_meta() {
         let mut iovec = VecBuf::from(vec![vec![0; 10], vec![0; 20], vec![0; 30]]);
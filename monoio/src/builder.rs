@@ -1,9 +1,9 @@
 use std::{io, marker::PhantomData};
 
-#[cfg(all(target_os = "linux", feature = "iouring"))]
-use crate::driver::IoUringDriver;
 #[cfg(feature = "legacy")]
 use crate::driver::LegacyDriver;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::{IoUringDriver, SubmitPolicy};
 #[cfg(any(feature = "legacy", feature = "iouring"))]
 use crate::utils::thread_id::gen_id;
 use crate::{
@@ -22,6 +22,53 @@ pub struct RuntimeBuilder<D> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     urb: io_uring::Builder,
 
+    // iouring submit policy
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    submit_policy: SubmitPolicy,
+
+    // iouring max cqes processed per tick
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    max_cqes_per_tick: Option<usize>,
+
+    // secondary iouring for file io
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fs_ring: Option<(io_uring::Builder, u32)>,
+
+    // number and size of the fixed buffers to register
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    registered_buffers: Option<(usize, usize)>,
+
+    // readiness events returned per poll
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    events_capacity: Option<usize>,
+
+    // numa node to allocate memory on
+    #[cfg(target_os = "linux")]
+    numa_node: Option<usize>,
+
+    // order of the tasks woken by a park
+    wake_order: crate::WakeOrder,
+
+    // what happens when a task or a waker panics
+    panic_policy: crate::PanicPolicy,
+
+    // when block_on returns
+    exit_policy: crate::ExitPolicy,
+    exit_timeout: Option<std::time::Duration>,
+
+    // how long to poll for completions before sleeping
+    spin_before_park: Option<std::time::Duration>,
+
+    // called with each fd closed when dropped
+    #[cfg(unix)]
+    on_drop_close: Option<std::sync::Arc<dyn Fn(std::os::fd::RawFd) + Send + Sync>>,
+
+    // callbacks run around the tasks
+    task_hook: Option<std::sync::Arc<dyn crate::task::TaskHook>>,
+
+    // operations a task may complete per poll
+    task_budget: Option<u32>,
+
     // blocking handle
     #[cfg(feature = "sync")]
     blocking_handle: crate::blocking::BlockingHandle,
@@ -62,12 +109,66 @@ impl<T> RuntimeBuilder<T> {
 
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb: io_uring::IoUring::builder(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy: SubmitPolicy::default(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            max_cqes_per_tick: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fs_ring: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            registered_buffers: None,
+            #[cfg(any(feature = "legacy", feature = "poll-io"))]
+            events_capacity: None,
+            #[cfg(target_os = "linux")]
+            numa_node: None,
+            wake_order: crate::WakeOrder::Completion,
+            panic_policy: crate::PanicPolicy::Unwind,
+            exit_policy: crate::ExitPolicy::MainFuture,
+            exit_timeout: None,
+            spin_before_park: None,
+            #[cfg(unix)]
+            on_drop_close: None,
+            task_hook: None,
+            task_budget: Some(crate::task::coop::DEFAULT_BUDGET),
 
             #[cfg(feature = "sync")]
             blocking_handle: crate::blocking::BlockingStrategy::ExecuteLocal.into(),
             _mark: PhantomData,
         }
     }
+
+    /// Change the driver mark, keeping all the settings.
+    fn cast<D>(self) -> RuntimeBuilder<D> {
+        RuntimeBuilder {
+            entries: self.entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            urb: self.urb,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy: self.submit_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            max_cqes_per_tick: self.max_cqes_per_tick,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fs_ring: self.fs_ring,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            registered_buffers: self.registered_buffers,
+            #[cfg(any(feature = "legacy", feature = "poll-io"))]
+            events_capacity: self.events_capacity,
+            #[cfg(target_os = "linux")]
+            numa_node: self.numa_node,
+            wake_order: self.wake_order,
+            panic_policy: self.panic_policy,
+            exit_policy: self.exit_policy,
+            exit_timeout: self.exit_timeout,
+            spin_before_park: self.spin_before_park,
+            #[cfg(unix)]
+            on_drop_close: self.on_drop_close,
+            task_hook: self.task_hook,
+            task_budget: self.task_budget,
+            #[cfg(feature = "sync")]
+            blocking_handle: self.blocking_handle,
+            _mark: PhantomData,
+        }
+    }
 }
 
 // ===== buildable trait and forward methods =====
@@ -109,14 +210,33 @@ impl Buildable for LegacyDriver {
         let blocking_handle = this.blocking_handle;
 
         BUILD_THREAD_ID.set(&thread_id, || {
+            #[cfg(target_os = "linux")]
+            if let Some(node) = this.numa_node {
+                crate::utils::numa::set_preferred_node(node)?;
+            }
             let driver = match this.entries {
                 Some(entries) => LegacyDriver::new_with_entries(entries)?,
                 None => LegacyDriver::new()?,
-            };
+            }
+            .with_events_capacity(this.events_capacity);
             #[cfg(feature = "sync")]
             let context = crate::runtime::Context::new(blocking_handle);
             #[cfg(not(feature = "sync"))]
             let context = crate::runtime::Context::new();
+            let context = crate::runtime::Context {
+                #[cfg(target_os = "linux")]
+                numa_node: this.numa_node,
+                wake_order: this.wake_order,
+                panic_policy: this.panic_policy,
+                exit_policy: this.exit_policy,
+                exit_timeout: this.exit_timeout,
+                spin_before_park: this.spin_before_park,
+                #[cfg(unix)]
+                on_drop_close: this.on_drop_close,
+                task_hooks: this.task_hook.map(crate::task::TaskHooks::new),
+                budget: crate::task::coop::Budget::new(this.task_budget),
+                ..context
+            };
             Ok(Runtime::new(context, driver))
         })
     }
@@ -130,14 +250,45 @@ impl Buildable for IoUringDriver {
         let blocking_handle = this.blocking_handle;
 
         BUILD_THREAD_ID.set(&thread_id, || {
+            if let Some(node) = this.numa_node {
+                crate::utils::numa::set_preferred_node(node)?;
+            }
             let driver = match this.entries {
                 Some(entries) => IoUringDriver::new_with_entries(&this.urb, entries)?,
                 None => IoUringDriver::new(&this.urb)?,
+            }
+            .with_submit_policy(this.submit_policy)
+            .with_max_cqes_per_tick(this.max_cqes_per_tick)
+            .with_fs_ring(this.fs_ring.as_ref().map(|(urb, entries)| (urb, *entries)))?;
+            #[cfg(feature = "poll-io")]
+            let driver = driver.with_events_capacity(this.events_capacity);
+            let fixed_bufs = match this.registered_buffers {
+                Some((count, size)) => {
+                    let arena = crate::buf::BufArena::map_arena(size, count, this.numa_node)?;
+                    driver.with(|| arena.register())?;
+                    Some(arena)
+                }
+                None => None,
             };
             #[cfg(feature = "sync")]
             let context = crate::runtime::Context::new(blocking_handle);
             #[cfg(not(feature = "sync"))]
             let context = crate::runtime::Context::new();
+            let context = crate::runtime::Context {
+                #[cfg(target_os = "linux")]
+                numa_node: this.numa_node,
+                wake_order: this.wake_order,
+                panic_policy: this.panic_policy,
+                exit_policy: this.exit_policy,
+                exit_timeout: this.exit_timeout,
+                spin_before_park: this.spin_before_park,
+                #[cfg(unix)]
+                on_drop_close: this.on_drop_close,
+                task_hooks: this.task_hook.map(crate::task::TaskHooks::new),
+                budget: crate::task::coop::Budget::new(this.task_budget),
+                fixed_bufs,
+                ..context
+            };
             Ok(Runtime::new(context, driver))
         })
     }
@@ -168,6 +319,389 @@ impl<D> RuntimeBuilder<D> {
         self.urb = urb;
         self
     }
+
+    /// Create the io_uring with `IORING_SETUP_IOPOLL`, so that completions
+    /// of O_DIRECT file IO are polled from the device instead of taking an
+    /// interrupt.
+    ///
+    /// Only file reads and writes can be issued on such a ring, other
+    /// operations fail with [`Unsupported`](io::ErrorKind::Unsupported),
+    /// and files are closed with a blocking syscall. The driver never sleeps
+    /// when parked, it busy-polls the ring instead. See the storage runtime
+    /// guide in the docs for a full setup.
+    ///
+    /// This sets the flag on the current [`io_uring::Builder`], so call it
+    /// after [`uring_builder`](Self::uring_builder).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_iopoll(mut self) -> Self {
+        self.urb.setup_iopoll();
+        self
+    }
+
+    /// Create the io_uring with `IORING_SETUP_SQPOLL`: a kernel thread polls
+    /// the submission queue, so that submitting operations does not take a
+    /// syscall while it is awake. It goes to sleep after `idle` without
+    /// submissions, and is woken by the next one with a syscall.
+    ///
+    /// The thread keeps a CPU busy while awake, see
+    /// [`with_sqpoll_cpu`](Self::with_sqpoll_cpu) to pin it.
+    ///
+    /// This sets the flag on the current [`io_uring::Builder`], so call it
+    /// after [`uring_builder`](Self::uring_builder).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_sqpoll(mut self, idle: std::time::Duration) -> Self {
+        self.urb
+            .setup_sqpoll(idle.as_millis().try_into().unwrap_or(u32::MAX));
+        self
+    }
+
+    /// Pin the submission queue polling thread of
+    /// [`with_sqpoll`](Self::with_sqpoll) to `cpu`. Building the runtime fails
+    /// if SQPOLL is not set.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_sqpoll_cpu(mut self, cpu: u32) -> Self {
+        self.urb.setup_sqpoll_cpu(cpu);
+        self
+    }
+
+    /// Create the io_uring with `IORING_SETUP_COOP_TASKRUN`: the kernel does
+    /// not interrupt the thread to post completions, it posts them on the
+    /// next transition to the kernel instead. As the runtime reaps
+    /// completions when it parks, this saves interrupts without delaying
+    /// them. Requires Linux 5.19.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_coop_taskrun(mut self) -> Self {
+        self.urb.setup_coop_taskrun();
+        self
+    }
+
+    /// Create the io_uring with `IORING_SETUP_SINGLE_ISSUER`, a hint that
+    /// only the thread running the runtime submits to it, which it always
+    /// is. Requires Linux 6.0.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_single_issuer(mut self) -> Self {
+        self.urb.setup_single_issuer();
+        self
+    }
+
+    /// Create the io_uring with `IORING_SETUP_DEFER_TASKRUN`: completions are
+    /// only processed when the runtime parks, in one batch, instead of as
+    /// they happen. This also sets
+    /// [`with_single_issuer`](Self::with_single_issuer), which it requires.
+    /// Requires Linux 6.1.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_defer_taskrun(mut self) -> Self {
+        self.urb.setup_single_issuer().setup_defer_taskrun();
+        self
+    }
+
+    /// Create a secondary io_uring from `urb` with `entries`, and submit file
+    /// IO to it, while network IO stays on the main ring.
+    ///
+    /// Slow disk operations then do not hold up the network ones queued
+    /// behind them, and the file ring can use its own setup flags, e.g.
+    /// [`setup_iopoll`](io_uring::Builder::setup_iopoll) for O_DIRECT files
+    /// or [`setup_sqpoll`](io_uring::Builder::setup_sqpoll). The main ring
+    /// reaps it when parking, and busy-polls an IOPOLL file ring while it has
+    /// IO in flight.
+    ///
+    /// All [`fs`](crate::fs) operations are routed to the file ring, closing
+    /// files goes through the main ring. An IOPOLL file ring only takes the
+    /// reads and writes, the other operations, e.g. opening files, stay on
+    /// the main ring.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_fs_ring(mut self, urb: io_uring::Builder, entries: u32) -> Self {
+        self.fs_ring = Some((urb, entries));
+        self
+    }
+
+    /// Submit queued io_uring operations once at least `threshold` of them
+    /// are queued, instead of waiting for the submission queue to be full or
+    /// the driver to park.
+    ///
+    /// A lower threshold reduces op latency under moderate load at the cost of
+    /// more syscalls.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_submit_threshold(mut self, threshold: u32) -> Self {
+        self.submit_policy.threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Submit queued io_uring operations once the oldest of them has been
+    /// queued for `interval`, instead of waiting for the submission queue to be
+    /// full or the driver to park.
+    ///
+    /// The interval is checked when new operations are queued, so it bounds
+    /// the latency only while operations keep being issued.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_submit_interval(mut self, interval: std::time::Duration) -> Self {
+        self.submit_policy.interval = Some(interval);
+        self
+    }
+
+    /// Process at most `max` io_uring completions per driver tick before
+    /// going back to execute tasks. The remaining completions are processed
+    /// in the next tick.
+    ///
+    /// This prevents completion storms from starving task progress. See
+    /// [`completion_stats`](crate::utils::completion_stats) for the observed
+    /// completions per tick.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_max_cqes_per_tick(mut self, max: usize) -> Self {
+        self.max_cqes_per_tick = Some(max.max(1));
+        self
+    }
+
+    /// Set the number of readiness events received per poll by the legacy
+    /// driver, and by the poller of `poll-io` on io_uring. Defaults to the
+    /// number of entries.
+    ///
+    /// The events buffer is allocated once and reused by every poll; if more
+    /// fds are ready, the rest is received by the next poll. A larger buffer
+    /// takes fewer syscalls to drain many ready connections.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[must_use]
+    pub fn with_events_capacity(mut self, capacity: usize) -> Self {
+        self.events_capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Allocate the memory of the runtime on the NUMA node `node`, to keep it
+    /// local to the CPUs the runtime thread is bound to (see
+    /// [`bind_to_cpu_set`](crate::utils::bind_to_cpu_set)) on multi-socket
+    /// machines.
+    ///
+    /// Building the runtime sets the memory policy of the current thread to
+    /// prefer `node`, so the rings, slabs and tasks of the driver and every
+    /// later allocation of the thread come from it while it has free
+    /// memory. The policy outlives the runtime. A
+    /// [`BufArena`](crate::buf::BufArena) created inside the runtime is
+    /// bound to the node as well.
+    ///
+    /// Building fails if the node does not exist or the kernel has no NUMA
+    /// support.
+    #[cfg(target_os = "linux")]
+    #[must_use]
+    pub fn numa_node(mut self, node: usize) -> Self {
+        self.numa_node = Some(node);
+        self
+    }
+
+    /// Register `count` buffers of `size` bytes each with the io_uring of the
+    /// runtime, to be taken with [`FixedBuf::get`](crate::buf::FixedBuf::get).
+    ///
+    /// The buffers are mapped from one [`BufArena`](crate::buf::BufArena)
+    /// when building the runtime, and registration pins their memory, which
+    /// counts against `RLIMIT_MEMLOCK` on older kernels. Building fails if
+    /// `count` is zero or larger than `u16::MAX`, or registration fails.
+    ///
+    /// The legacy driver has no fixed buffers, so this is ignored when a
+    /// [`FusionDriver`](crate::FusionDriver) falls back to it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_registered_buffers(mut self, count: usize, size: usize) -> Self {
+        self.registered_buffers = Some((count, size));
+        self
+    }
+
+    /// Set the order in which the tasks woken by one driver tick are run,
+    /// defaults to [`WakeOrder::Completion`](crate::WakeOrder::Completion).
+    ///
+    /// Shuffling or rotating the woken tasks avoids favoring the same
+    /// connections in every tick when many of them complete at once, at the
+    /// cost of reordering the task queue after each tick.
+    #[must_use]
+    pub fn with_wake_order(mut self, order: crate::WakeOrder) -> Self {
+        self.wake_order = order;
+        self
+    }
+
+    /// Set what happens when a spawned task, or a waker invoked by the
+    /// driver, panics, defaults to
+    /// [`PanicPolicy::Unwind`](crate::PanicPolicy::Unwind).
+    ///
+    /// With [`PanicPolicy::Isolate`](crate::PanicPolicy::Isolate), one
+    /// failing connection does not bring down the others served by the
+    /// runtime. Catching the panics costs little, but the state the panicking
+    /// task shared with others may be left inconsistent.
+    #[must_use]
+    pub fn with_panic_policy(mut self, policy: crate::PanicPolicy) -> Self {
+        self.panic_policy = policy;
+        self
+    }
+
+    /// Set when `block_on` returns, defaults to
+    /// [`ExitPolicy::MainFuture`](crate::ExitPolicy::MainFuture).
+    ///
+    /// With [`ExitPolicy::AllTasks`](crate::ExitPolicy::AllTasks), background
+    /// tasks spawned by the future, e.g. by a worker of a pool, run to
+    /// completion before the runtime is dropped. To also return when the
+    /// future can make no progress, see
+    /// [`Runtime::block_on_until_idle`](crate::Runtime::block_on_until_idle).
+    #[must_use]
+    pub fn with_exit_policy(mut self, policy: crate::ExitPolicy) -> Self {
+        self.exit_policy = policy;
+        self
+    }
+
+    /// Wait at most `timeout` for the tasks of the
+    /// [exit policy](RuntimeBuilder::with_exit_policy) and for the
+    /// [`KeepAlive`](crate::KeepAlive) guards once the future of `block_on`
+    /// completes, by default it waits for as long as they run. The tasks
+    /// left are run by the next calls to `block_on` or `turn`, or dropped
+    /// with the runtime.
+    #[must_use]
+    pub fn with_exit_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.exit_timeout = Some(timeout);
+        self
+    }
+
+    /// Poll the driver for completions for up to `spin` before putting the
+    /// thread to sleep when no task is ready to run.
+    ///
+    /// Io completing within the spin is handled without the wake up latency
+    /// of a blocking park, at the cost of keeping a CPU busy while the
+    /// runtime is idle.
+    #[must_use]
+    pub fn with_spin_before_park(mut self, spin: std::time::Duration) -> Self {
+        self.spin_before_park = (!spin.is_zero()).then_some(spin);
+        self
+    }
+
+    /// Warn about every fd closed when it is dropped rather than with the
+    /// `close` method of its type, e.g. [`TcpStream::close`](crate::net::TcpStream::close).
+    ///
+    /// The io_uring driver closes a dropped fd in the background, and an
+    /// error or a delay closing it goes unnoticed. The warnings are emitted
+    /// with `tracing` at the `monoio::fd` target, so they need the `tracing`
+    /// feature, see [`on_drop_close`](Self::on_drop_close) otherwise. These
+    /// fds are counted in any case, see
+    /// [`closed_on_drop`](crate::utils::closed_on_drop).
+    #[must_use]
+    pub fn warn_on_drop_close(self) -> Self {
+        #[cfg(unix)]
+        return self.on_drop_close(|_fd| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(target: "monoio::fd", fd = _fd, "fd closed on drop, without close()");
+        });
+        #[cfg(not(unix))]
+        self
+    }
+
+    /// Call `f` with every fd closed when it is dropped rather than with the
+    /// `close` method of its type, like
+    /// [`warn_on_drop_close`](Self::warn_on_drop_close) but reported as the
+    /// application sees fit. It replaces the warnings.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn on_drop_close(mut self, f: impl Fn(std::os::fd::RawFd) + Send + Sync + 'static) -> Self {
+        self.on_drop_close = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Run the callbacks of `hook` when the tasks of the runtime are spawned,
+    /// polled and completed, see [`TaskHook`](crate::task::TaskHook).
+    ///
+    /// With a hook, each poll of a task is timed, which costs two reads of
+    /// the clock.
+    #[must_use]
+    pub fn with_task_hook(mut self, hook: impl crate::task::TaskHook) -> Self {
+        self.task_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Set how many IO operations a task may complete per poll before it
+    /// yields to the other ready tasks, or `None` to never make it yield.
+    /// Defaults to 128.
+    ///
+    /// With the legacy driver, or with always ready sources such as
+    /// multishot operations, a task looping over IO may otherwise keep the
+    /// thread for as long as the IO is ready, starving the other tasks. See
+    /// also [`consume_budget`](crate::task::consume_budget).
+    #[must_use]
+    pub fn with_task_budget(mut self, budget: Option<u32>) -> Self {
+        self.task_budget = budget;
+        self
+    }
+
+    /// Configure the entries, io_uring submit policy, spin before park and
+    /// completions or events per tick for `profile`, see [`Profile`].
+    ///
+    /// Every knob of the profile is set, so call it first and refine the
+    /// result with the other methods. The timer has a fixed resolution of
+    /// one millisecond, which no profile changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::{Profile, RuntimeBuilder};
+    ///
+    /// let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///     .preset(Profile::LowLatency)
+    ///     .enable_timer()
+    ///     .build()
+    ///     .unwrap();
+    /// rt.block_on(async {});
+    /// ```
+    #[must_use]
+    pub fn preset(mut self, profile: Profile) -> Self {
+        let (entries, threshold, max_cqes, spin) = match profile {
+            Profile::LowLatency => (1024, Some(1), Some(64), Some(50)),
+            Profile::Balanced => (1024, Some(32), Some(256), None),
+            Profile::Throughput => (4096, None, None, None),
+        };
+        self.entries = Some(entries);
+        self.spin_before_park = spin.map(std::time::Duration::from_micros);
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        {
+            self.submit_policy = SubmitPolicy {
+                threshold,
+                interval: None,
+            };
+            self.max_cqes_per_tick = max_cqes;
+        }
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let _ = (threshold, max_cqes);
+        #[cfg(any(feature = "legacy", feature = "poll-io"))]
+        {
+            self.events_capacity = Some(match profile {
+                Profile::LowLatency => 256,
+                Profile::Balanced => 1024,
+                Profile::Throughput => 4096,
+            });
+        }
+        self
+    }
+}
+
+/// A coherent set of driver settings, applied with
+/// [`RuntimeBuilder::preset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Handle each operation as soon as possible: io_uring operations are
+    /// submitted as soon as they are queued, at most 64 completions or 256
+    /// readiness events are processed per tick, and the driver spins for
+    /// 50µs before parking.
+    LowLatency,
+    /// A middle ground: 1024 entries, operations submitted by batches of 32,
+    /// and at most 256 completions or 1024 readiness events processed per
+    /// tick.
+    Balanced,
+    /// Amortize syscalls over as many operations as possible: 4096 entries,
+    /// operations submitted only when the ring is full or the driver parks,
+    /// no limit on the completions processed per tick, and 4096 readiness
+    /// events per poll.
+    Throughput,
 }
 
 // ===== FusionDriver =====
@@ -182,23 +716,11 @@ impl RuntimeBuilder<FusionDriver> {
     #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<IoUringDriver, LegacyDriver>> {
         if crate::utils::detect_uring() {
-            let builder = RuntimeBuilder::<IoUringDriver> {
-                entries: self.entries,
-                urb: self.urb,
-                #[cfg(feature = "sync")]
-                blocking_handle: self.blocking_handle,
-                _mark: PhantomData,
-            };
+            let builder = self.cast::<IoUringDriver>();
             info!("io_uring driver built");
             Ok(builder.build()?.into())
         } else {
-            let builder = RuntimeBuilder::<LegacyDriver> {
-                entries: self.entries,
-                urb: self.urb,
-                #[cfg(feature = "sync")]
-                blocking_handle: self.blocking_handle,
-                _mark: PhantomData,
-            };
+            let builder = self.cast::<LegacyDriver>();
             info!("legacy driver built");
             Ok(builder.build()?.into())
         }
@@ -207,25 +729,14 @@ impl RuntimeBuilder<FusionDriver> {
     /// Build the runtime.
     #[cfg(not(all(target_os = "linux", feature = "iouring")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<LegacyDriver>> {
-        let builder = RuntimeBuilder::<LegacyDriver> {
-            entries: self.entries,
-            #[cfg(feature = "sync")]
-            blocking_handle: self.blocking_handle,
-            _mark: PhantomData,
-        };
+        let builder = self.cast::<LegacyDriver>();
         Ok(builder.build()?.into())
     }
 
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", not(feature = "legacy")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<IoUringDriver>> {
-        let builder = RuntimeBuilder::<IoUringDriver> {
-            entries: self.entries,
-            urb: self.urb,
-            #[cfg(feature = "sync")]
-            blocking_handle: self.blocking_handle,
-            _mark: PhantomData,
-        };
+        let builder = self.cast::<IoUringDriver>();
         Ok(builder.build()?.into())
     }
 }
@@ -238,23 +749,11 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
         self,
     ) -> io::Result<crate::FusionRuntime<TimeDriver<IoUringDriver>, TimeDriver<LegacyDriver>>> {
         if crate::utils::detect_uring() {
-            let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
-                entries: self.entries,
-                urb: self.urb,
-                #[cfg(feature = "sync")]
-                blocking_handle: self.blocking_handle,
-                _mark: PhantomData,
-            };
+            let builder = self.cast::<TimeDriver<IoUringDriver>>();
             info!("io_uring driver with timer built");
             Ok(builder.build()?.into())
         } else {
-            let builder = RuntimeBuilder::<TimeDriver<LegacyDriver>> {
-                entries: self.entries,
-                urb: self.urb,
-                #[cfg(feature = "sync")]
-                blocking_handle: self.blocking_handle,
-                _mark: PhantomData,
-            };
+            let builder = self.cast::<TimeDriver<LegacyDriver>>();
             info!("legacy driver with timer built");
             Ok(builder.build()?.into())
         }
@@ -263,29 +762,51 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
     /// Build the runtime.
     #[cfg(not(all(target_os = "linux", feature = "iouring")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<TimeDriver<LegacyDriver>>> {
-        let builder = RuntimeBuilder::<TimeDriver<LegacyDriver>> {
-            entries: self.entries,
-            #[cfg(feature = "sync")]
-            blocking_handle: self.blocking_handle,
-            _mark: PhantomData,
-        };
+        let builder = self.cast::<TimeDriver<LegacyDriver>>();
         Ok(builder.build()?.into())
     }
 
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", not(feature = "legacy")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<TimeDriver<IoUringDriver>>> {
-        let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
-            entries: self.entries,
-            urb: self.urb,
-            #[cfg(feature = "sync")]
-            blocking_handle: self.blocking_handle,
-            _mark: PhantomData,
-        };
+        let builder = self.cast::<TimeDriver<IoUringDriver>>();
         Ok(builder.build()?.into())
     }
 }
 
+// ===== AutoDriver =====
+
+/// Fake driver marking a builder of an [`AutoRuntime`](crate::AutoRuntime),
+/// see [`RuntimeBuilder::new_auto`].
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+pub struct AutoDriver;
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl RuntimeBuilder<AutoDriver> {
+    /// Create a builder of a runtime choosing its driver when it is built:
+    /// io_uring if the kernel supports it, the legacy driver otherwise.
+    ///
+    /// The runtime built is an [`AutoRuntime`](crate::AutoRuntime), whose type
+    /// does not depend on the driver, with or without the timer.
+    #[must_use]
+    pub fn new_auto() -> Self {
+        Self::new()
+    }
+
+    /// Build the runtime.
+    pub fn build(self) -> io::Result<crate::AutoRuntime> {
+        Ok(self.cast::<FusionDriver>().build()?.into())
+    }
+}
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl RuntimeBuilder<TimeDriver<AutoDriver>> {
+    /// Build the runtime.
+    pub fn build(self) -> io::Result<crate::AutoRuntime> {
+        Ok(self.cast::<TimeDriver<FusionDriver>>().build()?.into())
+    }
+}
+
 // ===== enable_timer related =====
 mod time_wrap {
     pub trait TimeWrapable {}
@@ -297,6 +818,8 @@ impl time_wrap::TimeWrapable for IoUringDriver {}
 impl time_wrap::TimeWrapable for LegacyDriver {}
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
 impl time_wrap::TimeWrapable for FusionDriver {}
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl time_wrap::TimeWrapable for AutoDriver {}
 
 impl<D: Driver> Buildable for TimeDriver<D>
 where
@@ -307,14 +830,7 @@ where
         let Runtime {
             driver,
             mut context,
-        } = Buildable::build(RuntimeBuilder::<D> {
-            entries: this.entries,
-            #[cfg(all(target_os = "linux", feature = "iouring"))]
-            urb: this.urb,
-            #[cfg(feature = "sync")]
-            blocking_handle: this.blocking_handle,
-            _mark: PhantomData,
-        })?;
+        } = Buildable::build(this.cast::<D>())?;
 
         let timer_driver = TimeDriver::new(driver, Clock::new());
         context.time_handle = Some(timer_driver.handle.clone());
@@ -335,22 +851,7 @@ impl<D: time_wrap::TimeWrapable> RuntimeBuilder<D> {
     /// Enable timer
     #[must_use]
     pub fn enable_timer(self) -> RuntimeBuilder<TimeDriver<D>> {
-        let Self {
-            entries,
-            #[cfg(all(target_os = "linux", feature = "iouring"))]
-            urb,
-            #[cfg(feature = "sync")]
-            blocking_handle,
-            ..
-        } = self;
-        RuntimeBuilder {
-            entries,
-            #[cfg(all(target_os = "linux", feature = "iouring"))]
-            urb,
-            #[cfg(feature = "sync")]
-            blocking_handle,
-            _mark: PhantomData,
-        }
+        self.cast()
     }
 }
 
@@ -118,8 +118,28 @@ impl LegacyDriver {
         Ok(driver)
     }
 
+    pub(crate) fn with_events_capacity(self, capacity: Option<usize>) -> Self {
+        if let Some(capacity) = capacity {
+            let inner = unsafe { &mut *self.inner.get() };
+            #[cfg(unix)]
+            {
+                inner.events = mio::Events::with_capacity(capacity);
+            }
+            #[cfg(windows)]
+            {
+                inner.events = iocp::Events::with_capacity(capacity);
+            }
+        }
+        self
+    }
+
     fn inner_park(&self, mut timeout: Option<Duration>) -> io::Result<()> {
         let inner = unsafe { &mut *self.inner.get() };
+        driver_event!(
+            "park",
+            driver = "legacy",
+            timeout_us = timeout.map(|t| t.as_micros() as u64),
+        );
 
         #[allow(unused_mut)]
         let mut need_wait = true;
@@ -161,7 +181,9 @@ impl LegacyDriver {
         let iter = events.iter();
         #[cfg(windows)]
         let iter = events.events.iter();
+        let mut ready = 0usize;
         for event in iter {
+            ready += 1;
             let token = event.token();
 
             #[cfg(feature = "sync")]
@@ -172,6 +194,7 @@ impl LegacyDriver {
             #[cfg(not(feature = "sync"))]
             inner.dispatch(token, Ready::from_mio(event));
         }
+        driver_event!("complete", driver = "legacy", events = ready);
         Ok(())
     }
 
@@ -314,6 +337,20 @@ impl LegacyInner {
         }
     }
 
+    /// Cancel the operations waiting for readiness.
+    pub(crate) fn cancel_all(this: &Rc<UnsafeCell<LegacyInner>>) {
+        let inner = unsafe { &mut *this.get() };
+        let tokens: Vec<usize> = inner.io_dispatch.keys().collect();
+        for token in tokens {
+            let Some(ready) = inner.io_dispatch.get(token).map(|io| io.waiting()) else {
+                continue;
+            };
+            if !ready.is_empty() {
+                inner.dispatch(mio::Token(token), ready);
+            }
+        }
+    }
+
     pub(crate) fn cancel_op(
         this: &Rc<UnsafeCell<LegacyInner>>,
         index: usize,
@@ -321,7 +358,7 @@ impl LegacyInner {
     ) {
         let inner = unsafe { &mut *this.get() };
         let ready = match direction {
-            ready::Direction::Read => Ready::READ_CANCELED,
+            ready::Direction::Read | ready::Direction::ReadClosed => Ready::READ_CANCELED,
             ready::Direction::Write => Ready::WRITE_CANCELED,
         };
         inner.dispatch(mio::Token(index), ready);
@@ -330,7 +367,7 @@ impl LegacyInner {
     pub(crate) fn submit_with_data<T>(
         this: &Rc<UnsafeCell<LegacyInner>>,
         data: T,
-    ) -> io::Result<Op<T>>
+    ) -> Result<Op<T>, (io::Error, T)>
     where
         T: OpAble,
     {
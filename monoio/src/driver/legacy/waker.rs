@@ -1,4 +1,4 @@
-use crate::driver::unpark::Unpark;
+use crate::{driver::unpark::Unpark, utils::cache_padded::CachePadded};
 
 pub(crate) struct EventWaker {
     // raw waker
@@ -6,8 +6,9 @@ pub(crate) struct EventWaker {
     waker: super::iocp::Waker,
     #[cfg(unix)]
     waker: mio::Waker,
-    // Atomic awake status
-    pub(crate) awake: std::sync::atomic::AtomicBool,
+    // Atomic awake status, written by the owning thread on every park while
+    // remote threads poke the ref counts next to it, so it gets its own line.
+    pub(crate) awake: CachePadded<std::sync::atomic::AtomicBool>,
 }
 
 impl EventWaker {
@@ -15,7 +16,7 @@ impl EventWaker {
     pub(crate) fn new(waker: mio::Waker) -> Self {
         Self {
             waker,
-            awake: std::sync::atomic::AtomicBool::new(true),
+            awake: CachePadded(std::sync::atomic::AtomicBool::new(true)),
         }
     }
 
@@ -23,7 +24,7 @@ impl EventWaker {
     pub(crate) fn new(waker: super::iocp::Waker) -> Self {
         Self {
             waker,
-            awake: std::sync::atomic::AtomicBool::new(true),
+            awake: CachePadded(std::sync::atomic::AtomicBool::new(true)),
         }
     }
 
@@ -34,6 +34,8 @@ use self::op::{CompletionMeta, Op, OpAble};
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 pub use self::uring::IoUringDriver;
 #[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use self::uring::SubmitPolicy;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
 use self::uring::UringInner;
 
 /// Unpark a runtime of another thread.
@@ -97,7 +99,7 @@ pub(crate) enum Inner {
 }
 
 impl Inner {
-    fn submit_with<T: OpAble>(&self, data: T) -> io::Result<Op<T>> {
+    fn submit_with<T: OpAble>(&self, data: T) -> Result<Op<T>, (io::Error, T)> {
         match self {
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             Inner::Uring(this) => UringInner::submit_with_data(this, data),
@@ -158,9 +160,15 @@ impl Inner {
 
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     #[inline]
-    fn drop_op<T: 'static>(&self, index: usize, data: &mut Option<T>, skip_cancel: bool) {
+    fn drop_op<T: OpAble + 'static>(
+        &self,
+        index: usize,
+        data: &mut Option<T>,
+        skip_cancel: bool,
+        multishot: bool,
+    ) {
         match self {
-            Inner::Uring(this) => UringInner::drop_op(this, index, data, skip_cancel),
+            Inner::Uring(this) => UringInner::drop_op(this, index, data, skip_cancel, multishot),
             #[cfg(feature = "legacy")]
             Inner::Legacy(_) => {}
         }
@@ -187,6 +195,87 @@ impl Inner {
         }
     }
 
+    /// Register `iovecs` as the fixed buffers of the ring.
+    ///
+    /// # Safety
+    ///
+    /// The buffers must stay valid until they are unregistered or the ring is
+    /// dropped.
+    #[cfg(unix)]
+    pub(crate) unsafe fn register_buffers(&self, iovecs: &[libc::iovec]) -> io::Result<()> {
+        match self {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Inner::Uring(this) => UringInner::register_buffers(this, iovecs),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => {
+                let _ = iovecs;
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "buffers can only be registered with io_uring",
+                ))
+            }
+            #[cfg(all(
+                not(feature = "legacy"),
+                not(all(target_os = "linux", feature = "iouring"))
+            ))]
+            _ => {
+                let _ = iovecs;
+                util::feature_panic();
+            }
+        }
+    }
+
+    /// Unregister the fixed buffers of the ring.
+    #[cfg(unix)]
+    pub(crate) fn unregister_buffers(&self) -> io::Result<()> {
+        match self {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Inner::Uring(this) => UringInner::unregister_buffers(this),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Ok(()),
+            #[cfg(all(
+                not(feature = "legacy"),
+                not(all(target_os = "linux", feature = "iouring"))
+            ))]
+            _ => {
+                util::feature_panic();
+            }
+        }
+    }
+
+    /// Register a provided buffer ring as the buffer group `bgid`.
+    ///
+    /// # Safety
+    ///
+    /// The ring must stay valid until it is unregistered or the ring is
+    /// dropped.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) unsafe fn register_buf_ring(
+        &self,
+        ring_addr: u64,
+        entries: u16,
+        bgid: u16,
+    ) -> io::Result<()> {
+        match self {
+            Inner::Uring(this) => UringInner::register_buf_ring(this, ring_addr, entries, bgid),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "buffer rings can only be registered with io_uring",
+            )),
+        }
+    }
+
+    /// Unregister the provided buffer ring of the buffer group `bgid`.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn unregister_buf_ring(&self, bgid: u16) -> io::Result<()> {
+        match self {
+            Inner::Uring(this) => UringInner::unregister_buf_ring(this, bgid),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Ok(()),
+        }
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
     fn is_legacy(&self) -> bool {
         matches!(self, Inner::Legacy(..))
@@ -204,6 +293,131 @@ impl Inner {
     }
 }
 
+/// Run `f` with the file IO ring of the current driver as the current driver,
+/// so the op it submits goes to that ring. Without a file IO ring, or if the
+/// op can not be polled by an IOPOLL file ring, this just runs `f`.
+#[inline]
+pub(crate) fn with_fs_ring<T: OpAble>(f: impl FnOnce() -> io::Result<Op<T>>) -> io::Result<Op<T>> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if CURRENT.is_set() {
+        let fs_ring = CURRENT.with(|inner| match inner {
+            Inner::Uring(this) => unsafe { (*this.get()).fs_ring.clone() },
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => None,
+        });
+        let fs_ring = fs_ring.filter(|fs_ring| T::IOPOLL || !unsafe { (*fs_ring.get()).iopoll });
+        if let Some(fs_ring) = fs_ring {
+            return CURRENT.set(&Inner::Uring(fs_ring), f);
+        }
+    }
+    f()
+}
+
+/// Completion statistics of the current driver.
+pub(crate) fn completion_stats() -> Option<crate::utils::CompletionStats> {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => Some(unsafe { (*this.get()).stats }),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => None,
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Cancel all the operations in flight on the current driver: they complete
+/// with `ECANCELED`, unless they complete meanwhile.
+pub(crate) fn cancel_all() {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => unsafe { UringInner::cancel_all(this) },
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(this) => LegacyInner::cancel_all(this),
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Returns whether the current driver waits for io: operations in flight,
+/// or fds registered for readiness.
+pub(crate) fn has_pending_io() -> bool {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => {
+            let this = unsafe { &*this.get() };
+            #[cfg(feature = "poll-io")]
+            if this.poll.io_dispatch.len() > 0 {
+                return true;
+            }
+            this.op_counts().0 > 0
+        }
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(this) => unsafe { (*this.get()).io_dispatch.len() > 0 },
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Capabilities of the current io_uring driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn uring_report() -> Option<crate::utils::UringReport> {
+    CURRENT.with(|inner| match inner {
+        Inner::Uring(this) => unsafe { (*this.get()).report.clone() },
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => None,
+    })
+}
+
+/// Number of in-flight and submitted operations of the current driver.
+pub(crate) fn op_counts() -> Option<(usize, u64)> {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => Some(unsafe { (*this.get()).op_counts() }),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => None,
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Poll-io registration statistics of the current driver.
+#[cfg(all(unix, feature = "poll-io"))]
+pub(crate) fn poll_io_stats() -> Option<crate::utils::PollIoStats> {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => Some(unsafe { (*this.get()).poll.stats }),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => None,
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
 /// The unified UnparkHandle.
 #[cfg(feature = "sync")]
 #[derive(Clone)]
@@ -12,11 +12,25 @@ pub(crate) mod read;
 pub(crate) mod write;
 
 mod accept;
+pub(crate) use accept::AcceptAddr;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use accept::AcceptMulti;
+mod op_box;
+pub(crate) use op_box::OpBox;
 mod connect;
+#[cfg(target_os = "linux")]
+pub(crate) mod fallocate;
+#[cfg(unix)]
+mod fixed;
 mod fsync;
+mod nop;
 mod open;
 mod poll;
+pub(crate) use poll::PollAdd;
 mod recv;
+pub(crate) use recv::PreparedRecv;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use recv::RecvMulti;
 mod send;
 #[cfg(unix)]
 mod statx;
@@ -143,8 +157,22 @@ pub(crate) trait OpAble {
     const RET_IS_FD: bool = false;
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     const SKIP_CANCEL: bool = false;
+    /// The op is polled IO, which is the only kind accepted on an
+    /// `IORING_SETUP_IOPOLL` ring.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = false;
+    /// The op posts completions until it fails or is canceled, which it
+    /// always is on drop.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const MULTISHOT: bool = false;
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry;
+    /// Handles a completion nobody takes as the op was dropped, e.g. to give
+    /// back the buffer the kernel selected for it, or to close the fds it
+    /// received.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    fn on_ignored(&mut self, _result: &io::Result<MaybeFd>, _flags: u32) {}
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_interest(&self) -> Option<(super::ready::Direction, usize)>;
@@ -195,9 +223,21 @@ impl<T: OpAble> Op<T> {
     /// `state` is stored during the operation tracking any state submitted to
     /// the kernel.
     pub(super) fn submit_with(data: T) -> io::Result<Op<T>> {
+        Op::submit_or_return(data).map_err(|(err, _)| err)
+    }
+
+    /// Submit an operation to uring, returning its data if it can not be
+    /// submitted.
+    pub(super) fn submit_or_return(data: T) -> Result<Op<T>, (io::Error, T)> {
         driver::CURRENT.with(|this| this.submit_with(data))
     }
 
+    /// Submit an operation to the file IO ring of the current driver if it
+    /// has one, see [`driver::with_fs_ring`].
+    pub(super) fn submit_fs_with(data: T) -> io::Result<Op<T>> {
+        driver::with_fs_ring(|| Op::submit_with(data))
+    }
+
     /// Try submitting an operation to uring
     #[allow(unused)]
     pub(super) fn try_submit_with(data: T) -> io::Result<Op<T>> {
@@ -231,6 +271,25 @@ impl<T: OpAble> Op<T> {
     }
 }
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl<T: OpAble + 'static> Op<T> {
+    /// Poll the next completion of a multishot operation, `None` once its
+    /// last completion has been returned.
+    pub(crate) fn poll_multishot(&mut self, cx: &mut Context<'_>) -> Poll<Option<CompletionMeta>> {
+        if self.index == usize::MAX {
+            return Poll::Ready(None);
+        }
+        let coop = ready!(crate::task::coop::poll_proceed(cx));
+        let data_mut = self.data.as_mut().expect("unexpected operation state");
+        let meta = ready!(self.driver.poll_op::<T>(data_mut, self.index, cx));
+        coop.made_progress();
+        if !io_uring::cqueue::more(meta.flags) {
+            self.index = usize::MAX;
+        }
+        Poll::Ready(Some(meta))
+    }
+}
+
 impl<T> Future for Op<T>
 where
     T: Unpin + OpAble + 'static,
@@ -239,8 +298,10 @@ where
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let me = &mut *self;
+        let coop = ready!(crate::task::coop::poll_proceed(cx));
         let data_mut = me.data.as_mut().expect("unexpected operation state");
         let meta = ready!(me.driver.poll_op::<T>(data_mut, me.index, cx));
+        coop.made_progress();
 
         me.index = usize::MAX;
         let data = me.data.take().expect("unexpected operation state");
@@ -253,8 +314,98 @@ impl<T: OpAble> Drop for Op<T> {
     #[inline]
     fn drop(&mut self) {
         self.driver
-            .drop_op(self.index, &mut self.data, T::SKIP_CANCEL);
+            .drop_op(self.index, &mut self.data, T::SKIP_CANCEL, T::MULTISHOT);
+    }
+}
+
+/// Runs `submit`, linking a timeout to the operation it submits with
+/// `IORING_OP_LINK_TIMEOUT`: the kernel cancels the operation if it does not
+/// complete within `timeout`, and it fails with `ECANCELED`.
+///
+/// Gives `submit` back without running it if the current driver can not link
+/// timeouts, i.e. the legacy one.
+pub(crate) fn with_link_timeout<R, F>(timeout: std::time::Duration, submit: F) -> Result<R, F>
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if let Some(this) = super::CURRENT.with(|inner| match inner {
+        driver::Inner::Uring(this) => Some(this.clone()),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }) {
+        driver::UringInner::set_link_timeout(&this, Some(timeout));
+        let res = submit();
+        driver::UringInner::set_link_timeout(&this, None);
+        return Ok(res);
+    }
+    let _ = timeout;
+    Err(submit)
+}
+
+/// Runs `poll` as a batch: the operations it submits are pushed without
+/// being submitted, then submitted at once, linked with `IOSQE_IO_LINK` if
+/// `linked`. A batch started within another joins it.
+///
+/// `len` is the number of operations expected: a linked batch is only
+/// submitted as a whole, its operations fail if the submission queue can not
+/// hold them all.
+///
+/// Only the uring driver batches operations, `poll` just runs with the
+/// legacy one.
+pub(crate) fn with_batch<R>(linked: bool, len: usize, poll: impl FnOnce() -> R) -> R {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if let Some(this) = super::CURRENT.with(|inner| match inner {
+        driver::Inner::Uring(this) => Some(this.clone()),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }) {
+        struct EndBatch(std::rc::Rc<std::cell::UnsafeCell<driver::UringInner>>);
+
+        impl Drop for EndBatch {
+            fn drop(&mut self) {
+                driver::UringInner::end_batch(&self.0);
+            }
+        }
+
+        if !driver::UringInner::start_batch(&this, linked, len) {
+            return poll();
+        }
+        // Ends the batch even if `poll` panics.
+        let _end = EndBatch(this);
+        return poll();
+    }
+    let _ = (linked, len);
+    poll()
+}
+
+/// Runs `poll`, submitting the operations it submits as drain barriers with
+/// `IOSQE_IO_DRAIN`: the kernel starts them once all the operations
+/// submitted before them have completed, and starts the ones submitted after
+/// them once they have completed.
+///
+/// Only the uring driver has barriers, `poll` just runs with the legacy one.
+pub(crate) fn with_drain<R>(poll: impl FnOnce() -> R) -> R {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if let Some(this) = super::CURRENT.with(|inner| match inner {
+        driver::Inner::Uring(this) => Some(this.clone()),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }) {
+        struct Restore(std::rc::Rc<std::cell::UnsafeCell<driver::UringInner>>, bool);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                driver::UringInner::set_drain(&self.0, self.1);
+            }
+        }
+
+        let prev = driver::UringInner::set_drain(&this, true);
+        // Restores the setting even if `poll` panics.
+        let _restore = Restore(this, prev);
+        return poll();
     }
+    poll()
 }
 
 /// Check if current driver is legacy.
@@ -15,38 +15,50 @@ use {
     },
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, Op, OpAble, OpBox};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::{driver::ready::Direction, MaybeFd};
 
+/// Storage for the peer address of an accepted connection.
+#[cfg(unix)]
+pub(crate) type AcceptAddr = (MaybeUninit<libc::sockaddr_storage>, libc::socklen_t);
+/// Storage for the peer address of an accepted connection.
+#[cfg(windows)]
+pub(crate) type AcceptAddr = (MaybeUninit<SOCKADDR_STORAGE>, socklen_t);
+
 /// Accept
 pub(crate) struct Accept {
     pub(crate) fd: SharedFd,
-    #[cfg(unix)]
-    pub(crate) addr: Box<(MaybeUninit<libc::sockaddr_storage>, libc::socklen_t)>,
-    #[cfg(windows)]
-    pub(crate) addr: Box<(MaybeUninit<SOCKADDR_STORAGE>, socklen_t)>,
+    // Boxed since the kernel writes to it while the op may be moved.
+    pub(crate) addr: OpBox<AcceptAddr>,
 }
 
 impl Op<Accept> {
     /// Accept a connection
     pub(crate) fn accept(fd: &SharedFd) -> io::Result<Self> {
-        #[cfg(unix)]
-        let addr = Box::new((
-            MaybeUninit::uninit(),
-            size_of::<libc::sockaddr_storage>() as libc::socklen_t,
-        ));
+        Self::accept_with_addr(fd, OpBox::new((MaybeUninit::zeroed(), 0))).map_err(|(err, _)| err)
+    }
 
+    /// Accept a connection, storing the peer address in `addr`. `addr` is
+    /// returned with the error if the op can not be submitted.
+    pub(crate) fn accept_with_addr(
+        fd: &SharedFd,
+        mut addr: OpBox<AcceptAddr>,
+    ) -> Result<Self, (io::Error, OpBox<AcceptAddr>)> {
+        #[cfg(unix)]
+        {
+            addr.1 = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        }
         #[cfg(windows)]
-        let addr = Box::new((
-            MaybeUninit::uninit(),
-            size_of::<SOCKADDR_STORAGE>() as socklen_t,
-        ));
+        {
+            addr.1 = size_of::<SOCKADDR_STORAGE>() as socklen_t;
+        }
 
-        Op::submit_with(Accept {
+        Op::submit_or_return(Accept {
             fd: fd.clone(),
             addr,
         })
+        .map_err(|(err, accept)| (err, accept.addr))
     }
 }
 
@@ -61,6 +73,7 @@ impl OpAble for Accept {
             self.addr.0.as_mut_ptr() as *mut _,
             &mut self.addr.1,
         )
+        .flags(libc::SOCK_CLOEXEC)
         .build()
     }
 
@@ -125,3 +138,47 @@ impl OpAble for Accept {
         };
     }
 }
+
+/// Multishot accept, posting a completion for each accepted connection.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct AcceptMulti {
+    pub(crate) fd: SharedFd,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<AcceptMulti> {
+    /// Accept connections until canceled, requires kernel 5.19+.
+    pub(crate) fn accept_multi(fd: &SharedFd) -> io::Result<Self> {
+        Op::submit_with(AcceptMulti { fd: fd.clone() })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for AcceptMulti {
+    const RET_IS_FD: bool = true;
+    const MULTISHOT: bool = true;
+
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::AcceptMulti::new(types::Fd(self.fd.raw_fd()))
+            .flags(libc::SOCK_CLOEXEC)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    // The legacy driver has no multishot ops, this accepts one connection.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        let flag = libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK;
+        crate::syscall!(accept4@FD(
+            self.fd.as_raw_fd(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            flag
+        ))
+    }
+}
@@ -8,13 +8,13 @@ use windows_sys::Win32::Networking::WinSock::{
     SOCKADDR_IN6, SOCKADDR_IN6_0, SOCKET_ERROR,
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, Op, OpAble, OpBox};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::{driver::ready::Direction, MaybeFd};
 
 pub(crate) struct Connect {
     pub(crate) fd: SharedFd,
-    socket_addr: Box<SocketAddrCRepr>,
+    socket_addr: OpBox<SocketAddrCRepr>,
     #[cfg(windows)]
     socket_addr_len: socklen_t,
     #[cfg(unix)]
@@ -33,7 +33,7 @@ impl Op<Connect> {
         let (raw_addr, raw_addr_length) = socket_addr(&addr);
         Op::submit_with(Connect {
             fd: socket,
-            socket_addr: Box::new(raw_addr),
+            socket_addr: OpBox::new(raw_addr),
             socket_addr_len: raw_addr_length,
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             tfo: _tfo,
@@ -120,7 +120,7 @@ pub(crate) struct ConnectUnix {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
     pub(crate) fd: SharedFd,
-    socket_addr: Box<(libc::sockaddr_un, libc::socklen_t)>,
+    socket_addr: OpBox<(libc::sockaddr_un, libc::socklen_t)>,
 }
 
 #[cfg(unix)]
@@ -133,7 +133,7 @@ impl Op<ConnectUnix> {
     ) -> io::Result<Op<ConnectUnix>> {
         Op::submit_with(ConnectUnix {
             fd: socket,
-            socket_addr: Box::new((socket_addr, socket_len)),
+            socket_addr: OpBox::new((socket_addr, socket_len)),
         })
     }
 }
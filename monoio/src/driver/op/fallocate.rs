@@ -0,0 +1,57 @@
+use std::{io, os::fd::RawFd};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use super::{driver::ready::Direction, MaybeFd};
+
+pub(crate) struct Fallocate {
+    fd: SharedFd,
+    offset: u64,
+    len: u64,
+    mode: i32,
+}
+
+impl Op<Fallocate> {
+    pub(crate) fn fallocate(fd: &SharedFd, offset: u64, len: u64, mode: i32) -> io::Result<Self> {
+        Op::submit_fs_with(Fallocate {
+            fd: fd.clone(),
+            offset,
+            len,
+            mode,
+        })
+    }
+}
+
+impl OpAble for Fallocate {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Fallocate::new(types::Fd(self.fd.raw_fd()), self.len)
+            .offset(self.offset)
+            .mode(self.mode)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        fallocate(self.fd.raw_fd(), self.offset, self.len, self.mode).map(|_| MaybeFd::zero())
+    }
+}
+
+/// Manipulates the space allocated to the file, with a blocking syscall.
+pub(crate) fn fallocate(fd: RawFd, offset: u64, len: u64, mode: i32) -> io::Result<()> {
+    let offset = libc::off_t::try_from(offset)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too big"))?;
+    let len = libc::off_t::try_from(len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length too big"))?;
+    crate::syscall!(fallocate@RAW(fd, mode, offset, len))?;
+    Ok(())
+}
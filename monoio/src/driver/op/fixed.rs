@@ -0,0 +1,144 @@
+use std::io;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use std::os::unix::prelude::AsRawFd;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Completion, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use super::{driver::ready::Direction, read, write, MaybeFd};
+#[cfg(any(
+    all(target_os = "linux", feature = "iouring"),
+    feature = "legacy",
+    feature = "poll-io"
+))]
+use crate::buf::IoBuf;
+use crate::{
+    buf::{FixedBuf, IoBufMut},
+    BufResult,
+};
+
+// Offset using (and advancing) the file position, like read(2) and write(2).
+const CURRENT_POS: u64 = -1i64 as u64;
+
+pub(crate) struct ReadFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    /// Reference to the in-flight buffer.
+    pub(crate) buf: FixedBuf,
+    offset: u64,
+}
+
+impl Op<ReadFixed> {
+    pub(crate) fn read_fixed(fd: SharedFd, buf: FixedBuf) -> io::Result<Self> {
+        Self::read_fixed_at(fd, buf, CURRENT_POS)
+    }
+
+    pub(crate) fn read_fixed_at(fd: SharedFd, buf: FixedBuf, offset: u64) -> io::Result<Self> {
+        Op::submit_with(ReadFixed { fd, buf, offset })
+    }
+
+    pub(crate) async fn result(self) -> BufResult<usize, FixedBuf> {
+        let Completion { data, meta } = self.await;
+        let res = meta.result.map(|v| v.into_inner() as usize);
+        let mut buf = data.buf;
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe { buf.set_init(n) };
+        }
+        (res, buf)
+    }
+}
+
+impl OpAble for ReadFixed {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::ReadFixed::new(
+            types::Fd(self.fd.raw_fd()),
+            self.buf.write_ptr(),
+            self.buf.bytes_total() as _,
+            self.buf.buf_index(),
+        )
+        .offset(self.offset)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        let fd = self.fd.as_raw_fd();
+        let (ptr, len) = (self.buf.write_ptr(), self.buf.bytes_total());
+        match self.offset {
+            CURRENT_POS => read::read(fd, ptr, len),
+            offset => read::read_at(fd, ptr, len, offset),
+        }
+    }
+}
+
+pub(crate) struct WriteFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    pub(crate) buf: FixedBuf,
+    offset: u64,
+}
+
+impl Op<WriteFixed> {
+    pub(crate) fn write_fixed(fd: SharedFd, buf: FixedBuf) -> io::Result<Self> {
+        Self::write_fixed_at(fd, buf, CURRENT_POS)
+    }
+
+    pub(crate) fn write_fixed_at(fd: SharedFd, buf: FixedBuf, offset: u64) -> io::Result<Self> {
+        Op::submit_with(WriteFixed { fd, buf, offset })
+    }
+
+    pub(crate) async fn result(self) -> BufResult<usize, FixedBuf> {
+        let Completion { data, meta } = self.await;
+        (meta.result.map(|v| v.into_inner() as _), data.buf)
+    }
+}
+
+impl OpAble for WriteFixed {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::WriteFixed::new(
+            types::Fd(self.fd.raw_fd()),
+            self.buf.read_ptr(),
+            self.buf.bytes_init() as _,
+            self.buf.buf_index(),
+        )
+        .offset(self.offset)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        let fd = self.fd.as_raw_fd();
+        let (ptr, len) = (self.buf.read_ptr(), self.buf.bytes_init());
+        match self.offset {
+            CURRENT_POS => write::write(fd, ptr, len),
+            offset => write::write_at(fd, ptr, len, offset),
+        }
+    }
+}
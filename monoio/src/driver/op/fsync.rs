@@ -16,7 +16,7 @@ pub(crate) struct Fsync {
 
 impl Op<Fsync> {
     pub(crate) fn fsync(fd: &SharedFd) -> io::Result<Op<Fsync>> {
-        Op::submit_with(Fsync {
+        Op::submit_fs_with(Fsync {
             fd: fd.clone(),
             #[cfg(target_os = "linux")]
             data_sync: false,
@@ -24,7 +24,7 @@ impl Op<Fsync> {
     }
 
     pub(crate) fn datasync(fd: &SharedFd) -> io::Result<Op<Fsync>> {
-        Op::submit_with(Fsync {
+        Op::submit_fs_with(Fsync {
             fd: fd.clone(),
             #[cfg(target_os = "linux")]
             data_sync: true,
@@ -15,7 +15,7 @@ pub(crate) struct MkDir {
 impl Op<MkDir> {
     pub(crate) fn mkdir<P: AsRef<Path>>(path: P, mode: mode_t) -> std::io::Result<Op<MkDir>> {
         let path = cstr(path.as_ref())?;
-        Op::submit_with(MkDir { path, mode })
+        Op::submit_fs_with(MkDir { path, mode })
     }
 }
 
@@ -0,0 +1,41 @@
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use super::{driver::ready::Direction, MaybeFd};
+use super::{Op, OpAble};
+
+/// An op doing nothing, it completes as soon as the driver reaps it.
+pub(crate) struct Nop;
+
+impl Op<Nop> {
+    pub(crate) fn nop() -> io::Result<Op<Nop>> {
+        Op::submit_with(Nop)
+    }
+}
+
+impl OpAble for Nop {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const SKIP_CANCEL: bool = true;
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Nop::new().build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        Ok(MaybeFd::zero())
+    }
+}
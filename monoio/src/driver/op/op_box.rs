@@ -0,0 +1,191 @@
+//! Heap storage for op data the kernel points into.
+//!
+//! Ops move after submission, so anything the kernel reads or writes through
+//! a pointer (addresses, msghdrs, iovecs) has to live on the heap. Values up
+//! to [`SMALL_OP_SIZE`] bytes are stored in the smallest of the
+//! [`SIZE_CLASSES`] that fits them, and freed slots are kept in a per-thread
+//! cache so steady-state submissions do not allocate. The cache holds at most
+//! [`SLOT_CACHE`] slots per class, so it keeps up to 60 KiB alive per thread
+//! that submitted ops, freed when the thread exits.
+
+use std::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    cell::RefCell,
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// Slot sizes of the size classes.
+const SIZE_CLASSES: [usize; 4] = [64, 128, 256, 512];
+/// Largest value stored in a size class, larger ones are boxed.
+pub(crate) const SMALL_OP_SIZE: usize = SIZE_CLASSES[SIZE_CLASSES.len() - 1];
+const SMALL_OP_ALIGN: usize = 16;
+/// Max number of free slots cached per thread and size class.
+const SLOT_CACHE: usize = 64;
+
+const fn class_layout(class: usize) -> Layout {
+    unsafe { Layout::from_size_align_unchecked(SIZE_CLASSES[class], SMALL_OP_ALIGN) }
+}
+
+/// Free slots of each size class.
+struct SlotCache([Vec<NonNull<u8>>; SIZE_CLASSES.len()]);
+
+impl Drop for SlotCache {
+    fn drop(&mut self) {
+        for (class, slots) in self.0.iter_mut().enumerate() {
+            for slot in slots.drain(..) {
+                unsafe { dealloc(slot.as_ptr(), class_layout(class)) };
+            }
+        }
+    }
+}
+
+thread_local! {
+    static SLOTS: RefCell<SlotCache> = const {
+        RefCell::new(SlotCache([Vec::new(), Vec::new(), Vec::new(), Vec::new()]))
+    };
+}
+
+/// A `Box` whose allocation is recycled if `T` is small enough.
+pub(crate) struct OpBox<T> {
+    ptr: NonNull<T>,
+}
+
+// Safety: slots come from the global allocator, so they may be freed or cached
+// on any thread.
+unsafe impl<T: Send> Send for OpBox<T> {}
+unsafe impl<T: Sync> Sync for OpBox<T> {}
+
+impl<T> OpBox<T> {
+    /// Size class of `T`, `None` if it is boxed.
+    const CLASS: Option<usize> = {
+        let mut class = 0;
+        loop {
+            if class == SIZE_CLASSES.len()
+                || size_of::<T>() == 0
+                || align_of::<T>() > SMALL_OP_ALIGN
+            {
+                break None;
+            }
+            if size_of::<T>() <= SIZE_CLASSES[class] {
+                break Some(class);
+            }
+            class += 1;
+        }
+    };
+
+    /// Moves `value` to the heap.
+    pub(crate) fn new(value: T) -> Self {
+        let Some(class) = Self::CLASS else {
+            let ptr = Box::into_raw(Box::new(value));
+            return Self {
+                ptr: unsafe { NonNull::new_unchecked(ptr) },
+            };
+        };
+
+        let slot = SLOTS
+            .try_with(|slots| slots.borrow_mut().0[class].pop())
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| {
+                let layout = class_layout(class);
+                let ptr = unsafe { alloc(layout) };
+                NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout))
+            });
+        let ptr = slot.cast::<T>();
+        unsafe { ptr.as_ptr().write(value) };
+        Self { ptr }
+    }
+}
+
+impl<T> Drop for OpBox<T> {
+    fn drop(&mut self) {
+        let Some(class) = Self::CLASS else {
+            drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+            return;
+        };
+
+        unsafe { self.ptr.as_ptr().drop_in_place() };
+        let slot = self.ptr.cast::<u8>();
+        let cached = SLOTS
+            .try_with(|slots| {
+                let slots = &mut slots.borrow_mut().0[class];
+                if slots.len() < SLOT_CACHE {
+                    slots.push(slot);
+                    true
+                } else {
+                    false
+                }
+            })
+            .unwrap_or(false);
+        if !cached {
+            unsafe { dealloc(slot.as_ptr(), class_layout(class)) };
+        }
+    }
+}
+
+impl<T> Deref for OpBox<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for OpBox<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for OpBox<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[repr(align(32))]
+    struct Aligned([u8; 32]);
+
+    #[test]
+    fn small_slots_are_reused() {
+        let a = OpBox::new([1_u64; 8]);
+        let ptr = &*a as *const _ as usize;
+        drop(a);
+        let b = OpBox::new((2_u32, [0_u8; 32]));
+        assert_eq!(&*b as *const _ as usize, ptr);
+        assert_eq!(b.0, 2);
+    }
+
+    #[test]
+    fn size_classes() {
+        assert_eq!(OpBox::<[u8; 1]>::CLASS, Some(0));
+        assert_eq!(OpBox::<[u64; 8]>::CLASS, Some(0));
+        assert_eq!(OpBox::<[u8; 65]>::CLASS, Some(1));
+        assert_eq!(OpBox::<[u8; 256]>::CLASS, Some(2));
+        assert_eq!(OpBox::<[u8; SMALL_OP_SIZE]>::CLASS, Some(3));
+        assert_eq!(OpBox::<[u8; SMALL_OP_SIZE + 1]>::CLASS, None);
+        assert_eq!(OpBox::<()>::CLASS, None);
+        assert_eq!(OpBox::<Aligned>::CLASS, None);
+    }
+
+    #[test]
+    fn drops_contents() {
+        let rc = Rc::new(());
+        let small = OpBox::new(rc.clone());
+        let large = OpBox::new((rc.clone(), [0_u8; SMALL_OP_SIZE]));
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(small);
+        drop(large);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
@@ -6,11 +6,16 @@ use io_uring::{opcode, types};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::{driver::ready::Direction, MaybeFd};
 use super::{Op, OpAble};
+#[cfg(unix)]
+use crate::driver::shared_fd::SharedFd;
 use crate::{driver::util::cstr, fs::OpenOptions};
 
 /// Open a file
 pub(crate) struct Open {
     pub(crate) path: CString,
+    /// Directory the path is relative to(cwd if None)
+    #[cfg(unix)]
+    dir: Option<SharedFd>,
     #[cfg(unix)]
     flags: i32,
     #[cfg(unix)]
@@ -23,6 +28,16 @@ impl Op<Open> {
     #[cfg(unix)]
     /// Submit a request to open a file.
     pub(crate) fn open<P: AsRef<Path>>(path: P, options: &OpenOptions) -> io::Result<Op<Open>> {
+        Self::open_at(None, path, options)
+    }
+
+    #[cfg(unix)]
+    /// Submit a request to open a file relative to a directory.
+    pub(crate) fn open_at<P: AsRef<Path>>(
+        dir: Option<&SharedFd>,
+        path: P,
+        options: &OpenOptions,
+    ) -> io::Result<Op<Open>> {
         // Here the path will be copied, so its safe.
         let path = cstr(path.as_ref())?;
         let flags = libc::O_CLOEXEC
@@ -31,7 +46,12 @@ impl Op<Open> {
             | (options.custom_flags & !libc::O_ACCMODE);
         let mode = options.mode;
 
-        Op::submit_with(Open { path, flags, mode })
+        Op::submit_fs_with(Open {
+            path,
+            dir: dir.cloned(),
+            flags,
+            mode,
+        })
     }
 
     #[cfg(windows)]
@@ -40,7 +60,7 @@ impl Op<Open> {
         // Here the path will be copied, so its safe.
         let path = cstr(path.as_ref())?;
 
-        Op::submit_with(Open {
+        Op::submit_fs_with(Open {
             path,
             opts: options.clone(),
         })
@@ -53,7 +73,7 @@ impl OpAble for Open {
 
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), self.path.as_c_str().as_ptr())
+        opcode::OpenAt::new(types::Fd(dirfd(&self.dir)), self.path.as_c_str().as_ptr())
             .flags(self.flags)
             .mode(self.mode)
             .build()
@@ -67,7 +87,8 @@ impl OpAble for Open {
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), not(windows)))]
     fn legacy_call(&mut self) -> io::Result<MaybeFd> {
-        crate::syscall!(open@FD(
+        crate::syscall!(openat@FD(
+            dirfd(&self.dir),
             self.path.as_c_str().as_ptr(),
             self.flags,
             self.mode as libc::c_int
@@ -102,3 +123,8 @@ impl OpAble for Open {
         )
     }
 }
+
+#[cfg(unix)]
+pub(crate) fn dirfd(dir: &Option<SharedFd>) -> libc::c_int {
+    dir.as_ref().map_or(libc::AT_FDCWD, SharedFd::raw_fd)
+}
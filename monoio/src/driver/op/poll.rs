@@ -127,3 +127,45 @@ impl OpAble for PollAdd {
         Ok(MaybeFd::new_non_fd(1))
     }
 }
+
+/// Waits for the peer to close the connection, or for an error on it.
+pub(crate) struct PollClosed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+}
+
+impl Op<PollClosed> {
+    pub(crate) fn poll_closed(fd: &SharedFd) -> io::Result<Op<PollClosed>> {
+        Op::submit_with(PollClosed { fd: fd.clone() })
+    }
+
+    pub(crate) async fn wait(self) -> io::Result<()> {
+        self.await.meta.result.map(|_| ())
+    }
+}
+
+impl OpAble for PollClosed {
+    // POLLHUP and POLLERR are always reported.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        use io_uring::{opcode, types};
+
+        opcode::PollAdd::new(types::Fd(self.fd.raw_fd()), libc::POLLRDHUP as _).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd
+            .registered_index()
+            .map(|idx| (Direction::ReadClosed, idx))
+    }
+
+    // Only the read closed readiness completes it, there is nothing to do.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        Ok(MaybeFd::new_non_fd(1))
+    }
+}
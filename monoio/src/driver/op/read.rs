@@ -65,6 +65,9 @@ impl<T: IoBufMut> Op<Read<T>> {
 }
 
 impl<T: IoBufMut> OpAble for Read<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         // Refers to https://docs.rs/io-uring/latest/io_uring/opcode/struct.Read.html.
@@ -113,6 +116,9 @@ impl<T: IoBufMut> Op<ReadAt<T>> {
 }
 
 impl<T: IoBufMut> OpAble for ReadAt<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Read::new(
@@ -159,6 +165,9 @@ impl<T: IoVecBufMut> Op<ReadVec<T>> {
 }
 
 impl<T: IoVecBufMut> OpAble for ReadVec<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let ptr = self.buf_vec.write_iovec_ptr() as _;
@@ -228,25 +237,37 @@ pub(crate) struct ReadVecAt<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf_vec: T,
     offset: u64,
+    /// `RWF_*` flags of `preadv2(2)`.
+    flags: i32,
 }
 
 impl<T: IoVecBufMut> Op<ReadVecAt<T>> {
-    pub(crate) fn read_vectored_at(fd: SharedFd, buf_vec: T, offset: u64) -> io::Result<Self> {
+    pub(crate) fn read_vectored_at(
+        fd: SharedFd,
+        buf_vec: T,
+        offset: u64,
+        flags: i32,
+    ) -> io::Result<Self> {
         Op::submit_with(ReadVecAt {
             fd,
             buf_vec,
             offset,
+            flags,
         })
     }
 }
 
 impl<T: IoVecBufMut> OpAble for ReadVecAt<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let ptr = self.buf_vec.write_iovec_ptr() as _;
         let len = self.buf_vec.write_iovec_len() as _;
         opcode::Readv::new(types::Fd(self.fd.raw_fd()), ptr, len)
             .offset(self.offset)
+            .rw_flags(self.flags)
             .build()
     }
 
@@ -263,6 +284,7 @@ impl<T: IoVecBufMut> OpAble for ReadVecAt<T> {
             self.buf_vec.write_iovec_ptr(),
             self.buf_vec.write_iovec_len().min(i32::MAX as usize) as _,
             self.offset,
+            self.flags,
         )
     }
 
@@ -276,6 +298,12 @@ impl<T: IoVecBufMut> OpAble for ReadVecAt<T> {
             System::IO::OVERLAPPED,
         };
 
+        if self.flags != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "read flags are not supported",
+            ));
+        }
         let seek_offset = self.offset;
         let mut nread = 0;
         let mut flags = 0;
@@ -332,17 +360,27 @@ pub(crate) mod impls {
         crate::syscall!(readv@NON_FD(fd, buf_vec as _, len as _))
     }
 
-    /// A wrapper of [`libc::preadv`]
+    /// A wrapper of [`libc::preadv`], or `preadv2` if there are `flags`
     pub(crate) fn read_vectored_at(
         fd: i32,
         buf_vec: *mut iovec,
         len: usize,
         offset: u64,
+        flags: i32,
     ) -> io::Result<MaybeFd> {
         let offset = libc::off_t::try_from(offset)
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "offset too big"))?;
 
-        crate::syscall!(preadv@NON_FD(fd, buf_vec as _, len as _, offset))
+        if flags == 0 {
+            return crate::syscall!(preadv@NON_FD(fd, buf_vec as _, len as _, offset));
+        }
+        #[cfg(target_os = "linux")]
+        return crate::syscall!(preadv2@NON_FD(fd, buf_vec as _, len as _, offset, flags));
+        #[cfg(not(target_os = "linux"))]
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "read flags are only supported on Linux",
+        ))
     }
 }
 
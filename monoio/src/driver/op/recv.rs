@@ -10,8 +10,12 @@ use std::{
 use io_uring::{opcode, types};
 #[cfg(unix)]
 use {
-    crate::net::unix::SocketAddr as UnixSocketAddr,
+    crate::net::unix::{
+        ancillary::{Control, RECV_FDS_FLAGS},
+        SocketAddr as UnixSocketAddr,
+    },
     libc::{sockaddr_in, sockaddr_in6, sockaddr_storage, socklen_t, AF_INET, AF_INET6},
+    std::os::unix::io::OwnedFd,
 };
 #[cfg(all(windows, any(feature = "legacy", feature = "poll-io")))]
 use {
@@ -32,11 +36,19 @@ use {
     },
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
-use super::{driver::ready::Direction, MaybeFd};
+use super::driver::ready::Direction;
+#[cfg(any(
+    all(target_os = "linux", feature = "iouring"),
+    feature = "legacy",
+    feature = "poll-io"
+))]
+use super::MaybeFd;
+use super::{super::shared_fd::SharedFd, Completion, Op, OpAble, OpBox};
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::buf::BufRing;
 use crate::{
-    buf::{IoBufMut, IoVecBufMut, IoVecMeta, MsgMeta},
+    buf::{write_vec_meta, IoBufMut, IoVecBufMut, IoVecMeta, MsgMeta},
     BufResult,
 };
 
@@ -122,6 +134,170 @@ impl<T: IoBufMut> OpAble for Recv<T> {
     }
 }
 
+/// A recv into the buffers of a provided buffer ring, which the kernel picks
+/// for every completion.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct RecvMulti {
+    fd: SharedFd,
+    /// Keeps the ring registered while the operation is in-flight.
+    pub(crate) ring: BufRing,
+    multishot: bool,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<RecvMulti> {
+    /// Receive until canceled, requires kernel 6.0+.
+    pub(crate) fn recv_multi(fd: &SharedFd, ring: BufRing) -> io::Result<Self> {
+        Op::submit_with(RecvMulti {
+            fd: fd.clone(),
+            ring,
+            multishot: true,
+        })
+    }
+
+    /// Receive once into a buffer of the ring, requires kernel 5.19+.
+    pub(crate) fn recv_select(fd: &SharedFd, ring: BufRing) -> io::Result<Self> {
+        Op::submit_with(RecvMulti {
+            fd: fd.clone(),
+            ring,
+            multishot: false,
+        })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for RecvMulti {
+    const MULTISHOT: bool = true;
+
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        let fd = types::Fd(self.fd.raw_fd());
+        if self.multishot {
+            return opcode::RecvMulti::new(fd, self.ring.bgid()).build();
+        }
+        opcode::Recv::new(fd, std::ptr::null_mut(), self.ring.buf_size() as _)
+            .buf_group(self.ring.bgid())
+            .build()
+            .flags(io_uring::squeue::Flags::BUFFER_SELECT)
+    }
+
+    fn on_ignored(&mut self, _result: &io::Result<MaybeFd>, flags: u32) {
+        // Give the buffer the kernel picked back to the ring.
+        if let Some(bid) = io_uring::cqueue::buffer_select(flags) {
+            drop(self.ring.take(bid, 0));
+        }
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    // Buffer rings only exist on io_uring.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "buffer rings can only be used with io_uring",
+        ))
+    }
+}
+
+/// A recv which builds its submission entry once, so it can be submitted over
+/// and over with the same fd and buffer.
+pub(crate) struct PreparedRecv<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    /// Reference to the in-flight buffer.
+    pub(crate) buf: T,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    entry: Option<io_uring::squeue::Entry>,
+}
+
+impl<T: IoBufMut> PreparedRecv<T> {
+    pub(crate) fn new(fd: SharedFd, buf: T) -> Self {
+        Self {
+            fd,
+            buf,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            entry: None,
+        }
+    }
+}
+
+impl<T: IoBufMut> Op<PreparedRecv<T>> {
+    pub(crate) fn prepared_recv(data: PreparedRecv<T>) -> io::Result<Self> {
+        Op::submit_with(data)
+    }
+
+    pub(crate) async fn result(self) -> (io::Result<usize>, PreparedRecv<T>) {
+        let Completion { mut data, meta } = self.await;
+        let res = meta.result.map(|v| v.into_inner() as _);
+
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                data.buf.set_init(n);
+            }
+        }
+        (res, data)
+    }
+}
+
+impl<T: IoBufMut> OpAble for PreparedRecv<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        // The buffer pointer is stable and its capacity does not change, so
+        // the entry stays valid across submissions.
+        let (fd, buf) = (&self.fd, &mut self.buf);
+        self.entry
+            .get_or_insert_with(|| {
+                opcode::Recv::new(
+                    types::Fd(fd.raw_fd()),
+                    buf.write_ptr(),
+                    buf.bytes_total() as _,
+                )
+                .build()
+            })
+            .clone()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        let fd = self.fd.as_raw_fd();
+        crate::syscall!(recv@NON_FD(
+            fd,
+            self.buf.write_ptr() as _,
+            self.buf.bytes_total().min(u32::MAX as usize),
+            0
+        ))
+    }
+
+    #[cfg(all(any(feature = "legacy", feature = "poll-io"), windows))]
+    fn legacy_call(&mut self) -> io::Result<MaybeFd> {
+        let fd = self.fd.as_raw_socket();
+        crate::syscall!(
+            recv@NON_FD(
+                fd as _,
+                self.buf.write_ptr(),
+                self.buf.bytes_total().min(i32::MAX as usize) as _,
+                0
+            ),
+            PartialOrd::lt,
+            0
+        )
+    }
+}
+
 pub(crate) struct RecvMsg<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -131,15 +307,52 @@ pub(crate) struct RecvMsg<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
     /// For multiple message recv in the future
-    pub(crate) info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, MsgMeta)>,
+    pub(crate) info: OpBox<(MaybeUninit<sockaddr_storage>, IoVecMeta, MsgMeta)>,
+    #[cfg(unix)]
+    flags: libc::c_int,
 }
 
 impl<T: IoBufMut> Op<RecvMsg<T>> {
-    pub(crate) fn recv_msg(fd: SharedFd, mut buf: T) -> io::Result<Self> {
-        let mut info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, MsgMeta)> =
-            Box::new((MaybeUninit::uninit(), IoVecMeta::from(&mut buf), unsafe {
-                std::mem::zeroed()
-            }));
+    pub(crate) fn recv_msg(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_with_flags(fd, buf, 0)
+    }
+
+    /// Receive with the given `MSG_*` flags, e.g. `MSG_PEEK`.
+    pub(crate) fn recv_msg_with_flags(fd: SharedFd, mut buf: T, flags: i32) -> io::Result<Self> {
+        let meta = IoVecMeta::from(&mut buf);
+        Self::recv_msg_meta(fd, buf, meta, flags)
+    }
+
+    pub(crate) async fn wait(self) -> BufResult<(usize, SocketAddr), T> {
+        let (res, mut buf) = self.wait_addr().await;
+        if let Ok((n, _)) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe { buf.set_init(n) };
+        }
+        (res, buf)
+    }
+}
+
+impl<T: IoVecBufMut> Op<RecvMsg<T>> {
+    pub(crate) fn recv_msg_vectored(fd: SharedFd, mut buf: T) -> io::Result<Self> {
+        let meta = write_vec_meta(&mut buf);
+        Self::recv_msg_meta(fd, buf, meta, 0)
+    }
+
+    pub(crate) async fn wait_vectored(self) -> BufResult<(usize, SocketAddr), T> {
+        let (res, mut buf) = self.wait_addr().await;
+        if let Ok((n, _)) = res {
+            // Safety: the kernel wrote `n` bytes to the buffers.
+            unsafe { buf.set_init(n) };
+        }
+        (res, buf)
+    }
+}
+
+impl<T: Unpin + 'static> Op<RecvMsg<T>> {
+    fn recv_msg_meta(fd: SharedFd, buf: T, meta: IoVecMeta, flags: i32) -> io::Result<Self> {
+        let mut info: OpBox<(MaybeUninit<sockaddr_storage>, IoVecMeta, MsgMeta)> =
+            OpBox::new((MaybeUninit::uninit(), meta, unsafe { std::mem::zeroed() }));
 
         #[cfg(unix)]
         {
@@ -154,15 +367,23 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
             info.2.dwBufferCount = info.1.write_wsabuf_len() as _;
             info.2.name = &mut info.0 as *mut _ as *mut SOCKADDR;
             info.2.namelen = std::mem::size_of::<sockaddr_storage>() as _;
+            info.2.dwFlags = flags as _;
         }
 
-        Op::submit_with(RecvMsg { fd, buf, info })
+        Op::submit_with(RecvMsg {
+            fd,
+            buf,
+            info,
+            #[cfg(unix)]
+            flags,
+        })
     }
 
-    pub(crate) async fn wait(self) -> BufResult<(usize, SocketAddr), T> {
+    // Returns the origin without marking the received bytes initialized.
+    async fn wait_addr(self) -> BufResult<(usize, SocketAddr), T> {
         let complete = self.await;
         let res = complete.meta.result.map(|v| v.into_inner() as _);
-        let mut buf = complete.data.buf;
+        let buf = complete.data.buf;
 
         let res = res.map(|n| {
             let storage = unsafe { complete.data.info.0.assume_init() };
@@ -201,9 +422,6 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
                 }
             };
 
-            // Safety: the kernel wrote `n` bytes to the buffer.
-            unsafe { buf.set_init(n) };
-
             (n, addr)
         });
         (res, buf)
@@ -222,10 +440,12 @@ static WSA_RECV_MSG: std::sync::OnceLock<
     ) -> i32,
 > = std::sync::OnceLock::new();
 
-impl<T: IoBufMut> OpAble for RecvMsg<T> {
+impl<T: Unpin + 'static> OpAble for RecvMsg<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut *self.info.2).build()
+        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut *self.info.2)
+            .flags(self.flags as _)
+            .build()
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -237,7 +457,7 @@ impl<T: IoBufMut> OpAble for RecvMsg<T> {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
     fn legacy_call(&mut self) -> io::Result<MaybeFd> {
         let fd = self.fd.as_raw_fd();
-        crate::syscall!(recvmsg@NON_FD(fd, &mut *self.info.2, 0))
+        crate::syscall!(recvmsg@NON_FD(fd, &mut *self.info.2, self.flags))
     }
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), windows))]
@@ -295,14 +515,36 @@ pub(crate) struct RecvMsgUnix<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
     /// For multiple message recv in the future
-    pub(crate) info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)>,
+    pub(crate) info: OpBox<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)>,
+    /// The control messages `info` points to.
+    control: Control,
+    flags: libc::c_int,
 }
 
 #[cfg(unix)]
 impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
-    pub(crate) fn recv_msg_unix(fd: SharedFd, mut buf: T) -> io::Result<Self> {
-        let mut info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)> =
-            Box::new((MaybeUninit::uninit(), IoVecMeta::from(&mut buf), unsafe {
+    pub(crate) fn recv_msg_unix(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_unix_control(fd, buf, Control::empty(), 0)
+    }
+
+    /// Receives control messages into `control` along with the data, see
+    /// [`wait_with_fds`](Self::wait_with_fds).
+    pub(crate) fn recv_msg_unix_with_control(
+        fd: SharedFd,
+        buf: T,
+        control: Control,
+    ) -> io::Result<Self> {
+        Self::recv_msg_unix_control(fd, buf, control, RECV_FDS_FLAGS)
+    }
+
+    fn recv_msg_unix_control(
+        fd: SharedFd,
+        mut buf: T,
+        mut control: Control,
+        flags: libc::c_int,
+    ) -> io::Result<Self> {
+        let mut info: OpBox<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)> =
+            OpBox::new((MaybeUninit::uninit(), IoVecMeta::from(&mut buf), unsafe {
                 std::mem::zeroed()
             }));
 
@@ -310,32 +552,46 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
         info.2.msg_iovlen = info.1.write_iovec_len() as _;
         info.2.msg_name = &mut info.0 as *mut _ as *mut libc::c_void;
         info.2.msg_namelen = std::mem::size_of::<sockaddr_storage>() as socklen_t;
+        control.set(&mut info.2);
 
-        Op::submit_with(RecvMsgUnix { fd, buf, info })
+        Op::submit_with(RecvMsgUnix {
+            fd,
+            buf,
+            info,
+            control,
+            flags,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<(usize, UnixSocketAddr), T> {
+        let (res, buf) = self.wait_with_fds().await;
+        (res.map(|(n, addr, _)| (n, addr)), buf)
+    }
+
+    pub(crate) async fn wait_with_fds(self) -> BufResult<(usize, UnixSocketAddr, Vec<OwnedFd>), T> {
         let complete = self.await;
         let res = complete.meta.result.map(|v| v.into_inner() as _);
-        let mut buf = complete.data.buf;
+        let mut data = complete.data;
 
         let res = res.map(|n| {
-            let storage = unsafe { complete.data.info.0.assume_init() };
-            let name_len = complete.data.info.2.msg_namelen;
+            let storage = unsafe { data.info.0.assume_init() };
+            let name_len = data.info.2.msg_namelen;
 
             let addr = unsafe {
                 let addr: &libc::sockaddr_un = transmute(&storage);
                 UnixSocketAddr::from_parts(*addr, name_len)
             };
+            // Safety: the recvmsg completed with the control buffer.
+            let fds = unsafe { data.control.take_fds(&data.info.2) };
 
             // Safety: the kernel wrote `n` bytes to the buffer.
             unsafe {
-                buf.set_init(n);
+                data.buf.set_init(n);
             }
 
-            (n, addr)
+            (n, addr, fds)
         });
-        (res, buf)
+        (res, data.buf)
     }
 }
 
@@ -343,7 +599,18 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
 impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _).build()
+        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _)
+            .flags(self.flags as u32)
+            .build()
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn on_ignored(&mut self, result: &io::Result<MaybeFd>, _flags: u32) {
+        // Close the descriptors nobody receives.
+        if result.is_ok() {
+            // Safety: the recvmsg completed with the control buffer.
+            drop(unsafe { self.control.take_fds(&self.info.2) });
+        }
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -355,6 +622,6 @@ impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_call(&mut self) -> io::Result<MaybeFd> {
         let fd = self.fd.as_raw_fd();
-        crate::syscall!(recvmsg@NON_FD(fd, &mut self.info.2 as *mut _, 0))
+        crate::syscall!(recvmsg@NON_FD(fd, &mut self.info.2 as *mut _, self.flags))
     }
 }
@@ -2,20 +2,37 @@ use std::{ffi::CString, path::Path};
 
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::MaybeFd;
-use super::{Op, OpAble};
-use crate::driver::util::cstr;
+use super::{open::dirfd, Op, OpAble};
+use crate::driver::{shared_fd::SharedFd, util::cstr};
 
 pub(crate) struct Rename {
     from: CString,
     to: CString,
+    /// Directories the paths are relative to(cwd if None)
+    from_dir: Option<SharedFd>,
+    to_dir: Option<SharedFd>,
 }
 
 impl Op<Rename> {
     pub(crate) fn rename(from: &Path, to: &Path) -> std::io::Result<Self> {
+        Self::rename_at(None, from, None, to)
+    }
+
+    pub(crate) fn rename_at(
+        from_dir: Option<&SharedFd>,
+        from: &Path,
+        to_dir: Option<&SharedFd>,
+        to: &Path,
+    ) -> std::io::Result<Self> {
         let from = cstr(from)?;
         let to = cstr(to)?;
 
-        Op::submit_with(Rename { from, to })
+        Op::submit_fs_with(Rename {
+            from,
+            to,
+            from_dir: from_dir.cloned(),
+            to_dir: to_dir.cloned(),
+        })
     }
 }
 
@@ -23,17 +40,17 @@ impl OpAble for Rename {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         use io_uring::{opcode::RenameAt, types};
-        use libc::AT_FDCWD;
 
         RenameAt::new(
-            types::Fd(AT_FDCWD),
+            types::Fd(dirfd(&self.from_dir)),
             self.from.as_ptr(),
-            types::Fd(AT_FDCWD),
+            types::Fd(dirfd(&self.to_dir)),
             self.to.as_ptr(),
         )
         .build()
     }
 
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_interest(&self) -> Option<(crate::driver::ready::Direction, usize)> {
         None
     }
@@ -41,9 +58,9 @@ impl OpAble for Rename {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
     fn legacy_call(&mut self) -> std::io::Result<MaybeFd> {
         crate::syscall!(renameat@NON_FD(
-            libc::AT_FDCWD,
+            dirfd(&self.from_dir),
             self.from.as_ptr(),
-            libc::AT_FDCWD,
+            dirfd(&self.to_dir),
             self.to.as_ptr()
         ))
     }
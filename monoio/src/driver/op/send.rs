@@ -11,13 +11,13 @@ use {
     windows_sys::Win32::Networking::WinSock::{send, WSASendMsg, SOCKET_ERROR},
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, Op, OpAble, OpBox};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::{driver::ready::Direction, MaybeFd};
 #[cfg(unix)]
-use crate::net::unix::SocketAddr as UnixSocketAddr;
+use crate::net::unix::{ancillary::Control, SocketAddr as UnixSocketAddr};
 use crate::{
-    buf::{IoBuf, IoVecBufMut, IoVecMeta, MsgMeta},
+    buf::{read_vec_meta, IoBuf, IoVecBuf, IoVecBufMut, IoVecMeta, MsgMeta},
     BufResult,
 };
 
@@ -131,7 +131,7 @@ pub(crate) struct SendMsg<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
     /// For multiple message send in the future
-    pub(crate) info: Box<(Option<SockAddr>, IoVecMeta, MsgMeta)>,
+    pub(crate) info: OpBox<(Option<SockAddr>, IoVecMeta, MsgMeta)>,
 }
 
 impl<T: IoBuf> Op<SendMsg<T>> {
@@ -140,14 +140,37 @@ impl<T: IoBuf> Op<SendMsg<T>> {
         buf: T,
         socket_addr: Option<SocketAddr>,
     ) -> io::Result<Self> {
-        let mut info: Box<(Option<SockAddr>, IoVecMeta, MsgMeta)> = Box::new((
-            socket_addr.map(Into::into),
-            IoVecMeta::from(&buf),
-            unsafe { std::mem::zeroed() },
-        ));
+        let meta = IoVecMeta::from(&buf);
+        Self::send_msg_meta(fd, buf, meta, socket_addr)
+    }
+}
+
+impl<T: IoVecBuf> Op<SendMsg<T>> {
+    pub(crate) fn send_msg_vectored(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<SocketAddr>,
+    ) -> io::Result<Self> {
+        let meta = read_vec_meta(&buf);
+        Self::send_msg_meta(fd, buf, meta, socket_addr)
+    }
+}
+
+impl<T: Unpin + 'static> Op<SendMsg<T>> {
+    fn send_msg_meta(
+        fd: SharedFd,
+        buf: T,
+        meta: IoVecMeta,
+        socket_addr: Option<SocketAddr>,
+    ) -> io::Result<Self> {
+        let mut info: OpBox<(Option<SockAddr>, IoVecMeta, MsgMeta)> =
+            OpBox::new((socket_addr.map(Into::into), meta, unsafe {
+                std::mem::zeroed()
+            }));
 
         #[cfg(unix)]
         {
+            let info = &mut *info;
             info.2.msg_iov = info.1.write_iovec_ptr();
             info.2.msg_iovlen = info.1.write_iovec_len() as _;
             match info.0.as_ref() {
@@ -163,6 +186,7 @@ impl<T: IoBuf> Op<SendMsg<T>> {
         }
         #[cfg(windows)]
         {
+            let info = &mut *info;
             info.2.lpBuffers = info.1.write_wsabuf_ptr();
             info.2.dwBufferCount = info.1.write_wsabuf_len() as _;
             match info.0.as_ref() {
@@ -188,7 +212,7 @@ impl<T: IoBuf> Op<SendMsg<T>> {
     }
 }
 
-impl<T: IoBuf> OpAble for SendMsg<T> {
+impl<T: Unpin + 'static> OpAble for SendMsg<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         #[allow(deprecated)]
@@ -249,7 +273,9 @@ pub(crate) struct SendMsgUnix<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
     /// For multiple message send in the future
-    pub(crate) info: Box<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)>,
+    pub(crate) info: OpBox<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)>,
+    /// The control messages `info` points to.
+    control: Control,
 }
 
 #[cfg(unix)]
@@ -259,27 +285,44 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
         buf: T,
         socket_addr: Option<UnixSocketAddr>,
     ) -> io::Result<Self> {
-        let mut info: Box<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)> = Box::new((
+        Self::send_msg_unix_with_control(fd, buf, socket_addr, Control::empty())
+    }
+
+    /// Sends the control messages of `control` along with the data.
+    pub(crate) fn send_msg_unix_with_control(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<UnixSocketAddr>,
+        mut control: Control,
+    ) -> io::Result<Self> {
+        let mut info: OpBox<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)> = OpBox::new((
             socket_addr.map(Into::into),
             IoVecMeta::from(&buf),
             unsafe { std::mem::zeroed() },
         ));
 
-        info.2.msg_iov = info.1.write_iovec_ptr();
-        info.2.msg_iovlen = info.1.write_iovec_len() as _;
+        let msg = &mut *info;
+        msg.2.msg_iov = msg.1.write_iovec_ptr();
+        msg.2.msg_iovlen = msg.1.write_iovec_len() as _;
 
-        match info.0.as_ref() {
+        match msg.0.as_ref() {
             Some(socket_addr) => {
-                info.2.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
-                info.2.msg_namelen = socket_addr.len();
+                msg.2.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
+                msg.2.msg_namelen = socket_addr.len();
             }
             None => {
-                info.2.msg_name = std::ptr::null_mut();
-                info.2.msg_namelen = 0;
+                msg.2.msg_name = std::ptr::null_mut();
+                msg.2.msg_namelen = 0;
             }
         }
+        control.set(&mut msg.2);
 
-        Op::submit_with(SendMsgUnix { fd, buf, info })
+        Op::submit_with(SendMsgUnix {
+            fd,
+            buf,
+            info,
+            control,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<usize, T> {
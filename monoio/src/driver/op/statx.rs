@@ -5,9 +5,14 @@ use io_uring::{opcode, types};
 #[cfg(target_os = "linux")]
 use libc::statx;
 
+#[cfg(any(
+    all(target_os = "linux", feature = "iouring"),
+    all(unix, any(feature = "legacy", feature = "poll-io"))
+))]
+use super::open::dirfd;
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use super::{driver::ready::Direction, MaybeFd};
-use super::{Op, OpAble};
+use super::{Op, OpAble, OpBox};
 use crate::driver::{shared_fd::SharedFd, util::cstr};
 
 #[derive(Debug)]
@@ -16,9 +21,9 @@ pub(crate) struct Statx<T> {
     #[cfg(target_os = "linux")]
     flags: i32,
     #[cfg(target_os = "linux")]
-    statx_buf: Box<MaybeUninit<statx>>,
+    statx_buf: OpBox<MaybeUninit<statx>>,
     #[cfg(target_os = "macos")]
-    stat_buf: Box<MaybeUninit<libc::stat>>,
+    stat_buf: OpBox<MaybeUninit<libc::stat>>,
     #[cfg(target_os = "macos")]
     follow_symlinks: bool,
 }
@@ -29,10 +34,10 @@ impl Op<FdStatx> {
     /// submit a statx operation
     #[cfg(target_os = "linux")]
     pub(crate) fn statx_using_fd(fd: SharedFd, flags: i32) -> std::io::Result<Self> {
-        Op::submit_with(Statx {
+        Op::submit_fs_with(Statx {
             inner: fd,
             flags,
-            statx_buf: Box::new(MaybeUninit::uninit()),
+            statx_buf: OpBox::new(MaybeUninit::uninit()),
         })
     }
 
@@ -46,10 +51,10 @@ impl Op<FdStatx> {
 
     #[cfg(target_os = "macos")]
     pub(crate) fn statx_using_fd(fd: SharedFd, follow_symlinks: bool) -> std::io::Result<Self> {
-        Op::submit_with(Statx {
+        Op::submit_fs_with(Statx {
             inner: fd,
             follow_symlinks,
-            stat_buf: Box::new(MaybeUninit::uninit()),
+            stat_buf: OpBox::new(MaybeUninit::uninit()),
         })
     }
 
@@ -111,17 +116,28 @@ impl OpAble for FdStatx {
     }
 }
 
-type PathStatx = Statx<CString>;
+/// Path and the directory it is relative to(cwd if None)
+type PathStatx = Statx<(Option<SharedFd>, CString)>;
 
 impl Op<PathStatx> {
     /// submit a statx operation
     #[cfg(target_os = "linux")]
     pub(crate) fn statx_using_path<P: AsRef<Path>>(path: P, flags: i32) -> std::io::Result<Self> {
+        Self::statx_at(None, path, flags)
+    }
+
+    /// submit a statx operation relative to a directory
+    #[cfg(target_os = "linux")]
+    pub(crate) fn statx_at<P: AsRef<Path>>(
+        dir: Option<&SharedFd>,
+        path: P,
+        flags: i32,
+    ) -> std::io::Result<Self> {
         let path = cstr(path.as_ref())?;
-        Op::submit_with(Statx {
-            inner: path,
+        Op::submit_fs_with(Statx {
+            inner: (dir.cloned(), path),
             flags,
-            statx_buf: Box::new(MaybeUninit::uninit()),
+            statx_buf: OpBox::new(MaybeUninit::uninit()),
         })
     }
 
@@ -137,12 +153,22 @@ impl Op<PathStatx> {
     pub(crate) fn statx_using_path<P: AsRef<Path>>(
         path: P,
         follow_symlinks: bool,
+    ) -> std::io::Result<Self> {
+        Self::statx_at(None, path, follow_symlinks)
+    }
+
+    /// submit a stat operation relative to a directory
+    #[cfg(target_os = "macos")]
+    pub(crate) fn statx_at<P: AsRef<Path>>(
+        dir: Option<&SharedFd>,
+        path: P,
+        follow_symlinks: bool,
     ) -> std::io::Result<Self> {
         let path = cstr(path.as_ref())?;
-        Op::submit_with(Statx {
-            inner: path,
+        Op::submit_fs_with(Statx {
+            inner: (dir.cloned(), path),
             follow_symlinks,
-            stat_buf: Box::new(MaybeUninit::uninit()),
+            stat_buf: OpBox::new(MaybeUninit::uninit()),
         })
     }
 
@@ -160,10 +186,14 @@ impl OpAble for PathStatx {
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let statxbuf = self.statx_buf.as_mut_ptr() as *mut _;
 
-        opcode::Statx::new(types::Fd(libc::AT_FDCWD), self.inner.as_ptr(), statxbuf)
-            .flags(self.flags)
-            .mask(libc::STATX_ALL)
-            .build()
+        opcode::Statx::new(
+            types::Fd(dirfd(&self.inner.0)),
+            self.inner.1.as_ptr(),
+            statxbuf,
+        )
+        .flags(self.flags)
+        .mask(libc::STATX_ALL)
+        .build()
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -174,8 +204,8 @@ impl OpAble for PathStatx {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), target_os = "linux"))]
     fn legacy_call(&mut self) -> std::io::Result<MaybeFd> {
         crate::syscall!(statx@NON_FD(
-            libc::AT_FDCWD,
-            self.inner.as_ptr(),
+            dirfd(&self.inner.0),
+            self.inner.1.as_ptr(),
             self.flags,
             libc::STATX_ALL,
             self.statx_buf.as_mut_ptr() as *mut _
@@ -189,16 +219,15 @@ impl OpAble for PathStatx {
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), target_os = "macos"))]
     fn legacy_call(&mut self) -> std::io::Result<MaybeFd> {
-        if self.follow_symlinks {
-            crate::syscall!(stat@NON_FD(
-                self.inner.as_ptr(),
-                self.stat_buf.as_mut_ptr() as *mut _
-            ))
-        } else {
-            crate::syscall!(lstat@NON_FD(
-                self.inner.as_ptr(),
-                self.stat_buf.as_mut_ptr() as *mut _
-            ))
-        }
+        crate::syscall!(fstatat@NON_FD(
+            dirfd(&self.inner.0),
+            self.inner.1.as_ptr(),
+            self.stat_buf.as_mut_ptr() as *mut _,
+            if self.follow_symlinks {
+                0
+            } else {
+                libc::AT_SYMLINK_NOFOLLOW
+            }
+        ))
     }
 }
@@ -16,7 +16,7 @@ impl Op<Symlink> {
     ) -> io::Result<Op<Symlink>> {
         let from = cstr(from.as_ref())?;
         let to = cstr(to.as_ref())?;
-        Op::submit_with(Symlink { from, to })
+        Op::submit_fs_with(Symlink { from, to })
     }
 }
 
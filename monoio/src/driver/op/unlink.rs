@@ -2,33 +2,39 @@ use std::{ffi::CString, io, path::Path};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use io_uring::{opcode, squeue::Entry, types::Fd};
-#[cfg(all(target_os = "linux", feature = "iouring"))]
-use libc::{AT_FDCWD, AT_REMOVEDIR};
+use libc::AT_REMOVEDIR;
 
-use super::{Op, OpAble};
-use crate::driver::util::cstr;
+use super::{open::dirfd, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::{op::MaybeFd, ready::Direction};
+use crate::driver::{shared_fd::SharedFd, util::cstr};
 
 pub(crate) struct Unlink {
     path: CString,
+    /// Directory the path is relative to(cwd if None)
+    dir: Option<SharedFd>,
     remove_dir: bool,
 }
 
 impl Op<Unlink> {
     pub(crate) fn unlink<P: AsRef<Path>>(path: P) -> io::Result<Op<Unlink>> {
-        let path = cstr(path.as_ref())?;
-        Op::submit_with(Unlink {
-            path,
-            remove_dir: false,
-        })
+        Self::unlink_at(None, path, false)
     }
 
     pub(crate) fn rmdir<P: AsRef<Path>>(path: P) -> io::Result<Op<Unlink>> {
+        Self::unlink_at(None, path, true)
+    }
+
+    pub(crate) fn unlink_at<P: AsRef<Path>>(
+        dir: Option<&SharedFd>,
+        path: P,
+        remove_dir: bool,
+    ) -> io::Result<Op<Unlink>> {
         let path = cstr(path.as_ref())?;
-        Op::submit_with(Unlink {
+        Op::submit_fs_with(Unlink {
             path,
-            remove_dir: true,
+            dir: dir.cloned(),
+            remove_dir,
         })
     }
 }
@@ -36,7 +42,7 @@ impl Op<Unlink> {
 impl OpAble for Unlink {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> Entry {
-        opcode::UnlinkAt::new(Fd(AT_FDCWD), self.path.as_c_str().as_ptr())
+        opcode::UnlinkAt::new(Fd(dirfd(&self.dir)), self.path.as_c_str().as_ptr())
             .flags(if self.remove_dir { AT_REMOVEDIR } else { 0 })
             .build()
     }
@@ -48,10 +54,10 @@ impl OpAble for Unlink {
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_call(&mut self) -> io::Result<MaybeFd> {
-        if self.remove_dir {
-            crate::syscall!(rmdir@NON_FD(self.path.as_c_str().as_ptr()))
-        } else {
-            crate::syscall!(unlink@NON_FD(self.path.as_c_str().as_ptr()))
-        }
+        crate::syscall!(unlinkat@NON_FD(
+            dirfd(&self.dir),
+            self.path.as_c_str().as_ptr(),
+            if self.remove_dir { AT_REMOVEDIR } else { 0 }
+        ))
     }
 }
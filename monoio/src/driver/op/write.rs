@@ -55,6 +55,9 @@ impl<T: IoBuf> Op<Write<T>> {
 }
 
 impl<T: IoBuf> OpAble for Write<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         // Refers to https://docs.rs/io-uring/latest/io_uring/opcode/struct.Write.html.
@@ -108,6 +111,9 @@ impl<T: IoBuf> Op<WriteAt<T>> {
 }
 
 impl<T: IoBuf> OpAble for WriteAt<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Write::new(
@@ -159,6 +165,9 @@ impl<T: IoVecBuf> Op<WriteVec<T>> {
 }
 
 impl<T: IoVecBuf> OpAble for WriteVec<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let ptr = self.buf_vec.read_iovec_ptr() as *const _;
@@ -213,19 +222,42 @@ pub(crate) struct WriteVecAt<T> {
     /// If `offset` is set to `-1`, the offset will use (and advance) the file position, like
     /// the writev(2) system calls.
     offset: u64,
+    /// `RWF_*` flags of `pwritev2(2)`.
+    flags: i32,
     buf_vec: T,
 }
 
+#[cfg(not(windows))]
+impl<T: IoVecBuf> Op<WriteVecAt<T>> {
+    pub(crate) fn write_vectored_at(
+        fd: SharedFd,
+        buf_vec: T,
+        offset: u64,
+        flags: i32,
+    ) -> io::Result<Self> {
+        Op::submit_with(WriteVecAt {
+            fd,
+            offset,
+            flags,
+            buf_vec,
+        })
+    }
+}
+
 #[cfg(not(windows))]
 impl<T: IoVecBuf> OpAble for WriteVecAt<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    const IOPOLL: bool = true;
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Writev::new(
-            types::Fd(libc::AT_FDCWD),
+            types::Fd(self.fd.raw_fd()),
             self.buf_vec.read_iovec_ptr(),
             self.buf_vec.read_iovec_len() as _,
         )
         .offset(self.offset)
+        .rw_flags(self.flags)
         .build()
     }
 
@@ -243,6 +275,7 @@ impl<T: IoVecBuf> OpAble for WriteVecAt<T> {
             self.buf_vec.read_iovec_ptr(),
             self.buf_vec.read_iovec_len(),
             self.offset,
+            self.flags,
         )
     }
 }
@@ -280,17 +313,27 @@ pub(crate) mod impls {
         crate::syscall!(writev@NON_FD(fd, buf_vec as _, len as _))
     }
 
-    /// A wrapper of [`libc::pwritev`]
+    /// A wrapper of [`libc::pwritev`], or `pwritev2` if there are `flags`
     pub(crate) fn write_vectored_at(
         fd: i32,
         buf_vec: *const iovec,
         len: usize,
         offset: u64,
+        flags: i32,
     ) -> io::Result<MaybeFd> {
         let offset = libc::off_t::try_from(offset)
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "offset too big"))?;
 
-        crate::syscall!(pwritev@NON_FD(fd, buf_vec as _, len as _, offset))
+        if flags == 0 {
+            return crate::syscall!(pwritev@NON_FD(fd, buf_vec as _, len as _, offset));
+        }
+        #[cfg(target_os = "linux")]
+        return crate::syscall!(pwritev2@NON_FD(fd, buf_vec as _, len as _, offset, flags));
+        #[cfg(not(target_os = "linux"))]
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "write flags are only supported on Linux",
+        ))
     }
 }
 
@@ -1,7 +1,10 @@
-use std::{io, task::Context, time::Duration};
+use std::{io, os::fd::RawFd, task::Context, time::Duration};
 
 use super::{op::MaybeFd, ready::Direction, scheduled_io::ScheduledIo};
-use crate::{driver::op::CompletionMeta, utils::slab::Slab};
+use crate::{
+    driver::op::CompletionMeta,
+    utils::{slab::Slab, PollIoStats},
+};
 
 /// Poller with io dispatch.
 // TODO: replace legacy impl with this Poll.
@@ -9,6 +12,10 @@ pub(crate) struct Poll {
     pub(crate) io_dispatch: Slab<ScheduledIo>,
     poll: mio::Poll,
     events: mio::Events,
+    // Deregistrations deferred until the next park, so fds converted back to
+    // poll mode before that keep their registration.
+    deferred: Vec<(RawFd, usize, mio::Interest)>,
+    pub(crate) stats: PollIoStats,
 }
 
 impl Poll {
@@ -18,9 +25,15 @@ impl Poll {
             io_dispatch: Slab::new(),
             poll: mio::Poll::new()?,
             events: mio::Events::with_capacity(capacity),
+            deferred: Vec::new(),
+            stats: PollIoStats::default(),
         })
     }
 
+    pub(crate) fn set_events_capacity(&mut self, capacity: usize) {
+        self.events = mio::Events::with_capacity(capacity);
+    }
+
     #[inline]
     pub(crate) fn tick(&mut self, timeout: Option<Duration>) -> io::Result<()> {
         match self.poll.poll(&mut self.events, timeout) {
@@ -57,6 +70,60 @@ impl Poll {
         }
     }
 
+    /// Registers `fd`, reusing its deferred deregistration if there is one.
+    pub(crate) fn register_fd(&mut self, fd: RawFd, interest: mio::Interest) -> io::Result<usize> {
+        if let Some(i) = self
+            .deferred
+            .iter()
+            .position(|&(f, _, i)| f == fd && i == interest)
+        {
+            let (_, token, _) = self.deferred.swap_remove(i);
+            self.stats.reused += 1;
+            return Ok(token);
+        }
+        let token = self.register(&mut mio::unix::SourceFd(&fd), interest)?;
+        self.stats.registered += 1;
+        Ok(token)
+    }
+
+    /// Deregisters `fd` on the next [`flush_deferred`](Poll::flush_deferred).
+    ///
+    /// The fd must not be closed or leave the runtime before, see
+    /// [`flush_fd`](Poll::flush_fd).
+    pub(crate) fn deregister_deferred(&mut self, fd: RawFd, token: usize, interest: mio::Interest) {
+        self.deferred.push((fd, token, interest));
+    }
+
+    /// Performs the deferred deregistrations.
+    pub(crate) fn flush_deferred(&mut self) {
+        if self.deferred.is_empty() {
+            return;
+        }
+        for (fd, token, _) in std::mem::take(&mut self.deferred) {
+            self.deregister_now(fd, token);
+        }
+        self.stats.flushes += 1;
+    }
+
+    /// Performs the deferred deregistration of `fd` right away, if any.
+    pub(crate) fn flush_fd(&mut self, fd: RawFd) {
+        if let Some(i) = self.deferred.iter().position(|&(f, ..)| f == fd) {
+            let (fd, token, _) = self.deferred.swap_remove(i);
+            self.deregister_now(fd, token);
+        }
+    }
+
+    fn deregister_now(&mut self, fd: RawFd, token: usize) {
+        // The fd is in completion mode by now, the registration only has to
+        // be gone and errors are not reported to anyone.
+        let _ = self
+            .poll
+            .registry()
+            .deregister(&mut mio::unix::SourceFd(&fd));
+        self.io_dispatch.remove(token);
+        self.stats.deregistered += 1;
+    }
+
     pub(crate) fn deregister(
         &mut self,
         source: &mut impl mio::event::Source,
@@ -233,6 +233,8 @@ impl fmt::Debug for Ready {
 pub(crate) enum Direction {
     Read,
     Write,
+    // Waiting for the peer to close, without reading.
+    ReadClosed,
 }
 
 impl Direction {
@@ -240,6 +242,7 @@ impl Direction {
         match self {
             Direction::Read => Ready::READABLE | Ready::READ_CLOSED | Ready::READ_CANCELED,
             Direction::Write => Ready::WRITABLE | Ready::WRITE_CLOSED | Ready::WRITE_CANCELED,
+            Direction::ReadClosed => Ready::READ_CLOSED | Ready::READ_CANCELED,
         }
     }
 }
@@ -9,6 +9,14 @@ pub(crate) struct ScheduledIo {
     reader: Option<Waker>,
     /// Waker used for AsyncWrite.
     writer: Option<Waker>,
+    /// Waker used for waiting on the peer to close.
+    closer: Option<Waker>,
+    /// Whether the reader or writer is waiting to be woken. Wakers are kept
+    /// after waking, so a task polling the same io again does not clone its
+    /// waker every time.
+    reader_armed: bool,
+    writer_armed: bool,
+    closer_armed: bool,
 }
 
 impl Default for ScheduledIo {
@@ -24,6 +32,10 @@ impl ScheduledIo {
             readiness: Ready::EMPTY,
             reader: None,
             writer: None,
+            closer: None,
+            reader_armed: false,
+            writer_armed: false,
+            closer_armed: false,
         }
     }
 
@@ -40,16 +52,35 @@ impl ScheduledIo {
 
     #[inline]
     pub(crate) fn wake(&mut self, ready: Ready) {
-        if ready.is_readable() {
-            if let Some(waker) = self.reader.take() {
-                waker.wake();
+        if ready.is_readable() && std::mem::take(&mut self.reader_armed) {
+            if let Some(waker) = &self.reader {
+                crate::runtime::isolate_wake(|| waker.wake_by_ref());
             }
         }
-        if ready.is_writable() {
-            if let Some(waker) = self.writer.take() {
-                waker.wake();
+        if ready.is_writable() && std::mem::take(&mut self.writer_armed) {
+            if let Some(waker) = &self.writer {
+                crate::runtime::isolate_wake(|| waker.wake_by_ref());
             }
         }
+        let closed = ready & Direction::ReadClosed.mask();
+        if !closed.is_empty() && std::mem::take(&mut self.closer_armed) {
+            if let Some(waker) = &self.closer {
+                crate::runtime::isolate_wake(|| waker.wake_by_ref());
+            }
+        }
+    }
+
+    /// The cancellation readiness of the directions waited for.
+    #[cfg(feature = "legacy")]
+    pub(crate) fn waiting(&self) -> Ready {
+        let mut ready = Ready::EMPTY;
+        if self.reader_armed || self.closer_armed {
+            ready |= Ready::READ_CANCELED;
+        }
+        if self.writer_armed {
+            ready |= Ready::WRITE_CANCELED;
+        }
+        ready
     }
 
     #[inline]
@@ -74,10 +105,12 @@ impl ScheduledIo {
 
     #[inline]
     pub(crate) fn set_waker(&mut self, cx: &mut Context<'_>, direction: Direction) {
-        let slot = match direction {
-            Direction::Read => &mut self.reader,
-            Direction::Write => &mut self.writer,
+        let (slot, armed) = match direction {
+            Direction::Read => (&mut self.reader, &mut self.reader_armed),
+            Direction::Write => (&mut self.writer, &mut self.writer_armed),
+            Direction::ReadClosed => (&mut self.closer, &mut self.closer_armed),
         };
+        *armed = true;
         match slot {
             Some(existing) => {
                 if !existing.will_wake(cx.waker()) {
@@ -90,3 +123,66 @@ impl ScheduledIo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::Wake,
+    };
+
+    use super::*;
+
+    struct CountWake(AtomicUsize);
+
+    impl Wake for CountWake {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn waker_is_cached_across_wakes() {
+        let count = Arc::new(CountWake(AtomicUsize::new(0)));
+        let waker = Waker::from(count.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut io = ScheduledIo::new();
+
+        for i in 1..=3 {
+            assert!(io.poll_readiness(&mut cx, Direction::Read).is_pending());
+            io.wake(Ready::READABLE);
+            io.wake(Ready::READABLE);
+            assert_eq!(count.0.load(Ordering::Relaxed), i);
+            // Only the cached clone is held besides ours.
+            assert_eq!(Arc::strong_count(&count), 3);
+        }
+        io.wake(Ready::WRITABLE);
+        assert_eq!(count.0.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn closer_is_woken_by_read_closed_only() {
+        let count = Arc::new(CountWake(AtomicUsize::new(0)));
+        let waker = Waker::from(count.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut io = ScheduledIo::new();
+
+        assert!(io
+            .poll_readiness(&mut cx, Direction::ReadClosed)
+            .is_pending());
+        io.set_readiness(|curr| curr | Ready::READABLE);
+        io.wake(Ready::READABLE);
+        assert_eq!(count.0.load(Ordering::Relaxed), 0);
+        assert!(io
+            .poll_readiness(&mut cx, Direction::ReadClosed)
+            .is_pending());
+
+        io.set_readiness(|curr| curr | Ready::READ_CLOSED);
+        io.wake(Ready::READ_CLOSED);
+        assert_eq!(count.0.load(Ordering::Relaxed), 1);
+        assert!(io.poll_readiness(&mut cx, Direction::ReadClosed).is_ready());
+    }
+}
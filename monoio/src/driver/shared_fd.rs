@@ -44,16 +44,13 @@ impl State {
         };
         // TODO: only Init state can convert?
         if matches!(state, UringState::Init) {
-            let mut source = mio::unix::SourceFd(&fd);
             crate::syscall!(fcntl@RAW(fd, libc::F_SETFL, libc::O_NONBLOCK))?;
             let reg = CURRENT
                 .with(|inner| match inner {
                     #[cfg(all(target_os = "linux", feature = "iouring"))]
-                    crate::driver::Inner::Uring(r) => super::IoUringDriver::register_poll_io(
-                        r,
-                        &mut source,
-                        super::ready::RW_INTERESTS,
-                    ),
+                    crate::driver::Inner::Uring(r) => {
+                        super::IoUringDriver::register_poll_io_fd(r, fd, super::ready::RW_INTERESTS)
+                    }
                     #[cfg(feature = "legacy")]
                     crate::driver::Inner::Legacy(_) => panic!("unexpected legacy runtime"),
                 })
@@ -85,20 +82,18 @@ impl State {
         let Some(token) = inner else {
             return Err(io::Error::new(io::ErrorKind::Other, "empty token"));
         };
-        let mut source = mio::unix::SourceFd(&fd);
         crate::syscall!(fcntl@RAW(fd, libc::F_SETFL, 0))?;
-        CURRENT
-            .with(|inner| match inner {
-                #[cfg(all(target_os = "linux", feature = "iouring"))]
-                crate::driver::Inner::Uring(r) => {
-                    super::IoUringDriver::deregister_poll_io(r, &mut source, *token)
-                }
-                #[cfg(feature = "legacy")]
-                crate::driver::Inner::Legacy(_) => panic!("unexpected legacy runtime"),
-            })
-            .inspect_err(|_| {
-                let _ = crate::syscall!(fcntl@RAW(fd, libc::F_SETFL, libc::O_NONBLOCK));
-            })?;
+        CURRENT.with(|inner| match inner {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            crate::driver::Inner::Uring(r) => super::IoUringDriver::deregister_poll_io_deferred(
+                r,
+                fd,
+                *token,
+                super::ready::RW_INTERESTS,
+            ),
+            #[cfg(feature = "legacy")]
+            crate::driver::Inner::Legacy(_) => panic!("unexpected legacy runtime"),
+        });
         *self = State::Uring(UringState::Init);
         Ok(())
     }
@@ -174,10 +169,9 @@ impl SharedFd {
                 true => {
                     #[cfg(feature = "poll-io")]
                     {
-                        let mut source = mio::unix::SourceFd(&fd);
-                        Reg::UringLegacy(super::IoUringDriver::register_poll_io(
+                        Reg::UringLegacy(super::IoUringDriver::register_poll_io_fd(
                             inner,
-                            &mut source,
+                            fd,
                             super::ready::RW_INTERESTS,
                         ))
                     }
@@ -324,21 +318,26 @@ impl SharedFd {
     #[cfg(unix)]
     /// Try unwrap Rc, then deregister if registered and return rawfd.
     /// Note: this action will consume self and return rawfd without closing it.
+    // Without a driver the state is uninhabited, and so is the fd.
+    #[cfg_attr(
+        not(any(all(target_os = "linux", feature = "iouring"), feature = "legacy")),
+        allow(unreachable_code, unused_variables)
+    )]
     pub(crate) fn try_unwrap(self) -> Result<RawFd, Self> {
-        use std::mem::{ManuallyDrop, MaybeUninit};
+        use std::mem::ManuallyDrop;
 
         let fd = self.inner.fd;
         match Rc::try_unwrap(self.inner) {
             Ok(inner) => {
                 // Only drop Inner's state, skip its drop impl.
-                let mut inner_skip_drop = ManuallyDrop::new(inner);
-                #[allow(invalid_value)]
-                #[allow(clippy::uninit_assumed_init)]
-                let mut state = unsafe { MaybeUninit::uninit().assume_init() };
-                std::mem::swap(&mut inner_skip_drop.state, &mut state);
+                let inner_skip_drop = ManuallyDrop::new(inner);
+                // Safety: the state is moved out of an Inner which is never
+                // dropped, nor used again.
+                #[cfg_attr(not(feature = "legacy"), allow(unused_variables))]
+                let state = unsafe { std::ptr::read(&inner_skip_drop.state) }.into_inner();
 
                 #[cfg(feature = "legacy")]
-                let state = unsafe { &*state.get() };
+                let state = &state;
 
                 #[cfg(feature = "legacy")]
                 #[allow(irrefutable_let_patterns)]
@@ -365,6 +364,8 @@ impl SharedFd {
                         })
                     }
                 }
+                #[cfg(all(target_os = "linux", feature = "iouring", feature = "poll-io"))]
+                flush_poll_io_fd(fd);
                 Ok(fd)
             }
             Err(inner) => Err(Self { inner }),
@@ -426,27 +427,45 @@ impl SharedFd {
     /// An FD cannot be closed until all in-flight operation have completed.
     /// This prevents bugs where in-flight reads could operate on the incorrect
     /// file descriptor.
-    pub(crate) async fn close(self) {
-        // Here we only submit close op for uring mode.
-        // Fd will be closed when Inner drops for legacy mode.
+    ///
+    /// Returns the result of closing the fd, which is `Ok` if it is still
+    /// held by in-flight operations and only closed once they complete.
+    pub(crate) async fn close(self) -> io::Result<()> {
+        // Submit close op for uring mode, close it right away for legacy mode.
+        #[allow(unused_mut, unused_variables)]
+        let mut this = self;
         #[cfg(all(target_os = "linux", feature = "iouring"))]
         {
-            let fd = self.inner.fd;
-            let mut this = self;
+            let fd = this.inner.fd;
             #[allow(irrefutable_let_patterns)]
             if let State::Uring(uring_state) = unsafe { &mut *this.inner.state.get() } {
                 if Rc::get_mut(&mut this.inner).is_some() {
+                    #[cfg(feature = "poll-io")]
+                    flush_poll_io_fd(fd);
                     *uring_state = match super::op::Op::close(fd) {
                         Ok(op) => UringState::Closing(op),
                         Err(_) => {
-                            let _ = unsafe { std::fs::File::from_raw_fd(fd) };
-                            return;
+                            // Do not close it again on drop.
+                            *uring_state = UringState::Closed;
+                            return crate::syscall!(close@RAW(fd)).map(drop);
                         }
                     };
                 }
-                this.inner.closed().await;
+                return this.inner.closed().await;
             }
         }
+        // If in-flight operations hold the fd, it is closed when the last of
+        // them completes.
+        #[cfg(all(unix, feature = "legacy"))]
+        if let Ok(fd) = this.try_unwrap() {
+            return crate::syscall!(close@RAW(fd)).map(drop);
+        }
+        #[cfg(all(windows, feature = "legacy"))]
+        if let Ok(socket) = this.try_unwrap() {
+            use windows_sys::Win32::Networking::WinSock::closesocket;
+            return crate::syscall!(closesocket@RAW(socket as _), PartialEq::ne, 0).map(drop);
+        }
+        Ok(())
     }
 
     #[cfg(feature = "poll-io")]
@@ -476,7 +495,7 @@ impl SharedFd {
 impl Inner {
     /// Completes when the FD has been closed.
     /// Should only be called for uring mode.
-    async fn closed(&self) {
+    async fn closed(&self) -> io::Result<()> {
         use std::task::Poll;
 
         crate::macros::support::poll_fn(|cx| {
@@ -503,19 +522,18 @@ impl Inner {
                         Poll::Pending
                     }
                     UringState::Closing(op) => {
-                        // Nothing to do if the close operation failed.
-                        let _ = ready!(Pin::new(op).poll(cx));
+                        let res = ready!(Pin::new(op).poll(cx)).meta.result;
                         *uring_state = UringState::Closed;
-                        Poll::Ready(())
+                        Poll::Ready(res.map(drop))
                     }
-                    UringState::Closed => Poll::Ready(()),
+                    UringState::Closed => Poll::Ready(Ok(())),
                     #[cfg(feature = "poll-io")]
-                    UringState::Legacy(_) => Poll::Ready(()),
+                    UringState::Legacy(_) => Poll::Ready(Ok(())),
                 };
             }
-            Poll::Ready(())
+            Poll::Ready(Ok(()))
         })
-        .await;
+        .await
     }
 }
 
@@ -528,19 +546,44 @@ impl Drop for Inner {
         match state {
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             State::Uring(UringState::Init) | State::Uring(UringState::Waiting(..)) => {
+                if matches!(state, State::Uring(UringState::Init)) {
+                    crate::runtime::record_close_on_drop(fd);
+                }
+                #[cfg(feature = "poll-io")]
+                flush_poll_io_fd(fd);
                 if super::op::Op::close(fd).is_err() {
                     let _ = unsafe { std::fs::File::from_raw_fd(fd) };
                 };
             }
             #[cfg(feature = "legacy")]
-            State::Legacy(idx) => drop_legacy(fd, *idx),
+            State::Legacy(idx) => {
+                crate::runtime::record_close_on_drop(fd);
+                drop_legacy(fd, *idx)
+            }
             #[cfg(all(target_os = "linux", feature = "iouring", feature = "poll-io"))]
-            State::Uring(UringState::Legacy(idx)) => drop_uring_legacy(fd, *idx),
+            State::Uring(UringState::Legacy(idx)) => {
+                crate::runtime::record_close_on_drop(fd);
+                drop_uring_legacy(fd, *idx)
+            }
             _ => {}
         }
     }
 }
 
+/// Deregisters `fd` from the poller if its deregistration was deferred, it
+/// must not stay registered once closed or handed out.
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "poll-io"))]
+fn flush_poll_io_fd(fd: RawFd) {
+    if CURRENT.is_set() {
+        CURRENT.with(|inner| {
+            #[allow(irrefutable_let_patterns)]
+            if let super::Inner::Uring(inner) = inner {
+                super::IoUringDriver::flush_poll_io_fd(inner, fd);
+            }
+        })
+    }
+}
+
 #[allow(unused_mut)]
 #[cfg(feature = "legacy")]
 fn drop_legacy(mut fd: RawFd, idx: Option<usize>) {
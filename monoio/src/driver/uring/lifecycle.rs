@@ -2,12 +2,15 @@
 //! Partly borrow from tokio-uring.
 
 use std::{
+    collections::VecDeque,
     io,
     task::{Context, Poll, Waker},
 };
 
+use io_uring::cqueue::more;
+
 use crate::{
-    driver::op::{CompletionMeta, MaybeFd},
+    driver::op::{CompletionMeta, MaybeFd, OpAble},
     utils::slab::Ref,
 };
 
@@ -20,16 +23,42 @@ enum Lifecycle {
 
     /// The submitter no longer has interest in the operation result. The state
     /// must be passed to the driver and held until the operation completes.
-    #[allow(dead_code)]
-    Ignored(Box<dyn std::any::Any>),
+    Ignored(Box<dyn IgnoredData>),
 
     /// The operation has completed.
     Completed(io::Result<MaybeFd>, u32),
+
+    /// A multishot operation has posted completions not polled yet, the last
+    /// one ends it if it has no `IORING_CQE_F_MORE` flag.
+    Multi(VecDeque<(io::Result<MaybeFd>, u32)>, Option<Waker>),
+}
+
+/// The state of an ignored operation, which is handed its completions.
+trait IgnoredData {
+    fn on_ignored(&mut self, result: &io::Result<MaybeFd>, flags: u32);
+}
+
+impl<T: OpAble> IgnoredData for T {
+    #[inline]
+    fn on_ignored(&mut self, result: &io::Result<MaybeFd>, flags: u32) {
+        OpAble::on_ignored(self, result, flags);
+    }
+}
+
+/// An ignored operation without state.
+struct NoData;
+
+impl IgnoredData for NoData {
+    #[inline]
+    fn on_ignored(&mut self, _result: &io::Result<MaybeFd>, _flags: u32) {}
 }
 
 pub(crate) struct MaybeFdLifecycle {
     is_fd: bool,
     lifecycle: Lifecycle,
+    /// The timeout linked to the operation, which the kernel reads when
+    /// submitting it.
+    link_timeout: Option<Box<io_uring::types::Timespec>>,
 }
 
 impl MaybeFdLifecycle {
@@ -38,8 +67,17 @@ impl MaybeFdLifecycle {
         Self {
             is_fd,
             lifecycle: Lifecycle::Submitted,
+            link_timeout: None,
         }
     }
+
+    /// Keeps `timeout` alive with the operation, returning its address.
+    pub(crate) fn set_link_timeout(
+        &mut self,
+        timeout: io_uring::types::Timespec,
+    ) -> *const io_uring::types::Timespec {
+        &**self.link_timeout.insert(Box::new(timeout))
+    }
 }
 
 impl Ref<'_, MaybeFdLifecycle> {
@@ -48,7 +86,17 @@ impl Ref<'_, MaybeFdLifecycle> {
     pub(crate) unsafe fn complete(mut self, result: io::Result<u32>, flags: u32) {
         let result = MaybeFd::new_result(result, self.is_fd);
         let ref_mut = &mut self.lifecycle;
+        if more(flags) {
+            // Results of an ignored multishot operation are dropped, closing
+            // the fds, until it is canceled.
+            match ref_mut {
+                Lifecycle::Ignored(data) => data.on_ignored(&result, flags),
+                _ => self.push_multi(result, flags),
+            }
+            return;
+        }
         match ref_mut {
+            Lifecycle::Multi(..) => self.push_multi(result, flags),
             Lifecycle::Submitted => {
                 *ref_mut = Lifecycle::Completed(result, flags);
             }
@@ -56,18 +104,42 @@ impl Ref<'_, MaybeFdLifecycle> {
                 let old = std::mem::replace(ref_mut, Lifecycle::Completed(result, flags));
                 match old {
                     Lifecycle::Waiting(waker) => {
-                        waker.wake();
+                        crate::runtime::isolate_wake(|| waker.wake());
                     }
                     _ => std::hint::unreachable_unchecked(),
                 }
             }
-            Lifecycle::Ignored(..) => {
+            Lifecycle::Ignored(data) => {
+                data.on_ignored(&result, flags);
                 self.remove();
             }
             Lifecycle::Completed(..) => std::hint::unreachable_unchecked(),
         }
     }
 
+    fn push_multi(&mut self, result: io::Result<MaybeFd>, flags: u32) {
+        let ref_mut = &mut self.lifecycle;
+        let waker = match ref_mut {
+            Lifecycle::Multi(queue, waker) => {
+                queue.push_back((result, flags));
+                waker.take()
+            }
+            _ => {
+                let old = std::mem::replace(
+                    ref_mut,
+                    Lifecycle::Multi(VecDeque::from([(result, flags)]), None),
+                );
+                match old {
+                    Lifecycle::Waiting(waker) => Some(waker),
+                    _ => None,
+                }
+            }
+        };
+        if let Some(waker) = waker {
+            crate::runtime::isolate_wake(|| waker.wake());
+        }
+    }
+
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub(crate) fn poll_op(mut self, cx: &mut Context<'_>) -> Poll<CompletionMeta> {
         let ref_mut = &mut self.lifecycle;
@@ -78,10 +150,22 @@ impl Ref<'_, MaybeFdLifecycle> {
             }
             Lifecycle::Waiting(waker) => {
                 if !waker.will_wake(cx.waker()) {
-                    *ref_mut = Lifecycle::Waiting(cx.waker().clone());
+                    waker.clone_from(cx.waker());
                 }
                 return Poll::Pending;
             }
+            Lifecycle::Multi(queue, waker) => {
+                let Some((result, flags)) = queue.pop_front() else {
+                    *waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                };
+                if more(flags) {
+                    return Poll::Ready(CompletionMeta { result, flags });
+                }
+                // The multishot operation has ended.
+                self.remove();
+                return Poll::Ready(CompletionMeta { result, flags });
+            }
             _ => {}
         }
 
@@ -92,19 +176,30 @@ impl Ref<'_, MaybeFdLifecycle> {
     }
 
     // return if the op must has been finished
-    pub(crate) fn drop_op<T: 'static>(mut self, data: &mut Option<T>) -> bool {
+    pub(crate) fn drop_op<T: OpAble + 'static>(mut self, data: &mut Option<T>) -> bool {
+        let ref_mut = &mut self.lifecycle;
+        // Hand the completions not taken to the op, they are dropped then.
+        match (ref_mut, data.as_mut()) {
+            (Lifecycle::Completed(result, flags), Some(data)) => data.on_ignored(result, *flags),
+            (Lifecycle::Multi(queue, _), Some(data)) => {
+                for (result, flags) in queue.drain(..) {
+                    data.on_ignored(&result, flags);
+                }
+            }
+            _ => {}
+        }
         let ref_mut = &mut self.lifecycle;
         match ref_mut {
+            Lifecycle::Multi(queue, _) if queue.back().is_none_or(|(_, f)| more(*f)) => {
+                // Ignore it like an operation in flight.
+                *ref_mut = Lifecycle::Ignored(ignored_data(data));
+                return false;
+            }
             Lifecycle::Submitted | Lifecycle::Waiting(_) => {
-                if let Some(data) = data.take() {
-                    *ref_mut = Lifecycle::Ignored(Box::new(data));
-                } else {
-                    *ref_mut = Lifecycle::Ignored(Box::new(())); // () is a ZST, so it does not
-                                                                 // allocate
-                };
+                *ref_mut = Lifecycle::Ignored(ignored_data(data));
                 return false;
             }
-            Lifecycle::Completed(..) => {
+            Lifecycle::Completed(..) | Lifecycle::Multi(..) => {
                 self.remove();
             }
             Lifecycle::Ignored(..) => unsafe { std::hint::unreachable_unchecked() },
@@ -112,3 +207,11 @@ impl Ref<'_, MaybeFdLifecycle> {
         true
     }
 }
+
+fn ignored_data<T: OpAble + 'static>(data: &mut Option<T>) -> Box<dyn IgnoredData> {
+    match data.take() {
+        Some(data) => Box::new(data),
+        // NoData is a ZST, so it does not allocate
+        None => Box::new(NoData),
+    }
+}
@@ -38,7 +38,11 @@ pub(crate) const EVENTFD_USERDATA: u64 = u64::MAX - 2;
 #[cfg(feature = "poll-io")]
 pub(crate) const POLLER_USERDATA: u64 = u64::MAX - 3;
 
-pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 3;
+pub(crate) const FS_RING_USERDATA: u64 = u64::MAX - 4;
+pub(crate) const LINK_TIMEOUT_USERDATA: u64 = u64::MAX - 5;
+pub(crate) const BATCH_END_USERDATA: u64 = u64::MAX - 6;
+
+pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 6;
 
 /// Driver with uring.
 pub struct IoUringDriver {
@@ -61,7 +65,7 @@ pub(crate) struct UringInner {
     ops: Ops,
 
     #[cfg(feature = "poll-io")]
-    poll: super::poll::Poll,
+    pub(crate) poll: super::poll::Poll,
     #[cfg(feature = "poll-io")]
     poller_installed: bool,
 
@@ -82,6 +86,58 @@ pub(crate) struct UringInner {
 
     // Uring support ext_arg
     ext_arg: bool,
+    // Uring is set up with IORING_SETUP_IOPOLL
+    pub(crate) iopoll: bool,
+
+    /// When to submit before parking
+    submit_policy: SubmitPolicy,
+    // Since when the SQ has unsubmitted entries(only tracked with submit interval)
+    pending_since: Option<std::time::Instant>,
+
+    /// Max CQEs processed per tick
+    max_cqes_per_tick: usize,
+    /// Completion statistics
+    pub(crate) stats: crate::utils::CompletionStats,
+    /// Number of operations submitted
+    ops_submitted: u64,
+    /// Capabilities of the main ring, reported when the driver is built
+    pub(crate) report: Option<crate::utils::UringReport>,
+
+    /// Secondary ring for file IO, reaped by this one
+    pub(crate) fs_ring: Option<Rc<UnsafeCell<UringInner>>>,
+    // Mark if the poll on the file IO ring is in the ring
+    fs_ring_installed: bool,
+
+    /// Timeout to link to the next submitted operation
+    link_timeout: Option<Duration>,
+
+    /// Batch of operations being pushed
+    batch: Option<Batch>,
+
+    /// Mark the submitted operations with `IOSQE_IO_DRAIN`
+    drain: bool,
+}
+
+/// Operations pushed together and submitted at once.
+#[derive(Debug, Clone, Copy)]
+struct Batch {
+    /// Link the operations with `IOSQE_IO_LINK`.
+    linked: bool,
+    /// Number of operations pushed so far.
+    pushed: usize,
+    /// The submission queue had room for the whole linked batch and the nop
+    /// ending it when the batch started.
+    fits: bool,
+}
+
+/// When to submit queued SQEs without waiting for the driver to park.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SubmitPolicy {
+    /// Submit once this many SQEs are queued.
+    pub(crate) threshold: Option<u32>,
+    /// Submit once the oldest queued SQE has waited this long. It is checked
+    /// when pushing new SQEs.
+    pub(crate) interval: Option<Duration>,
 }
 
 // When dropping the driver, all in-flight operations must have completed. This
@@ -102,17 +158,8 @@ impl IoUringDriver {
         urb: &io_uring::Builder,
         entries: u32,
     ) -> io::Result<IoUringDriver> {
-        let uring = ManuallyDrop::new(urb.build(entries)?);
-
-        let inner = Rc::new(UnsafeCell::new(UringInner {
-            #[cfg(feature = "poll-io")]
-            poll: super::poll::Poll::with_capacity(entries as usize)?,
-            #[cfg(feature = "poll-io")]
-            poller_installed: false,
-            ops: Ops::new(),
-            ext_arg: uring.params().is_feature_ext_arg(),
-            uring,
-        }));
+        let inner = Rc::new(UnsafeCell::new(UringInner::new(urb, entries)?));
+        UringInner::report(&inner);
 
         Ok(IoUringDriver {
             inner,
@@ -125,8 +172,6 @@ impl IoUringDriver {
         urb: &io_uring::Builder,
         entries: u32,
     ) -> io::Result<IoUringDriver> {
-        let uring = ManuallyDrop::new(urb.build(entries)?);
-
         // Create eventfd and register it to the ring.
         let waker = {
             let fd = crate::syscall!(eventfd@RAW(0, libc::EFD_CLOEXEC))?;
@@ -138,18 +183,13 @@ impl IoUringDriver {
 
         let (waker_sender, waker_receiver) = flume::unbounded::<std::task::Waker>();
 
-        let inner = Rc::new(UnsafeCell::new(UringInner {
-            #[cfg(feature = "poll-io")]
-            poller_installed: false,
-            #[cfg(feature = "poll-io")]
-            poll: super::poll::Poll::with_capacity(entries as usize)?,
-            ops: Ops::new(),
-            ext_arg: uring.params().is_feature_ext_arg(),
-            uring,
-            shared_waker: std::sync::Arc::new(waker::EventWaker::new(waker)),
-            eventfd_installed: false,
+        let inner = Rc::new(UnsafeCell::new(UringInner::new(
+            urb,
+            entries,
+            std::sync::Arc::new(waker::EventWaker::new(waker)),
             waker_receiver,
-        }));
+        )?));
+        UringInner::report(&inner);
 
         let thread_id = crate::builder::BUILD_THREAD_ID.with(|id| *id);
         let driver = IoUringDriver {
@@ -165,6 +205,48 @@ impl IoUringDriver {
         Ok(driver)
     }
 
+    pub(crate) fn with_submit_policy(self, policy: SubmitPolicy) -> Self {
+        unsafe { (*self.inner.get()).submit_policy = policy };
+        self
+    }
+
+    pub(crate) fn with_max_cqes_per_tick(self, max: Option<usize>) -> Self {
+        unsafe { (*self.inner.get()).max_cqes_per_tick = max.unwrap_or(usize::MAX) };
+        self
+    }
+
+    /// Attach a secondary ring built from `urb` that file IO is submitted
+    /// to.
+    pub(crate) fn with_fs_ring(
+        self,
+        fs_ring: Option<(&io_uring::Builder, u32)>,
+    ) -> io::Result<Self> {
+        let Some((urb, entries)) = fs_ring else {
+            return Ok(self);
+        };
+        let inner = unsafe { &mut *self.inner.get() };
+        #[cfg(not(feature = "sync"))]
+        let fs_ring = UringInner::new(urb, entries)?;
+        // The file IO ring never parks, it shares the waker of this one.
+        #[cfg(feature = "sync")]
+        let fs_ring = UringInner::new(
+            urb,
+            entries,
+            inner.shared_waker.clone(),
+            inner.waker_receiver.clone(),
+        )?;
+        inner.fs_ring = Some(Rc::new(UnsafeCell::new(fs_ring)));
+        Ok(self)
+    }
+
+    #[cfg(feature = "poll-io")]
+    pub(crate) fn with_events_capacity(self, capacity: Option<usize>) -> Self {
+        if let Some(capacity) = capacity {
+            unsafe { (*self.inner.get()).poll.set_events_capacity(capacity) };
+        }
+        self
+    }
+
     #[allow(unused)]
     fn num_operations(&self) -> usize {
         let inner = self.inner.get();
@@ -204,6 +286,21 @@ impl IoUringDriver {
         inner.poller_installed = true;
     }
 
+    fn install_fs_ring_poll(inner: &mut UringInner) {
+        let Some(fs_ring) = &inner.fs_ring else {
+            return;
+        };
+        let fd = unsafe { (*fs_ring.get()).uring.as_raw_fd() };
+        // The ring fd is readable once it has completions.
+        let entry = opcode::PollAdd::new(io_uring::types::Fd(fd), libc::POLLIN as _)
+            .build()
+            .user_data(FS_RING_USERDATA);
+
+        let mut sq = inner.uring.submission();
+        let _ = unsafe { sq.push(&entry) };
+        inner.fs_ring_installed = true;
+    }
+
     fn install_timeout(&self, inner: &mut UringInner, duration: Duration) {
         let timespec = timespec(duration);
         unsafe {
@@ -219,10 +316,35 @@ impl IoUringDriver {
 
     fn inner_park(&self, timeout: Option<Duration>) -> io::Result<()> {
         let inner = unsafe { &mut *self.inner.get() };
+        driver_event!(
+            "park",
+            driver = "uring",
+            timeout_us = timeout.map(|t| t.as_micros() as u64),
+        );
+
+        #[cfg(feature = "poll-io")]
+        inner.poll.flush_deferred();
 
         #[allow(unused_mut)]
         let mut need_wait = true;
 
+        // Submit to and reap the file IO ring, no need to wait if it has
+        // completions. An IOPOLL ring only completes when reaped, so it is
+        // busy-polled while it has IO in flight.
+        let mut fs_ring_pollable = false;
+        if let Some(fs_ring) = &inner.fs_ring {
+            let fs_ring = unsafe { &mut *fs_ring.get() };
+            fs_ring.submit()?;
+            if !fs_ring.uring.completion().is_empty() {
+                fs_ring.tick()?;
+                need_wait = false;
+            } else if fs_ring.iopoll {
+                need_wait &= fs_ring.ops.slab.len() == 0;
+            } else {
+                fs_ring_pollable = true;
+            }
+        }
+
         #[cfg(feature = "sync")]
         {
             // Process foreign wakers
@@ -246,7 +368,21 @@ impl IoUringDriver {
             }
         }
 
-        if need_wait {
+        if need_wait && inner.iopoll {
+            // IOPOLL rings only take polled IO, so the eventfd, the poller and
+            // the timeout cannot be installed. Waiting polls the device for
+            // completions and returns right away if no IO is in flight, so the
+            // runtime busy-polls instead of sleeping.
+            #[cfg(feature = "poll-io")]
+            inner.poll.tick(Some(Duration::ZERO))?;
+            let want = if timeout == Some(Duration::ZERO) {
+                0
+            } else {
+                1
+            };
+            let submitted = inner.uring.submit_and_wait(want)?;
+            driver_event!("submit", driver = "uring", submitted = submitted);
+        } else if need_wait {
             // Install timeout and eventfd for unpark if sync is enabled
 
             // 1. alloc spaces
@@ -259,6 +395,9 @@ impl IoUringDriver {
             if !inner.poller_installed {
                 space += 1;
             }
+            if fs_ring_pollable && !inner.fs_ring_installed {
+                space += 1;
+            }
             if timeout.is_some() {
                 space += 1;
             }
@@ -266,6 +405,11 @@ impl IoUringDriver {
                 Self::flush_space(inner, space)?;
             }
 
+            // 2.0 install poll on the file IO ring
+            if fs_ring_pollable && !inner.fs_ring_installed {
+                Self::install_fs_ring_poll(inner);
+            }
+
             // 2.1 install poller
             #[cfg(feature = "poll-io")]
             if !inner.poller_installed {
@@ -285,28 +429,34 @@ impl IoUringDriver {
                     // Better compatibility(5.4+).
                     false => {
                         self.install_timeout(inner, duration);
-                        inner.uring.submit_and_wait(1)?;
+                        let submitted = inner.uring.submit_and_wait(1)?;
+                        driver_event!("submit", driver = "uring", submitted = submitted);
                     }
                     // Submit and Wait with enter args.
                     // Better performance(5.11+).
                     true => {
                         let timespec = timespec(duration);
                         let args = io_uring::types::SubmitArgs::new().timespec(&timespec);
-                        if let Err(e) = inner.uring.submitter().submit_with_args(1, &args) {
-                            if e.raw_os_error() != Some(libc::ETIME) {
-                                return Err(e);
+                        match inner.uring.submitter().submit_with_args(1, &args) {
+                            Ok(submitted) => {
+                                driver_event!("submit", driver = "uring", submitted = submitted);
                             }
+                            Err(e) if e.raw_os_error() == Some(libc::ETIME) => (),
+                            Err(e) => return Err(e),
                         }
                     }
                 }
             } else {
                 // Submit and Wait without timeout
-                inner.uring.submit_and_wait(1)?;
+                let submitted = inner.uring.submit_and_wait(1)?;
+                driver_event!("submit", driver = "uring", submitted = submitted);
             }
         } else {
             // Submit only
-            inner.uring.submit()?;
+            let submitted = inner.uring.submit()?;
+            driver_event!("submit", driver = "uring", submitted = submitted);
         }
+        inner.pending_since = None;
 
         // Set status as awake
         #[cfg(feature = "sync")]
@@ -323,13 +473,32 @@ impl IoUringDriver {
 
     #[cfg(feature = "poll-io")]
     #[inline]
-    pub(crate) fn register_poll_io(
+    pub(crate) fn register_poll_io_fd(
         this: &Rc<UnsafeCell<UringInner>>,
-        source: &mut impl mio::event::Source,
+        fd: RawFd,
         interest: mio::Interest,
     ) -> io::Result<usize> {
         let inner = unsafe { &mut *this.get() };
-        inner.poll.register(source, interest)
+        inner.poll.register_fd(fd, interest)
+    }
+
+    #[cfg(feature = "poll-io")]
+    #[inline]
+    pub(crate) fn deregister_poll_io_deferred(
+        this: &Rc<UnsafeCell<UringInner>>,
+        fd: RawFd,
+        token: usize,
+        interest: mio::Interest,
+    ) {
+        let inner = unsafe { &mut *this.get() };
+        inner.poll.deregister_deferred(fd, token, interest)
+    }
+
+    #[cfg(feature = "poll-io")]
+    #[inline]
+    pub(crate) fn flush_poll_io_fd(this: &Rc<UnsafeCell<UringInner>>, fd: RawFd) {
+        let inner = unsafe { &mut *this.get() };
+        inner.poll.flush_fd(fd)
     }
 
     #[cfg(feature = "poll-io")]
@@ -354,6 +523,11 @@ impl Driver for IoUringDriver {
 
     fn submit(&self) -> io::Result<()> {
         let inner = unsafe { &mut *self.inner.get() };
+        if let Some(fs_ring) = &inner.fs_ring {
+            let fs_ring = unsafe { &mut *fs_ring.get() };
+            fs_ring.submit()?;
+            fs_ring.tick()?;
+        }
         inner.submit()?;
         inner.tick()?;
         Ok(())
@@ -377,10 +551,58 @@ impl Driver for IoUringDriver {
 }
 
 impl UringInner {
+    fn new(
+        urb: &io_uring::Builder,
+        entries: u32,
+        #[cfg(feature = "sync")] shared_waker: std::sync::Arc<waker::EventWaker>,
+        #[cfg(feature = "sync")] waker_receiver: flume::Receiver<std::task::Waker>,
+    ) -> io::Result<Self> {
+        let uring = ManuallyDrop::new(urb.build(entries)?);
+        Ok(UringInner {
+            #[cfg(feature = "poll-io")]
+            poll: super::poll::Poll::with_capacity(entries as usize)?,
+            #[cfg(feature = "poll-io")]
+            poller_installed: false,
+            ops: Ops::new(),
+            ext_arg: uring.params().is_feature_ext_arg(),
+            iopoll: uring.params().is_setup_iopoll(),
+            uring,
+            #[cfg(feature = "sync")]
+            shared_waker,
+            #[cfg(feature = "sync")]
+            eventfd_installed: false,
+            #[cfg(feature = "sync")]
+            waker_receiver,
+            submit_policy: SubmitPolicy::default(),
+            pending_since: None,
+            max_cqes_per_tick: usize::MAX,
+            stats: Default::default(),
+            ops_submitted: 0,
+            report: None,
+            fs_ring: None,
+            fs_ring_installed: false,
+            link_timeout: None,
+            batch: None,
+            drain: false,
+        })
+    }
+
+    fn report(this: &Rc<UnsafeCell<UringInner>>) {
+        let inner = unsafe { &mut *this.get() };
+        let report = crate::utils::UringReport::new(&inner.uring);
+        #[cfg(feature = "tracing")]
+        report.emit();
+        inner.report = Some(report);
+    }
+
     fn tick(&mut self) -> io::Result<()> {
-        let cq = self.uring.completion();
+        let mut cq = self.uring.completion();
 
-        for cqe in cq {
+        // CQEs beyond the limit are left in the CQ for the next tick, the
+        // next park will return immediately as they are ready.
+        let mut processed = 0;
+        for cqe in (&mut cq).take(self.max_cqes_per_tick) {
+            processed += 1;
             let index = cqe.user_data();
             match index {
                 #[cfg(feature = "sync")]
@@ -390,16 +612,31 @@ impl UringInner {
                     self.poller_installed = false;
                     self.poll.tick(Some(Duration::ZERO))?;
                 }
+                FS_RING_USERDATA => {
+                    self.fs_ring_installed = false;
+                    if let Some(fs_ring) = &self.fs_ring {
+                        unsafe { (*fs_ring.get()).tick()? };
+                    }
+                }
                 _ if index >= MIN_REVERSED_USERDATA => (),
                 // # Safety
                 // Here we can make sure the result is valid.
                 _ => unsafe { self.ops.complete(index as _, resultify(&cqe), cqe.flags()) },
             }
         }
+        let capped = !cq.is_empty();
+        self.stats.record(processed, capped);
+        driver_event!(
+            "complete",
+            driver = "uring",
+            completions = processed,
+            capped = capped
+        );
         Ok(())
     }
 
     fn submit(&mut self) -> io::Result<()> {
+        self.pending_since = None;
         loop {
             match self.uring.submit() {
                 #[cfg(feature = "unstable")]
@@ -418,9 +655,56 @@ impl UringInner {
                     // to get the raw error code.
                     self.tick()?;
                 }
-                e => return e.map(|_| ()),
+                Ok(submitted) => {
+                    driver_event!("submit", driver = "uring", submitted = submitted);
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Cancel the requests installed when parking, which only complete once
+    // the driver is woken. They are installed again by the next park.
+    fn cancel_parked_requests(&mut self) {
+        let requests = [
+            #[cfg(feature = "sync")]
+            (self.eventfd_installed, EVENTFD_USERDATA),
+            #[cfg(feature = "poll-io")]
+            (self.poller_installed, POLLER_USERDATA),
+            (self.fs_ring_installed, FS_RING_USERDATA),
+            // Not tracked, canceling it is a no-op if there is none.
+            (!self.ext_arg, TIMEOUT_USERDATA),
+        ];
+        for (_, user_data) in requests.into_iter().filter(|(installed, _)| *installed) {
+            let cancel = opcode::AsyncCancel::new(user_data)
+                .build()
+                .user_data(CANCEL_USERDATA);
+            if unsafe { self.uring.submission().push(&cancel).is_err() } {
+                let _ = self.submit();
+                let _ = unsafe { self.uring.submission().push(&cancel) };
+            }
+        }
+    }
+
+    // Submit early if the policy says so.
+    fn maybe_submit(&mut self) {
+        let policy = self.submit_policy;
+        if policy.threshold.is_none() && policy.interval.is_none() {
+            return;
+        }
+
+        let queued = self.uring.submission().len();
+        let mut submit = matches!(policy.threshold, Some(n) if queued >= n as usize);
+        if let Some(interval) = policy.interval {
+            match self.pending_since {
+                Some(since) => submit |= since.elapsed() >= interval,
+                None => self.pending_since = Some(std::time::Instant::now()),
             }
         }
+        if submit {
+            let _ = self.submit();
+        }
     }
 
     fn new_op<T: OpAble>(data: T, inner: &mut UringInner, driver: Inner) -> Op<T> {
@@ -431,33 +715,93 @@ impl UringInner {
         }
     }
 
+    /// Submit an operation, returning its data if it can not be submitted.
     pub(crate) fn submit_with_data<T>(
         this: &Rc<UnsafeCell<UringInner>>,
         data: T,
-    ) -> io::Result<Op<T>>
+    ) -> Result<Op<T>, (io::Error, T)>
     where
         T: OpAble,
     {
         let inner = unsafe { &mut *this.get() };
-        // If the submission queue is full, flush it to the kernel
-        if inner.uring.submission().is_full() {
-            inner.submit()?;
+        if inner.iopoll && !T::IOPOLL {
+            let err = io::Error::new(
+                io::ErrorKind::Unsupported,
+                "operation is not supported on an IOPOLL ring",
+            );
+            return Err((err, data));
+        }
+        // A barrier would wait for the requests of the parked driver
+        if inner.drain && !inner.iopoll {
+            inner.cancel_parked_requests();
+        }
+        // Timeouts can not be linked to polled IO
+        let link_timeout = inner.link_timeout.take().filter(|_| !inner.iopoll);
+        let entries = if link_timeout.is_some() { 2 } else { 1 };
+        let chain = match inner.batch {
+            Some(batch) if batch.linked && !inner.iopoll => Some(batch),
+            _ => None,
+        };
+        {
+            let sq = inner.uring.submission();
+            let room = sq.capacity() - sq.len();
+            if let Some(batch) = chain {
+                // Flushing would submit half of the chain, keep room for the
+                // nop ending it instead
+                if !batch.fits || room < entries + 1 {
+                    let err = io::Error::new(
+                        io::ErrorKind::Other,
+                        "the linked batch does not fit in the submission queue",
+                    );
+                    return Err((err, data));
+                }
+            } else if room < entries {
+                // If the submission queue has no room left, flush it to the
+                // kernel
+                drop(sq);
+                if let Err(err) = inner.submit() {
+                    return Err((err, data));
+                }
+            }
         }
 
         // Create the operation
         let mut op = Self::new_op(data, inner, Inner::Uring(this.clone()));
+        inner.ops_submitted += 1;
 
         // Configure the SQE
         let data_mut = unsafe { op.data.as_mut().unwrap_unchecked() };
-        let sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        let mut sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        if chain.is_some() {
+            sqe = sqe.flags(io_uring::squeue::Flags::IO_LINK);
+        }
+        if inner.drain {
+            sqe = sqe.flags(io_uring::squeue::Flags::IO_DRAIN);
+        }
+        let mut timeout_sqe = None;
+        if let Some(timeout) = link_timeout {
+            sqe = sqe.flags(io_uring::squeue::Flags::IO_LINK);
+            let mut lifecycle = unsafe { inner.ops.slab.get(op.index).unwrap_unchecked() };
+            let ts = lifecycle.set_link_timeout(timespec(timeout));
+            timeout_sqe = Some(
+                opcode::LinkTimeout::new(ts)
+                    .build()
+                    .user_data(LINK_TIMEOUT_USERDATA),
+            );
+        }
 
         {
             let mut sq = inner.uring.submission();
 
-            // Push the new operation
+            // Push the new operation, and the timeout linked to it
             if unsafe { sq.push(&sqe).is_err() } {
                 unimplemented!("when is this hit?");
             }
+            if let Some(timeout_sqe) = timeout_sqe {
+                if unsafe { sq.push(&timeout_sqe).is_err() } {
+                    unreachable!("room for the linked timeout is made before pushing its op");
+                }
+            }
         }
 
         // Submit the new operation. At this point, the operation has been
@@ -467,8 +811,12 @@ impl UringInner {
         // future `io_uring_enter` will fully submit the event.
 
         // CHIHAI: We are not going to do syscall now. If we are waiting
-        // for IO, we will submit on `park`.
-        // let _ = inner.submit();
+        // for IO, we will submit on `park`, unless the submit policy asks to
+        // do it earlier. A batch is submitted once complete.
+        match &mut inner.batch {
+            Some(batch) => batch.pushed += 1,
+            None => inner.maybe_submit(),
+        }
         Ok(op)
     }
 
@@ -507,11 +855,12 @@ impl UringInner {
             .poll_syscall(cx, index, direction, || OpAble::legacy_call(data))
     }
 
-    pub(crate) fn drop_op<T: 'static>(
+    pub(crate) fn drop_op<T: OpAble + 'static>(
         this: &Rc<UnsafeCell<UringInner>>,
         index: usize,
         data: &mut Option<T>,
         _skip_cancel: bool,
+        multishot: bool,
     ) {
         let inner = unsafe { &mut *this.get() };
         if index == usize::MAX {
@@ -520,8 +869,14 @@ impl UringInner {
         }
         if let Some(lifecycle) = inner.ops.slab.get(index) {
             let _must_finished = lifecycle.drop_op(data);
+            // A multishot op would run forever.
+            if !_must_finished && multishot {
+                unsafe { Self::cancel_op(this, index) };
+                return;
+            }
+            // Polled IO cannot be canceled.
             #[cfg(feature = "async-cancel")]
-            if !_must_finished && !_skip_cancel {
+            if !_must_finished && !_skip_cancel && !inner.iopoll {
                 unsafe {
                     let cancel = opcode::AsyncCancel::new(index as u64)
                         .build()
@@ -537,8 +892,97 @@ impl UringInner {
         }
     }
 
+    /// Number of in-flight operations and of operations submitted, including
+    /// the ones of the file IO ring.
+    pub(crate) fn op_counts(&self) -> (usize, u64) {
+        let (mut in_flight, mut submitted) = (self.ops.slab.len(), self.ops_submitted);
+        if let Some(fs_ring) = &self.fs_ring {
+            let (fs_in_flight, fs_submitted) = unsafe { (*fs_ring.get()).op_counts() };
+            in_flight += fs_in_flight;
+            submitted += fs_submitted;
+        }
+        (in_flight, submitted)
+    }
+
+    /// Start a batch of `len` operations, returns false if one is already
+    /// started.
+    ///
+    /// A linked batch needs room for all its operations and the nop ending the
+    /// chain, the submission queue is flushed first if it has not. The
+    /// operations of a linked batch that does not fit fail.
+    pub(crate) fn start_batch(this: &Rc<UnsafeCell<UringInner>>, linked: bool, len: usize) -> bool {
+        let inner = unsafe { &mut *this.get() };
+        if inner.batch.is_some() {
+            return false;
+        }
+        let mut fits = true;
+        if linked && !inner.iopoll {
+            let room = |inner: &mut UringInner| {
+                let sq = inner.uring.submission();
+                sq.capacity() - sq.len()
+            };
+            if room(inner) <= len {
+                let _ = inner.submit();
+                fits = room(inner) > len;
+            }
+        }
+        inner.batch = Some(Batch {
+            linked,
+            pushed: 0,
+            fits,
+        });
+        true
+    }
+
+    /// End the batch and submit its operations.
+    pub(crate) fn end_batch(this: &Rc<UnsafeCell<UringInner>>) {
+        let inner = unsafe { &mut *this.get() };
+        let Some(batch) = inner.batch.take() else {
+            return;
+        };
+        if batch.pushed == 0 {
+            return;
+        }
+        if batch.linked && !inner.iopoll {
+            // The last operation is linked to the next SQE, end the chain with
+            // a nop.
+            let nop = opcode::Nop::new().build().user_data(BATCH_END_USERDATA);
+            if unsafe { inner.uring.submission().push(&nop).is_err() } {
+                unreachable!("room for the nop is kept by the operations of the batch");
+            }
+        }
+        let _ = inner.submit();
+    }
+
+    /// Set whether the submitted operations are drain barriers, on this ring
+    /// and its file IO ring. Returns the previous setting.
+    pub(crate) fn set_drain(this: &Rc<UnsafeCell<UringInner>>, drain: bool) -> bool {
+        let inner = unsafe { &mut *this.get() };
+        if let Some(fs_ring) = &inner.fs_ring {
+            unsafe { (*fs_ring.get()).drain = drain };
+        }
+        std::mem::replace(&mut inner.drain, drain)
+    }
+
+    /// Set the timeout to link to the next submitted operation.
+    pub(crate) fn set_link_timeout(this: &Rc<UnsafeCell<UringInner>>, timeout: Option<Duration>) {
+        let inner = unsafe { &mut *this.get() };
+        inner.link_timeout = timeout;
+    }
+
+    /// Cancel the operations in flight on the ring.
+    pub(crate) unsafe fn cancel_all(this: &Rc<UnsafeCell<UringInner>>) {
+        let indexes: Vec<usize> = unsafe { (*this.get()).ops.slab.keys().collect() };
+        for index in indexes {
+            unsafe { Self::cancel_op(this, index) };
+        }
+    }
+
     pub(crate) unsafe fn cancel_op(this: &Rc<UnsafeCell<UringInner>>, index: usize) {
         let inner = &mut *this.get();
+        if inner.iopoll {
+            return;
+        }
         let cancel = opcode::AsyncCancel::new(index as u64)
             .build()
             .user_data(u64::MAX);
@@ -548,6 +992,40 @@ impl UringInner {
         }
     }
 
+    pub(crate) unsafe fn register_buffers(
+        this: &Rc<UnsafeCell<UringInner>>,
+        iovecs: &[libc::iovec],
+    ) -> io::Result<()> {
+        let inner = &*this.get();
+        inner.uring.submitter().register_buffers(iovecs)
+    }
+
+    pub(crate) fn unregister_buffers(this: &Rc<UnsafeCell<UringInner>>) -> io::Result<()> {
+        let inner = unsafe { &*this.get() };
+        inner.uring.submitter().unregister_buffers()
+    }
+
+    pub(crate) unsafe fn register_buf_ring(
+        this: &Rc<UnsafeCell<UringInner>>,
+        ring_addr: u64,
+        entries: u16,
+        bgid: u16,
+    ) -> io::Result<()> {
+        let inner = &*this.get();
+        inner
+            .uring
+            .submitter()
+            .register_buf_ring(ring_addr, entries, bgid)
+    }
+
+    pub(crate) fn unregister_buf_ring(
+        this: &Rc<UnsafeCell<UringInner>>,
+        bgid: u16,
+    ) -> io::Result<()> {
+        let inner = unsafe { &*this.get() };
+        inner.uring.submitter().unregister_buf_ring(bgid)
+    }
+
     #[cfg(feature = "sync")]
     pub(crate) fn unpark(this: &Rc<UnsafeCell<UringInner>>) -> waker::UnparkHandle {
         let inner = unsafe { &*this.get() };
@@ -2,15 +2,16 @@
 
 use std::os::unix::prelude::{AsRawFd, RawFd};
 
-use crate::driver::unpark::Unpark;
+use crate::{driver::unpark::Unpark, utils::cache_padded::CachePadded};
 
 pub(crate) struct EventWaker {
     // RawFd
     raw: RawFd,
     // File hold the ownership of fd, only useful when drop
     _file: std::fs::File,
-    // Atomic awake status
-    pub(crate) awake: std::sync::atomic::AtomicBool,
+    // Atomic awake status, written by the owning thread on every park while
+    // remote threads poke the ref counts next to it, so it gets its own line.
+    pub(crate) awake: CachePadded<std::sync::atomic::AtomicBool>,
 }
 
 impl EventWaker {
@@ -18,7 +19,7 @@ impl EventWaker {
         Self {
             raw: file.as_raw_fd(),
             _file: file,
-            awake: std::sync::atomic::AtomicBool::new(true),
+            awake: CachePadded(std::sync::atomic::AtomicBool::new(true)),
         }
     }
 
@@ -0,0 +1,202 @@
+use std::{io, os::fd::AsRawFd, path::Path};
+
+use crate::{
+    buf::IoBufMut,
+    fs::{File, OpenOptions},
+};
+
+// Bytes moved per read and write when the kernel cannot copy the range.
+const CHUNK: usize = 64 * 1024;
+
+// Bytes the kernel copies on the runtime thread before yielding to the other
+// tasks, without a thread pool.
+#[cfg(target_os = "linux")]
+const STEP: u64 = 1 << 20;
+
+/// Copies the contents of a file to another, creating or truncating `to`,
+/// and copies the permission bits of `from` to `to`. Returns the number of
+/// bytes copied.
+///
+/// On Linux the bytes are copied by the kernel with `copy_file_range(2)`,
+/// without going through user space, and file systems supporting it share
+/// the extents of the files instead of duplicating them. Otherwise, e.g.
+/// across file systems on older kernels, the file is read and written by
+/// chunks through the driver. See [`File::copy_range_to`].
+///
+/// # Errors
+///
+/// This function will return an error if `from` does not exist or is not a
+/// regular file, if `to` cannot be created, or if the copy fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::copy("foo.txt", "bar.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+    let from = File::open(from).await?;
+    let metadata = from.metadata().await?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the source path is not a regular file",
+        ));
+    }
+    let to = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(to)
+        .await?;
+    let copied = from.copy_range_to(&to, 0, 0, u64::MAX).await?;
+    let mode = metadata.permissions().0.mode;
+    crate::syscall!(fchmod@RAW(to.as_raw_fd(), mode))?;
+    to.close().await?;
+    Ok(copied)
+}
+
+pub(crate) async fn copy_range(
+    src: &File,
+    dst: &File,
+    src_offset: u64,
+    dst_offset: u64,
+    len: u64,
+) -> io::Result<u64> {
+    #[cfg(target_os = "linux")]
+    if let Some(copied) = copy_file_range(src, dst, src_offset, dst_offset, len).await? {
+        return Ok(copied);
+    }
+    copy_buffered(src, dst, src_offset, dst_offset, len).await
+}
+
+/// Returns `None` if the kernel cannot copy between the files at all.
+#[cfg(target_os = "linux")]
+async fn copy_file_range(
+    src: &File,
+    dst: &File,
+    src_offset: u64,
+    dst_offset: u64,
+    len: u64,
+) -> io::Result<Option<u64>> {
+    let offset = |offset: u64| {
+        libc::loff_t::try_from(offset)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too big"))
+    };
+    let (src_offset, dst_offset) = (offset(src_offset)?, offset(dst_offset)?);
+
+    #[cfg(feature = "sync")]
+    if crate::blocking::has_thread_pool() {
+        use std::os::fd::BorrowedFd;
+
+        // The duplicated fds stay valid even if the files are closed while
+        // copying.
+        let src = unsafe { BorrowedFd::borrow_raw(src.as_raw_fd()) }.try_clone_to_owned()?;
+        let dst = unsafe { BorrowedFd::borrow_raw(dst.as_raw_fd()) }.try_clone_to_owned()?;
+        return crate::fs::asyncify(move || {
+            copy_file_range_blocking(
+                src.as_raw_fd(),
+                src_offset,
+                dst.as_raw_fd(),
+                dst_offset,
+                len,
+            )
+        })
+        .await;
+    }
+    // Copy by steps on the runtime thread, the other tasks run in between.
+    let mut copied = 0;
+    while copied < len {
+        let step = (len - copied).min(STEP);
+        match copy_file_range_blocking(
+            src.as_raw_fd(),
+            src_offset + copied as libc::loff_t,
+            dst.as_raw_fd(),
+            dst_offset + copied as libc::loff_t,
+            step,
+        )? {
+            None if copied == 0 => return Ok(None),
+            // The end of the source file.
+            None => break,
+            Some(n) => {
+                copied += n;
+                if n < step {
+                    break;
+                }
+            }
+        }
+        crate::task::coop::yield_now().await;
+    }
+    Ok(Some(copied))
+}
+
+#[cfg(target_os = "linux")]
+fn copy_file_range_blocking(
+    src: std::os::fd::RawFd,
+    mut src_offset: libc::loff_t,
+    dst: std::os::fd::RawFd,
+    mut dst_offset: libc::loff_t,
+    len: u64,
+) -> io::Result<Option<u64>> {
+    let mut copied = 0;
+    while copied < len {
+        let chunk = (len - copied).min(1 << 30) as usize;
+        match crate::syscall!(copy_file_range@RAW(
+            src,
+            &mut src_offset,
+            dst,
+            &mut dst_offset,
+            chunk,
+            0
+        )) {
+            // Some special files, e.g. in procfs, report nothing to copy.
+            Ok(0) if copied == 0 => return Ok(None),
+            Ok(0) => break,
+            Ok(n) => copied += n as u64,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // Unsupported by the kernel or the file systems, or across them.
+            Err(e)
+                if copied == 0
+                    && matches!(
+                        e.raw_os_error(),
+                        Some(libc::ENOSYS | libc::EXDEV | libc::EOPNOTSUPP | libc::EINVAL)
+                    ) =>
+            {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Some(copied))
+}
+
+async fn copy_buffered(
+    src: &File,
+    dst: &File,
+    mut src_offset: u64,
+    mut dst_offset: u64,
+    len: u64,
+) -> io::Result<u64> {
+    let mut copied = 0;
+    let mut buf = Vec::with_capacity(len.min(CHUNK as u64) as usize);
+    while copied < len {
+        let want = (len - copied).min(buf.capacity() as u64) as usize;
+        let (res, slice) = src.read_at(buf.slice_mut(..want), src_offset).await;
+        buf = slice.into_inner();
+        let n = res?;
+        if n == 0 {
+            break;
+        }
+        let (res, written) = dst.write_all_at(buf, dst_offset).await;
+        res?;
+        buf = written;
+        buf.clear();
+        copied += n as u64;
+        src_offset += n as u64;
+        dst_offset += n as u64;
+    }
+    Ok(copied)
+}
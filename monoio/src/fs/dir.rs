@@ -0,0 +1,188 @@
+use std::{
+    io,
+    os::unix::{
+        fs::OpenOptionsExt,
+        io::{AsRawFd, RawFd},
+    },
+    path::Path,
+};
+
+use crate::{
+    driver::{op::Op, shared_fd::SharedFd},
+    fs::{metadata::FileAttr, File, Metadata, OpenOptions},
+};
+
+/// A handle to an open directory.
+///
+/// Paths given to its methods are resolved relative to the directory
+/// (absolute paths are used as is), like the `*at` family of syscalls. This
+/// avoids resolving the directory path again for every operation, and keeps
+/// operating on the same directory even if it is renamed or replaced in the
+/// meantime.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::fs::Dir;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let dir = Dir::open("/tmp/data").await?;
+///     let file = dir.create_at("a.txt").await?;
+///     let (res, _) = file.write_all_at(&b"hello"[..], 0).await;
+///     res?;
+///     file.close().await?;
+///     println!("{} bytes", dir.metadata_at("a.txt").await?.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Dir {
+    fd: SharedFd,
+}
+
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist or is not
+    /// a directory.
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Dir> {
+        Self::open_inner(None, path.as_ref()).await
+    }
+
+    /// Opens the directory at `path` relative to this directory.
+    pub async fn open_dir_at(&self, path: impl AsRef<Path>) -> io::Result<Dir> {
+        Self::open_inner(Some(&self.fd), path.as_ref()).await
+    }
+
+    async fn open_inner(dir: Option<&SharedFd>, path: &Path) -> io::Result<Dir> {
+        let op = Op::open_at(
+            dir,
+            path,
+            OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_DIRECTORY),
+        )?;
+        let fd = op.await.meta.result?.into_inner();
+        Ok(Dir {
+            fd: SharedFd::new_without_register(fd as _),
+        })
+    }
+
+    /// Opens the file at `path` relative to this directory with `options`.
+    ///
+    /// See [`OpenOptions::open`] for more details.
+    pub async fn open_at(&self, path: impl AsRef<Path>, options: &OpenOptions) -> io::Result<File> {
+        options.open_at(Some(&self.fd), path.as_ref()).await
+    }
+
+    /// Opens the file at `path` relative to this directory in write-only mode,
+    /// creating it if it does not exist and truncating it if it does.
+    ///
+    /// See [`File::create`] for more details.
+    pub async fn create_at(&self, path: impl AsRef<Path>) -> io::Result<File> {
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open_at(Some(&self.fd), path.as_ref())
+            .await
+    }
+
+    /// Removes the file at `path` relative to this directory.
+    ///
+    /// See [`remove_file`](super::remove_file) for more details.
+    #[cfg(feature = "unlinkat")]
+    pub async fn unlink_at(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Op::unlink_at(Some(&self.fd), path, false)?
+            .await
+            .meta
+            .result?;
+        Ok(())
+    }
+
+    /// Removes the empty directory at `path` relative to this directory.
+    ///
+    /// See [`remove_dir`](super::remove_dir) for more details.
+    #[cfg(feature = "unlinkat")]
+    pub async fn remove_dir_at(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Op::unlink_at(Some(&self.fd), path, true)?
+            .await
+            .meta
+            .result?;
+        Ok(())
+    }
+
+    /// Renames `from` relative to this directory to `to` relative to `to_dir`,
+    /// replacing the original file if `to` already exists.
+    ///
+    /// See [`rename`](super::rename) for more details.
+    #[cfg(feature = "renameat")]
+    pub async fn rename_at(
+        &self,
+        from: impl AsRef<Path>,
+        to_dir: &Dir,
+        to: impl AsRef<Path>,
+    ) -> io::Result<()> {
+        Op::rename_at(Some(&self.fd), from.as_ref(), Some(&to_dir.fd), to.as_ref())?
+            .await
+            .meta
+            .result?;
+        Ok(())
+    }
+
+    /// Queries the metadata of `path` relative to this directory, following
+    /// symlinks.
+    ///
+    /// See [`metadata`](super::metadata) for more details.
+    pub async fn metadata_at(&self, path: impl AsRef<Path>) -> io::Result<Metadata> {
+        self.stat_at(path.as_ref(), true).await
+    }
+
+    /// Queries the metadata of `path` relative to this directory, without
+    /// following symlinks.
+    ///
+    /// See [`symlink_metadata`](super::symlink_metadata) for more details.
+    pub async fn symlink_metadata_at(&self, path: impl AsRef<Path>) -> io::Result<Metadata> {
+        self.stat_at(path.as_ref(), false).await
+    }
+
+    async fn stat_at(&self, path: &Path, follow_symlinks: bool) -> io::Result<Metadata> {
+        #[cfg(target_os = "linux")]
+        let op = Op::statx_at(
+            Some(&self.fd),
+            path,
+            if follow_symlinks {
+                libc::AT_STATX_SYNC_AS_STAT
+            } else {
+                libc::AT_STATX_SYNC_AS_STAT | libc::AT_SYMLINK_NOFOLLOW
+            },
+        )?;
+
+        #[cfg(target_os = "macos")]
+        let op = Op::statx_at(Some(&self.fd), path, follow_symlinks)?;
+
+        op.result().await.map(FileAttr::from).map(Metadata)
+    }
+
+    /// Flushes the directory entries to disk, e.g. to make a file creation or
+    /// rename within it durable.
+    pub async fn sync_all(&self) -> io::Result<()> {
+        Op::fsync(&self.fd)?.await.meta.result?;
+        Ok(())
+    }
+
+    /// Closes the directory.
+    pub async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+}
+
+impl AsRawFd for Dir {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
@@ -54,6 +54,44 @@ impl DirBuilder {
         self
     }
 
+    /// Sets the mode of the parent directories created in recursive mode,
+    /// while the mode set with [`DirBuilderExt::mode`] only applies to the
+    /// last component of the path.
+    ///
+    /// By default parent directories use the same mode.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::os::unix::fs::DirBuilderExt;
+    ///
+    /// use monoio::fs::DirBuilder;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     DirBuilder::new()
+    ///         .recursive(true)
+    ///         .parent_mode(0o755)
+    ///         .mode(0o700)
+    ///         .create("/some/private/dir")
+    ///         .await
+    /// }
+    /// ```
+    pub fn parent_mode(&mut self, mode: u32) -> &mut Self {
+        self.inner.set_parent_mode(mode);
+        self
+    }
+
+    /// Sets the owner and group of the created directories, `None` leaves the
+    /// corresponding id unchanged.
+    ///
+    /// Directories that already exist are not changed. Changing the owner
+    /// usually requires privileges.
+    pub fn owner(&mut self, uid: Option<u32>, gid: Option<u32>) -> &mut Self {
+        self.inner.set_owner(uid, gid);
+        self
+    }
+
     /// Creates the specified directory with the options configured in this
     /// builder.
     ///
@@ -92,7 +130,7 @@ impl DirBuilder {
         if self.recursive {
             self.create_dir_all(path.as_ref()).await
         } else {
-            self.inner.mkdir(path.as_ref()).await
+            self.inner.mkdir(path.as_ref(), false).await
         }
     }
 
@@ -104,10 +142,10 @@ impl DirBuilder {
         let mut inexist_path = path;
         let mut need_create = vec![];
 
-        while match self.inner.mkdir(inexist_path).await {
+        while match self.inner.mkdir(inexist_path, inexist_path != path).await {
             Ok(()) => false,
             Err(ref e) if e.kind() == io::ErrorKind::NotFound => true,
-            Err(_) if is_dir(inexist_path).await => false,
+            Err(_) if crate::fs::is_dir(inexist_path).await => false,
             Err(e) => return Err(e),
         } {
             match inexist_path.parent() {
@@ -125,7 +163,7 @@ impl DirBuilder {
         }
 
         for p in need_create.into_iter().rev() {
-            self.inner.mkdir(p).await?;
+            self.inner.mkdir(p, p != path).await?;
         }
 
         Ok(())
@@ -144,9 +182,3 @@ impl DirBuilderExt for DirBuilder {
         self
     }
 }
-
-// currently, will use the std version of metadata, will change to use the io-uring version
-// when the statx is merge
-async fn is_dir(path: &Path) -> bool {
-    std::fs::metadata(path).is_ok_and(|metadata| metadata.is_dir())
-}
@@ -5,19 +5,53 @@ use libc::mode_t;
 use crate::driver::op::Op;
 
 pub(super) struct BuilderInner {
-    mode: libc::mode_t,
+    mode: mode_t,
+    parent_mode: Option<mode_t>,
+    owner: (Option<u32>, Option<u32>),
 }
 
 impl BuilderInner {
     pub(super) fn new() -> Self {
-        Self { mode: 0o777 }
+        Self {
+            mode: 0o777,
+            parent_mode: None,
+            owner: (None, None),
+        }
     }
 
-    pub(super) async fn mkdir(&self, path: &Path) -> std::io::Result<()> {
-        Op::mkdir(path, self.mode)?.await.meta.result.map(|_| ())
+    /// Creates the directory at `path`, using the parent mode if `parent` is
+    /// set.
+    pub(super) async fn mkdir(&self, path: &Path, parent: bool) -> std::io::Result<()> {
+        let mode = match self.parent_mode {
+            Some(mode) if parent => mode,
+            _ => self.mode,
+        };
+        Op::mkdir(path, mode)?.await.meta.result?;
+        let (uid, gid) = self.owner;
+        if let (None, None) = (uid, gid) {
+            return Ok(());
+        }
+        // There is no io_uring opcode for chown. The directory is removed if
+        // it can not be given its owner, it is empty as we have just created
+        // it.
+        let path = path.to_path_buf();
+        crate::fs::offload(move || {
+            std::os::unix::fs::lchown(&path, uid, gid).inspect_err(|_| {
+                let _ = std::fs::remove_dir(&path);
+            })
+        })
+        .await
     }
 
     pub(super) fn set_mode(&mut self, mode: u32) {
         self.mode = mode as mode_t;
     }
+
+    pub(super) fn set_parent_mode(&mut self, mode: u32) {
+        self.parent_mode = Some(mode as mode_t);
+    }
+
+    pub(super) fn set_owner(&mut self, uid: Option<u32>, gid: Option<u32>) {
+        self.owner = (uid, gid);
+    }
 }
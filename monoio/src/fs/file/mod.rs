@@ -1,10 +1,14 @@
-use std::{future::Future, io, path::Path};
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+    path::Path,
+};
 
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
     driver::{op::Op, shared_fd::SharedFd},
     fs::OpenOptions,
-    io::{AsyncReadRent, AsyncWriteRent},
+    io::{AsyncReadRent, AsyncSeek, AsyncWriteRent},
     BufResult,
 };
 
@@ -23,8 +27,12 @@ use crate::io::{AsyncReadRentAt, AsyncWriteRentAt};
 ///
 /// An instance of a `File` can be read and/or written depending on what options
 /// it was opened with. The `File` type provides **positional** read and write
-/// operations. The file does not maintain an internal cursor. The caller is
-/// required to specify an offset when issuing an operation.
+/// operations, which take the offset to operate at and leave the cursor of
+/// the file alone.
+///
+/// The [`AsyncReadRent`] and [`AsyncWriteRent`] implementations read and
+/// write at the cursor instead and advance it, like [`std::fs::File`].
+/// [`seek`](File::seek) moves the cursor, see [`AsyncSeek`].
 ///
 /// While files are automatically closed when they go out of scope, the
 /// operation happens asynchronously in the background. It is recommended to
@@ -130,7 +138,10 @@ impl File {
         File { fd }
     }
 
-    async fn read<T: IoBufMut>(&mut self, buf: T) -> crate::BufResult<usize, T> {
+    async fn read<T: IoBufMut>(&mut self, mut buf: T) -> crate::BufResult<usize, T> {
+        if crate::buf::is_zero_len_read(&mut buf) {
+            return (Ok(0), buf);
+        }
         file_impl::read(self.fd.clone(), buf).await
     }
 
@@ -205,7 +216,10 @@ impl File {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn read_at<T: IoBufMut>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+    pub async fn read_at<T: IoBufMut>(&self, mut buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        if crate::buf::is_zero_len_read(&mut buf) {
+            return (Ok(0), buf);
+        }
         file_impl::read_at(self.fd.clone(), buf, pos).await
     }
 
@@ -291,7 +305,69 @@ impl File {
         (Ok(()), buf)
     }
 
+    /// Read all bytes until EOF from the current file pointer, appending them
+    /// to `buf` and returning the number of bytes read.
+    ///
+    /// The buffer grows as needed, starting from its spare capacity; reserve
+    /// the expected size up front to avoid reallocations.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs, the error is returned with the buffer, which
+    /// holds all the data read so far. [`ErrorKind::Interrupted`] errors are
+    /// retried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut f = File::open("foo.txt").await?;
+    ///     let (res, buf) = f.read_to_end(Vec::new()).await;
+    ///     println!("read {} bytes: {:?}", res?, buf);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`ErrorKind::Interrupted`]: std::io::ErrorKind::Interrupted
+    pub async fn read_to_end(&mut self, mut buf: Vec<u8>) -> crate::BufResult<usize, Vec<u8>> {
+        const MIN_READ: usize = 32;
+
+        let start = buf.len();
+        let mut probe = Vec::with_capacity(MIN_READ);
+        loop {
+            if buf.capacity() - buf.len() < MIN_READ {
+                // The buffer may be exactly as large as the file, probe with a
+                // small read before growing it.
+                let (res, p) = self.read(probe).await;
+                probe = p;
+                match res {
+                    Ok(0) => return (Ok(buf.len() - start), buf),
+                    Ok(_) => buf.extend_from_slice(&probe),
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return (Err(e), buf),
+                }
+                probe.clear();
+                // Grow exponentially like std
+                buf.reserve(buf.capacity().max(MIN_READ));
+            }
+            let len = buf.len();
+            let cap = buf.capacity();
+            let (res, slice) = self.read(buf.slice_mut(len..cap)).await;
+            buf = slice.into_inner();
+            match res {
+                Ok(0) => return (Ok(buf.len() - start), buf),
+                Ok(_) => (),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return (Err(e), buf),
+            }
+        }
+    }
+
     async fn write<T: IoBuf>(&mut self, buf: T) -> crate::BufResult<usize, T> {
+        crate::buf::lint_zero_len_write(&buf);
         file_impl::write(self.fd.clone(), buf).await
     }
 
@@ -358,9 +434,42 @@ impl File {
     ///
     /// [`Ok(n)`]: Ok
     pub async fn write_at<T: IoBuf>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        crate::buf::lint_zero_len_write(&buf);
         file_impl::write_at(self.fd.clone(), buf, pos).await
     }
 
+    /// Read some bytes at the specified offset from the file into the fixed
+    /// buffer `buf`, with the `READ_FIXED` opcode on io_uring.
+    ///
+    /// This behaves like [`read_at`](File::read_at), without mapping the
+    /// buffer into the kernel. It is submitted to the main ring even if the
+    /// runtime has a file IO ring (`RuntimeBuilder::with_fs_ring`), as that
+    /// one has no registered buffers.
+    #[cfg(unix)]
+    pub async fn read_fixed_at(
+        &self,
+        buf: crate::buf::FixedBuf,
+        pos: u64,
+    ) -> crate::BufResult<usize, crate::buf::FixedBuf> {
+        let op = Op::read_fixed_at(self.fd.clone(), buf, pos).unwrap();
+        op.result().await
+    }
+
+    /// Write the fixed buffer `buf` into the file at the specified offset,
+    /// with the `WRITE_FIXED` opcode on io_uring.
+    ///
+    /// This behaves like [`write_at`](File::write_at), see
+    /// [`read_fixed_at`](File::read_fixed_at).
+    #[cfg(unix)]
+    pub async fn write_fixed_at(
+        &self,
+        buf: crate::buf::FixedBuf,
+        pos: u64,
+    ) -> crate::BufResult<usize, crate::buf::FixedBuf> {
+        let op = Op::write_fixed_at(self.fd.clone(), buf, pos).unwrap();
+        op.result().await
+    }
+
     /// Attempts to write an entire buffer into this file at the specified
     /// offset.
     ///
@@ -404,13 +513,12 @@ impl File {
     ///
     /// [`write_at`]: File::write_at
     /// [`ErrorKind::Interrupted`]: std::io::ErrorKind::Interrupted
-    pub async fn write_all_at<T: IoBuf>(&self, mut buf: T, pos: u64) -> crate::BufResult<(), T> {
-        let len = buf.bytes_init();
-        let mut written = 0;
-        while written < len {
-            let slice = unsafe { buf.slice_unchecked(written..len) };
-            let (res, slice) = self.write_at(slice, pos + written as u64).await;
-            buf = slice.into_inner();
+    pub async fn write_all_at<T: IoBuf>(&self, buf: T, pos: u64) -> crate::BufResult<(), T> {
+        let mut slice = buf.slice(..);
+        while slice.remaining() > 0 {
+            let written = slice.begin() as u64;
+            let (res, slice_) = self.write_at(slice, pos + written).await;
+            slice = slice_;
             match res {
                 Ok(0) => {
                     return (
@@ -418,16 +526,16 @@ impl File {
                             io::ErrorKind::WriteZero,
                             "failed to write whole buffer",
                         )),
-                        buf,
+                        slice.into_inner(),
                     )
                 }
-                Ok(n) => written += n,
+                Ok(n) => slice.advance(n),
                 Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(e) => return (Err(e), slice.into_inner()),
             };
         }
 
-        (Ok(()), buf)
+        (Ok(()), slice.into_inner())
     }
 
     /// Attempts to sync all OS-internal metadata to disk.
@@ -458,7 +566,7 @@ impl File {
     /// }
     /// ```
     pub async fn sync_all(&self) -> io::Result<()> {
-        let op = Op::fsync(&self.fd).unwrap();
+        let op = Op::fsync(&self.fd)?;
         let completion = op.await;
 
         completion.meta.result?;
@@ -498,7 +606,7 @@ impl File {
     /// }
     /// ```
     pub async fn sync_data(&self) -> io::Result<()> {
-        let op = Op::datasync(&self.fd).unwrap();
+        let op = Op::datasync(&self.fd)?;
         let completion = op.await;
 
         completion.meta.result?;
@@ -510,6 +618,38 @@ impl File {
         std::future::ready(Ok(()))
     }
 
+    /// Seeks to an offset, in bytes, returning the new position of the
+    /// cursor from the start of the file.
+    ///
+    /// The cursor is where the [`AsyncReadRent`] and [`AsyncWriteRent`]
+    /// operations read and write, it is shared by all the handles of the
+    /// file. Positional operations such as [`read_at`](File::read_at) do not
+    /// use or move it.
+    ///
+    /// Seeking does not wait for operations in flight, so the cursor should
+    /// not be moved while a read or write at the cursor is in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::SeekFrom;
+    ///
+    /// use monoio::{fs::File, io::AsyncReadRentExt};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut file = File::open("foo.txt").await?;
+    ///     file.seek(SeekFrom::Start(10)).await?;
+    ///     let (res, buf) = file.read_exact(vec![0; 5]).await;
+    ///     res?;
+    ///     println!("bytes 10..15: {:?}", buf);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        file_impl::seek(&self.fd, pos)
+    }
+
     /// Closes the file.
     ///
     /// The method completes once the close operation has completed,
@@ -517,7 +657,14 @@ impl File {
     ///
     /// If `close` is not called before dropping the file, the file is closed in
     /// the background, but there is no guarantee as to **when** the close
-    /// operation will complete.
+    /// operation will complete, and its errors are lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the close operation, e.g. a write-back failure
+    /// reported by a network file system. The file is closed either way. If
+    /// in-flight operations still hold the file, it is closed once they
+    /// complete and `Ok` is returned.
     ///
     /// # Examples
     ///
@@ -535,8 +682,7 @@ impl File {
     /// }
     /// ```
     pub async fn close(self) -> io::Result<()> {
-        self.fd.close().await;
-        Ok(())
+        self.fd.close().await
     }
 }
 
@@ -639,6 +785,13 @@ impl AsyncWriteRent for File {
     }
 }
 
+impl AsyncSeek for File {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = io::Result<u64>> {
+        File::seek(self, pos)
+    }
+}
+
 impl AsyncWriteRentAt for File {
     fn write_at<T: IoBuf>(
         &mut self,
@@ -1,7 +1,8 @@
 use std::{
     fs::File as StdFile,
-    io,
-    os::fd::{AsRawFd, IntoRawFd, RawFd},
+    io::{self, Seek, SeekFrom},
+    mem::ManuallyDrop,
+    os::fd::{AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
 };
 
 #[cfg(all(not(feature = "iouring"), feature = "sync"))]
@@ -33,6 +34,45 @@ impl File {
         })
     }
 
+    /// Converts the file into a [`std::fs::File`], or returns it back if
+    /// operations on it are still in flight, e.g. ones that were canceled but
+    /// have not completed in the kernel yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let file = File::open("foo.txt").await?;
+    ///     let std_file = file.try_into_std().expect("no in-flight operations");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn try_into_std(self) -> Result<StdFile, File> {
+        match self.fd.try_unwrap() {
+            Ok(fd) => Ok(unsafe { StdFile::from_raw_fd(fd) }),
+            Err(fd) => Err(File { fd }),
+        }
+    }
+
+    /// Converts the file into a [`std::fs::File`].
+    ///
+    /// If operations on the file are still in flight, the returned file uses
+    /// a duplicate of the file descriptor, which shares the offset, status
+    /// flags and locks with the original. The original descriptor is closed
+    /// once the operations complete.
+    pub fn into_std(self) -> io::Result<StdFile> {
+        match self.fd.try_unwrap() {
+            Ok(fd) => Ok(unsafe { StdFile::from_raw_fd(fd) }),
+            Err(fd) => {
+                let owned = unsafe { BorrowedFd::borrow_raw(fd.raw_fd()) }.try_clone_to_owned()?;
+                Ok(StdFile::from(owned))
+            }
+        }
+    }
+
     /// Queries metadata about the underlying file.
     ///
     /// # Examples
@@ -50,6 +90,324 @@ impl File {
     pub async fn metadata(&self) -> io::Result<Metadata> {
         metadata(self.fd.clone()).await
     }
+
+    /// Acquires an exclusive advisory lock on the file, waiting until it is
+    /// available.
+    ///
+    /// The lock is a `flock(2)` lock: it is held by the open file rather
+    /// than by the process, and is released by [`unlock`](File::unlock) or
+    /// once the file is closed.
+    ///
+    /// There is no io_uring opcode for `flock`, so if the lock is contended
+    /// the wait happens on the thread pool attached to the runtime, see
+    /// [`RuntimeBuilder::attach_thread_pool`](crate::RuntimeBuilder::attach_thread_pool).
+    /// If the returned future is dropped while waiting, the lock may still be
+    /// acquired in the background. Without a thread pool, the lock is tried
+    /// again after sleeping for up to 100ms, which needs the timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WouldBlock`](io::ErrorKind::WouldBlock) error if the lock
+    /// is held while the runtime has neither a thread pool nor the timer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let f = File::create("daemon.lock").await?;
+    ///     f.lock_exclusive().await?;
+    ///     // only one process gets here at a time
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn lock_exclusive(&self) -> io::Result<()> {
+        self.lock(libc::LOCK_EX).await
+    }
+
+    /// Acquires a shared advisory lock on the file, waiting until it is
+    /// available.
+    ///
+    /// See [`lock_exclusive`](File::lock_exclusive) for more details.
+    pub async fn lock_shared(&self) -> io::Result<()> {
+        self.lock(libc::LOCK_SH).await
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// waiting.
+    ///
+    /// Returns `Ok(false)` if the lock is held by another open file.
+    pub fn try_lock(&self) -> io::Result<bool> {
+        flock(self.fd.raw_fd(), libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without waiting.
+    ///
+    /// Returns `Ok(false)` if an exclusive lock is held by another open file.
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        flock(self.fd.raw_fd(), libc::LOCK_SH | libc::LOCK_NB)
+    }
+
+    /// Releases the advisory lock held on the file.
+    pub fn unlock(&self) -> io::Result<()> {
+        flock(self.fd.raw_fd(), libc::LOCK_UN).map(drop)
+    }
+
+    /// Reads into the buffers of `buf` in order, from the specified offset of
+    /// the file, without using or changing its position. Returns the number
+    /// of bytes read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{buf::VecBuf, fs::File};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt").await?;
+    ///     let bufs = VecBuf::from(vec![vec![0; 16], vec![0; 4096]]);
+    ///     let (res, bufs) = f.readv_at(bufs, 512).await;
+    ///     println!("read {} bytes", res?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn readv_at<T: IoVecBufMut>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        read_vectored_at(self.fd.clone(), buf, pos, 0).await
+    }
+
+    /// Like [`readv_at`](File::readv_at), with the `RWF_*` flags of
+    /// `preadv2(2)`, e.g. `libc::RWF_NOWAIT` to fail with
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock) instead of waiting for data
+    /// that is not in the page cache, or `libc::RWF_HIPRI` for polled IO.
+    ///
+    /// The flags are only supported on Linux, other platforms fail with
+    /// [`Unsupported`](io::ErrorKind::Unsupported) if any is set.
+    pub async fn readv_at_with_flags<T: IoVecBufMut>(
+        &self,
+        buf: T,
+        pos: u64,
+        flags: i32,
+    ) -> crate::BufResult<usize, T> {
+        read_vectored_at(self.fd.clone(), buf, pos, flags).await
+    }
+
+    /// Writes the buffers of `buf` in order, at the specified offset of the
+    /// file, without using or changing its position. Returns the number of
+    /// bytes written, which may be less than the length of the buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{buf::VecBuf, fs::File};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     let bufs = VecBuf::from(vec![b"header".to_vec(), b"body".to_vec()]);
+    ///     let (res, _) = f.writev_at(bufs, 0).await;
+    ///     println!("wrote {} bytes", res?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn writev_at<T: IoVecBuf>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        write_vectored_at(self.fd.clone(), buf, pos, 0).await
+    }
+
+    /// Like [`writev_at`](File::writev_at), with the `RWF_*` flags of
+    /// `pwritev2(2)`, e.g. `libc::RWF_DSYNC` to make this write durable
+    /// as [`sync_data`](File::sync_data) would, without syncing the rest of
+    /// the file.
+    ///
+    /// The flags are only supported on Linux, other platforms fail with
+    /// [`Unsupported`](io::ErrorKind::Unsupported) if any is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{buf::VecBuf, fs::File};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let wal = File::create("wal.log").await?;
+    ///     let record = VecBuf::from(vec![b"len:4;".to_vec(), b"data".to_vec()]);
+    ///     let (res, _) = wal.writev_at_with_flags(record, 0, libc::RWF_DSYNC).await;
+    ///     res?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn writev_at_with_flags<T: IoVecBuf>(
+        &self,
+        buf: T,
+        pos: u64,
+        flags: i32,
+    ) -> crate::BufResult<usize, T> {
+        write_vectored_at(self.fd.clone(), buf, pos, flags).await
+    }
+
+    /// Truncates or extends the file to `size` bytes, as
+    /// [`std::fs::File::set_len`] does. The bytes it is extended with read as
+    /// zeros.
+    ///
+    /// There is no io_uring opcode for `ftruncate`, so it runs on the thread
+    /// pool attached to the runtime if there is one, or on the current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     f.set_len(10).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn set_len(&self, size: u64) -> io::Result<()> {
+        let size = libc::off_t::try_from(size)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "size too big"))?;
+        self.offload(move |fd| crate::syscall!(ftruncate@RAW(fd, size)).map(drop))
+            .await
+    }
+
+    /// Manipulates the disk space allocated to the bytes `offset..offset +
+    /// len` of the file with `fallocate(2)`.
+    ///
+    /// With a `mode` of 0 the space is allocated, and the file is extended if
+    /// it is shorter, so that writing these bytes later on does not fail for a
+    /// lack of space. Other modes are made of the `libc::FALLOC_FL_*` flags,
+    /// e.g. `FALLOC_FL_KEEP_SIZE` preallocates without changing the size of
+    /// the file.
+    ///
+    /// It is an io_uring operation, and a blocking syscall on the legacy
+    /// driver, which runs on the thread pool attached to the runtime if there
+    /// is one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     // Preallocate a 64MiB segment.
+    ///     let f = File::create("segment.log").await?;
+    ///     f.allocate(0, 64 << 20, libc::FALLOC_FL_KEEP_SIZE).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn allocate(&self, offset: u64, len: u64, mode: i32) -> io::Result<()> {
+        use crate::driver::op::{fallocate::fallocate, is_legacy};
+
+        if is_legacy() {
+            return self
+                .offload(move |fd| fallocate(fd, offset, len, mode))
+                .await;
+        }
+        let op = Op::fallocate(&self.fd, offset, len, mode)?;
+        op.await.meta.result?;
+        Ok(())
+    }
+
+    /// Copies up to `len` bytes from `src_offset` in this file to
+    /// `dst_offset` in `dst`, without using or changing the position of
+    /// either file. Returns the number of bytes copied, which is less than
+    /// `len` only if the end of this file is reached.
+    ///
+    /// On Linux the bytes are copied by the kernel with `copy_file_range(2)`,
+    /// on the thread pool attached to the runtime if there is one, or on the
+    /// current thread. There is no io_uring opcode for it. If the kernel
+    /// cannot copy between the files, e.g. they are on different file
+    /// systems of an older kernel, and on other platforms, the range is read
+    /// and written by chunks through the driver instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     // Append the first kilobyte of a.bin to b.bin.
+    ///     let src = File::open("a.bin").await?;
+    ///     let dst = monoio::fs::OpenOptions::new().write(true).open("b.bin").await?;
+    ///     let end = dst.metadata().await?.len();
+    ///     src.copy_range_to(&dst, 0, end, 1024).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn copy_range_to(
+        &self,
+        dst: &File,
+        src_offset: u64,
+        dst_offset: u64,
+        len: u64,
+    ) -> io::Result<u64> {
+        crate::fs::copy::copy_range(self, dst, src_offset, dst_offset, len).await
+    }
+
+    /// Runs the blocking `f` with the fd of the file, on the thread pool
+    /// attached to the runtime if there is one, or on the current thread.
+    async fn offload<T, F>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce(RawFd) -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        #[cfg(feature = "sync")]
+        if crate::blocking::has_thread_pool() {
+            // The duplicated fd stays valid even if the file is closed while
+            // `f` runs.
+            let fd = unsafe { BorrowedFd::borrow_raw(self.fd.raw_fd()) }.try_clone_to_owned()?;
+            return crate::fs::asyncify(move || f(fd.as_raw_fd())).await;
+        }
+        f(self.fd.raw_fd())
+    }
+
+    async fn lock(&self, operation: libc::c_int) -> io::Result<()> {
+        if flock(self.fd.raw_fd(), operation | libc::LOCK_NB)? {
+            return Ok(());
+        }
+        #[cfg(feature = "sync")]
+        if crate::blocking::has_thread_pool() {
+            // The lock belongs to the open file, so it can be acquired through
+            // a duplicated fd, which stays valid even if the file is closed
+            // while waiting.
+            let fd = unsafe { BorrowedFd::borrow_raw(self.fd.raw_fd()) }.try_clone_to_owned()?;
+            return crate::fs::asyncify(move || flock(fd.as_raw_fd(), operation).map(drop)).await;
+        }
+        // A blocking flock would hold up the runtime thread, and retrying
+        // without sleeping in between would spin it.
+        if !crate::runtime::CURRENT.with(|cx| cx.time_handle.is_some()) {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the lock is held, waiting for it needs a thread pool or the timer",
+            ));
+        }
+        let mut backoff = std::time::Duration::from_millis(1);
+        loop {
+            crate::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(std::time::Duration::from_millis(100));
+            if flock(self.fd.raw_fd(), operation | libc::LOCK_NB)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns `Ok(false)` if a non-blocking `flock` would block.
+fn flock(fd: RawFd, operation: libc::c_int) -> io::Result<bool> {
+    loop {
+        match crate::syscall!(flock@RAW(fd, operation)) {
+            Ok(_) => return Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 impl AsRawFd for File {
@@ -58,6 +416,21 @@ impl AsRawFd for File {
     }
 }
 
+impl From<OwnedFd> for File {
+    fn from(fd: OwnedFd) -> Self {
+        File {
+            fd: SharedFd::new_without_register(fd.into_raw_fd()),
+        }
+    }
+}
+
+pub(crate) fn seek(fd: &SharedFd, pos: SeekFrom) -> io::Result<u64> {
+    // lseek only updates the file offset, it does not block. The std file
+    // borrows the fd.
+    let mut file = ManuallyDrop::new(unsafe { StdFile::from_raw_fd(fd.raw_fd()) });
+    file.seek(pos)
+}
+
 pub(crate) async fn metadata(fd: SharedFd) -> std::io::Result<Metadata> {
     #[cfg(target_os = "linux")]
     let flags = libc::AT_STATX_SYNC_AS_STAT | libc::AT_EMPTY_PATH;
@@ -77,10 +450,12 @@ mod iouring {
     uring_op!(read<IoBufMut>(read, buf));
     uring_op!(read_at<IoBufMut>(read_at, buf, pos: u64));
     uring_op!(read_vectored<IoVecBufMut>(readv, buf_vec));
+    uring_op!(read_vectored_at<IoVecBufMut>(read_vectored_at, buf_vec, pos: u64, flags: i32));
 
     uring_op!(write<IoBuf>(write, buf));
     uring_op!(write_at<IoBuf>(write_at, buf, pos: u64));
     uring_op!(write_vectored<IoVecBuf>(writev, buf_vec));
+    uring_op!(write_vectored_at<IoVecBuf>(write_vectored_at, buf_vec, pos: u64, flags: i32));
 }
 
 #[cfg(all(not(feature = "iouring"), feature = "sync"))]
@@ -94,8 +469,10 @@ mod asyncified {
     asyncify_op!(R, read<IoBufMut>(read::read, IoBufMut::write_ptr, IoBufMut::bytes_total));
     asyncify_op!(R, read_at<IoBufMut>(read::read_at, IoBufMut::write_ptr, IoBufMut::bytes_total, pos: u64));
     asyncify_op!(R, read_vectored<IoVecBufMut>(read::read_vectored, IoVecBufMut::write_iovec_ptr, IoVecBufMut::write_iovec_len));
+    asyncify_op!(R, read_vectored_at<IoVecBufMut>(read::read_vectored_at, IoVecBufMut::write_iovec_ptr, IoVecBufMut::write_iovec_len, pos: u64, flags: i32));
 
     asyncify_op!(W, write<IoBuf>(write::write, IoBuf::read_ptr, IoBuf::bytes_init));
     asyncify_op!(W, write_at<IoBuf>(write::write_at, IoBuf::read_ptr, IoBuf::bytes_init, pos: u64));
     asyncify_op!(W, write_vectored<IoVecBuf>(write::write_vectored, IoVecBuf::read_iovec_ptr, IoVecBuf::read_iovec_len));
+    asyncify_op!(W, write_vectored_at<IoVecBuf>(write::write_vectored_at, IoVecBuf::read_iovec_ptr, IoVecBuf::read_iovec_len, pos: u64, flags: i32));
 }
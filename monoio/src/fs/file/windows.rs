@@ -1,6 +1,8 @@
 use std::{
+    fs::File as StdFile,
+    io::{self, Seek, SeekFrom},
     mem::ManuallyDrop,
-    os::windows::io::{AsRawHandle, RawHandle},
+    os::windows::io::{AsRawHandle, FromRawHandle, RawHandle},
 };
 
 #[cfg(all(not(feature = "iouring"), feature = "sync"))]
@@ -13,14 +15,41 @@ use super::File;
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
     driver::shared_fd::SharedFd,
+    fs::Metadata,
 };
 
+impl File {
+    /// Queries metadata about the underlying file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut f = File::open("foo.txt").await?;
+    ///     let metadata = f.metadata().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        crate::fs::metadata::fd_metadata(&self.fd)
+    }
+}
+
 impl AsRawHandle for File {
     fn as_raw_handle(&self) -> RawHandle {
         self.fd.raw_handle()
     }
 }
 
+pub(crate) fn seek(fd: &SharedFd, pos: SeekFrom) -> io::Result<u64> {
+    // The std file borrows the handle.
+    let mut file = ManuallyDrop::new(unsafe { StdFile::from_raw_handle(fd.raw_handle()) });
+    file.seek(pos)
+}
+
 #[cfg(any(feature = "iouring", not(feature = "sync")))]
 mod blocking {
     use super::*;
@@ -25,6 +25,27 @@ impl FileType {
         self.is(libc::S_IFLNK)
     }
 
+    pub(crate) fn from_std(file_type: std::fs::FileType) -> FileType {
+        let mode = if file_type.is_dir() {
+            libc::S_IFDIR
+        } else if file_type.is_file() {
+            libc::S_IFREG
+        } else if file_type.is_symlink() {
+            libc::S_IFLNK
+        } else if file_type.is_block_device() {
+            libc::S_IFBLK
+        } else if file_type.is_char_device() {
+            libc::S_IFCHR
+        } else if file_type.is_fifo() {
+            libc::S_IFIFO
+        } else if file_type.is_socket() {
+            libc::S_IFSOCK
+        } else {
+            0
+        };
+        FileType { mode }
+    }
+
     pub(crate) fn is(&self, mode: mode_t) -> bool {
         self.masked() == mode
     }
@@ -0,0 +1,307 @@
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use super::{walk_dir, WalkDir, WalkEntry};
+use crate::io::stream::Stream;
+
+/// Returns a stream of the entries matching a glob `pattern`.
+///
+/// The directory tree is walked from the longest leading part of the pattern
+/// without wildcards, see [`GlobPattern`] for the supported syntax. Entries
+/// are yielded as [`WalkEntry`]s, whose paths are relative to the current
+/// directory if the pattern is.
+///
+/// # Errors
+///
+/// This function will return an error if the pattern is invalid. Errors
+/// while walking the tree are yielded by the stream.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::io::stream::Stream;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut logs = monoio::fs::glob("/var/log/**/*.log.[0-9]")?;
+///     while let Some(entry) = logs.next().await {
+///         let entry = entry?;
+///         println!("{}", entry.path().display());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn glob(pattern: &str) -> io::Result<Glob> {
+    let pattern = GlobPattern::new(pattern)?;
+    let prefix = pattern.prefix();
+    let rest = &pattern.segments[prefix.len()..];
+    let min_depth = rest.iter().filter(|s| !matches!(s, Segment::Any)).count();
+    let max_depth = if rest.iter().any(|s| matches!(s, Segment::Any)) {
+        usize::MAX
+    } else {
+        rest.len()
+    };
+
+    let strip_cur_dir = prefix.is_empty();
+    let root = if strip_cur_dir {
+        PathBuf::from(".")
+    } else {
+        prefix
+            .iter()
+            .map(|s| match s {
+                Segment::Exact(s) => s.as_os_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    };
+
+    Ok(Glob {
+        walk: walk_dir(root).min_depth(min_depth).max_depth(max_depth),
+        pattern,
+        strip_cur_dir,
+        started: false,
+    })
+}
+
+/// A stream of the entries matching a glob pattern, see [`glob`].
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct Glob {
+    walk: WalkDir,
+    pattern: GlobPattern,
+    strip_cur_dir: bool,
+    started: bool,
+}
+
+impl Glob {
+    /// Follow symbolic links while walking, see [`WalkDir::follow_links`].
+    pub fn follow_links(mut self, follow: bool) -> Self {
+        self.walk = self.walk.follow_links(follow);
+        self
+    }
+
+    /// Yield the entries of each directory sorted by file name.
+    pub fn sort_by_file_name(mut self) -> Self {
+        self.walk = self.walk.sort_by_file_name();
+        self
+    }
+}
+
+impl Stream for Glob {
+    type Item = io::Result<WalkEntry>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let started = std::mem::replace(&mut self.started, true);
+            let mut entry = match self.walk.next().await? {
+                Ok(entry) => entry,
+                // Nothing matches if the root does not exist.
+                Err(e) if !started && e.kind() == io::ErrorKind::NotFound => return None,
+                Err(e) => return Some(Err(e)),
+            };
+            if self.strip_cur_dir {
+                if let Ok(path) = entry.path.strip_prefix(".") {
+                    entry.path = path.to_path_buf();
+                }
+            }
+            if self.pattern.matches(&entry.path) {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+/// A compiled glob pattern.
+///
+/// Patterns are matched against paths component by component:
+///
+/// * `?` matches any single character.
+/// * `*` matches any sequence of characters within a component.
+/// * `[abc]`, `[a-z]` match any of the listed characters, and `[!abc]` any of
+///   the others.
+/// * `**` as a whole component matches any number of components.
+///
+/// Wildcards also match a leading `.`, and `.` components are ignored on both
+/// sides.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::fs::GlobPattern;
+///
+/// let pattern = GlobPattern::new("logs/**/*.log").unwrap();
+/// assert!(pattern.matches("logs/app.log"));
+/// assert!(pattern.matches("logs/2024/01/app.log"));
+/// assert!(!pattern.matches("logs/app.txt"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GlobPattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    // Matched literally, including root and parent components.
+    Exact(OsString),
+    // The `**` component.
+    Any,
+    Wildcard(Vec<Token>),
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Char(char),
+    AnyChar,
+    AnySeq,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl GlobPattern {
+    /// Compiles a glob pattern.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an [`InvalidInput`](io::ErrorKind::InvalidInput)
+    /// error if a `[` character class is not closed.
+    pub fn new(pattern: &str) -> io::Result<GlobPattern> {
+        let segments = Path::new(pattern)
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .map(|c| match c {
+                Component::Normal(s) if s == "**" => Ok(Segment::Any),
+                Component::Normal(s) => {
+                    let s = s.to_str().expect("pattern is valid UTF-8");
+                    if s.contains(['*', '?', '[']) {
+                        parse_wildcard(s).map(Segment::Wildcard)
+                    } else {
+                        Ok(Segment::Exact(s.into()))
+                    }
+                }
+                c => Ok(Segment::Exact(c.as_os_str().to_os_string())),
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(GlobPattern { segments })
+    }
+
+    /// Returns `true` if `path` matches the pattern.
+    pub fn matches(&self, path: impl AsRef<Path>) -> bool {
+        let components: Vec<&OsStr> = path
+            .as_ref()
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .map(|c| c.as_os_str())
+            .collect();
+        match_segments(&self.segments, &components)
+    }
+
+    // The leading segments without wildcards.
+    fn prefix(&self) -> &[Segment] {
+        let len = self
+            .segments
+            .iter()
+            .take_while(|s| matches!(s, Segment::Exact(_)))
+            .count();
+        &self.segments[..len]
+    }
+}
+
+fn parse_wildcard(s: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '?' => Token::AnyChar,
+            // Consecutive `*` are equivalent to a single one.
+            '*' if matches!(tokens.last(), Some(Token::AnySeq)) => continue,
+            '*' => Token::AnySeq,
+            '[' => {
+                let mut negated = false;
+                let mut ranges = Vec::new();
+                let mut first = true;
+                loop {
+                    let c = chars.next().ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "unclosed character class in glob pattern",
+                        )
+                    })?;
+                    match c {
+                        '!' | '^' if first && !negated => {
+                            negated = true;
+                            continue;
+                        }
+                        // A leading `]` is part of the class.
+                        ']' if !first => break,
+                        _ => {}
+                    }
+                    first = false;
+                    let mut lookahead = chars.clone();
+                    match (lookahead.next(), lookahead.next()) {
+                        (Some('-'), Some(end)) if end != ']' => {
+                            ranges.push((c, end));
+                            chars = lookahead;
+                        }
+                        _ => ranges.push((c, c)),
+                    }
+                }
+                Token::Class { negated, ranges }
+            }
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn match_segments(segments: &[Segment], components: &[&OsStr]) -> bool {
+    match segments.split_first() {
+        None => components.is_empty(),
+        Some((Segment::Any, rest)) => {
+            (0..=components.len()).any(|i| match_segments(rest, &components[i..]))
+        }
+        Some((segment, rest)) => match components.split_first() {
+            Some((component, components)) => {
+                match_segment(segment, component) && match_segments(rest, components)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_segment(segment: &Segment, component: &OsStr) -> bool {
+    match segment {
+        Segment::Exact(s) => s == component,
+        Segment::Any => true,
+        Segment::Wildcard(tokens) => {
+            let name: Vec<char> = component.to_string_lossy().chars().collect();
+            match_tokens(tokens, &name)
+        }
+    }
+}
+
+fn match_tokens(tokens: &[Token], name: &[char]) -> bool {
+    match tokens.split_first() {
+        None => name.is_empty(),
+        Some((Token::AnySeq, rest)) => (0..=name.len()).any(|i| match_tokens(rest, &name[i..])),
+        Some((token, rest)) => match name.split_first() {
+            Some((&c, name)) => {
+                let matched = match token {
+                    Token::Char(expected) => c == *expected,
+                    Token::AnyChar => true,
+                    Token::Class { negated, ranges } => {
+                        ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+                    }
+                    Token::AnySeq => unreachable!(),
+                };
+                matched && match_tokens(rest, name)
+            }
+            None => false,
+        },
+    }
+}
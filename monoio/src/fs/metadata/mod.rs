@@ -1,9 +1,19 @@
+#[cfg(unix)]
 mod unix;
+#[cfg(windows)]
 mod windows;
 
+#[cfg(unix)]
 use std::{os::unix::fs::MetadataExt, path::Path, time::SystemTime};
 
+#[cfg(windows)]
+pub(crate) use windows::metadata as fd_metadata;
+#[cfg(windows)]
+pub use windows::Metadata;
+
+#[cfg(unix)]
 use super::{file_type::FileType, permissions::Permissions};
+#[cfg(unix)]
 use crate::driver::op::Op;
 
 /// Given a path, query the file system to get information about a file,
@@ -38,6 +48,7 @@ use crate::driver::op::Op;
 ///     Ok(())
 /// }
 /// ```
+#[cfg(unix)]
 pub async fn metadata<P: AsRef<Path>>(path: P) -> std::io::Result<Metadata> {
     #[cfg(target_os = "linux")]
     let flags = libc::AT_STATX_SYNC_AS_STAT;
@@ -78,6 +89,7 @@ pub async fn metadata<P: AsRef<Path>>(path: P) -> std::io::Result<Metadata> {
 ///     Ok(())
 /// }
 /// ```
+#[cfg(unix)]
 pub async fn symlink_metadata<P: AsRef<Path>>(path: P) -> std::io::Result<Metadata> {
     #[cfg(target_os = "linux")]
     let flags = libc::AT_STATX_SYNC_AS_STAT | libc::AT_SYMLINK_NOFOLLOW;
@@ -91,6 +103,71 @@ pub async fn symlink_metadata<P: AsRef<Path>>(path: P) -> std::io::Result<Metada
     op.result().await.map(FileAttr::from).map(Metadata)
 }
 
+/// Returns `Ok(true)` if the path points at an existing entity.
+///
+/// This function will traverse symbolic links to query information about the
+/// destination file. In case of broken symbolic links this will return
+/// `Ok(false)`.
+///
+/// This is async version of [std::fs::exists].
+///
+/// # Errors
+///
+/// This function will return an error if the existence of `path` can neither
+/// be confirmed nor denied, e.g. if permission is denied on one of its parent
+/// directories.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use monoio::fs;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     if !fs::try_exists("/some/file/path.txt").await? {
+///         println!("not found");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub async fn try_exists<P: AsRef<Path>>(path: P) -> std::io::Result<bool> {
+    match metadata(path).await {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns `true` if the path points at an existing entity.
+///
+/// Errors are treated as non-existence, use [`try_exists`] to tell them
+/// apart.
+///
+/// This is async version of [std::path::Path::exists].
+#[cfg(unix)]
+pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+    metadata(path).await.is_ok()
+}
+
+/// Returns `true` if the path exists and points at a regular file, following
+/// symbolic links.
+///
+/// This is async version of [std::path::Path::is_file].
+#[cfg(unix)]
+pub async fn is_file<P: AsRef<Path>>(path: P) -> bool {
+    metadata(path).await.is_ok_and(|m| m.is_file())
+}
+
+/// Returns `true` if the path exists and points at a directory, following
+/// symbolic links.
+///
+/// This is async version of [std::path::Path::is_dir].
+#[cfg(unix)]
+pub async fn is_dir<P: AsRef<Path>>(path: P) -> bool {
+    metadata(path).await.is_ok_and(|m| m.is_dir())
+}
+
 #[cfg(unix)]
 pub(crate) use unix::FileAttr;
 
@@ -103,6 +180,7 @@ pub(crate) use unix::FileAttr;
 #[cfg(unix)]
 pub struct Metadata(pub(crate) FileAttr);
 
+#[cfg(unix)]
 impl Metadata {
     /// Returns `true` if this metadata is for a directory.
     ///
@@ -304,6 +382,7 @@ impl Metadata {
     }
 }
 
+#[cfg(unix)]
 impl std::fmt::Debug for Metadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut debug = f.debug_struct("Metadata");
@@ -1 +1,64 @@
+use std::{
+    fs::File as StdFile, io, mem::ManuallyDrop, os::windows::io::FromRawHandle, time::SystemTime,
+};
 
+use crate::driver::shared_fd::SharedFd;
+
+/// Metadata information about a file.
+///
+/// This structure is returned from the [`File::metadata`](crate::fs::File::metadata)
+/// method and represents known metadata about a file such as its size and
+/// modification times.
+pub struct Metadata(std::fs::Metadata);
+
+impl Metadata {
+    /// Returns `true` if this metadata is for a directory.
+    pub fn is_dir(&self) -> bool {
+        self.0.is_dir()
+    }
+
+    /// Returns `true` if this metadata is for a regular file.
+    pub fn is_file(&self) -> bool {
+        self.0.is_file()
+    }
+
+    /// Returns `true` if this metadata is for a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.0.is_symlink()
+    }
+
+    /// Returns the size of the file, in bytes, this metadata is for.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.0.len()
+    }
+
+    /// Returns the last modification time listed in this metadata.
+    pub fn modified(&self) -> io::Result<SystemTime> {
+        self.0.modified()
+    }
+
+    /// Returns the last access time of this metadata.
+    pub fn accessed(&self) -> io::Result<SystemTime> {
+        self.0.accessed()
+    }
+
+    /// Returns the creation time listed in this metadata.
+    pub fn created(&self) -> io::Result<SystemTime> {
+        self.0.created()
+    }
+}
+
+impl std::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Queries the metadata of an open file with `GetFileInformationByHandle`,
+/// which does not wait for the disk.
+pub(crate) fn metadata(fd: &SharedFd) -> io::Result<Metadata> {
+    // The std file borrows the handle.
+    let file = ManuallyDrop::new(unsafe { StdFile::from_raw_handle(fd.raw_handle()) });
+    file.metadata().map(Metadata)
+}
@@ -24,9 +24,35 @@ mod open_options;
 pub use open_options::OpenOptions;
 
 #[cfg(unix)]
+mod dir;
+#[cfg(unix)]
+pub use dir::Dir;
+
+#[cfg(unix)]
+mod copy;
+#[cfg(unix)]
+pub use copy::copy;
+
+#[cfg(unix)]
+mod read_dir;
+#[cfg(unix)]
+pub use read_dir::{read_dir, DirEntry, ReadDir};
+
+#[cfg(unix)]
+mod walk_dir;
+#[cfg(unix)]
+pub use walk_dir::{walk_dir, WalkDir, WalkEntry};
+
+#[cfg(unix)]
+mod glob;
+#[cfg(unix)]
+pub use glob::{glob, Glob, GlobPattern};
+
 mod metadata;
+#[cfg(windows)]
+pub use metadata::Metadata;
 #[cfg(unix)]
-pub use metadata::{metadata, symlink_metadata, Metadata};
+pub use metadata::{exists, is_dir, is_file, metadata, symlink_metadata, try_exists, Metadata};
 
 #[cfg(unix)]
 mod file_type;
@@ -69,7 +95,7 @@ use crate::driver::op::Op;
 /// - The blocking task returned an error, in which case the error is propagated.
 /// - The background task failed to complete due to an internal error, in which case an error with
 ///   `io::ErrorKind::Other` is returned.
-#[cfg(all(feature = "sync", not(feature = "iouring")))]
+#[cfg(all(feature = "sync", any(unix, not(feature = "iouring"))))]
 pub(crate) async fn asyncify<F, T>(f: F) -> io::Result<T>
 where
     F: FnOnce() -> io::Result<T> + Send + 'static,
@@ -86,13 +112,31 @@ where
     }
 }
 
+/// Runs a blocking call on the thread pool attached to the runtime, or on the
+/// current thread if there is none.
+#[cfg(unix)]
+pub(crate) async fn offload<F, T>(f: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "sync")]
+    if crate::blocking::has_thread_pool() {
+        return asyncify(f).await;
+    }
+    f()
+}
+
 /// A macro that generates the some Op-call functions.
 #[cfg(any(feature = "iouring", not(feature = "sync")))]
 #[macro_export]
 macro_rules! uring_op {
-    ($fn_name:ident<$trait_name:ident>($op_name: ident, $buf_name:ident $(, $pos:ident: $pos_type:ty)?)) => {
-        pub(crate) async fn $fn_name<T: $trait_name>(fd: SharedFd, $buf_name: T, $($pos: $pos_type)?) -> $crate::BufResult<usize, T> {
-            let op = $crate::driver::op::Op::$op_name(fd, $buf_name, $($pos)?).unwrap();
+    ($fn_name:ident<$trait_name:ident>($op_name: ident, $buf_name:ident $(, $pos:ident: $pos_type:ty)*)) => {
+        pub(crate) async fn $fn_name<T: $trait_name>(fd: SharedFd, $buf_name: T, $($pos: $pos_type),*) -> $crate::BufResult<usize, T> {
+            let op = $crate::driver::with_fs_ring(|| {
+                $crate::driver::op::Op::$op_name(fd, $buf_name, $($pos),*)
+            })
+            .unwrap();
             op.result().await
         }
     };
@@ -107,11 +151,11 @@ macro_rules! uring_op {
 #[cfg(all(feature = "sync", not(feature = "iouring")))]
 #[macro_export]
 macro_rules! asyncify_op {
-    (R, $fn_name:ident<$Trait: ident>($op:expr, $buf_ptr_expr:expr, $len_expr:expr $(, $extra_param:ident : $typ: ty)?)) => {
+    (R, $fn_name:ident<$Trait: ident>($op:expr, $buf_ptr_expr:expr, $len_expr:expr $(, $extra_param:ident : $typ: ty)*)) => {
         pub(crate) async fn $fn_name<T: $Trait>(
             fd: SharedFd,
             mut buf: T,
-            $($extra_param: $typ)?
+            $($extra_param: $typ),*
         ) -> $crate::BufResult<usize, T> {
             #[cfg(unix)]
             let fd = fd.as_raw_fd();
@@ -123,7 +167,7 @@ macro_rules! asyncify_op {
             let buf_ptr = $buf_ptr_expr(&mut buf) as usize;
             let len = $len_expr(&mut buf);
 
-            let res = $crate::fs::asyncify(move || $op(fd, buf_ptr as *mut _, len, $($extra_param)?))
+            let res = $crate::fs::asyncify(move || $op(fd, buf_ptr as *mut _, len, $($extra_param),*))
                 .await
                 .map(|n| n.into_inner() as usize);
 
@@ -132,11 +176,11 @@ macro_rules! asyncify_op {
             (res, buf)
         }
     };
-    (W, $fn_name:ident<$Trait: ident>($op:expr, $buf_ptr_expr:expr, $len_expr:expr $(, $extra_param:ident : $typ: ty)?)) => {
+    (W, $fn_name:ident<$Trait: ident>($op:expr, $buf_ptr_expr:expr, $len_expr:expr $(, $extra_param:ident : $typ: ty)*)) => {
         pub(crate) async fn $fn_name<T: $Trait>(
             fd: SharedFd,
             mut buf: T,
-            $($extra_param: $typ)?
+            $($extra_param: $typ),*
         ) -> $crate::BufResult<usize, T> {
             #[cfg(unix)]
             let fd = fd.as_raw_fd();
@@ -148,7 +192,7 @@ macro_rules! asyncify_op {
             let buf_ptr = $buf_ptr_expr(&mut buf) as usize;
             let len = $len_expr(&mut buf);
 
-            let res = $crate::fs::asyncify(move || $op(fd, buf_ptr as *mut _, len, $($extra_param)?))
+            let res = $crate::fs::asyncify(move || $op(fd, buf_ptr as *mut _, len, $($extra_param),*))
                 .await
                 .map(|n| n.into_inner() as usize);
 
@@ -161,9 +205,7 @@ macro_rules! asyncify_op {
 
 /// Read the entire contents of a file into a bytes vector.
 pub async fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
-    use crate::buf::IoBufMut;
-
-    let file = File::open(path).await?;
+    let mut file = File::open(path).await?;
 
     #[cfg(windows)]
     let size = {
@@ -176,11 +218,32 @@ pub async fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
     #[cfg(unix)]
     let size = file.metadata().await?.len() as usize;
 
-    let (res, buf) = file
-        .read_exact_at(Vec::with_capacity(size).slice_mut(0..size), 0)
-        .await;
+    // The size is only a hint: files like the ones in procfs report 0.
+    let (res, buf) = file.read_to_end(Vec::with_capacity(size)).await;
     res?;
-    Ok(buf.into_inner())
+    Ok(buf)
+}
+
+/// Read the entire contents of a file into a string.
+///
+/// # Errors
+///
+/// This function will return an error if `path` does not exist, if reading it
+/// fails, or if its contents are not valid UTF-8.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let config = monoio::fs::read_to_string("config.toml").await?;
+///     println!("{config}");
+///     Ok(())
+/// }
+/// ```
+pub async fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let buf = read(path).await?;
+    String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
 /// Write a buffer as the entire contents of a file.
@@ -191,6 +254,24 @@ pub async fn write<P: AsRef<Path>, C: IoBuf>(path: P, contents: C) -> (io::Resul
     }
 }
 
+/// Write a string slice as the entire contents of a file.
+///
+/// The string is copied into an owned buffer; pass an owned `String` to
+/// [`write`] to avoid the copy.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let name = "monoio";
+///     monoio::fs::write_str("hello.txt", &format!("hello {name}")).await
+/// }
+/// ```
+pub async fn write_str<P: AsRef<Path>>(path: P, contents: &str) -> io::Result<()> {
+    write(path, contents.as_bytes().to_vec()).await.0
+}
+
 /// Removes a file from the filesystem.
 ///
 /// Note that there is no
@@ -286,3 +367,104 @@ pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Resul
     Op::rename(from.as_ref(), to.as_ref())?.await.meta.result?;
     Ok(())
 }
+
+/// Atomically replace the contents of a file.
+///
+/// `contents` are written to a temporary file next to `path`, flushed to disk
+/// and renamed over `path`, then the parent directory is flushed so that the
+/// rename itself is durable. Readers observe either the old or the new
+/// contents, never a partially written file, even if the process or the
+/// machine crashes in between.
+///
+/// The file is created with the default permissions (`0o666` masked by the
+/// process umask), regardless of the permissions of the file it replaces.
+///
+/// # Errors
+///
+/// This function will return an error if `path` has no file name, if its
+/// parent directory does not exist, or if any of the steps above fails. On
+/// error the temporary file is removed and `path` is left untouched.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let (res, _) = monoio::fs::write_atomic("state.json", b"{}".to_vec()).await;
+///     res
+/// }
+/// ```
+#[cfg(all(unix, feature = "renameat", feature = "unlinkat"))]
+pub async fn write_atomic<P: AsRef<Path>, C: IoBuf>(path: P, contents: C) -> (io::Result<()>, C) {
+    let path = path.as_ref();
+    let name = match path.file_name() {
+        Some(name) => name,
+        None => {
+            return (
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "path has no file name",
+                )),
+                contents,
+            )
+        }
+    };
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => Dir::open(parent).await,
+        _ => Dir::open(".").await,
+    };
+    let dir = match dir {
+        Ok(dir) => dir,
+        Err(e) => return (Err(e), contents),
+    };
+
+    let (res, contents) = write_atomic_at(&dir, name, contents).await;
+    let _ = dir.close().await;
+    (res, contents)
+}
+
+#[cfg(all(unix, feature = "renameat", feature = "unlinkat"))]
+async fn write_atomic_at<C: IoBuf>(
+    dir: &Dir,
+    name: &std::ffi::OsStr,
+    contents: C,
+) -> (io::Result<()>, C) {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    // A stale temporary file may be left over by a crashed process with the
+    // same pid, so retry with another name if it exists.
+    let mut attempts = 0;
+    let (tmp, file) = loop {
+        let mut tmp = std::ffi::OsString::from(".");
+        tmp.push(name);
+        tmp.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        match dir
+            .open_at(&tmp, OpenOptions::new().write(true).create_new(true))
+            .await
+        {
+            Ok(file) => break (tmp, file),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < 16 => attempts += 1,
+            Err(e) => return (Err(e), contents),
+        }
+    };
+
+    let (mut res, contents) = file.write_all_at(contents, 0).await;
+    if res.is_ok() {
+        res = file.sync_all().await;
+    }
+    let _ = file.close().await;
+    if res.is_ok() {
+        res = dir.rename_at(&tmp, dir, name).await;
+    }
+    if res.is_err() {
+        let _ = dir.unlink_at(&tmp).await;
+        return (res, contents);
+    }
+    (dir.sync_all().await, contents)
+}
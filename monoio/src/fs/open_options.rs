@@ -1,5 +1,7 @@
 #[cfg(unix)]
 use std::os::unix::prelude::OpenOptionsExt;
+#[cfg(windows)]
+use std::os::windows::fs::OpenOptionsExt;
 use std::{io, path::Path};
 
 #[cfg(windows)]
@@ -9,7 +11,7 @@ use windows_sys::Win32::{
     Storage::FileSystem::{
         CREATE_ALWAYS, CREATE_NEW, FILE_FLAG_OPEN_REPARSE_POINT, FILE_GENERIC_WRITE,
         FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, FILE_WRITE_DATA, OPEN_ALWAYS,
-        OPEN_EXISTING, TRUNCATE_EXISTING,
+        OPEN_EXISTING, SECURITY_SQOS_PRESENT, TRUNCATE_EXISTING,
     },
 };
 
@@ -62,6 +64,30 @@ use crate::{
 ///     Ok(())
 /// }
 /// ```
+///
+/// Platform-specific options, like the permission bits of a new file or extra
+/// open flags, are set through the `OpenOptionsExt` trait of
+/// [unix](std::os::unix::fs::OpenOptionsExt) or
+/// [windows](std::os::windows::fs::OpenOptionsExt):
+///
+#[cfg_attr(unix, doc = "```no_run")]
+#[cfg_attr(not(unix), doc = "```ignore")]
+/// use std::os::unix::fs::OpenOptionsExt;
+///
+/// use monoio::fs::OpenOptions;
+///
+/// #[monoio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let file = OpenOptions::new()
+///         .write(true)
+///         .create(true)
+///         .mode(0o600)
+///         .custom_flags(libc::O_SYNC)
+///         .open("db.wal")
+///         .await?;
+///     Ok(())
+/// }
+/// ```
 #[derive(Debug, Clone)]
 pub struct OpenOptions {
     read: bool,
@@ -356,6 +382,21 @@ impl OpenOptions {
         )))
     }
 
+    /// Opens a file at `path` relative to `dir` with the options specified by
+    /// `self`.
+    #[cfg(unix)]
+    pub(crate) async fn open_at(&self, dir: Option<&SharedFd>, path: &Path) -> io::Result<File> {
+        let op = Op::open_at(dir, path, self)?;
+
+        // Await the completion of the event
+        let completion = op.await;
+
+        // The file is open
+        Ok(File::from_shared_fd(SharedFd::new_without_register(
+            completion.meta.result?.into_inner() as _,
+        )))
+    }
+
     #[cfg(unix)]
     pub(crate) fn access_mode(&self) -> io::Result<libc::c_int> {
         match (self.read, self.write, self.append) {
@@ -458,3 +499,33 @@ impl OpenOptionsExt for OpenOptions {
         self
     }
 }
+
+#[cfg(windows)]
+impl OpenOptionsExt for OpenOptions {
+    fn access_mode(&mut self, access: u32) -> &mut Self {
+        self.access_mode = Some(access);
+        self
+    }
+
+    fn share_mode(&mut self, share: u32) -> &mut Self {
+        self.share_mode = share;
+        self
+    }
+
+    fn custom_flags(&mut self, flags: u32) -> &mut Self {
+        self.custom_flags = flags;
+        self
+    }
+
+    fn attributes(&mut self, attributes: u32) -> &mut Self {
+        self.attributes = attributes;
+        self
+    }
+
+    fn security_qos_flags(&mut self, flags: u32) -> &mut Self {
+        // SECURITY_ANONYMOUS is 0, so the flags are only taken into account
+        // together with SECURITY_SQOS_PRESENT.
+        self.security_qos_flags = flags | SECURITY_SQOS_PRESENT;
+        self
+    }
+}
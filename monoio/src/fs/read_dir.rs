@@ -0,0 +1,158 @@
+use std::{
+    collections::VecDeque,
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    fs::{symlink_metadata, FileType, Metadata},
+    io::stream::Stream,
+};
+
+// Entries listed per trip to the thread pool.
+const BATCH: usize = 64;
+
+/// Returns a stream over the entries within a directory.
+///
+/// The entries do not include `.` and `..`, and are yielded in the order
+/// returned by the operating system.
+///
+/// There is no io_uring opcode for `getdents64`, so the directory is listed
+/// in batches on the thread pool attached to the runtime if there is one,
+/// otherwise on the current thread, letting the other tasks run between
+/// batches.
+///
+/// # Errors
+///
+/// This function will return an error if `path` does not exist, is not a
+/// directory, or the user lacks permissions to list it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::io::stream::Stream;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut entries = monoio::fs::read_dir("/var/www").await?;
+///     while let Some(entry) = entries.next().await {
+///         let entry = entry?;
+///         if entry.file_type().is_file() {
+///             let len = entry.metadata().await?.len();
+///             println!("{}: {len} bytes", entry.path().display());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub async fn read_dir(path: impl AsRef<Path>) -> io::Result<ReadDir> {
+    let path = path.as_ref().to_path_buf();
+    let inner = crate::fs::offload(move || std::fs::read_dir(path)).await?;
+    Ok(ReadDir {
+        inner: Some(inner),
+        buffered: VecDeque::new(),
+        listed: false,
+    })
+}
+
+/// A stream of the entries of a directory, see [`read_dir`].
+///
+/// If a call to [`next`](Stream::next) is canceled while a batch is being
+/// listed on the thread pool, the stream ends.
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct ReadDir {
+    // Taken while a batch is being listed, and once exhausted.
+    inner: Option<std::fs::ReadDir>,
+    buffered: VecDeque<io::Result<DirEntry>>,
+    // Whether a batch was listed already.
+    listed: bool,
+}
+
+impl ReadDir {
+    async fn next_entry(&mut self) -> Option<io::Result<DirEntry>> {
+        loop {
+            if let Some(entry) = self.buffered.pop_front() {
+                return Some(entry);
+            }
+            let mut inner = self.inner.take()?;
+            #[cfg(feature = "sync")]
+            let local = !crate::blocking::has_thread_pool();
+            #[cfg(not(feature = "sync"))]
+            let local = true;
+            if local && self.listed {
+                // Do not hold up the other tasks with a large directory.
+                crate::task::coop::yield_now().await;
+            }
+            self.listed = true;
+            let (inner, batch) = crate::fs::offload(move || {
+                let batch: VecDeque<_> =
+                    inner.by_ref().take(BATCH).map(DirEntry::from_std).collect();
+                let inner = (batch.len() == BATCH).then_some(inner);
+                Ok((inner, batch))
+            })
+            .await
+            .unwrap_or_else(|e| (None, VecDeque::from([Err(e)])));
+            self.inner = inner;
+            self.buffered = batch;
+            if self.buffered.is_empty() {
+                return None;
+            }
+        }
+    }
+}
+
+impl Stream for ReadDir {
+    type Item = io::Result<DirEntry>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().await
+    }
+}
+
+/// An entry yielded by [`ReadDir`].
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    path: PathBuf,
+    file_name: OsString,
+    file_type: FileType,
+}
+
+impl DirEntry {
+    fn from_std(entry: io::Result<std::fs::DirEntry>) -> io::Result<DirEntry> {
+        let entry = entry?;
+        Ok(DirEntry {
+            path: entry.path(),
+            file_name: entry.file_name(),
+            file_type: FileType::from_std(entry.file_type()?),
+        })
+    }
+
+    /// Returns the path of the entry, the listed path joined with its file
+    /// name.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the file name of the entry.
+    pub fn file_name(&self) -> &OsStr {
+        &self.file_name
+    }
+
+    /// Returns the file type of the entry, which is not followed if it is a
+    /// symbolic link.
+    ///
+    /// It is read along with the entry on most file systems, without a
+    /// `stat`.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Queries the metadata of the entry, which is not followed if it is a
+    /// symbolic link.
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        symlink_metadata(&self.path).await
+    }
+}
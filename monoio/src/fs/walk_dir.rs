@@ -0,0 +1,292 @@
+use std::{
+    ffi::OsStr,
+    io,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    fs::{metadata, read_dir, symlink_metadata, DirEntry, FileType, Metadata, ReadDir},
+    io::stream::Stream,
+};
+
+/// Recursively walks the directory tree rooted at `root`.
+///
+/// The returned [`WalkDir`] is a [`Stream`] yielding the root itself first,
+/// then every entry below it in depth-first order. Entries of a directory
+/// are yielded in the order returned by the operating system, unless
+/// [`sort_by_file_name`](WalkDir::sort_by_file_name) is set.
+///
+/// Directories are listed like with [`read_dir`], a sorted directory is
+/// listed whole before its first entry is yielded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::io::stream::Stream;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut walk = monoio::fs::walk_dir("/var/log").max_depth(2);
+///     while let Some(entry) = walk.next().await {
+///         let entry = entry?;
+///         println!("{}", entry.path().display());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn walk_dir(root: impl AsRef<Path>) -> WalkDir {
+    WalkDir {
+        root: Some(root.as_ref().to_path_buf()),
+        min_depth: 0,
+        max_depth: usize::MAX,
+        follow_links: false,
+        sort: false,
+        pending: None,
+        stack: Vec::new(),
+    }
+}
+
+/// A stream of the entries of a directory tree, see [`walk_dir`].
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct WalkDir {
+    root: Option<PathBuf>,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sort: bool,
+    // A directory entry that was yielded but not listed yet.
+    pending: Option<(PathBuf, usize)>,
+    stack: Vec<Level>,
+}
+
+#[derive(Debug)]
+struct Level {
+    path: PathBuf,
+    depth: usize,
+    // Device and inode of the directory, only tracked when following links.
+    id: Option<(u64, u64)>,
+    entries: Entries,
+}
+
+#[derive(Debug)]
+enum Entries {
+    Listing(ReadDir),
+    Sorted(std::vec::IntoIter<DirEntry>),
+}
+
+impl Entries {
+    async fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        match self {
+            Entries::Listing(entries) => entries.next().await,
+            Entries::Sorted(entries) => entries.next().map(Ok),
+        }
+    }
+}
+
+impl WalkDir {
+    /// Only yield entries at least `depth` levels below the root. The root
+    /// has depth 0.
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Do not descend more than `depth` levels below the root, a depth of 0
+    /// only yields the root.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Follow symbolic links, so that links to directories are descended
+    /// into and entries report the type of their target.
+    ///
+    /// Links pointing to one of their ancestors are reported as errors
+    /// instead of being walked forever. The root is always followed.
+    ///
+    /// This option defaults to `false`.
+    pub fn follow_links(mut self, follow: bool) -> Self {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Yield the entries of each directory sorted by file name.
+    pub fn sort_by_file_name(mut self) -> Self {
+        self.sort = true;
+        self
+    }
+
+    async fn next_entry(&mut self) -> Option<io::Result<WalkEntry>> {
+        loop {
+            if let Some((path, depth)) = self.pending.take() {
+                if let Err(e) = self.push(path, depth).await {
+                    return Some(Err(e));
+                }
+            }
+
+            let entry = match self.root.take() {
+                Some(root) => self.root_entry(root).await,
+                None => {
+                    let level = self.stack.last_mut()?;
+                    let dir_entry = match level.entries.next().await {
+                        Some(Ok(dir_entry)) => dir_entry,
+                        Some(Err(e)) => return Some(Err(e)),
+                        None => {
+                            self.stack.pop();
+                            continue;
+                        }
+                    };
+                    let path = level.path.join(dir_entry.file_name());
+                    let depth = level.depth + 1;
+                    self.child_entry(path, depth, dir_entry.file_type()).await
+                }
+            };
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if entry.file_type.is_dir() && entry.depth < self.max_depth {
+                self.pending = Some((entry.path.clone(), entry.depth));
+            }
+            if entry.depth >= self.min_depth {
+                return Some(Ok(entry));
+            }
+        }
+    }
+
+    async fn root_entry(&self, path: PathBuf) -> io::Result<WalkEntry> {
+        let mut file_type = symlink_metadata(&path).await?.file_type();
+        let is_symlink = file_type.is_symlink();
+        if is_symlink {
+            file_type = metadata(&path).await?.file_type();
+        }
+        Ok(WalkEntry {
+            path,
+            depth: 0,
+            file_type,
+            is_symlink,
+            follow_links: self.follow_links || is_symlink,
+        })
+    }
+
+    async fn child_entry(
+        &self,
+        path: PathBuf,
+        depth: usize,
+        mut file_type: FileType,
+    ) -> io::Result<WalkEntry> {
+        let is_symlink = file_type.is_symlink();
+        if is_symlink && self.follow_links {
+            file_type = metadata(&path).await?.file_type();
+        }
+        Ok(WalkEntry {
+            path,
+            depth,
+            file_type,
+            is_symlink,
+            follow_links: self.follow_links,
+        })
+    }
+
+    async fn push(&mut self, path: PathBuf, depth: usize) -> io::Result<()> {
+        let id = if self.follow_links {
+            let metadata = metadata(&path).await?;
+            let id = (metadata.dev(), metadata.ino());
+            if self.stack.iter().any(|level| level.id == Some(id)) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("file system loop found at {}", path.display()),
+                ));
+            }
+            Some(id)
+        } else {
+            None
+        };
+
+        let mut listing = read_dir(&path).await?;
+        let entries = if self.sort {
+            let mut entries = Vec::new();
+            while let Some(entry) = listing.next().await {
+                entries.push(entry?);
+            }
+            entries.sort_unstable_by(|a, b| a.file_name().cmp(b.file_name()));
+            Entries::Sorted(entries.into_iter())
+        } else {
+            Entries::Listing(listing)
+        };
+
+        self.stack.push(Level {
+            path,
+            depth,
+            id,
+            entries,
+        });
+        Ok(())
+    }
+}
+
+impl Stream for WalkDir {
+    type Item = io::Result<WalkEntry>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().await
+    }
+}
+
+/// An entry yielded by [`WalkDir`].
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    pub(super) path: PathBuf,
+    depth: usize,
+    file_type: FileType,
+    is_symlink: bool,
+    follow_links: bool,
+}
+
+impl WalkEntry {
+    /// Returns the path of the entry, the root joined with the names of the
+    /// directories leading to it.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the entry, returning its path.
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the file name of the entry.
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the number of levels below the root, which has depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file type of the entry, or of its target if it is a
+    /// followed symbolic link.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns `true` if the entry itself is a symbolic link.
+    pub fn path_is_symlink(&self) -> bool {
+        self.is_symlink
+    }
+
+    /// Queries the metadata of the entry, following symbolic links if they
+    /// are followed by the walk.
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        if self.follow_links {
+            metadata(&self.path).await
+        } else {
+            symlink_metadata(&self.path).await
+        }
+    }
+}
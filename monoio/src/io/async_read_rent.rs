@@ -26,6 +26,13 @@ pub trait AsyncReadRent {
     ///    it is not certain that no more bytes will ever be produced.
     /// 2. The provided buffer was 0 bytes in length.
     ///
+    /// Note that `buf` is filled up to its capacity, not its length: reading into `Vec::new()`
+    /// reads nothing and returns `Ok(0)`. The streams and files of this crate complete such reads
+    /// at once without any IO. Enable the `debug` feature to log them, or the `lint-zero-len-io`
+    /// feature to panic on them, and see
+    /// [`read_append`](super::AsyncReadRentExt::read_append) to read into the spare capacity of a
+    /// `Vec`.
+    ///
     /// # Errors
     ///
     /// If an I/O or other error occurs, an error variant will be returned, ensuring that no bytes
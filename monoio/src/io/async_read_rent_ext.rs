@@ -39,6 +39,9 @@ macro_rules! reader_le_impl {
     };
 }
 
+// Bytes `read_append` reserves when the buffer has no spare capacity.
+const APPEND_RESERVE: usize = 4096;
+
 /// AsyncReadRentExt
 pub trait AsyncReadRentExt {
     /// Read until buf capacity is fulfilled
@@ -53,6 +56,15 @@ pub trait AsyncReadRentExt {
         buf: T,
     ) -> impl Future<Output = BufResult<usize, T>>;
 
+    /// Read once into the spare capacity of `buf`, keeping its bytes, and
+    /// extend its length by the number of bytes read.
+    ///
+    /// [`read`](AsyncReadRent::read) fills a `Vec` from its start and only up
+    /// to its capacity, so reading into `Vec::new()` reads nothing and looks
+    /// like EOF. This reserves 4 KiB first if `buf` is full, so `Ok(0)`
+    /// always means EOF.
+    fn read_append(&mut self, buf: Vec<u8>) -> impl Future<Output = BufResult<usize, Vec<u8>>>;
+
     reader_trait!(ReadU8Future, u8, read_u8);
     reader_trait!(ReadU16Future, u16, read_u16);
     reader_trait!(ReadU32Future, u32, read_u32);
@@ -112,6 +124,17 @@ where
         (Ok(read), buf)
     }
 
+    async fn read_append(&mut self, mut buf: Vec<u8>) -> BufResult<usize, Vec<u8>> {
+        let len = buf.len();
+        if buf.capacity() == len {
+            buf.reserve(APPEND_RESERVE);
+        }
+        // Safety: the slice begins at the initialized length.
+        let slice = unsafe { buf.slice_mut_unchecked(len..) };
+        let (res, slice) = self.read(slice).await;
+        (res, slice.into_inner())
+    }
+
     async fn read_vectored_exact<T: IoVecBufMut + 'static>(
         &mut self,
         mut buf: T,
@@ -2,7 +2,7 @@ use std::future::Future;
 
 use super::{CancelHandle, CancelableAsyncReadRent, CancelableAsyncWriteRent};
 use crate::{
-    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, Slice, SliceMut},
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, SliceMut},
     BufResult,
 };
 
@@ -40,6 +40,10 @@ macro_rules! reader_le_impl {
 }
 
 /// CancelableAsyncReadRentExt
+///
+/// The handle is passed to each read the methods make, so a handle made
+/// [`with_timeout`](crate::io::CancelHandle::with_timeout) times each of
+/// them out.
 pub trait CancelableAsyncReadRentExt {
     /// Read until buf capacity is fulfilled
     fn cancelable_read_exact<T: IoBufMut + 'static>(
@@ -176,6 +180,10 @@ where
 }
 
 /// CancelableAsyncWriteRentExt
+///
+/// The handle is passed to each write the methods make, so a handle made
+/// [`with_timeout`](crate::io::CancelHandle::with_timeout) times each of
+/// them out.
 pub trait CancelableAsyncWriteRentExt {
     /// Write all
     fn write_all<T: IoBuf + 'static>(
@@ -198,15 +206,13 @@ where
 {
     async fn write_all<T: IoBuf + 'static>(
         &mut self,
-        mut buf: T,
+        buf: T,
         c: CancelHandle,
     ) -> BufResult<usize, T> {
-        let len = buf.bytes_init();
-        let mut written = 0;
-        while written < len {
-            let buf_slice = unsafe { Slice::new_unchecked(buf, written, len) };
-            let (result, buf_slice) = self.cancelable_write(buf_slice, c.clone()).await;
-            buf = buf_slice.into_inner();
+        let mut slice = buf.slice(..);
+        while slice.remaining() > 0 {
+            let (result, slice_) = self.cancelable_write(slice, c.clone()).await;
+            slice = slice_;
             match result {
                 Ok(0) => {
                     return (
@@ -214,15 +220,15 @@ where
                             std::io::ErrorKind::WriteZero,
                             "failed to write whole buffer",
                         )),
-                        buf,
+                        slice.into_inner(),
                     )
                 }
-                Ok(n) => written += n,
+                Ok(n) => slice.advance(n),
                 Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(e) => return (Err(e), slice.into_inner()),
             }
         }
-        (Ok(written), buf)
+        (Ok(slice.begin()), slice.into_inner())
     }
 
     async fn write_vectored_all<T: IoVecBuf + 'static>(
@@ -0,0 +1,40 @@
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+};
+
+/// The `AsyncSeek` trait moves the cursor of a stream of bytes, which the
+/// following [`AsyncReadRent`](super::AsyncReadRent) and
+/// [`AsyncWriteRent`](super::AsyncWriteRent) operations start from.
+///
+/// It mirrors [`std::io::Seek`], so code written against it ports over
+/// without tracking the offsets by hand.
+pub trait AsyncSeek {
+    /// Seeks to an offset, in bytes, in the stream, returning the new
+    /// position from the start of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Seeking to a negative offset is an error.
+    fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = io::Result<u64>>;
+
+    /// Rewinds to the start of the stream.
+    fn rewind(&mut self) -> impl Future<Output = io::Result<()>> {
+        async {
+            self.seek(SeekFrom::Start(0)).await?;
+            Ok(())
+        }
+    }
+
+    /// Returns the current position from the start of the stream.
+    fn stream_position(&mut self) -> impl Future<Output = io::Result<u64>> {
+        self.seek(SeekFrom::Current(0))
+    }
+}
+
+impl<A: ?Sized + AsyncSeek> AsyncSeek for &mut A {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = io::Result<u64>> {
+        (**self).seek(pos)
+    }
+}
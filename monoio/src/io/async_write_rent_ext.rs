@@ -1,7 +1,7 @@
 use std::future::Future;
 
 use crate::{
-    buf::{IoBuf, IoVecBuf, Slice},
+    buf::{IoBuf, IoVecBuf},
     io::AsyncWriteRent,
     BufResult,
 };
@@ -25,13 +25,11 @@ impl<A> AsyncWriteRentExt for A
 where
     A: AsyncWriteRent + ?Sized,
 {
-    async fn write_all<T: IoBuf + 'static>(&mut self, mut buf: T) -> BufResult<usize, T> {
-        let len = buf.bytes_init();
-        let mut written = 0;
-        while written < len {
-            let buf_slice = unsafe { Slice::new_unchecked(buf, written, len) };
-            let (result, buf_slice) = self.write(buf_slice).await;
-            buf = buf_slice.into_inner();
+    async fn write_all<T: IoBuf + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
+        let mut slice = buf.slice(..);
+        while slice.remaining() > 0 {
+            let (result, slice_) = self.write(slice).await;
+            slice = slice_;
             match result {
                 Ok(0) => {
                     return (
@@ -39,15 +37,15 @@ where
                             std::io::ErrorKind::WriteZero,
                             "failed to write whole buffer",
                         )),
-                        buf,
+                        slice.into_inner(),
                     )
                 }
-                Ok(n) => written += n,
+                Ok(n) => slice.advance(n),
                 Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(e) => return (Err(e), slice.into_inner()),
             }
         }
-        (Ok(written), buf)
+        (Ok(slice.begin()), slice.into_inner())
     }
 
     async fn write_vectored_all<T: IoVecBuf + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
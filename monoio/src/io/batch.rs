@@ -0,0 +1,171 @@
+use std::{future::Future, pin::Pin, task::Poll};
+
+use crate::macros::support::{maybe_done, poll_fn, MaybeDone};
+
+/// A group of IO futures whose operations are submitted to the kernel at
+/// once.
+///
+/// Operations are usually pushed to the submission queue as their futures are
+/// first polled, and submitted when the driver parks, or earlier as set by
+/// [`with_submit_threshold`](crate::RuntimeBuilder::with_submit_threshold). A
+/// batch polls all its futures first, then submits the operations they pushed
+/// with a single `io_uring_enter`, and runs them to completion concurrently.
+///
+/// The operations of a [`linked`](Batch::linked) batch are chained with
+/// `IOSQE_IO_LINK`: the kernel starts each one once the previous one has
+/// completed, and cancels the rest of the chain if one fails, which completes
+/// them with `ECANCELED`. Only the first operation submitted by each future
+/// is part of the chain in a meaningful order, so futures should submit a
+/// single operation, like [`read_at`](crate::fs::File::read_at) or
+/// [`write_at`](crate::fs::File::write_at).
+///
+/// A batch larger than the submission queue is split, unless it is linked:
+/// the submission queue must then have room for all its operations and the
+/// one ending the chain, and the operations which do not fit fail, their
+/// futures returning an error, or panicking for those which can not report
+/// it. Any batch may also be split on a ring with
+/// [SQPOLL](crate::RuntimeBuilder::with_sqpoll), whose kernel thread can pick
+/// operations up before the batch is complete.
+///
+/// The legacy driver has no submission queue: the futures just run
+/// concurrently, and are not linked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{fs::File, io::Batch};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let file = File::open("data.bin").await?;
+///     let reads: Batch<_> = (0..16)
+///         .map(|i| file.read_at(vec![0; 4096], i * 4096))
+///         .collect();
+///     for (res, _buf) in reads.submit().await {
+///         println!("read {} bytes", res?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[must_use = "a batch does nothing unless submitted"]
+pub struct Batch<F> {
+    futures: Vec<F>,
+    linked: bool,
+}
+
+impl<F: Future> Batch<F> {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty batch with room for `capacity` futures.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            futures: Vec::with_capacity(capacity),
+            linked: false,
+        }
+    }
+
+    /// Links the operations of the batch in the order of its futures, see
+    /// [`Batch`].
+    pub fn linked(mut self) -> Self {
+        self.linked = true;
+        self
+    }
+
+    /// Adds a future to the batch.
+    pub fn push(&mut self, future: F) -> &mut Self {
+        self.futures.push(future);
+        self
+    }
+
+    /// Returns the number of futures in the batch.
+    pub fn len(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Returns true if the batch has no future.
+    pub fn is_empty(&self) -> bool {
+        self.futures.is_empty()
+    }
+
+    /// Submits the operations of the batch and waits for all its futures,
+    /// returning their outputs in order.
+    pub async fn submit(self) -> Vec<F::Output> {
+        let (linked, len) = (self.linked, self.futures.len());
+        let futures: Box<[MaybeDone<F>]> = self.futures.into_iter().map(maybe_done).collect();
+        let mut futures = Pin::from(futures);
+        let mut submitted = false;
+        poll_fn(move |cx| {
+            let mut poll_all = || {
+                let mut done = true;
+                for i in 0..futures.len() {
+                    // Safety: the futures are pinned in the boxed slice, which
+                    // is never moved or resized.
+                    let future = unsafe { futures.as_mut().map_unchecked_mut(|f| &mut f[i]) };
+                    done &= future.poll(cx).is_ready();
+                }
+                done
+            };
+            let done = if submitted {
+                poll_all()
+            } else {
+                submitted = true;
+                crate::driver::op::with_batch(linked, len, poll_all)
+            };
+            if !done {
+                return Poll::Pending;
+            }
+            Poll::Ready(
+                (0..futures.len())
+                    .map(|i| {
+                        let future = unsafe { futures.as_mut().map_unchecked_mut(|f| &mut f[i]) };
+                        future.take_output().expect("batch polled after completion")
+                    })
+                    .collect(),
+            )
+        })
+        .await
+    }
+}
+
+impl<F: Future> Default for Batch<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Future> FromIterator<F> for Batch<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        Self {
+            futures: iter.into_iter().collect(),
+            linked: false,
+        }
+    }
+}
+
+impl<F: Future> Extend<F> for Batch<F> {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, iter: I) {
+        self.futures.extend(iter);
+    }
+}
+
+impl<F> std::fmt::Debug for Batch<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch")
+            .field("len", &self.futures.len())
+            .field("linked", &self.linked)
+            .finish()
+    }
+}
+
+/// Submits the operations of `futures` at once and waits for them, returning
+/// their outputs in order. See [`Batch`].
+pub async fn submit_batch<I>(futures: I) -> Vec<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    futures.into_iter().collect::<Batch<_>>().submit().await
+}
@@ -0,0 +1,39 @@
+use std::{io, os::unix::io::AsRawFd};
+
+/// Sets or clears the close-on-exec flag of `fd`.
+///
+/// All the fds monoio creates, such as sockets, accepted connections, pipes
+/// and files, are close-on-exec, so they do not leak into the processes
+/// spawned by the program. Clearing the flag lets a child process inherit
+/// one of them, e.g. a listener handed over on a graceful restart.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::os::unix::io::AsRawFd;
+///
+/// use monoio::{io::set_cloexec, net::TcpListener};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:8080")?;
+///     set_cloexec(&listener, false)?;
+///     std::process::Command::new("./next-version")
+///         .env("LISTEN_FD", listener.as_raw_fd().to_string())
+///         .spawn()?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_cloexec<F: AsRawFd>(fd: &F, cloexec: bool) -> io::Result<()> {
+    let fd = fd.as_raw_fd();
+    let flags = crate::syscall!(fcntl@RAW(fd, libc::F_GETFD))?;
+    let new_flags = if cloexec {
+        flags | libc::FD_CLOEXEC
+    } else {
+        flags & !libc::FD_CLOEXEC
+    };
+    if new_flags != flags {
+        crate::syscall!(fcntl@RAW(fd, libc::F_SETFD, new_flags))?;
+    }
+    Ok(())
+}
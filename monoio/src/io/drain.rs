@@ -0,0 +1,50 @@
+use std::future::Future;
+
+use crate::macros::support::poll_fn;
+
+/// Runs `future`, submitting the operations it submits when first polled as
+/// drain barriers, with `IOSQE_IO_DRAIN`: the kernel starts them once all the
+/// operations submitted before them have completed, and starts the ones
+/// submitted after them once they have completed.
+///
+/// This orders e.g. the commit record of a write-ahead log after the data
+/// writes it commits, without waiting for them in user space. The barrier
+/// covers the whole ring, including the pending operations of other tasks
+/// like reads from idle connections, so it is best used on a
+/// [file IO ring](crate::RuntimeBuilder::with_fs_ring), where file IO is
+/// submitted. It also makes the kernel serialize the ring around it, so it
+/// is costly.
+///
+/// Only the io_uring driver has barriers, the future just runs with the
+/// legacy one, which runs file IO in the order it is submitted unless it is
+/// offloaded to a thread pool.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{fs::File, io::with_drain};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let wal = File::create("wal.log").await?;
+///     let (data, commit) = monoio::join!(
+///         wal.write_at(b"data".to_vec(), 0),
+///         with_drain(wal.write_at(b"commit".to_vec(), 4)),
+///     );
+///     data.0?;
+///     commit.0?;
+///     wal.sync_data().await
+/// }
+/// ```
+pub async fn with_drain<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut submitted = false;
+    poll_fn(move |cx| {
+        if submitted {
+            return future.as_mut().poll(cx);
+        }
+        submitted = true;
+        crate::driver::op::with_drain(|| future.as_mut().poll(cx))
+    })
+    .await
+}
@@ -0,0 +1,67 @@
+//! Linux eventfd.
+
+use std::{
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+};
+
+use crate::driver::{op::Op, shared_fd::SharedFd};
+
+/// An eventfd counter registered with the driver.
+///
+/// Writes add to the counter, reads return the counter and reset it to zero
+/// (or decrement it by one in semaphore mode), waiting for it to be nonzero.
+/// It can be shared with other threads, processes or the kernel (e.g.
+/// vhost/VDUSE, KVM irqfd) through its fd.
+#[derive(Debug)]
+pub struct EventFd {
+    fd: SharedFd,
+}
+
+impl EventFd {
+    /// Create an eventfd with the given initial value.
+    pub fn new(initval: u32) -> io::Result<Self> {
+        Self::with_flags(initval, 0)
+    }
+
+    /// Create an eventfd in semaphore mode(`EFD_SEMAPHORE`): every read
+    /// returns 1 and decrements the counter by one.
+    pub fn semaphore(initval: u32) -> io::Result<Self> {
+        Self::with_flags(initval, libc::EFD_SEMAPHORE)
+    }
+
+    fn with_flags(initval: u32, flags: libc::c_int) -> io::Result<Self> {
+        let flags = if crate::driver::op::is_legacy() {
+            flags | libc::EFD_CLOEXEC | libc::EFD_NONBLOCK
+        } else {
+            flags | libc::EFD_CLOEXEC
+        };
+        let fd = crate::syscall!(eventfd@RAW(initval, flags))?;
+        Ok(Self {
+            fd: SharedFd::new::<false>(fd)?,
+        })
+    }
+
+    /// Wait for the counter to be nonzero and read it.
+    pub async fn read(&self) -> io::Result<u64> {
+        let op = Op::read(self.fd.clone(), Box::new([0_u8; 8]))?;
+        let (res, buf) = op.result().await;
+        res?;
+        Ok(u64::from_ne_bytes(*buf))
+    }
+
+    /// Add `value` to the counter.
+    ///
+    /// Waits if the counter would overflow, until it is read.
+    pub async fn write(&self, value: u64) -> io::Result<()> {
+        let op = Op::write(self.fd.clone(), Box::new(value.to_ne_bytes()))?;
+        op.result().await.0.map(|_| ())
+    }
+}
+
+impl AsRawFd for EventFd {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
@@ -0,0 +1,115 @@
+//! Drive foreign event loops from monoio.
+
+use std::{
+    cell::{Cell, RefCell},
+    future::{poll_fn, Future},
+    io,
+    os::unix::io::RawFd,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use crate::driver::{op::Op, shared_fd::SharedFd};
+
+/// Handle of a fd registered with [`register_external_fd`].
+///
+/// The fd is polled until the handle is dropped. If dropped out of the
+/// runtime, polling stops the next time the fd becomes readable, or when the
+/// runtime is dropped.
+#[must_use = "the fd is deregistered when the handle is dropped"]
+#[derive(Debug)]
+pub struct ExternalFd {
+    fd: RawFd,
+    state: Rc<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    stopped: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl ExternalFd {
+    /// The registered fd.
+    #[inline]
+    pub fn fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for ExternalFd {
+    fn drop(&mut self) {
+        self.state.stopped.set(true);
+        // Out of the runtime the task can not be woken, it will stop on its
+        // next poll instead.
+        if crate::runtime::CURRENT.is_set() {
+            if let Some(waker) = self.state.waker.borrow_mut().take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Register a fd owned by a foreign event loop, calling `callback` every time
+/// it becomes readable.
+///
+/// This allows driving libraries which own their own epoll fd or expose a fd
+/// to wait on (e.g. librdkafka, c-ares) from the monoio runtime without
+/// background threads: the callback is expected to process the pending events
+/// of the library, without blocking.
+///
+/// The fd is polled with the current driver (a `PollAdd` op on io_uring
+/// driver, the poller on legacy driver). It is duplicated, so the original fd
+/// stays owned by the caller and is never closed by monoio; it must stay open
+/// until the returned handle is dropped.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime. See
+/// [`Runtime::register_external_fd`](crate::Runtime::register_external_fd)
+/// to register from outside.
+pub fn register_external_fd<F>(fd: RawFd, mut callback: F) -> io::Result<ExternalFd>
+where
+    F: FnMut() + 'static,
+{
+    let dup = crate::syscall!(fcntl@RAW(fd, libc::F_DUPFD_CLOEXEC, 0))?;
+    let shared = match SharedFd::new::<false>(dup) {
+        Ok(shared) => shared,
+        Err(e) => {
+            unsafe { libc::close(dup) };
+            return Err(e);
+        }
+    };
+
+    let state = Rc::new(State::default());
+    let task_state = state.clone();
+    crate::spawn(async move {
+        while !task_state.stopped.get() {
+            let op = match Op::poll_read(&shared, false) {
+                Ok(op) => op,
+                Err(_) => break,
+            };
+            let mut wait = std::pin::pin!(op.wait());
+            let readable = poll_fn(|cx| {
+                if task_state.stopped.get() {
+                    return Poll::Ready(false);
+                }
+                match wait.as_mut().poll(cx) {
+                    Poll::Ready(res) => Poll::Ready(res.is_ok()),
+                    Poll::Pending => {
+                        *task_state.waker.borrow_mut() = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+            .await;
+            if !readable {
+                break;
+            }
+            callback();
+        }
+        let _ = shared.close().await;
+    });
+
+    Ok(ExternalFd { fd, state })
+}
@@ -6,6 +6,7 @@ mod async_read_rent;
 mod async_read_rent_ext;
 mod async_rent_cancelable;
 mod async_rent_cancelable_ext;
+mod async_seek;
 mod async_write_rent;
 mod async_write_rent_ext;
 
@@ -13,6 +14,15 @@ pub mod sink;
 pub mod stream;
 
 pub mod as_fd;
+mod batch;
+#[cfg(unix)]
+mod cloexec;
+mod drain;
+#[cfg(target_os = "linux")]
+mod eventfd;
+#[cfg(unix)]
+mod external_fd;
+mod prepared;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub mod splice;
 
@@ -22,20 +32,31 @@ pub use async_read_rent::{AsyncReadRent, AsyncReadRentAt};
 pub use async_read_rent_ext::AsyncReadRentExt;
 pub use async_rent_cancelable::{CancelableAsyncReadRent, CancelableAsyncWriteRent};
 pub use async_rent_cancelable_ext::{CancelableAsyncReadRentExt, CancelableAsyncWriteRentExt};
+pub use async_seek::AsyncSeek;
 pub use async_write_rent::{AsyncWriteRent, AsyncWriteRentAt};
 pub use async_write_rent_ext::AsyncWriteRentExt;
+pub use batch::{submit_batch, Batch};
+pub use drain::with_drain;
 
 mod util;
 
+#[cfg(unix)]
+pub use cloexec::set_cloexec;
+#[cfg(target_os = "linux")]
+pub use eventfd::EventFd;
+#[cfg(unix)]
+pub use external_fd::{register_external_fd, ExternalFd};
+pub use prepared::PreparedOp;
 #[cfg(feature = "poll-io")]
 pub use tokio::io as poll_io;
-pub(crate) use util::operation_canceled;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub use util::zero_copy;
 pub use util::{
-    copy, BufReader, BufWriter, CancelHandle, Canceller, OwnedReadHalf, OwnedWriteHalf,
-    PrefixedReadIo, Split, Splitable,
+    cancelable_copy, cancelable_copy_bidirectional, copy, BufReader, BufWriter, CancelHandle,
+    CancellationRegion, Canceller, Entered, OwnedReadHalf, OwnedWriteHalf, PrefixedReadIo, Split,
+    Splitable,
 };
+pub(crate) use util::{operation_canceled, timeout_op};
 #[cfg(feature = "poll-io")]
 /// Convert a completion-based io to a poll-based io.
 pub trait IntoPollIo: Sized {
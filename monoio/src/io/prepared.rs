@@ -0,0 +1,82 @@
+use std::io;
+
+use crate::{
+    buf::IoBufMut,
+    driver::{
+        op::{Op, PreparedRecv},
+        shared_fd::SharedFd,
+    },
+};
+
+/// A recv operation prepared once and submitted repeatedly.
+///
+/// The fd, buffer and submission entry are captured when the op is prepared
+/// (e.g. by [`TcpStream::prepare_recv`](crate::net::TcpStream::prepare_recv)),
+/// so resubmitting does not rebuild the op. The buffer is reused by every
+/// submission and holds the data received by the last one.
+///
+/// If a [`resubmit`](PreparedOp::resubmit) future is dropped before it
+/// completes, the buffer stays with the in-flight op until the kernel is done
+/// with it, and further submissions fail.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{io::AsyncWriteRentExt, net::TcpStream};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut recv = stream.prepare_recv(vec![0; 4096]);
+///     loop {
+///         if recv.resubmit().await? == 0 {
+///             return Ok(());
+///         }
+///         let data = recv.get_ref().unwrap().clone();
+///         stream.write_all(data).await.0?;
+///     }
+/// }
+/// ```
+pub struct PreparedOp<T> {
+    data: Option<PreparedRecv<T>>,
+}
+
+impl<T: IoBufMut> PreparedOp<T> {
+    pub(crate) fn recv(fd: SharedFd, buf: T) -> Self {
+        Self {
+            data: Some(PreparedRecv::new(fd, buf)),
+        }
+    }
+
+    /// Submits the op again and waits for it, returning the number of bytes
+    /// received into the buffer.
+    pub async fn resubmit(&mut self) -> io::Result<usize> {
+        let data = self.data.take().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "prepared op was dropped while in flight",
+            )
+        })?;
+        let (res, data) = Op::prepared_recv(data)?.result().await;
+        self.data = Some(data);
+        res
+    }
+
+    /// Returns the buffer, or `None` if it was lost to a dropped submission.
+    pub fn get_ref(&self) -> Option<&T> {
+        self.data.as_ref().map(|data| &data.buf)
+    }
+
+    /// Consumes the op, returning the buffer.
+    pub fn into_inner(self) -> Option<T> {
+        self.data.map(|data| data.buf)
+    }
+}
+
+impl<T> std::fmt::Debug for PreparedOp<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreparedOp")
+            .field("in_flight", &self.data.is_none())
+            .finish_non_exhaustive()
+    }
+}
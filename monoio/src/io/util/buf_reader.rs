@@ -1,8 +1,8 @@
-use std::future::Future;
+use std::{future::Future, io::SeekFrom};
 
 use crate::{
-    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapperMut},
-    io::{AsyncBufRead, AsyncReadRent, AsyncWriteRent},
+    buf::{AdaptiveReadSize, IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapperMut},
+    io::{AsyncBufRead, AsyncReadRent, AsyncSeek, AsyncWriteRent},
     BufResult,
 };
 
@@ -11,9 +11,10 @@ use crate::{
 /// will delegate the implementation.
 pub struct BufReader<R> {
     inner: R,
-    buf: Option<Box<[u8]>>,
+    buf: Option<Vec<u8>>,
     pos: usize,
     cap: usize,
+    sizer: Option<AdaptiveReadSize>,
 }
 
 const DEFAULT_BUF_SIZE: usize = 8 * 1024;
@@ -28,15 +29,35 @@ impl<R> BufReader<R> {
     /// Create BufReader with given buffer size
     #[inline]
     pub fn with_capacity(capacity: usize, inner: R) -> Self {
-        let buffer = vec![0; capacity];
+        // The buffer is filled by the inner reader, no need to zero it.
         Self {
             inner,
-            buf: Some(buffer.into_boxed_slice()),
+            buf: Some(Vec::with_capacity(capacity)),
             pos: 0,
             cap: 0,
+            sizer: None,
         }
     }
 
+    /// Create BufReader whose buffer is resized before each read of the
+    /// inner reader, to the capacity suggested by `sizer` from the sizes of
+    /// the previous reads.
+    ///
+    /// The buffer shrinks when the connection goes quiet and grows back
+    /// when it gets busy, see [`AdaptiveReadSize`].
+    #[inline]
+    pub fn with_adaptive_capacity(sizer: AdaptiveReadSize, inner: R) -> Self {
+        let mut reader = Self::with_capacity(sizer.next_capacity(), inner);
+        reader.sizer = Some(sizer);
+        reader
+    }
+
+    /// Returns the capacity of the internal buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().expect("unable to take buffer").capacity()
+    }
+
     /// Gets a reference to the underlying reader.
     ///
     /// It is inadvisable to directly read from the underlying reader.
@@ -53,12 +74,48 @@ impl<R> BufReader<R> {
 
     /// Consumes this `BufReader`, returning the underlying reader.
     ///
-    /// Note that any leftover data in the internal buffer is lost.
+    /// Note that any leftover data in the internal buffer is lost, see
+    /// [`into_parts`](BufReader::into_parts) to keep it.
     #[inline]
     pub fn into_inner(self) -> R {
         self.inner
     }
 
+    /// Consumes this `BufReader`, returning the underlying reader and the
+    /// data buffered but not read yet.
+    ///
+    /// When upgrading a connection to another protocol, the data already read
+    /// from the socket is the beginning of the new protocol's stream, which
+    /// [`PrefixedReadIo`](crate::io::PrefixedReadIo) may put back in front of
+    /// the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monoio::io::{AsyncBufReadExt, AsyncReadRentExt, BufReader, PrefixedReadIo};
+    ///
+    /// #[monoio::main]
+    /// async fn main() {
+    ///     let mut reader = BufReader::new(&b"UPGRADE\nraw bytes"[..]);
+    ///     let mut line = String::new();
+    ///     reader.read_line(&mut line).await.unwrap();
+    ///     assert_eq!(line, "UPGRADE\n");
+    ///
+    ///     let (inner, buffered) = reader.into_parts();
+    ///     assert_eq!(buffered, b"raw bytes");
+    ///     let mut raw = PrefixedReadIo::new(inner, std::io::Cursor::new(buffered));
+    ///     let (res, buf) = raw.read_exact(vec![0; 9]).await;
+    ///     res.unwrap();
+    ///     assert_eq!(buf, b"raw bytes");
+    /// }
+    /// ```
+    pub fn into_parts(mut self) -> (R, Vec<u8>) {
+        let mut buf = self.buf.take().expect("unable to take buffer");
+        buf.truncate(self.cap);
+        buf.drain(..self.pos);
+        (self.inner, buf)
+    }
+
     /// Returns a reference to the internally buffered data.
     ///
     /// Unlike `fill_buf`, this will not attempt to fill the buffer if it is
@@ -82,7 +139,7 @@ impl<R: AsyncReadRent> AsyncReadRent for BufReader<R> {
         // (larger than our internal buffer), bypass our internal buffer
         // entirely.
         let owned_buf = self.buf.as_ref().unwrap();
-        if self.pos == self.cap && buf.bytes_total() >= owned_buf.len() {
+        if self.pos == self.cap && buf.bytes_total() >= owned_buf.capacity() {
             self.discard_buffer();
             return self.inner.read(buf).await;
         }
@@ -121,19 +178,28 @@ impl<R: AsyncReadRent> AsyncBufRead for BufReader<R> {
     async fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
         if self.pos == self.cap {
             // there's no buffered data
-            let buf = self
+            let mut buf = self
                 .buf
                 .take()
                 .expect("no buffer available, generated future must be awaited");
+            buf.clear();
+            if let Some(sizer) = &self.sizer {
+                if sizer.next_capacity() != buf.capacity() {
+                    buf = Vec::with_capacity(sizer.next_capacity());
+                }
+            }
             let (res, buf_) = self.inner.read(buf).await;
             self.buf = Some(buf_);
             match res {
                 Ok(n) => {
+                    if let Some(sizer) = &mut self.sizer {
+                        sizer.record(n);
+                    }
                     self.pos = 0;
                     self.cap = n;
                     return Ok(unsafe {
                         // We just put the buf into Option, so it must be Some.
-                        &(self.buf.as_ref().unwrap_unchecked().as_ref())[self.pos..self.cap]
+                        &self.buf.as_ref().unwrap_unchecked()[self.pos..self.cap]
                     });
                 }
                 Err(e) => {
@@ -141,11 +207,10 @@ impl<R: AsyncReadRent> AsyncBufRead for BufReader<R> {
                 }
             }
         }
-        Ok(&(self
+        Ok(&self
             .buf
             .as_ref()
-            .expect("no buffer available, generated future must be awaited")
-            .as_ref())[self.pos..self.cap])
+            .expect("no buffer available, generated future must be awaited")[self.pos..self.cap])
     }
 
     fn consume(&mut self, amt: usize) {
@@ -153,6 +218,30 @@ impl<R: AsyncReadRent> AsyncBufRead for BufReader<R> {
     }
 }
 
+impl<R: AsyncSeek> AsyncSeek for BufReader<R> {
+    /// Seeks the inner reader and discards the buffered data.
+    ///
+    /// [`SeekFrom::Current`] is relative to the position of the data
+    /// returned next, i.e. it accounts for the data still buffered.
+    async fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let result = if let SeekFrom::Current(n) = pos {
+            let remainder = (self.cap - self.pos) as i64;
+            if let Some(offset) = n.checked_sub(remainder) {
+                self.inner.seek(SeekFrom::Current(offset)).await?
+            } else {
+                // Seek twice if the offset would overflow.
+                self.inner.seek(SeekFrom::Current(-remainder)).await?;
+                self.discard_buffer();
+                self.inner.seek(SeekFrom::Current(n)).await?
+            }
+        } else {
+            self.inner.seek(pos).await?
+        };
+        self.discard_buffer();
+        Ok(result)
+    }
+}
+
 impl<R: AsyncReadRent + AsyncWriteRent> AsyncWriteRent for BufReader<R> {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
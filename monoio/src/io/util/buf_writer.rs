@@ -2,18 +2,26 @@ use std::{future::Future, io};
 
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapper, Slice},
-    io::{AsyncBufRead, AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt},
+    io::{AsyncBufRead, AsyncReadRent, AsyncWriteRent},
     BufResult,
 };
 
 /// BufWriter is a struct with a buffer. BufWriter implements AsyncWriteRent,
 /// and if the inner io implements AsyncReadRent, it will delegate the
 /// implementation.
+///
+/// Buffered data is written to the inner io once it would exceed the
+/// watermark (the buffer size by default, see [`BufWriter::with_watermark`]),
+/// or on [`flush`](AsyncWriteRent::flush). With
+/// [`flush_on_park`](BufWriter::flush_on_park), the flush is deferred to the
+/// end of the current event loop iteration.
 pub struct BufWriter<W> {
     inner: W,
-    buf: Option<Box<[u8]>>,
+    buf: Option<Vec<u8>>,
     pos: usize,
     cap: usize,
+    watermark: usize,
+    auto_flush: bool,
 }
 
 const DEFAULT_BUF_SIZE: usize = 8 * 1024;
@@ -28,15 +36,47 @@ impl<W> BufWriter<W> {
     /// Create BufWriter with given buffer size
     #[inline]
     pub fn with_capacity(capacity: usize, inner: W) -> Self {
-        let buffer = vec![0; capacity];
+        // Only the buffered data gets initialized, see `write`.
         Self {
             inner,
-            buf: Some(buffer.into_boxed_slice()),
+            buf: Some(Vec::with_capacity(capacity)),
             pos: 0,
             cap: 0,
+            watermark: capacity,
+            auto_flush: false,
         }
     }
 
+    /// Set the high watermark: buffered data is written to the inner io
+    /// before it grows beyond `watermark` bytes, and writes larger than it
+    /// bypass the buffer.
+    ///
+    /// The watermark is capped to the buffer size.
+    #[must_use]
+    #[inline]
+    pub fn with_watermark(mut self, watermark: usize) -> Self {
+        self.watermark = watermark.min(self.capacity());
+        self
+    }
+
+    /// Flush buffered data before the runtime parks when reading from the
+    /// inner io, see [`flush_on_park`](BufWriter::flush_on_park).
+    ///
+    /// This fits request-response protocols: responses written while handling
+    /// a batch of requests are sent together before waiting for more requests.
+    #[must_use]
+    #[inline]
+    pub fn with_auto_flush(mut self, auto_flush: bool) -> Self {
+        self.auto_flush = auto_flush;
+        self
+    }
+
+    /// Returns the buffer size.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().expect("unable to take buffer").capacity()
+    }
+
     /// Gets a reference to the underlying writer.
     #[inline]
     pub fn get_ref(&self) -> &W {
@@ -49,12 +89,17 @@ impl<W> BufWriter<W> {
         &mut self.inner
     }
 
-    /// Consumes this `BufWriter`, returning the underlying writer.
+    /// Consumes this `BufWriter` without flushing, returning the underlying
+    /// writer and the data still buffered.
     ///
-    /// Note that any leftover data in the internal buffer is lost.
-    #[inline]
-    pub fn into_inner(self) -> W {
-        self.inner
+    /// This fits upgrading a buffered connection to a raw one when the
+    /// buffered data must be handled separately, see also
+    /// [`into_inner`](BufWriter::into_inner).
+    pub fn into_parts(mut self) -> (W, Vec<u8>) {
+        let mut buf = self.buf.take().expect("unable to take buffer");
+        buf.truncate(self.cap);
+        buf.drain(..self.pos);
+        (self.inner, buf)
     }
 
     /// Returns a reference to the internally buffered data.
@@ -66,6 +111,9 @@ impl<W> BufWriter<W> {
     /// Invalidates all data in the internal buffer.
     #[inline]
     fn discard_buffer(&mut self) {
+        if let Some(buf) = self.buf.as_mut() {
+            buf.clear();
+        }
         self.pos = 0;
         self.cap = 0;
     }
@@ -73,31 +121,66 @@ impl<W> BufWriter<W> {
 
 impl<W: AsyncWriteRent> BufWriter<W> {
     async fn flush_buf(&mut self) -> io::Result<()> {
-        if self.pos != self.cap {
-            // there is some data left inside internal buf
+        // Data written is consumed as it goes, so that only the data left
+        // remains buffered on error.
+        while self.pos != self.cap {
             let buf = self
                 .buf
                 .take()
                 .expect("no buffer available, generated future must be awaited");
-            // move buf to slice and write_all
+            // move buf to slice and write
             let slice = Slice::new(buf, self.pos, self.cap);
-            let (ret, slice) = self.inner.write_all(slice).await;
+            let (ret, slice) = self.inner.write(slice).await;
             // move it back and return
             self.buf = Some(slice.into_inner());
-            ret?;
-            self.discard_buffer();
+            match ret {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ))
+                }
+                Ok(n) => self.pos += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
         }
+        self.discard_buffer();
         Ok(())
     }
+
+    /// Writes out the buffered data, then returns the underlying writer.
+    ///
+    /// The underlying writer itself is not flushed. On error the `BufWriter`
+    /// is returned along with the error, with the data which could not be
+    /// written still buffered, see [`buffer`](BufWriter::buffer) and
+    /// [`into_parts`](BufWriter::into_parts).
+    pub async fn into_inner(mut self) -> Result<W, (io::Error, Self)> {
+        match self.flush_buf().await {
+            Ok(()) => Ok(self.into_parts().0),
+            Err(e) => Err((e, self)),
+        }
+    }
+
+    /// Flush the buffered data right before the runtime parks, so that all
+    /// the writes issued during the current event loop iteration are sent
+    /// together.
+    ///
+    /// Returns immediately if there is no buffered data.
+    pub async fn flush_on_park(&mut self) -> io::Result<()> {
+        if self.pos == self.cap {
+            return Ok(());
+        }
+        crate::task::before_park().await;
+        self.flush().await
+    }
 }
 
 impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
     async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
-        let owned_buf = self.buf.as_ref().unwrap();
-        let owned_len = owned_buf.len();
         let amt = buf.bytes_init();
 
-        if self.pos + amt > owned_len {
+        if self.cap + amt > self.watermark {
             // Buf can not be copied directly into OwnedBuf,
             // we must flush OwnedBuf first.
             match self.flush_buf().await {
@@ -109,20 +192,25 @@ impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
         }
 
         // Now there are two situations here:
-        // 1. OwnedBuf has data, and self.pos + amt <= owned_len,
+        // 1. OwnedBuf has data, and self.cap + amt <= watermark,
         // which means the data can be copied into OwnedBuf.
         // 2. OwnedBuf is empty. If we can copy buf into OwnedBuf,
         // we will copy it, otherwise we will send it directly(in
         // this situation, the OwnedBuf must be already empty).
-        if amt > owned_len {
+        if amt > self.watermark {
             self.inner.write(buf).await
         } else {
+            let owned_buf = self.buf.as_mut().unwrap();
+            // Safety: `amt` fits the spare capacity since `self.cap + amt`
+            // is within the watermark, and the data is initialized right
+            // after being copied.
             unsafe {
-                let owned_buf = self.buf.as_mut().unwrap();
                 owned_buf
+                    .spare_capacity_mut()
                     .as_mut_ptr()
-                    .add(self.cap)
+                    .cast::<u8>()
                     .copy_from_nonoverlapping(buf.read_ptr(), amt);
+                owned_buf.assume_init(amt);
             }
             self.cap += amt;
             (Ok(amt), buf)
@@ -152,14 +240,22 @@ impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
 }
 
 impl<W: AsyncWriteRent + AsyncReadRent> AsyncReadRent for BufWriter<W> {
-    #[inline]
-    fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        self.inner.read(buf)
+    async fn read<T: IoBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        if self.auto_flush {
+            if let Err(e) = self.flush_on_park().await {
+                return (Err(e), buf);
+            }
+        }
+        self.inner.read(buf).await
     }
 
-    #[inline]
-    fn readv<T: IoVecBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        self.inner.readv(buf)
+    async fn readv<T: IoVecBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        if self.auto_flush {
+            if let Err(e) = self.flush_on_park().await {
+                return (Err(e), buf);
+            }
+        }
+        self.inner.readv(buf).await
     }
 }
 
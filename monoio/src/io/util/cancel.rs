@@ -1,12 +1,30 @@
-use std::{cell::RefCell, collections::HashSet, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
 
-use crate::driver::op::OpCanceller;
+use pin_project_lite::pin_project;
+
+use crate::{
+    driver::op::{Op, OpAble, OpCanceller},
+    BufResult,
+};
 
 /// CancelHandle is used to pass to io actions with CancelableAsyncReadRent.
 /// Create a CancelHandle with Canceller::handle.
+///
+/// A handle may also [time out](CancelHandle::with_timeout) the operations
+/// it is passed to.
 #[derive(Clone)]
 pub struct CancelHandle {
     shared: Rc<RefCell<Shared>>,
+    timeout: Option<Duration>,
 }
 
 /// Canceller is a user-hold struct to cancel io operations.
@@ -27,6 +45,14 @@ struct Shared {
     slot_ref: HashSet<OpCanceller>,
 }
 
+impl Shared {
+    // Marks the operations canceled and returns the in-flight ones.
+    fn cancel(&mut self) -> HashSet<OpCanceller> {
+        self.canceled = true;
+        std::mem::take(&mut self.slot_ref)
+    }
+}
+
 impl Canceller {
     /// Create a new Canceller.
     #[inline]
@@ -36,12 +62,7 @@ impl Canceller {
 
     /// Cancel all related operations.
     pub fn cancel(self) -> Self {
-        let mut slot = HashSet::new();
-        {
-            let mut shared = self.shared.borrow_mut();
-            shared.canceled = true;
-            std::mem::swap(&mut slot, &mut shared.slot_ref);
-        }
+        let mut slot = self.shared.borrow_mut().cancel();
 
         for op_canceller in slot.iter() {
             unsafe { op_canceller.cancel() };
@@ -60,11 +81,59 @@ impl Canceller {
     pub fn handle(&self) -> CancelHandle {
         CancelHandle {
             shared: self.shared.clone(),
+            timeout: None,
         }
     }
 }
 
 impl CancelHandle {
+    /// Time out each operation the handle is passed to after `timeout`: the
+    /// operation is canceled, and fails with a `TimedOut` error giving the
+    /// buffer back. Canceling the handle still cancels the operations.
+    ///
+    /// On io_uring, the timeout is linked to the operation
+    /// (`IORING_OP_LINK_TIMEOUT`) and canceled by the kernel, without a timer.
+    /// On the legacy driver it takes a timer, which needs to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use monoio::{
+    ///     io::{CancelableAsyncReadRentExt, Canceller},
+    ///     net::TcpStream,
+    /// };
+    ///
+    /// #[monoio::main(timer_enabled = true)]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///     let canceller = Canceller::new();
+    ///     // Each read waits for its peer for 5 seconds at most.
+    ///     let handle = canceller.handle().with_timeout(Duration::from_secs(5));
+    ///     let (res, header) = stream.cancelable_read_exact(vec![0; 16], handle).await;
+    ///     res?;
+    ///     println!("{header:?}");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timeout` is zero.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        assert!(!timeout.is_zero(), "`timeout` must be non-zero.");
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the timeout of the operations, see
+    /// [`with_timeout`](CancelHandle::with_timeout).
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     pub(crate) fn canceled(&self) -> bool {
         self.shared.borrow().canceled
     }
@@ -81,6 +150,46 @@ impl CancelHandle {
     }
 }
 
+impl CancelHandle {
+    /// Submits the operation of `submit`, associated with the handle, and
+    /// awaits its `result`, timing it out as of the handle.
+    pub(crate) async fn run<T, S, F, Fut, R, B>(self, submit: S, result: F) -> BufResult<R, B>
+    where
+        T: OpAble + 'static,
+        S: FnOnce() -> Op<T>,
+        F: FnOnce(Op<T>) -> Fut,
+        Fut: Future<Output = BufResult<R, B>>,
+    {
+        let Some(timeout) = self.timeout else {
+            let op = submit();
+            let _guard = self.associate_op(op.op_canceller());
+            return result(op).await;
+        };
+        match crate::driver::op::with_link_timeout(timeout, submit) {
+            Ok(op) => {
+                let shared = self.shared.clone();
+                let _guard = self.associate_op(op.op_canceller());
+                let (res, buf) = result(op).await;
+                match res {
+                    Err(e)
+                        if e.raw_os_error() == operation_canceled().raw_os_error()
+                            && !shared.borrow().canceled =>
+                    {
+                        (Err(std::io::ErrorKind::TimedOut.into()), buf)
+                    }
+                    res => (res, buf),
+                }
+            }
+            Err(submit) => {
+                let op = submit();
+                let op_canceller = op.op_canceller();
+                let _guard = self.associate_op(op_canceller.clone());
+                timeout_op(op_canceller, Some(timeout), result(op)).await
+            }
+        }
+    }
+}
+
 impl Drop for AssociateGuard {
     fn drop(&mut self) {
         let mut shared = self.shared.borrow_mut();
@@ -88,6 +197,219 @@ impl Drop for AssociateGuard {
     }
 }
 
+/// A group of IO futures and operations that are canceled together, e.g.
+/// everything issued to serve one request.
+///
+/// Any number of futures can [`enter`](CancellationRegion::enter) the
+/// region. Once it is [canceled](CancellationRegion::cancel), they are all
+/// dropped the next time they are polled, which cancels the operations they
+/// have in flight, and resolve to `None`. Futures entering a canceled region
+/// are never polled.
+///
+/// The region also hands out [`CancelHandle`]s for the `cancelable_*`
+/// operations: they complete with an `ECANCELED` error and give the buffers
+/// back instead of being dropped.
+///
+/// Clones of a region refer to the same region.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{AsyncReadRent, CancelableAsyncReadRent, CancellationRegion},
+///     net::TcpStream,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let region = CancellationRegion::new();
+///     let mut a = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut b = TcpStream::connect("127.0.0.1:8081").await?;
+///
+///     let r = region.clone();
+///     let task_a = monoio::spawn(async move { r.enter(a.read(vec![0; 1024])).await });
+///     let handle = region.handle();
+///     let task_b = monoio::spawn(async move { b.cancelable_read(vec![0; 1024], handle).await });
+///
+///     // Tear the whole request down.
+///     region.cancel();
+///     assert!(task_a.await.is_none());
+///     let (res, _buf) = task_b.await;
+///     assert!(res.is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationRegion {
+    shared: Rc<RefCell<RegionShared>>,
+}
+
+#[derive(Default)]
+struct RegionShared {
+    ops: Rc<RefCell<Shared>>,
+    // Wakers of the entered futures, by id.
+    waiters: HashMap<usize, Waker>,
+    next_id: usize,
+}
+
+impl CancellationRegion {
+    /// Create a new region.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Cancel the region: the entered futures are dropped and the operations
+    /// associated with its handles are canceled. Canceling it again does
+    /// nothing.
+    pub fn cancel(&self) {
+        let (slot, waiters) = {
+            let mut shared = self.shared.borrow_mut();
+            let slot = shared.ops.borrow_mut().cancel();
+            (slot, std::mem::take(&mut shared.waiters))
+        };
+        for op_canceller in slot.iter() {
+            unsafe { op_canceller.cancel() };
+        }
+        waiters.into_values().for_each(Waker::wake);
+    }
+
+    /// Returns `true` if the region is canceled.
+    #[inline]
+    pub fn is_canceled(&self) -> bool {
+        self.shared.borrow().ops.borrow().canceled
+    }
+
+    /// Create a [`CancelHandle`] for `cancelable_*` operations, they are
+    /// canceled with the region.
+    #[inline]
+    pub fn handle(&self) -> CancelHandle {
+        CancelHandle {
+            shared: self.shared.borrow().ops.clone(),
+            timeout: None,
+        }
+    }
+
+    /// Run `future` in the region. It resolves to `None` if the region is
+    /// canceled before the future completes.
+    pub fn enter<F: Future>(&self, future: F) -> Entered<F> {
+        let mut shared = self.shared.borrow_mut();
+        let id = shared.next_id;
+        shared.next_id = shared.next_id.wrapping_add(1);
+        Entered {
+            region: self.clone(),
+            id,
+            future: Some(future),
+        }
+    }
+}
+
+impl fmt::Debug for CancellationRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationRegion")
+            .field("canceled", &self.is_canceled())
+            .field("waiting", &self.shared.borrow().waiters.len())
+            .finish()
+    }
+}
+
+pin_project! {
+    /// Future returned by [`CancellationRegion::enter`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Entered<F> {
+        region: CancellationRegion,
+        id: usize,
+        #[pin]
+        future: Option<F>,
+    }
+
+    impl<F> PinnedDrop for Entered<F> {
+        fn drop(this: Pin<&mut Self>) {
+            let me = this.project();
+            me.region.shared.borrow_mut().waiters.remove(me.id);
+        }
+    }
+}
+
+impl<F: Future> Future for Entered<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut me = self.project();
+        if me.region.is_canceled() {
+            // Dropping the future cancels its operations.
+            me.future.set(None);
+            return Poll::Ready(None);
+        }
+        let future = me
+            .future
+            .as_mut()
+            .as_pin_mut()
+            .expect("`Entered` polled after completion");
+        if let Poll::Ready(out) = future.poll(cx) {
+            me.future.set(None);
+            me.region.shared.borrow_mut().waiters.remove(me.id);
+            return Poll::Ready(Some(out));
+        }
+        let mut shared = me.region.shared.borrow_mut();
+        match shared.waiters.get_mut(me.id) {
+            Some(waker) if waker.will_wake(cx.waker()) => (),
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => {
+                shared.waiters.insert(*me.id, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<F> fmt::Debug for Entered<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entered")
+            .field("region", &self.region)
+            .field("done", &self.future.is_none())
+            .finish()
+    }
+}
+
 pub(crate) fn operation_canceled() -> std::io::Error {
     std::io::Error::from_raw_os_error(125)
 }
+
+/// Awaits `result`, the result of the operation `op_canceller` refers to,
+/// canceling the operation once `timeout` elapses. It then fails with a
+/// `TimedOut` error, unless it completed meanwhile, and still gives its
+/// buffer back.
+pub(crate) async fn timeout_op<F, R, B>(
+    op_canceller: OpCanceller,
+    timeout: Option<Duration>,
+    result: F,
+) -> BufResult<R, B>
+where
+    F: Future<Output = BufResult<R, B>>,
+{
+    let Some(timeout) = timeout else {
+        return result.await;
+    };
+    let mut result = std::pin::pin!(result);
+    let mut sleep = std::pin::pin!(crate::time::sleep(timeout));
+    let mut timed_out = false;
+    let (res, buf) = std::future::poll_fn(|cx| {
+        if let Poll::Ready(out) = result.as_mut().poll(cx) {
+            return Poll::Ready(out);
+        }
+        if !timed_out && sleep.as_mut().poll(cx).is_ready() {
+            timed_out = true;
+            unsafe { op_canceller.cancel() };
+            return result.as_mut().poll(cx);
+        }
+        Poll::Pending
+    })
+    .await;
+    match res {
+        Err(e) if timed_out && e.raw_os_error() == operation_canceled().raw_os_error() => {
+            (Err(std::io::ErrorKind::TimedOut.into()), buf)
+        }
+        res => (res, buf),
+    }
+}
@@ -1,10 +1,21 @@
 #![allow(unused)]
 
-use std::io;
+use std::{
+    cell::Cell,
+    future::{poll_fn, Future},
+    io,
+    task::Poll,
+};
 
-use crate::io::{AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt};
 #[cfg(unix)]
 use crate::net::unix::new_pipe;
+use crate::{
+    buf::IoBuf,
+    io::{
+        AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt, CancelHandle, CancelableAsyncReadRent,
+        CancelableAsyncWriteRent, Split,
+    },
+};
 
 const BUF_SIZE: usize = 4 * 1024;
 
@@ -70,6 +81,182 @@ where
     Ok(transferred)
 }
 
+/// Copy data from reader to writer until the reader is closed, or the
+/// handle is canceled.
+///
+/// Each read and write is made with the handle, so canceling it cancels the
+/// one in flight and no other is made. The number of bytes written is
+/// returned in any case, along with the error of the operation which failed
+/// or was canceled. Data read but not written when canceled is discarded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{cancelable_copy, Canceller},
+///     net::TcpStream,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let mut upstream = TcpStream::connect("127.0.0.1:8080").await.unwrap();
+///     let mut downstream = TcpStream::connect("127.0.0.1:8081").await.unwrap();
+///     let canceller = Canceller::new();
+///     let (res, transferred) =
+///         cancelable_copy(&mut upstream, &mut downstream, canceller.handle()).await;
+///     println!("{transferred} bytes copied: {res:?}");
+/// }
+/// ```
+pub async fn cancelable_copy<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    c: CancelHandle,
+) -> (io::Result<()>, u64)
+where
+    R: CancelableAsyncReadRent + ?Sized,
+    W: CancelableAsyncWriteRent + ?Sized,
+{
+    let transferred = Cell::new(0);
+    let res = copy_counted(reader, writer, c, &transferred).await;
+    (res, transferred.get())
+}
+
+// Counts the bytes as they are written, so that a copy canceled halfway is
+// accounted for.
+async fn copy_counted<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    c: CancelHandle,
+    transferred: &Cell<u64>,
+) -> io::Result<()>
+where
+    R: CancelableAsyncReadRent + ?Sized,
+    W: CancelableAsyncWriteRent + ?Sized,
+{
+    let mut buf: Vec<u8> = Vec::with_capacity(BUF_SIZE);
+
+    loop {
+        let (read_res, buf_read) = reader.cancelable_read(buf, c.clone()).await;
+        buf = buf_read;
+        match read_res {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+
+        let mut slice = buf.slice(..);
+        while slice.remaining() > 0 {
+            let (write_res, slice_) = writer.cancelable_write(slice, c.clone()).await;
+            slice = slice_;
+            match write_res {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "write zero byte into writer",
+                    ))
+                }
+                Ok(n) => {
+                    transferred.set(transferred.get() + n as u64);
+                    slice.advance(n);
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        buf = slice.into_inner();
+        buf.clear();
+    }
+}
+
+/// Copy data between `a` and `b` in both directions at once, until both
+/// readers are closed, one of the copies fails, or the handle is canceled.
+///
+/// When a side is closed for reading, the other side is shut down for
+/// writing. The numbers of bytes copied from `a` to `b` and from `b` to `a`
+/// are returned in any case, see [`cancelable_copy`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{cancelable_copy_bidirectional, Canceller},
+///     net::TcpListener,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let listener = TcpListener::bind("127.0.0.1:8080").unwrap();
+///     let (mut client, _) = listener.accept().await.unwrap();
+///     let mut server = monoio::net::TcpStream::connect("127.0.0.1:8081")
+///         .await
+///         .unwrap();
+///     let canceller = Canceller::new();
+///     let (res, (up, down)) =
+///         cancelable_copy_bidirectional(&mut client, &mut server, canceller.handle()).await;
+///     println!("{up} bytes up, {down} bytes down: {res:?}");
+/// }
+/// ```
+pub async fn cancelable_copy_bidirectional<A, B>(
+    a: &mut A,
+    b: &mut B,
+    c: CancelHandle,
+) -> (io::Result<()>, (u64, u64))
+where
+    A: CancelableAsyncReadRent + CancelableAsyncWriteRent + Split,
+    B: CancelableAsyncReadRent + CancelableAsyncWriteRent + Split,
+{
+    async fn one_way<R, W>(
+        reader: &mut R,
+        writer: &mut W,
+        c: CancelHandle,
+        transferred: &Cell<u64>,
+    ) -> io::Result<()>
+    where
+        R: CancelableAsyncReadRent,
+        W: CancelableAsyncWriteRent,
+    {
+        copy_counted(reader, writer, c.clone(), transferred).await?;
+        writer.cancelable_shutdown(c).await
+    }
+
+    let (up, down) = (Cell::new(0), Cell::new(0));
+    let (a, b) = (a as *mut A, b as *mut B);
+    // Safety: `Split` types may be read from and written to concurrently.
+    let (a_to_b, b_to_a) = unsafe {
+        (
+            one_way(&mut *a, &mut *b, c.clone(), &up),
+            one_way(&mut *b, &mut *a, c, &down),
+        )
+    };
+    let mut a_to_b = std::pin::pin!(a_to_b);
+    let mut b_to_a = std::pin::pin!(b_to_a);
+    let (mut a_to_b_done, mut b_to_a_done) = (false, false);
+
+    let res = poll_fn(|cx| {
+        // The first error ends both copies.
+        if !a_to_b_done {
+            if let Poll::Ready(res) = a_to_b.as_mut().poll(cx) {
+                res?;
+                a_to_b_done = true;
+            }
+        }
+        if !b_to_a_done {
+            if let Poll::Ready(res) = b_to_a.as_mut().poll(cx) {
+                res?;
+                b_to_a_done = true;
+            }
+        }
+        if a_to_b_done && b_to_a_done {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+    (res, (up.get(), down.get()))
+}
+
 /// Copy with splice.
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub async fn zero_copy<SRC: crate::io::as_fd::AsReadFd, DST: crate::io::as_fd::AsWriteFd>(
@@ -9,10 +9,10 @@ mod split;
 
 pub use buf_reader::BufReader;
 pub use buf_writer::BufWriter;
-pub(crate) use cancel::operation_canceled;
-pub use cancel::{CancelHandle, Canceller};
-pub use copy::copy;
+pub(crate) use cancel::{operation_canceled, timeout_op};
+pub use cancel::{CancelHandle, CancellationRegion, Canceller, Entered};
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub use copy::zero_copy;
+pub use copy::{cancelable_copy, cancelable_copy_bidirectional, copy};
 pub use prefixed_io::PrefixedReadIo;
 pub use split::{OwnedReadHalf, OwnedWriteHalf, Split, Splitable};
@@ -30,6 +30,9 @@ pub mod buf;
 pub mod fs;
 pub mod io;
 pub mod net;
+#[cfg(target_os = "linux")]
+pub mod process;
+pub mod sync;
 pub mod task;
 pub mod utils;
 
@@ -37,7 +40,7 @@ use std::future::Future;
 
 #[cfg(feature = "sync")]
 pub use blocking::spawn_blocking;
-pub use builder::{Buildable, RuntimeBuilder};
+pub use builder::{Buildable, Profile, RuntimeBuilder};
 pub use driver::Driver;
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 pub use driver::IoUringDriver;
@@ -45,9 +48,18 @@ pub use driver::IoUringDriver;
 pub use driver::LegacyDriver;
 #[cfg(feature = "macros")]
 pub use monoio_macros::{main, test, test_all};
-pub use runtime::{spawn, Runtime};
+pub use runtime::{
+    keep_alive, ping_driver, spawn, spawn_with_context, spawn_with_priority, ExitPolicy, KeepAlive,
+    PanicPolicy, Runtime,
+};
+#[cfg(feature = "sync")]
+pub use runtime::{RemoteJoinHandle, RuntimeHandle};
+pub use scheduler::{Priority, WakeOrder};
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
-pub use {builder::FusionDriver, runtime::FusionRuntime};
+pub use {
+    builder::{AutoDriver, FusionDriver},
+    runtime::{AutoRuntime, FusionRuntime},
+};
 
 /// Start a monoio runtime.
 ///
@@ -90,6 +102,33 @@ where
     rt.block_on(future)
 }
 
+/// Start a monoio runtime with the timer enabled, and with the io_uring
+/// driver if the kernel supports it or the legacy driver otherwise.
+///
+/// Unlike [`start`], the driver is not a type parameter, see
+/// [`RuntimeBuilder::new_auto`].
+///
+/// # Examples
+///
+/// ```
+/// let n = monoio::start_auto(async {
+///     monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+///     monoio::spawn(async { 1 }).await
+/// });
+/// assert_eq!(n, 1);
+/// ```
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+pub fn start_auto<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    let mut rt = builder::RuntimeBuilder::new_auto()
+        .enable_timer()
+        .build()
+        .expect("Unable to build runtime.");
+    rt.block_on(future)
+}
+
 /// A specialized `Result` type for `io-uring` operations with buffers.
 ///
 /// This type is used as a return value for asynchronous `io-uring` methods that
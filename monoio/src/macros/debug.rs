@@ -19,3 +19,38 @@ macro_rules! info {
 macro_rules! info {
     ($( $args:expr ),*) => {};
 }
+
+#[allow(unused_macros)]
+#[cfg(all(debug_assertions, feature = "debug"))]
+macro_rules! warn {
+    ($( $args:expr ),*) => { tracing::warn!( $( $args ),* ); }
+}
+
+#[allow(unused_macros)]
+#[cfg(not(all(debug_assertions, feature = "debug")))]
+macro_rules! warn {
+    ($( $args:expr ),*) => {};
+}
+
+/// Emits a structured event of the driver at the trace level, with the
+/// target `monoio::driver`, e.g. `driver_event!("submit", submitted = n)`.
+///
+/// It compiles to nothing unless the `tracing` feature is enabled; the
+/// fields are still type checked, but never evaluated.
+#[allow(unused_macros)]
+#[cfg(feature = "tracing")]
+macro_rules! driver_event {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        tracing::trace!(target: "monoio::driver", $($field = $value,)* $name)
+    };
+}
+
+#[allow(unused_macros)]
+#[cfg(not(feature = "tracing"))]
+macro_rules! driver_event {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        if false {
+            $(let _ = $value;)*
+        }
+    };
+}
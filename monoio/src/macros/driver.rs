@@ -0,0 +1,45 @@
+// Used by `#[monoio::main(driver = [...])]` to skip the drivers monoio is
+// built without. The cfgs are evaluated in this crate, so they follow the
+// features of monoio rather than the ones of the crate using the attribute.
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_uring_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($yes)* };
+}
+
+#[cfg(not(all(target_os = "linux", feature = "iouring")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_uring_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($no)* };
+}
+
+#[cfg(feature = "legacy")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_legacy_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($yes)* };
+}
+
+#[cfg(not(feature = "legacy"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_legacy_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($no)* };
+}
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_fusion_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($yes)* };
+}
+
+#[cfg(not(any(all(target_os = "linux", feature = "iouring"), feature = "legacy")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_fusion_driver {
+    ({ $($yes:tt)* } else { $($no:tt)* }) => { $($no)* };
+}
@@ -30,6 +30,14 @@
 ///
 /// [`monoio::spawn`]: crate::spawn
 ///
+/// ### IO operations
+///
+/// IO futures push their operations to the driver when first polled, so the
+/// operations of joined futures, e.g. reads on several files or sockets, are
+/// in flight at the same time and are submitted together, without spawning a
+/// task per operation. To submit a dynamic number of them at once, see
+/// [`Batch`](crate::io::Batch).
+///
 /// # Examples
 ///
 /// Basic join with two branches
@@ -115,3 +123,97 @@ macro_rules! join {
         $crate::join!(@{ () } $($e,)*)
     };
 }
+
+/// Wait on multiple submitted operations, returning their completions when
+/// **all** of them complete.
+///
+/// Each expression evaluates to an [`Op`](crate::driver::op::Op), and the
+/// macro evaluates to a tuple of their
+/// [`Completion`](crate::driver::op::Completion)s, in order. The operations
+/// are polled in place on the current task, like the futures of
+/// [`join!`](macro@join), without spawning a task or wrapping them in another
+/// future: their completions are returned as the driver reaped them, with the
+/// result and flags of each in its `meta`.
+#[allow(unused_macros)]
+macro_rules! join_ops {
+    (@ {
+        // One `_` for each operation. This is not used once normalization is
+        // complete.
+        ( $($count:tt)* )
+
+        // Normalized operations
+        $( ( $($skip:tt)* ) $e:expr, )*
+
+    }) => {{
+        use $crate::macros::support::{poll_fn, Future, Pin};
+        use $crate::macros::support::Poll::{Ready, Pending};
+
+        // Each operation with its completion, once it completed.
+        let mut ops = ( $( ($e, None), )* );
+
+        poll_fn(move |cx| {
+            let mut is_pending = false;
+
+            $(
+                let ( $($skip,)* (op, completion), .. ) = &mut ops;
+                let op: &mut $crate::driver::op::Op<_> = op;
+                if completion.is_none() {
+                    match Pin::new(op).poll(cx) {
+                        Ready(c) => *completion = Some(c),
+                        Pending => is_pending = true,
+                    }
+                }
+            )*
+
+            if is_pending {
+                Pending
+            } else {
+                Ready(($({
+                    let ( $($skip,)* (_, completion), .. ) = &mut ops;
+                    completion.take().expect("expected completed operation")
+                },)*))
+            }
+        }).await
+    }};
+
+    // ===== Normalize =====
+
+    (@ { ( $($s:tt)* ) $($t:tt)* } $e:expr, $($r:tt)* ) => {
+        join_ops!(@{ ($($s)* _) $($t)* ($($s)*) $e, } $($r)*)
+    };
+
+    // ===== Entry point =====
+
+    ( $($e:expr),+ $(,)?) => {
+        join_ops!(@{ () } $($e,)*)
+    };
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{io::Write, os::unix::io::FromRawFd};
+
+    use crate::driver::{op::Op, shared_fd::SharedFd};
+
+    #[test]
+    fn join_ops() {
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut fds = [0; 2];
+            assert_eq!(
+                unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) },
+                0
+            );
+            let mut tx = unsafe { std::fs::File::from_raw_fd(fds[1]) };
+            tx.write_all(b"hello").unwrap();
+            let rx = SharedFd::new::<false>(fds[0]).unwrap();
+
+            let (nop, read) = join_ops!(Op::nop().unwrap(), Op::read(rx, vec![0; 5]).unwrap(),);
+            assert_eq!(nop.meta.result.unwrap().into_inner(), 0);
+            assert_eq!(read.meta.result.unwrap().into_inner(), 5);
+            assert_eq!(read.data.buf, b"hello");
+        });
+    }
+}
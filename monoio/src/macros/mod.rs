@@ -24,3 +24,6 @@ pub mod support;
 
 #[macro_use]
 mod debug;
+
+#[macro_use]
+mod driver;
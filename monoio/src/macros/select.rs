@@ -54,6 +54,16 @@
 ///
 /// [`monoio::spawn`]: crate::spawn
 ///
+/// # IO operations
+///
+/// The operations of IO futures are in flight at the same time, as with
+/// [`join!`](macro@join), and the futures of the branches that did not
+/// complete are dropped. With the `async-cancel` feature, dropping them
+/// cancels their in-flight operations on io_uring; otherwise the operations
+/// run to completion in the background. Either way, the buffers they own are
+/// kept by the driver until the kernel is done with them, and their results
+/// are discarded: a losing read may still have consumed data.
+///
 /// # Fairness
 ///
 /// By default, `select!` randomly picks a branch to check first. This provides
@@ -839,3 +849,109 @@ macro_rules! select_variant {
         $($p)::*::_63 $($t)*
     };
 }
+
+/// Wait on multiple submitted operations, returning when the **first** of
+/// them completes.
+///
+/// Each expression evaluates to an [`Op`](crate::driver::op::Op), and the
+/// macro evaluates to a tuple with an `Option` of the
+/// [`Completion`](crate::driver::op::Completion) of each, in order. The
+/// operations are polled in place on the current task, like the branches of
+/// [`select!`](macro@select), without spawning a task or wrapping them in
+/// another future.
+///
+/// Once an operation completes, the completions of all the operations found
+/// complete in the same poll are returned, so that the results of operations
+/// completing together are not lost. The others are `None`, and dropped when
+/// the macro returns: with the `async-cancel` feature, their in-flight
+/// operations are cancelled on io_uring, otherwise they run to completion in
+/// the background and their results are discarded.
+#[allow(unused_macros)]
+macro_rules! select_ops {
+    (@ {
+        // One `_` for each operation. This is not used once normalization is
+        // complete.
+        ( $($count:tt)* )
+
+        // Normalized operations
+        $( ( $($skip:tt)* ) $e:expr, )*
+
+    }) => {{
+        use $crate::macros::support::{poll_fn, Future, Pin};
+        use $crate::macros::support::Poll::{Ready, Pending};
+
+        // Each operation with its completion, once it completed.
+        let mut ops = ( $( ($e, None), )* );
+
+        poll_fn(move |cx| {
+            let mut is_ready = false;
+
+            $(
+                let ( $($skip,)* (op, completion), .. ) = &mut ops;
+                let op: &mut $crate::driver::op::Op<_> = op;
+                if let Ready(c) = Pin::new(op).poll(cx) {
+                    *completion = Some(c);
+                    is_ready = true;
+                }
+            )*
+
+            if is_ready {
+                Ready(($({
+                    let ( $($skip,)* (_, completion), .. ) = &mut ops;
+                    completion.take()
+                },)*))
+            } else {
+                Pending
+            }
+        }).await
+    }};
+
+    // ===== Normalize =====
+
+    (@ { ( $($s:tt)* ) $($t:tt)* } $e:expr, $($r:tt)* ) => {
+        select_ops!(@{ ($($s)* _) $($t)* ($($s)*) $e, } $($r)*)
+    };
+
+    // ===== Entry point =====
+
+    ( $($e:expr),+ $(,)?) => {
+        select_ops!(@{ () } $($e,)*)
+    };
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::io::FromRawFd;
+
+    use crate::driver::{op::Op, shared_fd::SharedFd};
+
+    #[test]
+    fn select_ops() {
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut fds = [0; 2];
+            assert_eq!(
+                unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) },
+                0
+            );
+            let tx = unsafe { std::fs::File::from_raw_fd(fds[1]) };
+            let rx = SharedFd::new::<false>(fds[0]).unwrap();
+
+            // Nothing to read, the nop completes first.
+            let (read, nop) = select_ops!(
+                Op::read(rx.clone(), vec![0; 5]).unwrap(),
+                Op::nop().unwrap(),
+            );
+            assert!(read.is_none());
+            assert_eq!(nop.unwrap().meta.result.unwrap().into_inner(), 0);
+
+            // Operations complete together.
+            let (nop, other) = select_ops!(Op::nop().unwrap(), Op::nop().unwrap());
+            assert!(nop.is_some() && other.is_some());
+            // Ends the read left in flight.
+            drop(tx);
+        });
+    }
+}
@@ -0,0 +1,102 @@
+//! Pooled buffers of the datagram streams.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::buf::IoBuf;
+
+// Fits the largest UDP datagram.
+const DATAGRAM_BUF_SIZE: usize = 64 * 1024;
+// Buffers kept for reuse per thread.
+const POOL_SIZE: usize = 16;
+
+thread_local! {
+    static POOL: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A datagram received by the [`Stream`](crate::io::stream::Stream)
+/// implementation of [`UdpSocket`](crate::net::udp::UdpSocket) and
+/// [`UnixDatagram`](crate::net::UnixDatagram).
+///
+/// The buffers come from a small per-thread pool, each of them fits 64 KiB
+/// and goes back to the pool when dropped. Larger datagrams are truncated.
+///
+/// It implements [`IoBuf`], so it can be sent again as is, e.g. through the
+/// [`Sink`](crate::io::sink::Sink) implementation of the socket.
+pub struct DatagramBuf {
+    buf: Vec<u8>,
+}
+
+impl DatagramBuf {
+    /// Takes a buffer out of the pool, the pool allocates one if it is empty.
+    pub(crate) fn take() -> Vec<u8> {
+        POOL.try_with(|pool| pool.borrow_mut().pop())
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| Vec::with_capacity(DATAGRAM_BUF_SIZE))
+    }
+
+    pub(crate) fn new(buf: Vec<u8>) -> Self {
+        Self { buf }
+    }
+
+    /// Returns the data as a `Vec`, which does not go back to the pool.
+    pub fn into_vec(mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl Drop for DatagramBuf {
+    fn drop(&mut self) {
+        let mut buf = std::mem::take(&mut self.buf);
+        if buf.capacity() != DATAGRAM_BUF_SIZE {
+            return;
+        }
+        buf.clear();
+        let _ = POOL.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < POOL_SIZE {
+                pool.push(buf);
+            }
+        });
+    }
+}
+
+impl Deref for DatagramBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl DerefMut for DatagramBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl fmt::Debug for DatagramBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatagramBuf")
+            .field("len", &self.buf.len())
+            .finish()
+    }
+}
+
+unsafe impl IoBuf for DatagramBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.buf.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.buf.len()
+    }
+}
@@ -0,0 +1,269 @@
+//! Closing of idle connections.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::time::{Instant, MissedTickBehavior};
+
+/// Closes connections that have been idle for too long.
+///
+/// Connections are [registered](IdleReaper::register) with a close
+/// callback, and report their activity through the returned [`IdleGuard`].
+/// Instead of one timeout per connection, a single timer ticks every
+/// `resolution` and invokes the callbacks of the connections idle for at
+/// least `idle`, so a connection is closed after `idle`, give or take
+/// `resolution`.
+///
+/// The timer runs in a task spawned on the current thread while connections
+/// are registered, it needs the timer to be enabled. The guards keep the
+/// reaper alive, so connections are still closed after the reaper itself is
+/// dropped.
+///
+/// Clones of a reaper refer to the same reaper.
+///
+/// # Examples
+///
+/// Canceling the connections idle for 30 seconds through a
+/// [`ShutdownSignal`](super::ShutdownSignal) of their own:
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monoio::{
+///     io::AsyncReadRent,
+///     net::{IdleReaper, ShutdownSignal, TcpListener},
+/// };
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:8080")?;
+///     let reaper = IdleReaper::new(Duration::from_secs(30));
+///     loop {
+///         let (mut stream, _) = listener.accept().await?;
+///         let idle = ShutdownSignal::new();
+///         let guard = reaper.register({
+///             let idle = idle.clone();
+///             move || idle.shutdown()
+///         });
+///         monoio::spawn(async move {
+///             let mut buf = vec![0; 1024];
+///             loop {
+///                 monoio::select! {
+///                     (res, b) = stream.read(buf) => {
+///                         if !matches!(res, Ok(n) if n > 0) {
+///                             return;
+///                         }
+///                         guard.touch();
+///                         buf = b;
+///                     }
+///                     _ = idle.wait() => return,
+///                 }
+///             }
+///         });
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct IdleReaper {
+    shared: Rc<ReaperShared>,
+}
+
+struct ReaperShared {
+    idle: Duration,
+    resolution: Cell<Duration>,
+    // The time of the last tick, which activity is stamped with.
+    now: Cell<Instant>,
+    entries: RefCell<HashMap<u64, Rc<Entry>>>,
+    next_id: Cell<u64>,
+    running: Cell<bool>,
+}
+
+struct Entry {
+    last_active: Cell<Instant>,
+    on_idle: RefCell<Option<Box<dyn FnOnce()>>>,
+}
+
+impl IdleReaper {
+    /// Create a reaper closing the connections idle for `idle`. The timer
+    /// ticks every quarter of it, see
+    /// [`with_resolution`](IdleReaper::with_resolution).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idle` is zero.
+    pub fn new(idle: Duration) -> Self {
+        assert!(!idle.is_zero(), "`idle` must be non-zero.");
+        Self {
+            shared: Rc::new(ReaperShared {
+                idle,
+                resolution: Cell::new(idle / 4),
+                now: Cell::new(Instant::now()),
+                entries: RefCell::new(HashMap::new()),
+                next_id: Cell::new(0),
+                running: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Set how often the timer ticks. A finer resolution closes idle
+    /// connections closer to their deadline, at the cost of more wakeups.
+    ///
+    /// It takes effect when the timer starts next, i.e. when a connection is
+    /// registered while none is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is zero.
+    #[must_use]
+    pub fn with_resolution(self, resolution: Duration) -> Self {
+        assert!(!resolution.is_zero(), "`resolution` must be non-zero.");
+        self.shared.resolution.set(resolution);
+        self
+    }
+
+    /// Returns how long connections may stay idle.
+    pub fn idle(&self) -> Duration {
+        self.shared.idle
+    }
+
+    /// Returns how often the timer ticks.
+    pub fn resolution(&self) -> Duration {
+        self.shared.resolution.get()
+    }
+
+    /// Register a connection, `on_idle` is invoked once it has been idle for
+    /// too long. The connection counts as active now.
+    ///
+    /// Dropping the returned guard unregisters the connection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn register<F>(&self, on_idle: F) -> IdleGuard
+    where
+        F: FnOnce() + 'static,
+    {
+        let shared = &self.shared;
+        let id = shared.next_id.get();
+        shared.next_id.set(id + 1);
+        let entry = Rc::new(Entry {
+            last_active: Cell::new(Instant::now()),
+            on_idle: RefCell::new(Some(Box::new(on_idle))),
+        });
+        shared.entries.borrow_mut().insert(id, entry.clone());
+        if !shared.running.replace(true) {
+            shared.now.set(Instant::now());
+            crate::spawn(run(Rc::downgrade(shared), shared.resolution.get()));
+        }
+        IdleGuard {
+            reaper: shared.clone(),
+            id,
+            entry,
+        }
+    }
+
+    /// Returns the number of registered connections.
+    pub fn len(&self) -> usize {
+        self.shared.entries.borrow().len()
+    }
+
+    /// Returns `true` if no connection is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for IdleReaper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleReaper")
+            .field("idle", &self.shared.idle)
+            .field("resolution", &self.shared.resolution.get())
+            .field("connections", &self.len())
+            .finish()
+    }
+}
+
+// Ticks until no connection is left.
+async fn run(shared: Weak<ReaperShared>, resolution: Duration) {
+    let mut interval = crate::time::interval_at(Instant::now() + resolution, resolution);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        if !shared.reap() {
+            return;
+        }
+    }
+}
+
+impl ReaperShared {
+    // Invokes the callbacks of the idle connections, returns `false` and
+    // stops the timer if none is left.
+    fn reap(&self) -> bool {
+        let now = Instant::now();
+        self.now.set(now);
+        let mut expired = Vec::new();
+        self.entries.borrow_mut().retain(|_, entry| {
+            let active = now.saturating_duration_since(entry.last_active.get()) < self.idle;
+            if !active {
+                expired.extend(entry.on_idle.borrow_mut().take());
+            }
+            active
+        });
+        // Invoked once the entries are released, the callbacks may register
+        // or drop connections.
+        expired.into_iter().for_each(|on_idle| on_idle());
+        let running = !self.entries.borrow().is_empty();
+        self.running.set(running);
+        running
+    }
+}
+
+/// A connection registered with an [`IdleReaper`].
+///
+/// Dropping it unregisters the connection.
+pub struct IdleGuard {
+    reaper: Rc<ReaperShared>,
+    id: u64,
+    entry: Rc<Entry>,
+}
+
+impl IdleGuard {
+    /// Mark the connection active, this is cheap enough to be called on
+    /// every read or write.
+    #[inline]
+    pub fn touch(&self) {
+        let now = self.reaper.now.get();
+        if now > self.entry.last_active.get() {
+            self.entry.last_active.set(now);
+        }
+    }
+
+    /// Returns `true` if the connection has been idle for too long, and its
+    /// callback invoked.
+    pub fn is_reaped(&self) -> bool {
+        self.entry.on_idle.borrow().is_none()
+    }
+}
+
+impl Drop for IdleGuard {
+    fn drop(&mut self) {
+        self.reaper.entries.borrow_mut().remove(&self.id);
+    }
+}
+
+impl fmt::Debug for IdleGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleGuard")
+            .field("id", &self.id)
+            .field("reaped", &self.is_reaped())
+            .finish()
+    }
+}
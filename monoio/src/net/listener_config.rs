@@ -14,6 +14,8 @@ pub struct ListenerOpts {
     pub recv_buf_size: Option<usize>,
     /// TCP fast open.
     pub tcp_fast_open: bool,
+    /// IPV6_V6ONLY for IPv6 addresses or None to use default.
+    pub only_v6: Option<bool>,
 }
 
 impl Default for ListenerOpts {
@@ -34,6 +36,7 @@ impl ListenerOpts {
             send_buf_size: None,
             recv_buf_size: None,
             tcp_fast_open: false,
+            only_v6: None,
         }
     }
 
@@ -88,4 +91,17 @@ impl ListenerOpts {
         self.tcp_fast_open = fast_open;
         self
     }
+
+    /// Specify IPV6_V6ONLY, which decides whether a listener bound to an
+    /// unspecified IPv6 address also accepts IPv4 connections. It is ignored
+    /// for IPv4 addresses.
+    ///
+    /// The OS default differs: Linux accepts both unless configured
+    /// otherwise, while Windows only accepts IPv6.
+    #[must_use]
+    #[inline]
+    pub fn only_v6(mut self, only_v6: bool) -> Self {
+        self.only_v6 = Some(only_v6);
+        self
+    }
 }
@@ -1,16 +1,31 @@
 //! Network related
 //! Currently, TCP/UnixStream/UnixDatagram are implemented.
 
+mod datagram;
+mod idle_reaper;
 mod listener_config;
+mod poll_ready;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod recv_multishot;
+mod resolve;
+mod serve;
 pub mod tcp;
 pub mod udp;
 #[cfg(unix)]
 pub mod unix;
 
+pub use datagram::DatagramBuf;
+pub use idle_reaper::{IdleGuard, IdleReaper};
 pub use listener_config::ListenerOpts;
 #[deprecated(since = "0.2.0", note = "use ListenerOpts")]
 pub use listener_config::ListenerOpts as ListenerConfig;
-pub use tcp::{TcpConnectOpts, TcpListener, TcpStream};
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use recv_multishot::RecvMultishot;
+pub use resolve::{lookup_host, set_resolver, GaiResolver, Resolver, ToSocketAddrs};
+pub use serve::{serve, ShutdownSignal};
+pub use tcp::{
+    AcceptAddrBuf, AcceptMultishot, ConnectError, TcpConnectOpts, TcpListener, TcpStream,
+};
 #[cfg(unix)]
 pub use unix::{Pipe, UnixDatagram, UnixListener, UnixStream};
 #[cfg(windows)]
@@ -124,3 +139,13 @@ pub(crate) fn new_socket(
         }
     })
 }
+
+// The host has no IPv6, or it is disabled, so dual stack binds fall back to
+// IPv4.
+pub(crate) fn is_ipv6_unavailable(e: &std::io::Error) -> bool {
+    #[cfg(unix)]
+    const EAFNOSUPPORT: i32 = libc::EAFNOSUPPORT;
+    #[cfg(windows)]
+    const EAFNOSUPPORT: i32 = windows_sys::Win32::Networking::WinSock::WSAEAFNOSUPPORT as _;
+    e.raw_os_error() == Some(EAFNOSUPPORT) || e.kind() == std::io::ErrorKind::AddrNotAvailable
+}
@@ -0,0 +1,67 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::driver::{
+    op::{Op, PollAdd},
+    shared_fd::SharedFd,
+};
+
+/// Readiness waits of the `poll_read_ready` and `poll_write_ready` methods of
+/// a socket, kept across polls so a pending one is not pushed again. They are
+/// boxed to keep the sockets small.
+#[derive(Default)]
+pub(crate) struct PollReady(Cell<Option<Box<Pending>>>);
+
+#[derive(Default)]
+struct Pending {
+    read: Option<Op<PollAdd>>,
+    write: Option<Op<PollAdd>>,
+}
+
+impl PollReady {
+    pub(crate) fn poll_read_ready(
+        &self,
+        fd: &SharedFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.poll(cx, |pending| &mut pending.read, || Op::poll_read(fd, false))
+    }
+
+    pub(crate) fn poll_write_ready(
+        &self,
+        fd: &SharedFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.poll(
+            cx,
+            |pending| &mut pending.write,
+            || Op::poll_write(fd, false),
+        )
+    }
+
+    fn poll(
+        &self,
+        cx: &mut Context<'_>,
+        slot: impl FnOnce(&mut Pending) -> &mut Option<Op<PollAdd>>,
+        submit: impl FnOnce() -> io::Result<Op<PollAdd>>,
+    ) -> Poll<io::Result<()>> {
+        let mut pending = self.0.take().unwrap_or_default();
+        let res = (|| {
+            let slot = slot(&mut pending);
+            let op = match slot {
+                Some(op) => op,
+                None => slot.insert(submit()?),
+            };
+            let complete = std::task::ready!(Pin::new(op).poll(cx));
+            *slot = None;
+            Poll::Ready(complete.meta.result.map(|_| ()))
+        })();
+        self.0.set(Some(pending));
+        res
+    }
+}
@@ -0,0 +1,98 @@
+use std::{future::poll_fn, io};
+
+use io_uring::cqueue;
+
+use crate::{
+    buf::{BufRing, RingBuf},
+    driver::{
+        op::{Op, RecvMulti},
+        shared_fd::SharedFd,
+    },
+    io::stream::Stream,
+};
+
+/// A stream of the data received on a socket into the buffers of a
+/// [`BufRing`], see [`TcpStream::recv_multishot`] and
+/// [`UdpSocket::recv_multishot`].
+///
+/// [`TcpStream::recv_multishot`]: crate::net::TcpStream::recv_multishot
+/// [`UdpSocket::recv_multishot`]: crate::net::udp::UdpSocket::recv_multishot
+pub struct RecvMultishot<'a> {
+    fd: &'a SharedFd,
+    ring: BufRing,
+    op: Option<Op<RecvMulti>>,
+    multishot: bool,
+    // Empty datagrams do not end the stream.
+    datagram: bool,
+    eof: bool,
+}
+
+impl<'a> RecvMultishot<'a> {
+    pub(crate) fn new(fd: &'a SharedFd, ring: &BufRing, datagram: bool) -> Self {
+        Self {
+            fd,
+            ring: ring.clone(),
+            op: None,
+            multishot: true,
+            datagram,
+            eof: false,
+        }
+    }
+}
+
+impl Stream for RecvMultishot<'_> {
+    type Item = io::Result<RingBuf>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        while !self.eof {
+            let op = match &mut self.op {
+                Some(op) => op,
+                None => {
+                    let op = match self.multishot {
+                        true => Op::recv_multi(self.fd, self.ring.clone()),
+                        false => Op::recv_select(self.fd, self.ring.clone()),
+                    };
+                    match op {
+                        Ok(op) => self.op.insert(op),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            };
+            let Some(meta) = poll_fn(|cx| op.poll_multishot(cx)).await else {
+                self.op = None;
+                continue;
+            };
+            if !cqueue::more(meta.flags) {
+                // Re-armed on the next call.
+                self.op = None;
+            }
+            let res = meta.result.map(|n| n.into_inner() as usize);
+            let len = *res.as_ref().unwrap_or(&0);
+            let buf = cqueue::buffer_select(meta.flags).map(|bid| self.ring.take(bid, len));
+            match (res, buf) {
+                (Ok(0), _) if !self.datagram => self.eof = true,
+                (Ok(0), None) => {}
+                (Ok(_), Some(buf)) => return Some(Ok(buf)),
+                (Ok(_), None) => {
+                    return Some(Err(io::Error::other("no buffer selected by the kernel")))
+                }
+                // Multishot recv needs kernel 6.0+, fall back to one recv per
+                // buffer.
+                (Err(e), _) if self.multishot && e.raw_os_error() == Some(libc::EINVAL) => {
+                    self.multishot = false;
+                }
+                (Err(e), _) => return Some(Err(e)),
+            }
+        }
+        None
+    }
+}
+
+impl std::fmt::Debug for RecvMultishot<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecvMultishot")
+            .field("fd", self.fd)
+            .field("ring", &self.ring)
+            .finish()
+    }
+}
@@ -0,0 +1,191 @@
+//! Host name resolution of the connect methods.
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    pin::Pin,
+    rc::Rc,
+};
+
+thread_local! {
+    static RESOLVER: RefCell<Option<Rc<dyn Resolver>>> = const { RefCell::new(None) };
+}
+
+/// Resolves host names to socket addresses for
+/// [`TcpStream::connect`](crate::net::TcpStream::connect) and
+/// [`lookup_host`].
+///
+/// The default is [`GaiResolver`], another one can be set for the current
+/// thread with [`set_resolver`], e.g. wrapping an async DNS client.
+pub trait Resolver {
+    /// Resolves `host` to the socket addresses of `port`.
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + 'a>>;
+}
+
+/// The default [`Resolver`], calling `getaddrinfo` like
+/// [`std::net::ToSocketAddrs`].
+///
+/// The call runs on the thread pool attached to the runtime, with the `sync`
+/// feature enabled. Without one, it blocks the current thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GaiResolver;
+
+impl Resolver for GaiResolver {
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + 'a>> {
+        fn lookup(host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+            std::net::ToSocketAddrs::to_socket_addrs(&(host, port)).map(Iterator::collect)
+        }
+
+        Box::pin(async move {
+            #[cfg(feature = "sync")]
+            if crate::blocking::has_thread_pool() {
+                let host = host.to_owned();
+                return crate::spawn_blocking(move || lookup(&host, port))
+                    .await
+                    .unwrap_or_else(|_| Err(io::Error::other("background task failed")));
+            }
+            lookup(host, port)
+        })
+    }
+}
+
+/// Set the [`Resolver`] of the current thread, replacing the previous one.
+///
+/// # Examples
+///
+/// ```
+/// use std::{future::Future, io, net::SocketAddr, pin::Pin};
+///
+/// use monoio::net::{lookup_host, set_resolver, Resolver};
+///
+/// struct Static;
+///
+/// impl Resolver for Static {
+///     fn resolve<'a>(
+///         &'a self,
+///         host: &'a str,
+///         port: u16,
+///     ) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + 'a>> {
+///         Box::pin(async move {
+///             match host {
+///                 "service.local" => Ok(vec![SocketAddr::from(([10, 0, 0, 1], port))]),
+///                 _ => Err(io::ErrorKind::NotFound.into()),
+///             }
+///         })
+///     }
+/// }
+///
+/// #[monoio::main]
+/// async fn main() -> io::Result<()> {
+///     set_resolver(Static);
+///     let addrs = lookup_host("service.local:80").await?;
+///     assert_eq!(addrs, [SocketAddr::from(([10, 0, 0, 1], 80))]);
+///     Ok(())
+/// }
+/// ```
+pub fn set_resolver<R: Resolver + 'static>(resolver: R) {
+    RESOLVER.with(|current| *current.borrow_mut() = Some(Rc::new(resolver)));
+}
+
+/// Resolves `addr` to socket addresses with the [`Resolver`] of the current
+/// thread. Addresses are returned as is, without a lookup.
+pub async fn lookup_host<A: ToSocketAddrs>(addr: A) -> io::Result<Vec<SocketAddr>> {
+    match addr.to_target()? {
+        Target::Addrs(addrs) => Ok(addrs),
+        Target::Host(host, port) => {
+            let resolver = RESOLVER.with(|current| current.borrow().clone());
+            match resolver {
+                Some(resolver) => resolver.resolve(host, port).await,
+                None => GaiResolver.resolve(host, port).await,
+            }
+        }
+    }
+}
+
+/// Types that can be resolved to socket addresses by [`lookup_host`], the
+/// same as the ones of [`std::net::ToSocketAddrs`].
+///
+/// This trait is sealed.
+pub trait ToSocketAddrs: sealed::ToTarget {}
+
+mod sealed {
+    use super::*;
+
+    pub enum Target<'a> {
+        Addrs(Vec<SocketAddr>),
+        Host(&'a str, u16),
+    }
+
+    pub trait ToTarget {
+        fn to_target(&self) -> io::Result<Target<'_>>;
+    }
+}
+
+use sealed::{Target, ToTarget};
+
+macro_rules! to_socket_addrs {
+    ($($ty:ty => |$this:ident| $target:expr;)*) => {
+        $(
+            impl ToSocketAddrs for $ty {}
+
+            impl ToTarget for $ty {
+                fn to_target(&self) -> io::Result<Target<'_>> {
+                    let $this = self;
+                    $target
+                }
+            }
+        )*
+    };
+}
+
+to_socket_addrs! {
+    SocketAddr => |addr| Ok(Target::Addrs(vec![*addr]));
+    SocketAddrV4 => |addr| Ok(Target::Addrs(vec![(*addr).into()]));
+    SocketAddrV6 => |addr| Ok(Target::Addrs(vec![(*addr).into()]));
+    (IpAddr, u16) => |addr| Ok(Target::Addrs(vec![(*addr).into()]));
+    (Ipv4Addr, u16) => |addr| Ok(Target::Addrs(vec![(*addr).into()]));
+    (Ipv6Addr, u16) => |addr| Ok(Target::Addrs(vec![(*addr).into()]));
+    (&str, u16) => |addr| Ok(host_target(addr.0, addr.1));
+    (String, u16) => |addr| Ok(host_target(&addr.0, addr.1));
+    str => |addr| str_target(addr);
+    String => |addr| str_target(addr);
+    [SocketAddr] => |addrs| Ok(Target::Addrs(addrs.to_vec()));
+}
+
+impl<T: ToSocketAddrs + ?Sized> ToSocketAddrs for &T {}
+
+impl<T: ToSocketAddrs + ?Sized> ToTarget for &T {
+    fn to_target(&self) -> io::Result<Target<'_>> {
+        (**self).to_target()
+    }
+}
+
+fn host_target(host: &str, port: u16) -> Target<'_> {
+    match host.parse::<IpAddr>() {
+        Ok(ip) => Target::Addrs(vec![SocketAddr::new(ip, port)]),
+        Err(_) => Target::Host(host, port),
+    }
+}
+
+// Parses `host:port` the way std does.
+fn str_target(s: &str) -> io::Result<Target<'_>> {
+    if let Ok(addr) = s.parse::<SocketAddr>() {
+        return Ok(Target::Addrs(vec![addr]));
+    }
+    let invalid = |msg| io::Error::new(io::ErrorKind::InvalidInput, msg);
+    let (host, port) = s
+        .rsplit_once(':')
+        .ok_or_else(|| invalid("invalid socket address"))?;
+    let port = port.parse().map_err(|_| invalid("invalid port value"))?;
+    Ok(Target::Host(host, port))
+}
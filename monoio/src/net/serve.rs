@@ -0,0 +1,263 @@
+//! A graceful TCP server loop.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    net::SocketAddr,
+    rc::Rc,
+    task::{Poll, Waker},
+    time::Duration,
+};
+
+use super::{TcpListener, TcpStream};
+use crate::io::CancellationRegion;
+
+/// A signal telling [`serve`] to shut down.
+///
+/// Clones of a signal refer to the same signal, so one can be given to
+/// `serve` and another kept to [`shutdown`](ShutdownSignal::shutdown) the
+/// server, e.g. on Ctrl+C. Connection handlers can also keep a clone and
+/// [`wait`](ShutdownSignal::wait) on it to finish their work early.
+#[derive(Clone, Default)]
+pub struct ShutdownSignal {
+    shared: Rc<RefCell<SignalShared>>,
+}
+
+#[derive(Default)]
+struct SignalShared {
+    triggered: bool,
+    grace_period: Option<Duration>,
+    waiters: Vec<Waker>,
+}
+
+impl ShutdownSignal {
+    /// Create a signal. Without a grace period, [`serve`] waits for the
+    /// connections to finish for as long as it takes.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set how long [`serve`] waits for the connections to finish after the
+    /// signal, before canceling the remaining ones. This needs the timer to
+    /// be enabled.
+    #[must_use]
+    pub fn with_grace_period(self, grace_period: Duration) -> Self {
+        self.shared.borrow_mut().grace_period = Some(grace_period);
+        self
+    }
+
+    /// Trigger the signal. Triggering it again does nothing.
+    pub fn shutdown(&self) {
+        let waiters = {
+            let mut shared = self.shared.borrow_mut();
+            shared.triggered = true;
+            std::mem::take(&mut shared.waiters)
+        };
+        waiters.into_iter().for_each(Waker::wake);
+    }
+
+    /// Returns `true` if the signal has been triggered.
+    pub fn is_shutdown(&self) -> bool {
+        self.shared.borrow().triggered
+    }
+
+    /// Wait for the signal to be triggered.
+    pub async fn wait(&self) {
+        poll_fn(|cx| {
+            let mut shared = self.shared.borrow_mut();
+            if shared.triggered {
+                return Poll::Ready(());
+            }
+            if !shared.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                shared.waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn grace_period(&self) -> Option<Duration> {
+        self.shared.borrow().grace_period
+    }
+}
+
+impl fmt::Debug for ShutdownSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shared = self.shared.borrow();
+        f.debug_struct("ShutdownSignal")
+            .field("triggered", &shared.triggered)
+            .field("grace_period", &shared.grace_period)
+            .finish()
+    }
+}
+
+// Counts the running connection tasks.
+#[derive(Clone, Default)]
+struct Tracker {
+    shared: Rc<RefCell<(usize, Option<Waker>)>>,
+}
+
+struct TrackerGuard(Tracker);
+
+impl Tracker {
+    fn enter(&self) -> TrackerGuard {
+        self.shared.borrow_mut().0 += 1;
+        TrackerGuard(self.clone())
+    }
+
+    async fn drained(&self) {
+        poll_fn(|cx| {
+            let mut shared = self.shared.borrow_mut();
+            if shared.0 == 0 {
+                return Poll::Ready(());
+            }
+            shared.1 = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Drop for TrackerGuard {
+    fn drop(&mut self) {
+        let mut shared = self.0.shared.borrow_mut();
+        shared.0 -= 1;
+        if shared.0 == 0 {
+            if let Some(waker) = shared.1.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Accept connections on `listener` and serve each of them with `handler` in
+/// its own task, until `signal` is triggered.
+///
+/// On shutdown the listener stops accepting, and `serve` waits for the
+/// connection tasks to finish. If the signal has a
+/// [grace period](ShutdownSignal::with_grace_period), the tasks still
+/// running after it are canceled: their futures are dropped, which cancels
+/// the operations they have in flight. `serve` returns once all of them are
+/// gone.
+///
+/// Accept errors caused by one failed connection, such as
+/// `ConnectionAborted`, are skipped. Other ones shut the server down the same
+/// way, and are returned once the connections are drained.
+///
+/// # Examples
+///
+/// An echo server shutting down after serving one client:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use monoio::{
+///     io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt},
+///     net::{serve, ShutdownSignal, TcpListener, TcpStream},
+/// };
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:0")?;
+///     let addr = listener.local_addr()?;
+///     let signal = ShutdownSignal::new().with_grace_period(Duration::from_secs(5));
+///
+///     let trigger = signal.clone();
+///     monoio::spawn(async move {
+///         let mut client = TcpStream::connect(addr).await.unwrap();
+///         client.write_all(&b"hello"[..]).await.0.unwrap();
+///         let (res, buf) = client.read_exact(vec![0; 5]).await;
+///         res.unwrap();
+///         assert_eq!(buf, b"hello");
+///         trigger.shutdown();
+///     });
+///
+///     serve(
+///         listener,
+///         |mut stream, _addr| async move {
+///             let mut buf = vec![0; 1024];
+///             loop {
+///                 let (res, b) = stream.read(buf).await;
+///                 if !matches!(res, Ok(n) if n > 0) {
+///                     return;
+///                 }
+///                 let (res, b) = stream.write_all(b).await;
+///                 if res.is_err() {
+///                     return;
+///                 }
+///                 buf = b;
+///                 buf.clear();
+///             }
+///         },
+///         signal,
+///     )
+///     .await
+/// }
+/// ```
+pub async fn serve<H, F>(
+    listener: TcpListener,
+    mut handler: H,
+    signal: ShutdownSignal,
+) -> io::Result<()>
+where
+    H: FnMut(TcpStream, SocketAddr) -> F,
+    F: Future<Output = ()> + 'static,
+{
+    let tracker = Tracker::default();
+    let region = CancellationRegion::new();
+
+    let result = loop {
+        let accepted = {
+            let mut accept = std::pin::pin!(listener.accept());
+            let mut shutdown = std::pin::pin!(signal.wait());
+            poll_fn(|cx| {
+                if shutdown.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                accept.as_mut().poll(cx).map(Some)
+            })
+            .await
+        };
+        match accepted {
+            None => break Ok(()),
+            Some(Ok((stream, addr))) => {
+                let guard = tracker.enter();
+                let connection = region.enter(handler(stream, addr));
+                crate::spawn(async move {
+                    connection.await;
+                    drop(guard);
+                });
+            }
+            Some(Err(e)) if is_connection_error(&e) => continue,
+            Some(Err(e)) => break Err(e),
+        }
+    };
+    drop(listener);
+
+    if let Some(grace_period) = signal.grace_period() {
+        let mut drained = std::pin::pin!(tracker.drained());
+        let mut timeout = std::pin::pin!(crate::time::sleep(grace_period));
+        poll_fn(|cx| {
+            if drained.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(());
+            }
+            timeout.as_mut().poll(cx)
+        })
+        .await;
+        region.cancel();
+    }
+    tracker.drained().await;
+    result
+}
+
+fn is_connection_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::Interrupted
+    )
+}
@@ -0,0 +1,177 @@
+//! Connecting to the addresses of a host one after the other, or racing them
+//! with Happy Eyeballs.
+
+use std::{
+    error::Error,
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    task::Poll,
+    time::Duration,
+};
+
+use super::{TcpConnectOpts, TcpStream};
+use crate::time::Instant;
+
+/// The error of connecting to a host whose every address failed, with the
+/// error of each of them.
+///
+/// It is the inner error of the [`io::Error`] returned by
+/// [`TcpStream::connect`] when the host has more than one address, which
+/// takes the kind of the last attempt.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::net::{ConnectError, TcpStream};
+///
+/// #[monoio::main]
+/// async fn main() {
+///     if let Err(e) = TcpStream::connect("example.com:80").await {
+///         if let Some(e) = e.get_ref().and_then(|e| e.downcast_ref::<ConnectError>()) {
+///             for (addr, e) in e.errors() {
+///                 println!("{addr}: {e}");
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ConnectError {
+    errors: Vec<(SocketAddr, io::Error)>,
+}
+
+impl ConnectError {
+    /// Returns the address and the error of each attempt, in the order they
+    /// failed.
+    pub fn errors(&self) -> &[(SocketAddr, io::Error)] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to connect to any address")?;
+        for (i, (addr, e)) in self.errors.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{sep}{addr}: {e}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConnectError {}
+
+fn aggregate(mut errors: Vec<(SocketAddr, io::Error)>) -> io::Error {
+    match errors.len() {
+        0 => io::Error::new(io::ErrorKind::Other, "empty address"),
+        1 => errors.pop().unwrap().1,
+        _ => io::Error::new(errors.last().unwrap().1.kind(), ConnectError { errors }),
+    }
+}
+
+/// Tries `addrs` in order, until one of them connects.
+pub(super) async fn connect_each(
+    addrs: Vec<SocketAddr>,
+    opts: &TcpConnectOpts,
+) -> io::Result<TcpStream> {
+    let mut errors = Vec::new();
+    for addr in addrs {
+        match TcpStream::connect_addr_inner(addr, opts).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => errors.push((addr, e)),
+        }
+    }
+    Err(aggregate(errors))
+}
+
+type Attempt = Pin<Box<dyn Future<Output = io::Result<TcpStream>>>>;
+
+/// Races `addrs` as of RFC 8305: the address families are interleaved, and
+/// an attempt starts every `attempt_delay`, or as soon as the previous one
+/// fails. The first connection wins, the other attempts are canceled.
+pub(super) async fn happy_eyeballs(
+    addrs: Vec<SocketAddr>,
+    attempt_delay: Duration,
+    opts: &TcpConnectOpts,
+) -> io::Result<TcpStream> {
+    let opts = *opts;
+    let mut next = interleave(addrs).into_iter();
+    let mut attempts: Vec<(SocketAddr, Attempt)> = Vec::new();
+    let mut errors = Vec::new();
+    let mut delay = std::pin::pin!(crate::time::sleep(attempt_delay));
+
+    poll_fn(|cx| loop {
+        let mut failed = false;
+        let mut i = 0;
+        while i < attempts.len() {
+            match attempts[i].1.as_mut().poll(cx) {
+                Poll::Ready(Ok(stream)) => return Poll::Ready(Ok(stream)),
+                Poll::Ready(Err(e)) => {
+                    let (addr, _) = attempts.remove(i);
+                    errors.push((addr, e));
+                    failed = true;
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+
+        if next.len() > 0 && (failed || attempts.is_empty() || delay.as_mut().poll(cx).is_ready()) {
+            let addr = next.next().unwrap();
+            attempts.push((
+                addr,
+                Box::pin(async move { TcpStream::connect_addr_inner(addr, &opts).await }),
+            ));
+            delay.as_mut().reset(Instant::now() + attempt_delay);
+            continue;
+        }
+        if attempts.is_empty() {
+            return Poll::Ready(Err(aggregate(std::mem::take(&mut errors))));
+        }
+        return Poll::Pending;
+    })
+    .await
+}
+
+// Alternates the address families, starting with the family of the first
+// address.
+fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else {
+        return addrs;
+    };
+    let first_v6 = first.is_ipv6();
+    let (mut preferred, mut other): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_v6);
+    let mut addrs = Vec::with_capacity(preferred.len() + other.len());
+    preferred.reverse();
+    other.reverse();
+    loop {
+        match (preferred.pop(), other.pop()) {
+            (None, None) => return addrs,
+            (a, b) => addrs.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interleave_families() {
+        let v4 = |n| SocketAddr::from(([127, 0, 0, n], 80));
+        let v6 = |n| SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, n], 80));
+        assert_eq!(
+            interleave(vec![v6(1), v6(2), v6(3), v4(1)]),
+            [v6(1), v4(1), v6(2), v6(3)]
+        );
+        assert_eq!(
+            interleave(vec![v4(1), v4(2), v6(1), v6(2)]),
+            [v4(1), v6(1), v4(2), v6(2)]
+        );
+        assert!(interleave(Vec::new()).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
     io,
+    mem::MaybeUninit,
     net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs},
 };
 
@@ -18,8 +19,13 @@ use {
 };
 
 use super::stream::TcpStream;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::op::AcceptMulti;
 use crate::{
-    driver::{op::Op, shared_fd::SharedFd},
+    driver::{
+        op::{AcceptAddr, MaybeFd, Op, OpBox},
+        shared_fd::SharedFd,
+    },
     io::{stream::Stream, CancelHandle},
     net::ListenerOpts,
 };
@@ -29,6 +35,7 @@ pub struct TcpListener {
     fd: SharedFd,
     sys_listener: Option<std::net::TcpListener>,
     meta: UnsafeCell<ListenerMeta>,
+    addr_buf: Cell<Option<AcceptAddrBuf>>,
 }
 
 impl TcpListener {
@@ -42,6 +49,7 @@ impl TcpListener {
             fd,
             sys_listener: Some(sys_listener),
             meta: UnsafeCell::new(ListenerMeta::default()),
+            addr_buf: Cell::new(None),
         }
     }
 
@@ -51,7 +59,70 @@ impl TcpListener {
             .to_socket_addrs()?
             .next()
             .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "empty address"))?;
+        Self::bind_addr(addr, opts)
+    }
+
+    /// Bind a listener to each of the addresses with the same config.
+    ///
+    /// Unlike [`bind_with_config`](TcpListener::bind_with_config), which only
+    /// uses the first one, every address `addrs` resolves to gets a listener,
+    /// e.g. `"localhost:8080"` may give one for `127.0.0.1` and one for `::1`.
+    /// Listeners for IPv6 addresses are IPv6 only unless
+    /// [`only_v6`](ListenerOpts::only_v6) says otherwise, so `[::]` and
+    /// `0.0.0.0` can be bound to the same port. If any of them fails, the
+    /// error is returned and the ones already bound are closed.
+    pub fn bind_all_with_config<A: ToSocketAddrs>(
+        addrs: A,
+        opts: &ListenerOpts,
+    ) -> io::Result<Vec<Self>> {
+        let opts = ListenerOpts {
+            only_v6: opts.only_v6.or(Some(true)),
+            ..*opts
+        };
+        let listeners = addrs
+            .to_socket_addrs()?
+            .map(|addr| Self::bind_addr(addr, &opts))
+            .collect::<io::Result<Vec<_>>>()?;
+        if listeners.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::Other, "empty address"));
+        }
+        Ok(listeners)
+    }
+
+    /// Bind a listener to each of the addresses.
+    ///
+    /// See [`bind_all_with_config`](TcpListener::bind_all_with_config).
+    pub fn bind_all<A: ToSocketAddrs>(addrs: A) -> io::Result<Vec<Self>> {
+        Self::bind_all_with_config(addrs, &ListenerOpts::new())
+    }
+
+    /// Bind to `port` on all interfaces, accepting both IPv4 and IPv6
+    /// connections with one listener.
+    ///
+    /// This binds to `[::]` with IPV6_V6ONLY explicitly disabled, as the OS
+    /// defaults differ. If IPv6 is not available on the host, it falls back
+    /// to `0.0.0.0`.
+    pub fn dual_stack(port: u16) -> io::Result<Self> {
+        Self::dual_stack_with_config(port, &ListenerOpts::new())
+    }
+
+    /// Bind to `port` on all interfaces for both IPv4 and IPv6 with config.
+    ///
+    /// See [`dual_stack`](TcpListener::dual_stack).
+    pub fn dual_stack_with_config(port: u16, opts: &ListenerOpts) -> io::Result<Self> {
+        let opts = ListenerOpts {
+            only_v6: Some(false),
+            ..*opts
+        };
+        match Self::bind_addr((Ipv6Addr::UNSPECIFIED, port).into(), &opts) {
+            Err(e) if crate::net::is_ipv6_unavailable(&e) => {
+                Self::bind_addr((Ipv4Addr::UNSPECIFIED, port).into(), &opts)
+            }
+            res => res,
+        }
+    }
 
+    fn bind_addr(addr: SocketAddr, opts: &ListenerOpts) -> io::Result<Self> {
         let domain = if addr.is_ipv6() {
             socket2::Domain::IPV6
         } else {
@@ -63,6 +134,9 @@ impl TcpListener {
         #[cfg(feature = "legacy")]
         Self::set_non_blocking(&sys_listener)?;
 
+        if let (Some(only_v6), true) = (opts.only_v6, addr.is_ipv6()) {
+            sys_listener.set_only_v6(only_v6)?;
+        }
         let addr = socket2::SockAddr::from(addr);
         #[cfg(unix)]
         if opts.reuse_port {
@@ -107,54 +181,14 @@ impl TcpListener {
     }
 
     /// Accept
+    ///
+    /// The peer address is the one the kernel wrote with the connection, it
+    /// is not queried again.
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
-        let op = Op::accept(&self.fd)?;
-
-        // Await the completion of the event
-        let completion = op.await;
-
-        // Convert fd
-        let fd = completion.meta.result?;
-
-        // Construct stream
-        let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd.into_inner() as _)?);
-
-        // Construct SocketAddr
-        let storage = completion.data.addr.0.as_ptr();
-        let addr = unsafe {
-            match (*storage).ss_family as _ {
-                AF_INET => {
-                    // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
-                    let addr: &sockaddr_in = &*(storage as *const sockaddr_in);
-                    #[cfg(unix)]
-                    let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
-                    #[cfg(windows)]
-                    let ip = Ipv4Addr::from(addr.sin_addr.S_un.S_addr.to_ne_bytes());
-                    let port = u16::from_be(addr.sin_port);
-                    SocketAddr::V4(SocketAddrV4::new(ip, port))
-                }
-                AF_INET6 => {
-                    // Safety: if the ss_family field is AF_INET6 then storage must be a
-                    // sockaddr_in6.
-                    let addr: &sockaddr_in6 = &*(storage as *const sockaddr_in6);
-                    #[cfg(unix)]
-                    let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
-                    #[cfg(windows)]
-                    let ip = Ipv6Addr::from(addr.sin6_addr.u.Byte);
-                    let port = u16::from_be(addr.sin6_port);
-                    #[cfg(unix)]
-                    let scope_id = addr.sin6_scope_id;
-                    #[cfg(windows)]
-                    let scope_id = addr.Anonymous.sin6_scope_id;
-                    SocketAddr::V6(SocketAddrV6::new(ip, port, addr.sin6_flowinfo, scope_id))
-                }
-                _ => {
-                    return Err(io::ErrorKind::InvalidInput.into());
-                }
-            }
-        };
-
-        Ok((stream, addr))
+        let addr = self.addr_buf.take().unwrap_or_default();
+        let (res, addr) = self.accept_inner(addr, None).await;
+        self.addr_buf.set(Some(addr));
+        res
     }
 
     /// Cancelable accept
@@ -164,54 +198,112 @@ impl TcpListener {
         if c.canceled() {
             return Err(operation_canceled());
         }
-        let op = Op::accept(&self.fd)?;
-        let _guard = c.associate_op(op.op_canceller());
+        let addr = self.addr_buf.take().unwrap_or_default();
+        let (res, addr) = self.accept_inner(addr, Some(c)).await;
+        self.addr_buf.set(Some(addr));
+        res
+    }
+
+    /// Accept a connection, using `addr_buf` to receive the peer address.
+    ///
+    /// [`accept`](TcpListener::accept) reuses a single buffer per listener,
+    /// this allows concurrent accepts on the same listener to avoid
+    /// allocating one for each call as well. The buffer is returned for the
+    /// next call.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::net::{AcceptAddrBuf, TcpListener};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let listener = TcpListener::bind("127.0.0.1:8080")?;
+    ///     let mut addr_buf = AcceptAddrBuf::new();
+    ///     loop {
+    ///         let (res, buf) = listener.accept_with_addr_buf(addr_buf).await;
+    ///         addr_buf = buf;
+    ///         let (stream, addr) = res?;
+    ///         println!("accepted {addr}");
+    ///         drop(stream);
+    ///     }
+    /// }
+    /// ```
+    pub async fn accept_with_addr_buf(
+        &self,
+        addr_buf: AcceptAddrBuf,
+    ) -> (io::Result<(TcpStream, SocketAddr)>, AcceptAddrBuf) {
+        self.accept_inner(addr_buf, None).await
+    }
+
+    /// Accept a connection and close it at once, returning only the peer
+    /// address.
+    ///
+    /// This skips registering the connection with the driver and
+    /// constructing a [`TcpStream`], for callers such as connection-rate
+    /// limiters which only look at who connected.
+    pub async fn accept_addronly(&self) -> io::Result<SocketAddr> {
+        let addr = self.addr_buf.take().unwrap_or_default();
+        let (res, addr) = self.accept_raw(addr, None).await;
+        self.addr_buf.set(Some(addr));
+        // Dropping the fd closes the connection.
+        res.map(|(_fd, addr)| addr)
+    }
+
+    async fn accept_inner(
+        &self,
+        addr_buf: AcceptAddrBuf,
+        c: Option<CancelHandle>,
+    ) -> (io::Result<(TcpStream, SocketAddr)>, AcceptAddrBuf) {
+        let (res, addr_buf) = self.accept_raw(addr_buf, c).await;
+        let res = res.and_then(|(fd, addr)| {
+            // Construct stream
+            let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd.into_inner() as _)?);
+            Ok((stream, addr))
+        });
+        (res, addr_buf)
+    }
+
+    // The peer address is the one written by the kernel with the accepted
+    // connection, on every driver.
+    async fn accept_raw(
+        &self,
+        addr_buf: AcceptAddrBuf,
+        c: Option<CancelHandle>,
+    ) -> (io::Result<(MaybeFd, SocketAddr)>, AcceptAddrBuf) {
+        let op = match Op::accept_with_addr(&self.fd, addr_buf.inner) {
+            Ok(op) => op,
+            Err((e, inner)) => return (Err(e), AcceptAddrBuf { inner }),
+        };
+        let _guard = c.map(|c| c.associate_op(op.op_canceller()));
 
         // Await the completion of the event
         let completion = op.await;
-
-        // Convert fd
-        let fd = completion.meta.result?;
-
-        // Construct stream
-        let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd.into_inner() as _)?);
-
-        // Construct SocketAddr
-        let storage = completion.data.addr.0.as_ptr();
-        let addr = unsafe {
-            match (*storage).ss_family as _ {
-                AF_INET => {
-                    // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
-                    let addr: &sockaddr_in = &*(storage as *const sockaddr_in);
-                    #[cfg(unix)]
-                    let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
-                    #[cfg(windows)]
-                    let ip = Ipv4Addr::from(addr.sin_addr.S_un.S_addr.to_ne_bytes());
-                    let port = u16::from_be(addr.sin_port);
-                    SocketAddr::V4(SocketAddrV4::new(ip, port))
-                }
-                AF_INET6 => {
-                    // Safety: if the ss_family field is AF_INET6 then storage must be a
-                    // sockaddr_in6.
-                    let addr: &sockaddr_in6 = &*(storage as *const sockaddr_in6);
-                    #[cfg(unix)]
-                    let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
-                    #[cfg(windows)]
-                    let ip = Ipv6Addr::from(addr.sin6_addr.u.Byte);
-                    let port = u16::from_be(addr.sin6_port);
-                    #[cfg(unix)]
-                    let scope_id = addr.sin6_scope_id;
-                    #[cfg(windows)]
-                    let scope_id = addr.Anonymous.sin6_scope_id;
-                    SocketAddr::V6(SocketAddrV6::new(ip, port, addr.sin6_flowinfo, scope_id))
-                }
-                _ => {
-                    return Err(io::ErrorKind::InvalidInput.into());
-                }
-            }
+        let addr_buf = AcceptAddrBuf {
+            inner: completion.data.addr,
         };
 
-        Ok((stream, addr))
+        let res = completion.meta.result.and_then(|fd| {
+            // Construct SocketAddr
+            let addr = unsafe { to_socket_addr(&addr_buf.inner) }?;
+            Ok((fd, addr))
+        });
+        (res, addr_buf)
+    }
+
+    /// Closes the listener, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        let listener = this.sys_listener.take().unwrap();
+        #[cfg(unix)]
+        let _ = listener.into_raw_fd();
+        #[cfg(windows)]
+        let _ = listener.into_raw_socket();
+        // Safety: the fields are moved out of a listener which is never
+        // dropped, nor used again.
+        let (fd, _addr_buf) = unsafe { (std::ptr::read(&this.fd), std::ptr::read(&this.addr_buf)) };
+        fd.close().await
     }
 
     /// Returns the local address that this listener is bound to.
@@ -273,6 +365,110 @@ impl TcpListener {
     }
 }
 
+/// A stream of the connections accepted by a [`TcpListener`], see
+/// [`accept_multishot`](TcpListener::accept_multishot).
+pub struct AcceptMultishot<'a> {
+    listener: &'a TcpListener,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    op: Option<Op<AcceptMulti>>,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    multishot: bool,
+}
+
+impl TcpListener {
+    /// Returns a stream of the accepted connections.
+    ///
+    /// On io_uring this submits one multishot accept, which posts a
+    /// completion for every connection instead of taking one submission per
+    /// accept, and is re-armed if the kernel stops it. The peer address is
+    /// queried with `getpeername(2)`, as multishot accept does not return
+    /// it. Kernels older than 5.19, and the legacy driver, accept in a loop
+    /// instead.
+    ///
+    /// Connections accepted while the stream is not polled are queued.
+    /// Dropping the stream cancels the accept, closing the connections not
+    /// taken from it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{io::stream::Stream, net::TcpListener};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let listener = TcpListener::bind("127.0.0.1:8080")?;
+    ///     let mut incoming = listener.accept_multishot();
+    ///     while let Some(accepted) = incoming.next().await {
+    ///         let (stream, addr) = accepted?;
+    ///         println!("accepted {addr}");
+    ///         drop(stream);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn accept_multishot(&self) -> AcceptMultishot<'_> {
+        AcceptMultishot {
+            listener: self,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            op: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            multishot: !crate::driver::op::is_legacy(),
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl AcceptMultishot<'_> {
+    // Returns `None` if multishot accept is not supported.
+    async fn next_multishot(&mut self) -> Option<io::Result<(TcpStream, SocketAddr)>> {
+        let op = match &mut self.op {
+            Some(op) => op,
+            None => match Op::accept_multi(&self.listener.fd) {
+                Ok(op) => self.op.insert(op),
+                Err(e) => return Some(Err(e)),
+            },
+        };
+        let meta = std::future::poll_fn(|cx| op.poll_multishot(cx)).await?;
+        if !io_uring::cqueue::more(meta.flags) {
+            // Re-armed on the next call.
+            self.op = None;
+        }
+        match meta.result {
+            Ok(fd) => Some((|| {
+                let stream =
+                    TcpStream::from_shared_fd(SharedFd::new::<false>(fd.into_inner() as _)?);
+                let addr = stream.peer_addr()?;
+                Ok((stream, addr))
+            })()),
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl Stream for AcceptMultishot<'_> {
+    type Item = io::Result<(TcpStream, SocketAddr)>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if self.multishot {
+            match self.next_multishot().await {
+                Some(res) => return Some(res),
+                None => self.multishot = false,
+            }
+        }
+        Some(self.listener.accept().await)
+    }
+}
+
+impl std::fmt::Debug for AcceptMultishot<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcceptMultishot")
+            .field("listener", self.listener)
+            .finish()
+    }
+}
+
 impl Stream for TcpListener {
     type Item = io::Result<(TcpStream, SocketAddr)>;
 
@@ -315,6 +511,75 @@ impl Drop for TcpListener {
     }
 }
 
+/// Reusable storage for the peer address of accepted connections, see
+/// [`TcpListener::accept_with_addr_buf`].
+pub struct AcceptAddrBuf {
+    inner: OpBox<AcceptAddr>,
+}
+
+impl AcceptAddrBuf {
+    /// Allocates a new address buffer.
+    pub fn new() -> Self {
+        Self {
+            inner: OpBox::new((MaybeUninit::zeroed(), 0)),
+        }
+    }
+}
+
+impl Default for AcceptAddrBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for AcceptAddrBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcceptAddrBuf").finish_non_exhaustive()
+    }
+}
+
+/// Converts the IPv4 or IPv6 peer address filled by the kernel.
+///
+/// # Safety
+///
+/// `addr` must have been written by a successful accept.
+unsafe fn to_socket_addr(addr: &AcceptAddr) -> io::Result<SocketAddr> {
+    let storage = addr.0.as_ptr();
+    match (*storage).ss_family as _ {
+        AF_INET => {
+            // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
+            let addr: &sockaddr_in = &*(storage as *const sockaddr_in);
+            #[cfg(unix)]
+            let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
+            #[cfg(windows)]
+            let ip = Ipv4Addr::from(addr.sin_addr.S_un.S_addr.to_ne_bytes());
+            let port = u16::from_be(addr.sin_port);
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        AF_INET6 => {
+            // Safety: if the ss_family field is AF_INET6 then storage must be a
+            // sockaddr_in6.
+            let addr: &sockaddr_in6 = &*(storage as *const sockaddr_in6);
+            #[cfg(unix)]
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            #[cfg(windows)]
+            let ip = Ipv6Addr::from(addr.sin6_addr.u.Byte);
+            let port = u16::from_be(addr.sin6_port);
+            #[cfg(unix)]
+            let scope_id = addr.sin6_scope_id;
+            #[cfg(windows)]
+            let scope_id = addr.Anonymous.sin6_scope_id;
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                scope_id,
+            )))
+        }
+        _ => Err(io::ErrorKind::InvalidInput.into()),
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct ListenerMeta {
     local_addr: Option<SocketAddr>,
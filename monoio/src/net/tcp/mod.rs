@@ -1,12 +1,14 @@
 #![allow(unreachable_pub)]
 //! TCP related.
 
+mod connect;
 mod listener;
 mod split;
 mod stream;
 mod tfo;
 
-pub use listener::TcpListener;
+pub use connect::ConnectError;
+pub use listener::{AcceptAddrBuf, AcceptMultishot, TcpListener};
 pub use split::{TcpOwnedReadHalf, TcpOwnedWriteHalf};
 pub use stream::{TcpConnectOpts, TcpStream};
 
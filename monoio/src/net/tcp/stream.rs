@@ -1,13 +1,17 @@
 use std::{
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
     future::Future,
     io,
-    net::{SocketAddr, ToSocketAddrs},
+    net::SocketAddr,
+    task::{Context, Poll},
     time::Duration,
 };
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::{buf::BufRing, net::RecvMultishot};
 #[cfg(unix)]
 use {
+    crate::buf::FixedBuf,
     libc::{shutdown, AF_INET, AF_INET6, SHUT_WR, SOCK_STREAM},
     std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
 };
@@ -24,9 +28,10 @@ use crate::{
     driver::{op::Op, shared_fd::SharedFd},
     io::{
         as_fd::{AsReadFd, AsWriteFd, SharedFdWrapper},
-        operation_canceled, AsyncReadRent, AsyncWriteRent, CancelHandle, CancelableAsyncReadRent,
-        CancelableAsyncWriteRent, Split,
+        operation_canceled, timeout_op, AsyncReadRent, AsyncWriteRent, CancelHandle,
+        CancelableAsyncReadRent, CancelableAsyncWriteRent, PreparedOp, Split,
     },
+    net::{lookup_host, poll_ready::PollReady, ToSocketAddrs},
     BufResult,
 };
 
@@ -36,6 +41,10 @@ use crate::{
 pub struct TcpConnectOpts {
     /// TCP fast open.
     pub tcp_fast_open: bool,
+    /// The delay between the attempts of Happy Eyeballs, if enabled.
+    pub happy_eyeballs: Option<Duration>,
+    /// The time a connect may take, if limited.
+    pub timeout: Option<Duration>,
 }
 
 impl Default for TcpConnectOpts {
@@ -51,6 +60,8 @@ impl TcpConnectOpts {
     pub const fn new() -> Self {
         Self {
             tcp_fast_open: false,
+            happy_eyeballs: None,
+            timeout: None,
         }
     }
 
@@ -65,11 +76,41 @@ impl TcpConnectOpts {
         self.tcp_fast_open = fast_open;
         self
     }
+
+    /// Race the addresses of a host with Happy Eyeballs (RFC 8305) when
+    /// connecting to it, instead of trying them one after the other.
+    ///
+    /// The address families are interleaved, and an attempt starts every
+    /// `attempt_delay` until one of them connects, or as soon as the previous
+    /// one fails. The delay recommended by the RFC is 250 milliseconds.
+    ///
+    /// This needs the timer to be enabled.
+    #[must_use]
+    #[inline]
+    pub fn happy_eyeballs(mut self, attempt_delay: Duration) -> Self {
+        self.happy_eyeballs = Some(attempt_delay);
+        self
+    }
+
+    /// Fail connecting with a `TimedOut` error if it takes longer than
+    /// `timeout`. When connecting to a host, it covers resolving its name and
+    /// trying all of its addresses.
+    ///
+    /// This needs the timer to be enabled.
+    #[must_use]
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 }
 /// TcpStream
 pub struct TcpStream {
     pub(super) fd: SharedFd,
     meta: StreamMeta,
+    read_timeout: Cell<Option<Duration>>,
+    write_timeout: Cell<Option<Duration>>,
+    ready: PollReady,
 }
 
 /// TcpStream is safe to split to two parts
@@ -85,28 +126,49 @@ impl TcpStream {
         // enable SOCK_ZEROCOPY
         meta.set_zero_copy();
 
-        Self { fd, meta }
+        Self {
+            fd,
+            meta,
+            read_timeout: Cell::new(None),
+            write_timeout: Cell::new(None),
+            ready: PollReady::default(),
+        }
     }
 
     /// Open a TCP connection to a remote host.
-    /// Note: This function may block the current thread while resolution is
-    /// performed.
-    // TODO(chihai): Fix it, maybe spawn_blocking like tokio.
+    ///
+    /// Host names are resolved with the [`Resolver`](crate::net::Resolver)
+    /// of the current thread, see [`lookup_host`]. Their addresses are tried
+    /// one after the other until one of them connects. If all of them fail,
+    /// the error of each one is kept in a [`ConnectError`].
+    ///
+    /// [`ConnectError`]: crate::net::ConnectError
     pub async fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
-        // TODO(chihai): loop for all addrs
-        let addr = addr
-            .to_socket_addrs()?
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "empty address"))?;
+        Self::connect_with_config(addr, &TcpConnectOpts::new()).await
+    }
 
-        Self::connect_addr(addr).await
+    /// Open a TCP connection to a remote host with given config, see
+    /// [`connect`](Self::connect) and
+    /// [`TcpConnectOpts::happy_eyeballs`].
+    pub async fn connect_with_config<A: ToSocketAddrs>(
+        addr: A,
+        opts: &TcpConnectOpts,
+    ) -> io::Result<Self> {
+        with_connect_timeout(opts, async {
+            let addrs = lookup_host(addr).await?;
+            match opts.happy_eyeballs {
+                Some(attempt_delay) if addrs.len() > 1 => {
+                    super::connect::happy_eyeballs(addrs, attempt_delay, opts).await
+                }
+                _ => super::connect::connect_each(addrs, opts).await,
+            }
+        })
+        .await
     }
 
     /// Establish a connection to the specified `addr`.
     pub async fn connect_addr(addr: SocketAddr) -> io::Result<Self> {
-        const DEFAULT_OPTS: TcpConnectOpts = TcpConnectOpts {
-            tcp_fast_open: false,
-        };
+        const DEFAULT_OPTS: TcpConnectOpts = TcpConnectOpts::new();
         Self::connect_addr_with_config(addr, &DEFAULT_OPTS).await
     }
 
@@ -114,6 +176,14 @@ impl TcpStream {
     pub async fn connect_addr_with_config(
         addr: SocketAddr,
         opts: &TcpConnectOpts,
+    ) -> io::Result<Self> {
+        with_connect_timeout(opts, Self::connect_addr_inner(addr, opts)).await
+    }
+
+    // Connects to `addr`, ignoring the timeout of `opts`.
+    pub(super) async fn connect_addr_inner(
+        addr: SocketAddr,
+        opts: &TcpConnectOpts,
     ) -> io::Result<Self> {
         let domain = match addr {
             SocketAddr::V4(_) => AF_INET,
@@ -177,6 +247,43 @@ impl TcpStream {
         Ok(stream)
     }
 
+    /// Closes the stream.
+    ///
+    /// It completes once the socket is closed, with an asynchronous operation
+    /// on io_uring. A stream dropped without `close` is closed in the
+    /// background instead, with no guarantee as to **when**, and is counted
+    /// by [`closed_on_drop`](crate::utils::closed_on_drop).
+    ///
+    /// If operations on the stream are still in flight, e.g. on the halves
+    /// of a split stream, it is closed once they complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the close operation. The socket is closed either
+    /// way.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{io::AsyncWriteRentExt, net::TcpStream};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///     stream.write_all(b"bye").await.0?;
+    ///     stream.close().await
+    /// }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let TcpStream {
+            fd, meta, ready, ..
+        } = self;
+        // Releases the socket of the metadata without closing it.
+        drop(meta);
+        drop(ready);
+        fd.close().await
+    }
+
     /// Return the local address that this stream is bound to.
     #[inline]
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -201,6 +308,45 @@ impl TcpStream {
         self.meta.set_no_delay(nodelay)
     }
 
+    /// Set the timeout of each read, `None` for no timeout.
+    ///
+    /// A read taking longer is canceled and fails with a `TimedOut` error,
+    /// giving the buffer back. Unlike `SO_RCVTIMEO`, it also applies to the
+    /// reads submitted to io_uring. This needs the timer to be enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timeout` is zero.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) {
+        assert_ne!(timeout, Some(Duration::ZERO), "`timeout` must be non-zero.");
+        self.read_timeout.set(timeout);
+    }
+
+    /// Returns the timeout of each read, see
+    /// [`set_read_timeout`](Self::set_read_timeout).
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout.get()
+    }
+
+    /// Set the timeout of each write, `None` for no timeout.
+    ///
+    /// A write taking longer is canceled and fails with a `TimedOut` error,
+    /// giving the buffer back. This needs the timer to be enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timeout` is zero.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) {
+        assert_ne!(timeout, Some(Duration::ZERO), "`timeout` must be non-zero.");
+        self.write_timeout.set(timeout);
+    }
+
+    /// Returns the timeout of each write, see
+    /// [`set_write_timeout`](Self::set_write_timeout).
+    pub fn write_timeout(&self) -> Option<Duration> {
+        self.write_timeout.get()
+    }
+
     /// Set the value of the `SO_KEEPALIVE` option on this socket.
     #[inline]
     pub fn set_tcp_keepalive(
@@ -230,6 +376,46 @@ impl TcpStream {
         }
     }
 
+    /// Prepares a recv into `buf` which can be submitted repeatedly, see
+    /// [`PreparedOp`].
+    pub fn prepare_recv<T: IoBufMut>(&self, buf: T) -> PreparedOp<T> {
+        PreparedOp::recv(self.fd.clone(), buf)
+    }
+
+    /// Read some bytes into the fixed buffer `buf`, with the `READ_FIXED`
+    /// opcode on io_uring, which skips mapping the buffer into the kernel.
+    #[cfg(unix)]
+    pub async fn read_fixed(&mut self, buf: FixedBuf) -> BufResult<usize, FixedBuf> {
+        let op = Op::read_fixed(self.fd.clone(), buf).unwrap();
+        op.result().await
+    }
+
+    /// Write the fixed buffer `buf`, with the `WRITE_FIXED` opcode on
+    /// io_uring, which skips mapping the buffer into the kernel.
+    #[cfg(unix)]
+    pub async fn write_fixed(&mut self, buf: FixedBuf) -> BufResult<usize, FixedBuf> {
+        let op = Op::write_fixed(self.fd.clone(), buf).unwrap();
+        op.result().await
+    }
+
+    /// Returns a stream of the data received on the connection, each
+    /// chunk in a buffer of `ring`.
+    ///
+    /// This submits one multishot recv (`IORING_RECV_MULTISHOT`), which posts
+    /// a completion every time data arrives instead of taking one submission
+    /// per read. It is re-armed if the kernel stops it, e.g. with an
+    /// `ENOBUFS` error, returned by the stream, when all the buffers of the
+    /// ring are in use. Kernels older than 6.0 receive once per buffer
+    /// instead.
+    ///
+    /// The stream ends when the peer shuts down its side of the connection.
+    /// Dropping it cancels the recv and gives the buffers of the data not
+    /// taken from it back to the ring.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub fn recv_multishot(&mut self, ring: &BufRing) -> RecvMultishot<'_> {
+        RecvMultishot::new(&self.fd, ring, false)
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -245,6 +431,19 @@ impl TcpStream {
         op.wait().await
     }
 
+    /// Wait for the peer to close the connection, without reading from it.
+    ///
+    /// This resolves once the peer has shut down its writing half, or the
+    /// connection failed, e.g. with a reset. Data sent by the peer before
+    /// closing is left for the following reads. A proxy can wait on it to
+    /// tear down the paired connection as soon as one side goes away.
+    ///
+    /// It uses `POLLRDHUP` on io_uring and `EPOLLRDHUP` on the legacy driver.
+    pub async fn closed(&self) -> io::Result<()> {
+        let op = Op::poll_closed(&self.fd)?;
+        op.wait().await
+    }
+
     /// Wait for write readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -259,6 +458,35 @@ impl TcpStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Poll for read readiness, for futures implemented by hand, which can not
+    /// await [`readable`](Self::readable).
+    ///
+    /// It waits like `readable(false)`. The pending wait is kept in the
+    /// stream and polled again by the next call, which wakes the waker of the
+    /// last `cx` only, so a single task should poll it at a time.
+    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_read_ready(&self.fd, cx)
+    }
+
+    /// Poll for write readiness, for futures implemented by hand, which can
+    /// not await [`writable`](Self::writable). See
+    /// [`poll_read_ready`](Self::poll_read_ready).
+    pub fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_write_ready(&self.fd, cx)
+    }
+}
+
+// Fails with a `TimedOut` error if `connect` takes longer than the timeout
+// of `opts`.
+async fn with_connect_timeout<F>(opts: &TcpConnectOpts, connect: F) -> io::Result<TcpStream>
+where
+    F: Future<Output = io::Result<TcpStream>>,
+{
+    match opts.timeout {
+        Some(timeout) => crate::time::timeout(timeout, connect).await?,
+        None => connect.await,
+    }
 }
 
 impl AsReadFd for TcpStream {
@@ -319,15 +547,16 @@ impl std::fmt::Debug for TcpStream {
 impl AsyncWriteRent for TcpStream {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
+        crate::buf::lint_zero_len_write(&buf);
         // Submit the write operation
         let op = Op::send(self.fd.clone(), buf).unwrap();
-        op.result()
+        timeout_op(op.op_canceller(), self.write_timeout.get(), op.result())
     }
 
     #[inline]
     fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> impl Future<Output = BufResult<usize, T>> {
         let op = Op::writev(self.fd.clone(), buf_vec).unwrap();
-        op.result()
+        timeout_op(op.op_canceller(), self.write_timeout.get(), op.result())
     }
 
     #[inline]
@@ -364,9 +593,7 @@ impl CancelableAsyncWriteRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::send(fd, buf).unwrap(), |op| op.result()).await
     }
 
     #[inline]
@@ -381,9 +608,11 @@ impl CancelableAsyncWriteRent for TcpStream {
             return (Err(operation_canceled()), buf_vec);
         }
 
-        let op = Op::writev(fd.clone(), buf_vec).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(
+            || Op::writev(fd.clone(), buf_vec).unwrap(),
+            |op| op.result(),
+        )
+        .await
     }
 
     #[inline]
@@ -409,17 +638,26 @@ impl CancelableAsyncWriteRent for TcpStream {
 
 impl AsyncReadRent for TcpStream {
     #[inline]
-    fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::recv(self.fd.clone(), buf).unwrap();
-        op.result()
+    fn read<T: IoBufMut>(&mut self, mut buf: T) -> impl Future<Output = BufResult<usize, T>> {
+        // Submit the read operation, a zero-length one completes at once
+        let op = match crate::buf::is_zero_len_read(&mut buf) {
+            false => Ok(Op::recv(self.fd.clone(), buf).unwrap()),
+            true => Err(buf),
+        };
+        let timeout = self.read_timeout.get();
+        async move {
+            match op {
+                Ok(op) => timeout_op(op.op_canceller(), timeout, op.result()).await,
+                Err(buf) => (Ok(0), buf),
+            }
+        }
     }
 
     #[inline]
     fn readv<T: IoVecBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
         // Submit the read operation
         let op = Op::readv(self.fd.clone(), buf).unwrap();
-        op.result()
+        timeout_op(op.op_canceller(), self.read_timeout.get(), op.result())
     }
 }
 
@@ -436,9 +674,7 @@ impl CancelableAsyncReadRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::recv(fd, buf).unwrap(), |op| op.result()).await
     }
 
     #[inline]
@@ -453,9 +689,8 @@ impl CancelableAsyncReadRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::readv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::readv(fd, buf).unwrap(), |op| op.result())
+            .await
     }
 }
 
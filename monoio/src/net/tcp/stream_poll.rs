@@ -34,6 +34,7 @@ impl crate::io::IntoPollIo for TcpStream {
 impl TcpStream {
     /// Convert to poll-io style TcpStreamPoll
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn try_into_poll_io(mut self) -> Result<TcpStreamPoll, (io::Error, TcpStream)> {
         match self.fd.cvt_poll() {
             Ok(_) => Ok(TcpStreamPoll(self)),
@@ -54,6 +55,7 @@ impl crate::io::IntoCompIo for TcpStreamPoll {
 impl TcpStreamPoll {
     /// Convert to normal TcpStream
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub fn try_into_comp_io(mut self) -> Result<TcpStream, (io::Error, TcpStreamPoll)> {
         match self.0.fd.cvt_comp() {
             Ok(_) => Ok(self.0),
@@ -6,15 +6,24 @@ use std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd};
 use std::os::windows::prelude::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
 use std::{
     io,
-    net::{SocketAddr, ToSocketAddrs},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
+    task::{Context, Poll},
 };
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::{buf::BufRing, net::RecvMultishot};
 use crate::{
-    buf::{IoBuf, IoBufMut},
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
     driver::{op::Op, shared_fd::SharedFd},
-    io::{operation_canceled, CancelHandle, Split},
+    io::{operation_canceled, sink::Sink, stream::Stream, CancelHandle, Split},
+    net::{poll_ready::PollReady, DatagramBuf},
 };
 
+#[cfg(unix)]
+const MSG_PEEK: i32 = libc::MSG_PEEK;
+#[cfg(windows)]
+const MSG_PEEK: i32 = windows_sys::Win32::Networking::WinSock::MSG_PEEK as _;
+
 /// A UDP socket.
 ///
 /// After creating a `UdpSocket` by [`bind`]ing it to a socket address, data can be
@@ -24,9 +33,9 @@ use crate::{
 /// to set an address where data should be sent and received from. After setting a remote
 /// address with [`connect`], data can be sent to and received from that address with
 /// [`send`] and [`recv`].
-#[derive(Debug)]
 pub struct UdpSocket {
     fd: SharedFd,
+    ready: PollReady,
 }
 
 /// UdpSocket is safe to split to two parts
@@ -34,7 +43,10 @@ unsafe impl Split for UdpSocket {}
 
 impl UdpSocket {
     pub(crate) fn from_shared_fd(fd: SharedFd) -> Self {
-        Self { fd }
+        Self {
+            fd,
+            ready: PollReady::default(),
+        }
     }
 
     #[cfg(feature = "legacy")]
@@ -53,6 +65,41 @@ impl UdpSocket {
             .to_socket_addrs()?
             .next()
             .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "empty address"))?;
+        Self::bind_addr(addr, None)
+    }
+
+    /// Creates a UDP socket for each of the addresses `addrs` resolves to.
+    ///
+    /// Sockets for IPv6 addresses are IPv6 only, so `[::]` and `0.0.0.0` can
+    /// be bound to the same port. If any of them fails, the error is returned
+    /// and the ones already bound are closed.
+    pub fn bind_all<A: ToSocketAddrs>(addrs: A) -> io::Result<Vec<Self>> {
+        let sockets = addrs
+            .to_socket_addrs()?
+            .map(|addr| Self::bind_addr(addr, Some(true)))
+            .collect::<io::Result<Vec<_>>>()?;
+        if sockets.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::Other, "empty address"));
+        }
+        Ok(sockets)
+    }
+
+    /// Creates a UDP socket bound to `port` on all interfaces, receiving both
+    /// IPv4 and IPv6 datagrams.
+    ///
+    /// This binds to `[::]` with IPV6_V6ONLY explicitly disabled, as the OS
+    /// defaults differ. IPv4 peers show up as IPv4-mapped IPv6 addresses. If
+    /// IPv6 is not available on the host, it falls back to `0.0.0.0`.
+    pub fn dual_stack(port: u16) -> io::Result<Self> {
+        match Self::bind_addr((Ipv6Addr::UNSPECIFIED, port).into(), Some(false)) {
+            Err(e) if crate::net::is_ipv6_unavailable(&e) => {
+                Self::bind_addr((Ipv4Addr::UNSPECIFIED, port).into(), None)
+            }
+            res => res,
+        }
+    }
+
+    fn bind_addr(addr: SocketAddr, only_v6: Option<bool>) -> io::Result<Self> {
         let domain = if addr.is_ipv6() {
             socket2::Domain::IPV6
         } else {
@@ -63,6 +110,9 @@ impl UdpSocket {
         #[cfg(feature = "legacy")]
         Self::set_non_blocking(&socket)?;
 
+        if let (Some(only_v6), true) = (only_v6, addr.is_ipv6()) {
+            socket.set_only_v6(only_v6)?;
+        }
         let addr = socket2::SockAddr::from(addr);
         socket.bind(&addr)?;
 
@@ -92,6 +142,36 @@ impl UdpSocket {
         op.wait().await
     }
 
+    /// Receives a single datagram message into the buffers of `buf`, filling
+    /// them in order. On success, returns the number of bytes read and the
+    /// origin.
+    pub async fn recv_from_vectored<T: IoVecBufMut>(
+        &self,
+        buf: T,
+    ) -> crate::BufResult<(usize, SocketAddr), T> {
+        let op = Op::recv_msg_vectored(self.fd.clone(), buf).unwrap();
+        op.wait_vectored().await
+    }
+
+    /// Sends the buffers of `buf` as one datagram to the given address. On
+    /// success, returns the number of bytes written.
+    pub async fn send_to_vectored<T: IoVecBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::send_msg_vectored(self.fd.clone(), buf, Some(socket_addr)).unwrap();
+        op.wait().await
+    }
+
+    /// Closes the socket, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        let UdpSocket { fd, ready } = self;
+        drop(ready);
+        fd.close().await
+    }
+
     /// Returns the socket address of the remote peer this socket was connected to.
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         #[cfg(unix)]
@@ -135,9 +215,12 @@ impl UdpSocket {
     }
 
     /// Sends data on the socket to the remote address to which it is connected.
+    ///
+    /// Unlike [`send_to`](Self::send_to), no address is passed to the kernel,
+    /// which makes this the cheaper one for connected sockets.
     pub async fn send<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
-        let op = Op::send_msg(self.fd.clone(), buf, None).unwrap();
-        op.wait().await
+        let op = Op::send(self.fd.clone(), buf).unwrap();
+        op.result().await
     }
 
     /// Receives a single datagram message on the socket from the remote address to
@@ -147,6 +230,38 @@ impl UdpSocket {
         op.result().await
     }
 
+    /// Receives a single datagram message on the socket, without removing it
+    /// from the queue. On success, returns the number of bytes read and the
+    /// origin.
+    ///
+    /// The next `recv`, `recv_from` or `peek_from` returns the same datagram.
+    pub async fn peek_from<T: IoBufMut>(&self, buf: T) -> crate::BufResult<(usize, SocketAddr), T> {
+        let op = Op::recv_msg_with_flags(self.fd.clone(), buf, MSG_PEEK).unwrap();
+        op.wait().await
+    }
+
+    /// Returns the origin of the next datagram, without removing it from the
+    /// queue.
+    ///
+    /// This waits for a datagram to arrive. The datagram payload is not read,
+    /// so it does not need a buffer.
+    pub async fn peek_sender(&self) -> io::Result<SocketAddr> {
+        let (res, _) = self.peek_from(Vec::new()).await;
+        res.map(|(_, addr)| addr)
+    }
+
+    /// Returns a stream of the datagrams received on the socket, each in a
+    /// buffer of `ring`, see
+    /// [`TcpStream::recv_multishot`](crate::net::TcpStream::recv_multishot).
+    ///
+    /// Datagrams larger than the buffers are truncated. The origin of the
+    /// datagrams is not returned, this is meant for
+    /// [`connect`](UdpSocket::connect)ed sockets.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub fn recv_multishot(&self, ring: &BufRing) -> RecvMultishot<'_> {
+        RecvMultishot::new(&self.fd, ring, true)
+    }
+
     /// Creates new `UdpSocket` from a `std::net::UdpSocket`.
     pub fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
         #[cfg(unix)]
@@ -165,6 +280,98 @@ impl UdpSocket {
         }
     }
 
+    /// Sets the value of the `SO_BROADCAST` option for this socket, which
+    /// allows sending to a broadcast address.
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        self.with_socket(|socket| socket.set_broadcast(broadcast))
+    }
+
+    /// Gets the value of the `SO_BROADCAST` option for this socket.
+    pub fn broadcast(&self) -> io::Result<bool> {
+        self.with_socket(|socket| socket.broadcast())
+    }
+
+    /// Sets the value of the `IP_TTL` option for this socket, the time to
+    /// live of the packets it sends.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.with_socket(|socket| socket.set_ttl(ttl))
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.with_socket(|socket| socket.ttl())
+    }
+
+    /// Joins the IPv4 multicast group `multiaddr` on the interface with the
+    /// address `interface`, [`UNSPECIFIED`](Ipv4Addr::UNSPECIFIED) letting
+    /// the system pick one.
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.with_socket(|socket| socket.join_multicast_v4(&multiaddr, &interface))
+    }
+
+    /// Leaves the IPv4 multicast group `multiaddr`, see
+    /// [`join_multicast_v4`](Self::join_multicast_v4).
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.with_socket(|socket| socket.leave_multicast_v4(&multiaddr, &interface))
+    }
+
+    /// Joins the IPv6 multicast group `multiaddr` on the interface with the
+    /// index `interface`, 0 letting the system pick one.
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.with_socket(|socket| socket.join_multicast_v6(multiaddr, interface))
+    }
+
+    /// Leaves the IPv6 multicast group `multiaddr`, see
+    /// [`join_multicast_v6`](Self::join_multicast_v6).
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.with_socket(|socket| socket.leave_multicast_v6(multiaddr, interface))
+    }
+
+    /// Sets the value of the `IP_MULTICAST_LOOP` option for this socket,
+    /// whether the IPv4 multicast packets it sends are looped back to the
+    /// local sockets.
+    pub fn set_multicast_loop_v4(&self, on: bool) -> io::Result<()> {
+        self.with_socket(|socket| socket.set_multicast_loop_v4(on))
+    }
+
+    /// Gets the value of the `IP_MULTICAST_LOOP` option for this socket.
+    pub fn multicast_loop_v4(&self) -> io::Result<bool> {
+        self.with_socket(|socket| socket.multicast_loop_v4())
+    }
+
+    /// Sets the value of the `IP_MULTICAST_TTL` option for this socket, the
+    /// time to live of the IPv4 multicast packets it sends.
+    pub fn set_multicast_ttl_v4(&self, ttl: u32) -> io::Result<()> {
+        self.with_socket(|socket| socket.set_multicast_ttl_v4(ttl))
+    }
+
+    /// Gets the value of the `IP_MULTICAST_TTL` option for this socket.
+    pub fn multicast_ttl_v4(&self) -> io::Result<u32> {
+        self.with_socket(|socket| socket.multicast_ttl_v4())
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_LOOP` option for this socket,
+    /// whether the IPv6 multicast packets it sends are looped back to the
+    /// local sockets.
+    pub fn set_multicast_loop_v6(&self, on: bool) -> io::Result<()> {
+        self.with_socket(|socket| socket.set_multicast_loop_v6(on))
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_LOOP` option for this socket.
+    pub fn multicast_loop_v6(&self) -> io::Result<bool> {
+        self.with_socket(|socket| socket.multicast_loop_v6())
+    }
+
+    // Runs `f` on a socket2 view of the socket, without taking ownership.
+    fn with_socket<R>(&self, f: impl FnOnce(&socket2::Socket) -> io::Result<R>) -> io::Result<R> {
+        #[cfg(unix)]
+        let socket = unsafe { socket2::Socket::from_raw_fd(self.fd.as_raw_fd()) };
+        #[cfg(windows)]
+        let socket = unsafe { socket2::Socket::from_raw_socket(self.fd.as_raw_socket()) };
+        let socket = std::mem::ManuallyDrop::new(socket);
+        f(&socket)
+    }
+
     /// Set value for the `SO_REUSEADDR` option on this socket.
     #[allow(unused_variables)]
     pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
@@ -232,6 +439,58 @@ impl UdpSocket {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Poll for read readiness, for futures implemented by hand, which can not
+    /// await [`readable`](Self::readable).
+    ///
+    /// It waits like `readable(false)`. The pending wait is kept in the
+    /// socket and polled again by the next call, which wakes the waker of the
+    /// last `cx` only, so a single task should poll it at a time.
+    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_read_ready(&self.fd, cx)
+    }
+
+    /// Poll for write readiness, for futures implemented by hand, which can
+    /// not await [`writable`](Self::writable). See
+    /// [`poll_read_ready`](Self::poll_read_ready).
+    pub fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_write_ready(&self.fd, cx)
+    }
+}
+
+impl std::fmt::Debug for UdpSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UdpSocket").field("fd", &self.fd).finish()
+    }
+}
+
+/// Receives datagrams with their origin into pooled buffers, see
+/// [`DatagramBuf`].
+impl Stream for UdpSocket {
+    type Item = io::Result<(DatagramBuf, SocketAddr)>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        let (res, buf) = self.recv_from(DatagramBuf::take()).await;
+        let buf = DatagramBuf::new(buf);
+        Some(res.map(|(_, addr)| (buf, addr)))
+    }
+}
+
+/// Sends each buffer as a datagram to its address.
+impl<B: IoBuf> Sink<(B, SocketAddr)> for UdpSocket {
+    type Error = io::Error;
+
+    async fn send(&mut self, (buf, addr): (B, SocketAddr)) -> io::Result<()> {
+        self.send_to(buf, addr).await.0.map(|_| ())
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(unix)]
@@ -261,9 +520,11 @@ impl UdpSocket {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv_msg(self.fd.clone(), buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.wait().await
+        c.run(
+            || Op::recv_msg(self.fd.clone(), buf).unwrap(),
+            |op| op.wait(),
+        )
+        .await
     }
 
     /// Sends data on the socket to the given address. On success, returns the
@@ -278,9 +539,11 @@ impl UdpSocket {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send_msg(self.fd.clone(), buf, Some(socket_addr)).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.wait().await
+        c.run(
+            || Op::send_msg(self.fd.clone(), buf, Some(socket_addr)).unwrap(),
+            |op| op.wait(),
+        )
+        .await
     }
 
     /// Sends data on the socket to the remote address to which it is connected.
@@ -293,9 +556,8 @@ impl UdpSocket {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send_msg(self.fd.clone(), buf, None).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.wait().await
+        c.run(|| Op::send(self.fd.clone(), buf).unwrap(), |op| op.result())
+            .await
     }
 
     /// Receives a single datagram message on the socket from the remote address to
@@ -309,8 +571,7 @@ impl UdpSocket {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv(self.fd.clone(), buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::recv(self.fd.clone(), buf).unwrap(), |op| op.result())
+            .await
     }
 }
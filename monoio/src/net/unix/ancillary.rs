@@ -0,0 +1,117 @@
+//! Control messages passing file descriptors over unix sockets.
+
+use std::{
+    io,
+    mem::size_of,
+    os::unix::io::{FromRawFd, OwnedFd, RawFd},
+};
+
+/// The control buffer of a `sendmsg` or `recvmsg`.
+///
+/// It is made of `u64`s, as control messages must be aligned like
+/// `cmsghdr`.
+pub(crate) struct Control {
+    buf: Vec<u64>,
+    len: usize,
+}
+
+impl Control {
+    /// No control message.
+    pub(crate) const fn empty() -> Self {
+        Self {
+            buf: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// An `SCM_RIGHTS` message passing `fds`.
+    pub(crate) fn with_fds(fds: &[RawFd]) -> io::Result<Self> {
+        if fds.is_empty() {
+            return Ok(Self::empty());
+        }
+        let data_len = u32::try_from(std::mem::size_of_val(fds))
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many fds"))?;
+        let mut control = Self::with_space(unsafe { libc::CMSG_SPACE(data_len) } as usize);
+        let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+        control.set(&mut msg);
+        // Safety: the buffer fits one message of `data_len` bytes.
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(data_len) as _;
+            std::ptr::copy_nonoverlapping(
+                fds.as_ptr(),
+                libc::CMSG_DATA(cmsg) as *mut RawFd,
+                fds.len(),
+            );
+        }
+        Ok(control)
+    }
+
+    /// Room to receive up to `max_fds` descriptors.
+    pub(crate) fn for_fds(max_fds: usize) -> io::Result<Self> {
+        if max_fds == 0 {
+            return Ok(Self::empty());
+        }
+        let data_len = max_fds
+            .checked_mul(size_of::<RawFd>())
+            .and_then(|len| u32::try_from(len).ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "too many fds"))?;
+        Ok(Self::with_space(
+            unsafe { libc::CMSG_SPACE(data_len) } as usize
+        ))
+    }
+
+    fn with_space(len: usize) -> Self {
+        Self {
+            buf: vec![0; len.div_ceil(size_of::<u64>())],
+            len,
+        }
+    }
+
+    /// Points the control buffer of `msg` to this one.
+    pub(crate) fn set(&mut self, msg: &mut libc::msghdr) {
+        if self.len == 0 {
+            msg.msg_control = std::ptr::null_mut();
+        } else {
+            msg.msg_control = self.buf.as_mut_ptr() as *mut libc::c_void;
+        }
+        msg.msg_controllen = self.len as _;
+    }
+
+    /// Takes the descriptors received by the `recvmsg` of `msg`.
+    ///
+    /// # Safety
+    ///
+    /// The `recvmsg` must have completed, with `msg` pointing to this
+    /// control buffer.
+    pub(crate) unsafe fn take_fds(&mut self, msg: &libc::msghdr) -> Vec<OwnedFd> {
+        let mut fds = Vec::new();
+        if self.len == 0 {
+            return fds;
+        }
+        let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let data = libc::CMSG_DATA(cmsg);
+                let data_len = (*cmsg).cmsg_len as usize - (data as usize - cmsg as usize);
+                for i in 0..data_len / size_of::<RawFd>() {
+                    let fd = std::ptr::read_unaligned((data as *const RawFd).add(i));
+                    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+                    let _ = crate::syscall!(fcntl@RAW(fd, libc::F_SETFD, libc::FD_CLOEXEC));
+                    fds.push(OwnedFd::from_raw_fd(fd));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+        }
+        fds
+    }
+}
+
+/// The `recvmsg` flags receiving descriptors, which are close-on-exec like
+/// the ones monoio creates.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) const RECV_FDS_FLAGS: libc::c_int = libc::MSG_CMSG_CLOEXEC;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) const RECV_FDS_FLAGS: libc::c_int = 0;
@@ -4,19 +4,21 @@ use std::{
     io,
     os::unix::{
         net::UnixDatagram as StdUnixDatagram,
-        prelude::{AsRawFd, IntoRawFd, RawFd},
+        prelude::{AsRawFd, IntoRawFd, OwnedFd, RawFd},
     },
     path::Path,
 };
 
 use super::{
+    ancillary::Control,
     socket_addr::{local_addr, pair, peer_addr, socket_addr},
     SocketAddr,
 };
 use crate::{
     buf::{IoBuf, IoBufMut},
     driver::{op::Op, shared_fd::SharedFd},
-    net::new_socket,
+    io::{sink::Sink, stream::Stream},
+    net::{new_socket, DatagramBuf},
 };
 
 /// UnixDatagram
@@ -81,6 +83,12 @@ impl UnixDatagram {
         }
     }
 
+    /// Closes the socket, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         local_addr(self.as_raw_fd())
@@ -154,6 +162,46 @@ impl UnixDatagram {
         op.wait().await
     }
 
+    /// Sends data on the socket along with the file descriptors `fds`, as an
+    /// `SCM_RIGHTS` control message, to the given address or to the connected
+    /// peer if `None`. On success, returns the number of bytes written.
+    ///
+    /// The peer receives duplicates of the descriptors with
+    /// [`recv_with_fds`](Self::recv_with_fds), the ones of `fds` stay open.
+    pub async fn send_with_fds<T: IoBuf>(
+        &self,
+        buf: T,
+        fds: &[RawFd],
+        addr: Option<SocketAddr>,
+    ) -> crate::BufResult<usize, T> {
+        let control = match Control::with_fds(fds) {
+            Ok(control) => control,
+            Err(e) => return (Err(e), buf),
+        };
+        let op = Op::send_msg_unix_with_control(self.fd.clone(), buf, addr, control).unwrap();
+        op.wait().await
+    }
+
+    /// Receives a single datagram message on the socket along with up to
+    /// `max_fds` file descriptors sent by
+    /// [`send_with_fds`](Self::send_with_fds). On success, returns the number
+    /// of bytes read, the origin and the descriptors.
+    ///
+    /// The kernel closes the descriptors beyond `max_fds`. The received ones
+    /// are close-on-exec.
+    pub async fn recv_with_fds<T: IoBufMut>(
+        &self,
+        buf: T,
+        max_fds: usize,
+    ) -> crate::BufResult<(usize, SocketAddr, Vec<OwnedFd>), T> {
+        let control = match Control::for_fds(max_fds) {
+            Ok(control) => control,
+            Err(e) => return (Err(e), buf),
+        };
+        let op = Op::recv_msg_unix_with_control(self.fd.clone(), buf, control).unwrap();
+        op.wait_with_fds().await
+    }
+
     /// Receives a single datagram message on the socket from the remote address to
     /// which it is connected. On success, returns the number of bytes read.
     pub async fn recv<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
@@ -162,6 +210,36 @@ impl UnixDatagram {
     }
 }
 
+/// Receives datagrams with their origin into pooled buffers, see
+/// [`DatagramBuf`].
+impl Stream for UnixDatagram {
+    type Item = io::Result<(DatagramBuf, SocketAddr)>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        let (res, buf) = self.recv_from(DatagramBuf::take()).await;
+        let buf = DatagramBuf::new(buf);
+        Some(res.map(|(_, addr)| (buf, addr)))
+    }
+}
+
+/// Sends each buffer as a datagram to its address.
+impl<B: IoBuf> Sink<(B, SocketAddr)> for UnixDatagram {
+    type Error = io::Error;
+
+    async fn send(&mut self, (buf, addr): (B, SocketAddr)) -> io::Result<()> {
+        let op = Op::send_msg_unix(self.fd.clone(), buf, Some(addr)).unwrap();
+        op.wait().await.0.map(|_| ())
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl AsRawFd for UnixDatagram {
     #[inline]
     fn as_raw_fd(&self) -> RawFd {
@@ -1,6 +1,6 @@
 use std::{
     io,
-    mem::{ManuallyDrop, MaybeUninit},
+    mem::ManuallyDrop,
     os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
     path::Path,
 };
@@ -64,6 +64,17 @@ impl UnixListener {
         Self::bind_with_config(path, &ListenerOpts::default())
     }
 
+    /// Closes the listener, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        let mut this = ManuallyDrop::new(self);
+        let _ = this.sys_listener.take().unwrap().into_raw_fd();
+        // Safety: the fd is moved out of a listener which is never dropped,
+        // nor used again.
+        let fd = unsafe { std::ptr::read(&this.fd) };
+        fd.close().await
+    }
+
     /// Accept
     pub async fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
         let op = Op::accept(&self.fd)?;
@@ -166,17 +177,10 @@ impl IntoRawFd for UnixListener {
     #[inline]
     fn into_raw_fd(self) -> RawFd {
         let mut this = ManuallyDrop::new(self);
-        #[allow(invalid_value)]
-        #[allow(clippy::uninit_assumed_init)]
-        let (mut fd, mut sys_listener) = unsafe {
-            (
-                MaybeUninit::uninit().assume_init(),
-                MaybeUninit::uninit().assume_init(),
-            )
-        };
-        std::mem::swap(&mut this.fd, &mut fd);
-        std::mem::swap(&mut this.sys_listener, &mut sys_listener);
-        let _ = sys_listener.take().unwrap().into_raw_fd();
+        let _ = this.sys_listener.take().unwrap().into_raw_fd();
+        // Safety: the fd is moved out of a listener which is never dropped,
+        // nor used again.
+        let fd = unsafe { std::ptr::read(&this.fd) };
 
         fd.try_unwrap()
             .expect("unexpected multiple reference to rawfd")
@@ -1,6 +1,7 @@
 #![allow(unreachable_pub)]
 //! Unix related.
 
+pub(crate) mod ancillary;
 mod datagram;
 mod listener;
 mod pipe;
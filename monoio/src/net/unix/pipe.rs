@@ -1,4 +1,7 @@
-use std::{io, os::unix::prelude::RawFd};
+use std::{
+    io,
+    os::unix::prelude::{AsRawFd, RawFd},
+};
 
 use crate::driver::shared_fd::SharedFd;
 
@@ -18,20 +21,37 @@ impl Pipe {
     }
 }
 
+impl AsRawFd for Pipe {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
 /// Create a new pair of pipe.
+///
+/// Both ends are close-on-exec, see [`set_cloexec`](crate::io::set_cloexec).
 pub fn new_pipe() -> io::Result<(Pipe, Pipe)> {
     let mut pipes = [0 as libc::c_int; 2];
     #[cfg(target_os = "linux")]
     let flag = {
         if crate::driver::op::is_legacy() {
-            libc::O_NONBLOCK
+            libc::O_CLOEXEC | libc::O_NONBLOCK
         } else {
-            0
+            libc::O_CLOEXEC
         }
     };
     #[cfg(target_os = "linux")]
     crate::syscall!(pipe2@RAW(pipes.as_mut_ptr() as _, flag))?;
     #[cfg(not(target_os = "linux"))]
-    crate::syscall!(pipe@RAW(pipes.as_mut_ptr() as _))?;
+    {
+        crate::syscall!(pipe@RAW(pipes.as_mut_ptr() as _))?;
+        for fd in pipes {
+            crate::syscall!(fcntl@RAW(fd, libc::F_SETFD, libc::FD_CLOEXEC)).inspect_err(|_| {
+                let _ = crate::syscall!(close@RAW(pipes[0]));
+                let _ = crate::syscall!(close@RAW(pipes[1]));
+            })?;
+        }
+    }
     Ok((Pipe::from_raw_fd(pipes[0]), Pipe::from_raw_fd(pipes[1])))
 }
@@ -39,6 +39,12 @@ impl UnixSeqpacketListener {
         Self::bind_with_backlog(path, DEFAULT_BACKLOG)
     }
 
+    /// Closes the listener, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
     /// Accept a UnixSeqpacket
     pub async fn accept(&self) -> io::Result<(UnixSeqpacket, SocketAddr)> {
         let op = Op::accept(&self.fd)?;
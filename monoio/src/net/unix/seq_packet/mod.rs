@@ -64,6 +64,12 @@ impl UnixSeqpacket {
         Ok(Self::from_shared_fd(completion.data.fd))
     }
 
+    /// Closes the socket, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         local_addr(self.as_raw_fd())
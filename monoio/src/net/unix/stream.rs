@@ -1,11 +1,13 @@
 use std::{
     future::Future,
     io::{self},
-    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
     path::Path,
+    task::{Context, Poll},
 };
 
 use super::{
+    ancillary::Control,
     socket_addr::{local_addr, pair, peer_addr, socket_addr, SocketAddr},
     ucred::UCred,
 };
@@ -15,15 +17,16 @@ use crate::{
     io::{
         as_fd::{AsReadFd, AsWriteFd, SharedFdWrapper},
         operation_canceled, AsyncReadRent, AsyncWriteRent, CancelHandle, CancelableAsyncReadRent,
-        CancelableAsyncWriteRent, Split,
+        CancelableAsyncWriteRent, PreparedOp, Split,
     },
-    net::new_socket,
+    net::{new_socket, poll_ready::PollReady},
     BufResult,
 };
 
 /// UnixStream
 pub struct UnixStream {
     pub(super) fd: SharedFd,
+    ready: PollReady,
 }
 
 /// UnixStream is safe to split to two parts
@@ -31,7 +34,10 @@ unsafe impl Split for UnixStream {}
 
 impl UnixStream {
     pub(crate) fn from_shared_fd(fd: SharedFd) -> Self {
-        Self { fd }
+        Self {
+            fd,
+            ready: PollReady::default(),
+        }
     }
 
     /// Connect UnixStream to a path.
@@ -95,6 +101,14 @@ impl UnixStream {
         }
     }
 
+    /// Closes the stream, completing once it is closed. See
+    /// [`TcpStream::close`](crate::net::TcpStream::close).
+    pub async fn close(self) -> io::Result<()> {
+        let UnixStream { fd, ready } = self;
+        drop(ready);
+        fd.close().await
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         local_addr(self.as_raw_fd())
@@ -105,6 +119,50 @@ impl UnixStream {
         peer_addr(self.as_raw_fd())
     }
 
+    /// Prepares a recv into `buf` which can be submitted repeatedly, see
+    /// [`PreparedOp`].
+    pub fn prepare_recv<T: IoBufMut>(&self, buf: T) -> PreparedOp<T> {
+        PreparedOp::recv(self.fd.clone(), buf)
+    }
+
+    /// Sends data on the socket along with the file descriptors `fds`, as an
+    /// `SCM_RIGHTS` control message. On success, returns the number of bytes
+    /// written.
+    ///
+    /// The peer receives duplicates of the descriptors with
+    /// [`recv_with_fds`](Self::recv_with_fds), the ones of `fds` stay open.
+    /// They are passed with the first byte of the data, so the data must not
+    /// be empty.
+    pub async fn send_with_fds<T: IoBuf>(&self, buf: T, fds: &[RawFd]) -> BufResult<usize, T> {
+        let control = match Control::with_fds(fds) {
+            Ok(control) => control,
+            Err(e) => return (Err(e), buf),
+        };
+        let op = Op::send_msg_unix_with_control(self.fd.clone(), buf, None, control).unwrap();
+        op.wait().await
+    }
+
+    /// Receives data on the socket along with up to `max_fds` file
+    /// descriptors sent by [`send_with_fds`](Self::send_with_fds). On
+    /// success, returns the number of bytes read and the descriptors.
+    ///
+    /// A read stops at the data the descriptors were sent with. The kernel
+    /// closes the descriptors beyond `max_fds`. The received ones are
+    /// close-on-exec.
+    pub async fn recv_with_fds<T: IoBufMut>(
+        &self,
+        buf: T,
+        max_fds: usize,
+    ) -> BufResult<(usize, Vec<OwnedFd>), T> {
+        let control = match Control::for_fds(max_fds) {
+            Ok(control) => control,
+            Err(e) => return (Err(e), buf),
+        };
+        let op = Op::recv_msg_unix_with_control(self.fd.clone(), buf, control).unwrap();
+        let (res, buf) = op.wait_with_fds().await;
+        (res.map(|(n, _, fds)| (n, fds)), buf)
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -134,6 +192,23 @@ impl UnixStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Poll for read readiness, for futures implemented by hand, which can not
+    /// await [`readable`](Self::readable).
+    ///
+    /// It waits like `readable(false)`. The pending wait is kept in the
+    /// stream and polled again by the next call, which wakes the waker of the
+    /// last `cx` only, so a single task should poll it at a time.
+    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_read_ready(&self.fd, cx)
+    }
+
+    /// Poll for write readiness, for futures implemented by hand, which can
+    /// not await [`writable`](Self::writable). See
+    /// [`poll_read_ready`](Self::poll_read_ready).
+    pub fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.ready.poll_write_ready(&self.fd, cx)
+    }
 }
 
 impl AsReadFd for UnixStream {
@@ -175,6 +250,7 @@ impl std::fmt::Debug for UnixStream {
 impl AsyncWriteRent for UnixStream {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
+        crate::buf::lint_zero_len_write(&buf);
         // Submit the write operation
         let op = Op::send(self.fd.clone(), buf).unwrap();
         op.result()
@@ -218,9 +294,7 @@ impl CancelableAsyncWriteRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::send(fd, buf).unwrap(), |op| op.result()).await
     }
 
     #[inline]
@@ -235,9 +309,11 @@ impl CancelableAsyncWriteRent for UnixStream {
             return (Err(operation_canceled()), buf_vec);
         }
 
-        let op = Op::writev(fd.clone(), buf_vec).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(
+            || Op::writev(fd.clone(), buf_vec).unwrap(),
+            |op| op.result(),
+        )
+        .await
     }
 
     #[inline]
@@ -259,10 +335,18 @@ impl CancelableAsyncWriteRent for UnixStream {
 
 impl AsyncReadRent for UnixStream {
     #[inline]
-    fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::recv(self.fd.clone(), buf).unwrap();
-        op.result()
+    fn read<T: IoBufMut>(&mut self, mut buf: T) -> impl Future<Output = BufResult<usize, T>> {
+        // Submit the read operation, a zero-length one completes at once
+        let op = match crate::buf::is_zero_len_read(&mut buf) {
+            false => Ok(Op::recv(self.fd.clone(), buf).unwrap()),
+            true => Err(buf),
+        };
+        async move {
+            match op {
+                Ok(op) => op.result().await,
+                Err(buf) => (Ok(0), buf),
+            }
+        }
     }
 
     #[inline]
@@ -286,9 +370,7 @@ impl CancelableAsyncReadRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::recv(fd, buf).unwrap(), |op| op.result()).await
     }
 
     #[inline]
@@ -303,9 +385,8 @@ impl CancelableAsyncReadRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::readv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.result().await
+        c.run(|| Op::readv(fd, buf).unwrap(), |op| op.result())
+            .await
     }
 }
 
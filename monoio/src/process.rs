@@ -0,0 +1,156 @@
+//! Waiting for processes through pidfds.
+//!
+//! A [`PidFd`] refers to a process, and becomes readable when it exits. It is
+//! polled with the current driver like any other fd, so waiting for a process
+//! takes neither a `SIGCHLD` handler nor a thread blocked in `waitpid`, and
+//! works for processes the program did not spawn as well.
+//!
+//! Pidfds require Linux 5.3+.
+
+use std::{
+    io,
+    os::unix::{
+        io::{AsRawFd, RawFd},
+        process::ExitStatusExt,
+    },
+    process::{Child, ExitStatus},
+};
+
+use crate::driver::{op::Op, shared_fd::SharedFd};
+
+/// Opens a pidfd referring to the process `pid`.
+///
+/// The pidfd is close-on-exec.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::Command;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let child = Command::new("sleep").arg("1").spawn()?;
+///     let pidfd = monoio::process::pidfd_open(child.id())?;
+///     let status = pidfd.wait().await?;
+///     println!("exited with {status}");
+///     Ok(())
+/// }
+/// ```
+pub fn pidfd_open(pid: u32) -> io::Result<PidFd> {
+    let pid = libc::pid_t::try_from(pid)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid pid"))?;
+    let fd = crate::syscall!(syscall@RAW(libc::SYS_pidfd_open, pid, 0))? as RawFd;
+    let fd = match SharedFd::new::<false>(fd) {
+        Ok(fd) => fd,
+        Err(e) => {
+            let _ = crate::syscall!(close@RAW(fd));
+            return Err(e);
+        }
+    };
+    Ok(PidFd {
+        fd,
+        pid: pid as u32,
+    })
+}
+
+/// A pidfd, an fd referring to a process, see [`pidfd_open`].
+#[derive(Debug)]
+pub struct PidFd {
+    fd: SharedFd,
+    pid: u32,
+}
+
+impl PidFd {
+    /// Opens a pidfd referring to `child`.
+    ///
+    /// The child must not have been waited for yet, or its pid may already
+    /// refer to another process.
+    pub fn from_child(child: &Child) -> io::Result<PidFd> {
+        pidfd_open(child.id())
+    }
+
+    /// Returns the pid of the process.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Waits for the process to exit, without reaping it.
+    ///
+    /// This works for any process, not only the children of the current one.
+    pub async fn exited(&self) -> io::Result<()> {
+        let op = Op::poll_read(&self.fd, false)?;
+        op.wait().await
+    }
+
+    /// Waits for the process to exit and reaps it, returning its exit
+    /// status.
+    ///
+    /// The process must be a child of the current one, otherwise an `ECHILD`
+    /// error is returned once it exits.
+    pub async fn wait(&self) -> io::Result<ExitStatus> {
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(status);
+            }
+            self.exited().await?;
+        }
+    }
+
+    /// Reaps the process if it has exited, returning its exit status, or
+    /// returns `None` if it is still running.
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        crate::syscall!(waitid@RAW(
+            libc::P_PIDFD,
+            self.fd.raw_fd() as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG
+        ))?;
+        // Safety: waitid filled the child fields of `info`, or left them
+        // zeroed if the child is still running.
+        let (pid, status) = unsafe { (info.si_pid(), info.si_status()) };
+        if pid == 0 {
+            return Ok(None);
+        }
+        // Rebuild the status of waitpid, which ExitStatus wraps.
+        let status = match info.si_code {
+            libc::CLD_EXITED => (status & 0xff) << 8,
+            libc::CLD_KILLED => status,
+            libc::CLD_DUMPED => status | 0x80,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "unexpected child state",
+                ))
+            }
+        };
+        Ok(Some(ExitStatus::from_raw(status)))
+    }
+
+    /// Sends the signal `signal` to the process.
+    ///
+    /// Unlike `kill`, the signal can not reach another process reusing the
+    /// pid after this one was reaped.
+    pub fn send_signal(&self, signal: libc::c_int) -> io::Result<()> {
+        crate::syscall!(syscall@RAW(
+            libc::SYS_pidfd_send_signal,
+            self.fd.raw_fd(),
+            signal,
+            std::ptr::null::<libc::siginfo_t>(),
+            0
+        ))?;
+        Ok(())
+    }
+
+    /// Kills the process with `SIGKILL`.
+    pub fn kill(&self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+}
+
+impl AsRawFd for PidFd {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
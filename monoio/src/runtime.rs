@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
 use crate::time::TimeDriver;
@@ -8,11 +8,11 @@ use crate::IoUringDriver;
 use crate::LegacyDriver;
 use crate::{
     driver::Driver,
-    scheduler::{LocalScheduler, TaskQueue},
+    scheduler::{LocalScheduler, Priority, TaskQueue, WakeOrder},
     task::{
         new_task,
-        waker_fn::{dummy_waker, set_poll, should_poll},
-        JoinHandle,
+        waker_fn::{dummy_waker, is_woken, set_poll, should_poll},
+        JoinHandle, Task, TaskHooks,
     },
     time::driver::Handle as TimeHandle,
 };
@@ -26,11 +26,176 @@ thread_local! {
         tasks: Default::default(),
         time_handle: None,
         blocking_handle: crate::blocking::BlockingHandle::Empty(crate::blocking::BlockingStrategy::Panic),
+        park_waiters: Default::default(),
+        park_round: Default::default(),
+        #[cfg(target_os = "linux")]
+        numa_node: None,
+        wake_order: WakeOrder::Completion,
+        wake_round: Default::default(),
+        panic_policy: PanicPolicy::Unwind,
+        exit_policy: ExitPolicy::MainFuture,
+        exit_timeout: None,
+        keep_alive: Default::default(),
+        shutting_down: Default::default(),
+        spin_before_park: None,
+        closed_on_drop: Default::default(),
+        #[cfg(unix)]
+        on_drop_close: None,
+        counters: Default::default(),
+        task_hooks: None,
+        budget: crate::task::coop::Budget::new(None),
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        fixed_bufs: None,
+        locals: Default::default(),
     };
 }
 
 scoped_thread_local!(pub(crate) static CURRENT: Context);
 
+/// What happens when a spawned task, or a waker invoked by the driver,
+/// panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// The panic unwinds out of [`Runtime::block_on`], leaving the runtime.
+    #[default]
+    Unwind,
+    /// The panic is caught, and the runtime keeps running. A panicking task
+    /// is dropped, awaiting its [`JoinHandle`] resumes the panic in the
+    /// awaiting task. A panicking waker is skipped.
+    Isolate,
+    /// The process is aborted, once the panic hook has run.
+    Abort,
+}
+
+/// When [`Runtime::block_on`] returns, see
+/// [`RuntimeBuilder::with_exit_policy`](crate::RuntimeBuilder::with_exit_policy).
+///
+/// With either policy, `block_on` also waits for the [`KeepAlive`] guards of
+/// the runtime to be dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExitPolicy {
+    /// `block_on` returns as soon as its future completes. The tasks left
+    /// are run by the next calls to `block_on` or `turn`, or dropped with the
+    /// runtime.
+    #[default]
+    MainFuture,
+    /// Once its future completes, `block_on` keeps running the tasks left,
+    /// and returns when all of them have completed, detached ones included.
+    AllTasks,
+}
+
+/// A guard keeping [`Runtime::block_on`] from returning while it is alive,
+/// created by [`keep_alive`].
+///
+/// Once the future of `block_on` completes, it keeps running the tasks until
+/// all the guards of the runtime are dropped, or until the [exit
+/// timeout](crate::RuntimeBuilder::with_exit_timeout) elapses. A background
+/// task, e.g. a daemon flushing logs, holds a guard to be run to the end
+/// without being awaited by the future. Clones of a guard keep the runtime
+/// alive too.
+pub struct KeepAlive {
+    count: std::rc::Rc<std::cell::Cell<usize>>,
+}
+
+impl KeepAlive {
+    fn new(count: std::rc::Rc<std::cell::Cell<usize>>) -> Self {
+        count.set(count.get() + 1);
+        Self { count }
+    }
+}
+
+impl Clone for KeepAlive {
+    fn clone(&self) -> Self {
+        Self::new(self.count.clone())
+    }
+}
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        self.count.set(self.count.get() - 1);
+    }
+}
+
+impl std::fmt::Debug for KeepAlive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeepAlive")
+            .field("guards", &self.count.get())
+            .finish()
+    }
+}
+
+/// Create a guard keeping the current runtime from returning from
+/// `block_on` while it is alive, see [`KeepAlive`].
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc, time::Duration};
+///
+/// let flushed = Rc::new(Cell::new(false));
+/// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///     .enable_timer()
+///     .build()
+///     .unwrap();
+/// rt.block_on(async {
+///     let guard = monoio::keep_alive();
+///     let flushed = flushed.clone();
+///     monoio::spawn(async move {
+///         monoio::time::sleep(Duration::from_millis(1)).await;
+///         flushed.set(true);
+///         drop(guard);
+///     });
+/// });
+/// assert!(flushed.get());
+/// ```
+pub fn keep_alive() -> KeepAlive {
+    CURRENT.with(|ctx| KeepAlive::new(ctx.keep_alive.clone()))
+}
+
+/// Returns the panic policy of the current runtime.
+pub(crate) fn panic_policy() -> PanicPolicy {
+    if CURRENT.is_set() {
+        CURRENT.with(|ctx| ctx.panic_policy)
+    } else {
+        PanicPolicy::Unwind
+    }
+}
+
+/// Records a fd closed when dropped, without awaiting its close.
+#[cfg(unix)]
+pub(crate) fn record_close_on_drop(fd: std::os::fd::RawFd) {
+    if !CURRENT.is_set() {
+        return;
+    }
+    CURRENT.with(|ctx| {
+        ctx.closed_on_drop.set(ctx.closed_on_drop.get() + 1);
+        if let Some(f) = &ctx.on_drop_close {
+            f(fd);
+        }
+    });
+}
+
+/// Invokes `wake`, waking a task from the driver, as of the panic policy of
+/// the current runtime, so that a panicking waker leaves the driver in a
+/// consistent state.
+#[inline]
+pub(crate) fn isolate_wake(wake: impl FnOnce()) {
+    match panic_policy() {
+        PanicPolicy::Unwind => wake(),
+        policy => {
+            if std::panic::catch_unwind(std::panic::AssertUnwindSafe(wake)).is_err()
+                && policy == PanicPolicy::Abort
+            {
+                std::process::abort();
+            }
+        }
+    }
+}
+
 pub(crate) struct Context {
     /// Owned task set and local run queue
     pub(crate) tasks: TaskQueue,
@@ -54,6 +219,80 @@ pub(crate) struct Context {
     /// Blocking Handle
     #[cfg(feature = "sync")]
     pub(crate) blocking_handle: crate::blocking::BlockingHandle,
+
+    /// Wakers to wake before the driver parks
+    pub(crate) park_waiters: std::cell::RefCell<Vec<std::task::Waker>>,
+
+    /// Number of times park waiters have been woken
+    pub(crate) park_round: std::cell::Cell<u64>,
+
+    /// NUMA node the runtime allocates its memory on
+    #[cfg(target_os = "linux")]
+    pub(crate) numa_node: Option<usize>,
+
+    /// Order of the tasks woken by a driver park
+    pub(crate) wake_order: WakeOrder,
+
+    /// Number of parks reordering woken tasks
+    pub(crate) wake_round: std::cell::Cell<usize>,
+
+    /// What happens when a task or a waker panics
+    pub(crate) panic_policy: PanicPolicy,
+
+    /// When `block_on` returns
+    pub(crate) exit_policy: ExitPolicy,
+
+    /// How long `block_on` waits for tasks or keep alive guards once its
+    /// future completed
+    pub(crate) exit_timeout: Option<Duration>,
+
+    /// Number of live keep alive guards
+    pub(crate) keep_alive: std::rc::Rc<std::cell::Cell<usize>>,
+
+    /// Whether the runtime drains its tasks
+    pub(crate) shutting_down: std::cell::Cell<bool>,
+
+    /// How long to poll the driver for completions before sleeping
+    pub(crate) spin_before_park: Option<std::time::Duration>,
+
+    /// Number of fds closed when dropped, without `close()`
+    pub(crate) closed_on_drop: std::cell::Cell<u64>,
+
+    /// Called with each fd closed when dropped
+    #[cfg(unix)]
+    pub(crate) on_drop_close: Option<std::sync::Arc<dyn Fn(std::os::fd::RawFd) + Send + Sync>>,
+
+    /// Counters of tasks and parks, read by `utils::runtime_metrics`
+    pub(crate) counters: Counters,
+
+    /// Callbacks run around the tasks
+    pub(crate) task_hooks: Option<TaskHooks>,
+
+    /// Budget of the task being polled
+    pub(crate) budget: crate::task::coop::Budget,
+
+    /// Buffers registered with the ring, handed out as fixed buffers
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fixed_bufs: Option<crate::buf::BufArena>,
+
+    /// Values of the runtime-local cells, keyed by their address
+    pub(crate) locals: std::cell::RefCell<fxhash::FxHashMap<usize, std::rc::Rc<dyn std::any::Any>>>,
+}
+
+/// Counters of a runtime, see [`RuntimeMetrics`](crate::utils::RuntimeMetrics).
+#[derive(Default)]
+pub(crate) struct Counters {
+    pub(crate) tasks_spawned: std::cell::Cell<u64>,
+    pub(crate) tasks_completed: std::cell::Cell<u64>,
+    pub(crate) tasks_polled: std::cell::Cell<u64>,
+    pub(crate) parks: std::cell::Cell<u64>,
+}
+
+impl Counters {
+    #[inline]
+    pub(crate) fn add(counter: &std::cell::Cell<u64>, n: u64) {
+        counter.set(counter.get() + n);
+    }
 }
 
 impl Context {
@@ -68,6 +307,27 @@ impl Context {
             tasks: TaskQueue::default(),
             time_handle: None,
             blocking_handle,
+            park_waiters: Default::default(),
+            park_round: Default::default(),
+            #[cfg(target_os = "linux")]
+            numa_node: None,
+            wake_order: WakeOrder::Completion,
+            wake_round: Default::default(),
+            panic_policy: PanicPolicy::Unwind,
+            exit_policy: ExitPolicy::MainFuture,
+            exit_timeout: None,
+            keep_alive: Default::default(),
+            shutting_down: Default::default(),
+            spin_before_park: None,
+            closed_on_drop: Default::default(),
+            #[cfg(unix)]
+            on_drop_close: None,
+            counters: Default::default(),
+            task_hooks: None,
+            budget: crate::task::coop::Budget::new(Some(crate::task::coop::DEFAULT_BUDGET)),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fixed_bufs: None,
+            locals: Default::default(),
         }
     }
 
@@ -79,7 +339,105 @@ impl Context {
             thread_id,
             tasks: TaskQueue::default(),
             time_handle: None,
+            park_waiters: Default::default(),
+            park_round: Default::default(),
+            #[cfg(target_os = "linux")]
+            numa_node: None,
+            wake_order: WakeOrder::Completion,
+            wake_round: Default::default(),
+            panic_policy: PanicPolicy::Unwind,
+            exit_policy: ExitPolicy::MainFuture,
+            exit_timeout: None,
+            keep_alive: Default::default(),
+            shutting_down: Default::default(),
+            spin_before_park: None,
+            closed_on_drop: Default::default(),
+            #[cfg(unix)]
+            on_drop_close: None,
+            counters: Default::default(),
+            task_hooks: None,
+            budget: crate::task::coop::Budget::new(Some(crate::task::coop::DEFAULT_BUDGET)),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fixed_bufs: None,
+            locals: Default::default(),
+        }
+    }
+
+    /// Queue a newly spawned task.
+    pub(crate) fn spawn_task(&self, task: Task<LocalScheduler>, priority: Priority) {
+        Counters::add(&self.counters.tasks_spawned, 1);
+        if let Some(hooks) = &self.task_hooks {
+            hooks.spawned(task.id());
+        }
+        self.tasks.push(task, priority);
+    }
+
+    /// Run a task taken from the queue.
+    pub(crate) fn run_task(&self, task: Task<LocalScheduler>) {
+        Counters::add(&self.counters.tasks_polled, 1);
+        self.budget.with(|| match &self.task_hooks {
+            Some(hooks) => hooks.run(task),
+            None => task.run(),
+        })
+    }
+
+    /// Number of tasks spawned and not completed yet.
+    fn tasks_alive(&self) -> u64 {
+        self.counters.tasks_spawned.get() - self.counters.tasks_completed.get()
+    }
+
+    /// Returns whether `block_on` may return once its future completed, as
+    /// of the exit policy, the keep alive guards and the exit `deadline`.
+    fn may_exit(&self, deadline: Option<std::time::Instant>) -> bool {
+        if self.shutting_down.get() {
+            return true;
         }
+        let waiting = self.keep_alive.get() > 0
+            || (self.exit_policy == ExitPolicy::AllTasks && self.tasks_alive() > 0);
+        !waiting || deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    }
+
+    /// Cancel the operations in flight so the tasks waiting for them
+    /// complete, and drain the tasks.
+    fn begin_shutdown(&self) {
+        self.shutting_down.set(true);
+        crate::driver::cancel_all();
+    }
+
+    /// Returns whether nothing but a wake up from another thread may make the
+    /// future of `block_on`, the only task left, progress.
+    fn is_idle(&self) -> bool {
+        self.tasks_alive() <= 1
+            && !self
+                .time_handle
+                .as_ref()
+                .is_some_and(TimeHandle::has_pending_timers)
+            && !crate::driver::has_pending_io()
+    }
+
+    /// Wake all tasks waiting for the driver to park. Returns false if there
+    /// was none.
+    pub(crate) fn wake_park_waiters(&self) -> bool {
+        let waiters = std::mem::take(&mut *self.park_waiters.borrow_mut());
+        if waiters.is_empty() {
+            return false;
+        }
+        self.park_round.set(self.park_round.get() + 1);
+        for waker in waiters {
+            isolate_wake(|| waker.wake());
+        }
+        true
+    }
+
+    /// Apply the wake order to the tasks queued by a driver park or submit,
+    /// `queued` is the length of the queue before it.
+    pub(crate) fn reorder_woken(&self, queued: usize) {
+        if self.wake_order == WakeOrder::Completion {
+            return;
+        }
+        let round = self.wake_round.get();
+        self.wake_round.set(round.wrapping_add(1));
+        self.tasks.reorder(queued, self.wake_order, round);
     }
 
     #[allow(unused)]
@@ -129,6 +487,188 @@ impl<D> Runtime<D> {
 
     /// Block on
     pub fn block_on<F>(&mut self, future: F) -> F::Output
+    where
+        F: Future,
+        D: Driver,
+    {
+        self.run(future, None)
+    }
+
+    /// Block on `future` until it completes, or until the runtime is idle:
+    /// no task is left but the future, no timer is pending and the driver
+    /// waits for no io. The future can then make no progress, unless woken
+    /// from another thread, and it is dropped and `None` is returned.
+    ///
+    /// This keeps an ephemeral runtime, e.g. a worker of a pool, from hanging
+    /// once its peers are gone. Wakers invoked from other threads, e.g. by
+    /// [`spawn_blocking`](crate::spawn_blocking) or by a channel shared
+    /// between threads, are not accounted for. The legacy driver counts any
+    /// registered fd as io it waits for. The [exit
+    /// policy](crate::RuntimeBuilder::with_exit_policy) applies once the
+    /// future completes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///     .build()
+    ///     .unwrap();
+    /// let (tx, mut rx) = monoio::sync::mpsc::channel::<u32>(1);
+    /// // The sender is never used, and the receiver waits forever.
+    /// let out = rt.block_on_until_idle(async move {
+    ///     let _tx = tx;
+    ///     rx.recv().await
+    /// });
+    /// assert!(out.is_none());
+    /// ```
+    pub fn block_on_until_idle<F>(&mut self, future: F) -> Option<F::Output>
+    where
+        F: Future,
+        D: Driver,
+    {
+        let abort = Abort {
+            on_idle: true,
+            ..Default::default()
+        };
+        let future = async {
+            let mut future = std::pin::pin!(future);
+            std::future::poll_fn(|cx| abort.poll(future.as_mut(), cx)).await
+        };
+        self.run(future, Some(&abort))
+    }
+
+    /// Block on `future` until it completes, or until `signal` completes
+    /// first and the runtime shuts down gracefully:
+    /// - the operations in flight are canceled, on io_uring with
+    ///   `IORING_OP_ASYNC_CANCEL`, and complete with `ECANCELED` unless they
+    ///   complete meanwhile, so the tasks waiting for them can finish;
+    /// - the future and the tasks keep running for up to `timeout`, with the
+    ///   tasks they spawn meanwhile, e.g. to finish their work.
+    ///
+    /// Returns the output of the future, or `None` if it did not complete
+    /// within `timeout` and was dropped. The tasks still running are dropped
+    /// with the runtime, or run by the next calls to `block_on`. The
+    /// operations submitted after the cancellation are not canceled.
+    ///
+    /// Dropping the runtime instead drops the tasks wherever they are, e.g.
+    /// in the middle of writing a response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///     .enable_timer()
+    ///     .build()
+    ///     .unwrap();
+    /// let (tx, rx) = monoio::sync::oneshot::channel::<()>();
+    /// let out = rt.block_on_with_shutdown(
+    ///     async move {
+    ///         let _ = tx.send(());
+    ///         // Serve until the deadline.
+    ///         monoio::time::sleep(Duration::from_secs(60)).await;
+    ///     },
+    ///     rx,
+    ///     Duration::from_millis(10),
+    /// );
+    /// assert!(out.is_none());
+    /// ```
+    pub fn block_on_with_shutdown<F, S>(
+        &mut self,
+        future: F,
+        signal: S,
+        timeout: Duration,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+        D: Driver,
+    {
+        let abort = Abort::default();
+        let future = async {
+            let mut future = std::pin::pin!(future);
+            let mut signal = std::pin::pin!(signal);
+            let mut signaled = false;
+            std::future::poll_fn(|cx| {
+                let out = abort.poll(future.as_mut(), cx);
+                if out.is_pending() && !signaled && signal.as_mut().poll(cx).is_ready() {
+                    signaled = true;
+                    abort
+                        .deadline
+                        .set(Some(std::time::Instant::now() + timeout));
+                    // The canceled operations wake the future
+                    CURRENT.with(Context::begin_shutdown);
+                }
+                out
+            })
+            .await
+        };
+        let output = self.run(future, Some(&abort));
+        if let Some(deadline) = abort.deadline.get() {
+            self.drain(deadline);
+        }
+        output
+    }
+
+    /// Shut the runtime down gracefully: cancel the operations in flight, and
+    /// run the tasks, including the ones they spawn meanwhile, for up to
+    /// `timeout`. The tasks still running then are dropped with the runtime.
+    /// See
+    /// [`block_on_with_shutdown`](Runtime::block_on_with_shutdown).
+    ///
+    /// Returns whether all the tasks completed within `timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///     .enable_timer()
+    ///     .build()
+    ///     .unwrap();
+    /// rt.spawn(async { monoio::time::sleep(Duration::from_millis(1)).await });
+    /// assert!(rt.shutdown_timeout(Duration::from_secs(1)));
+    /// ```
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> bool
+    where
+        D: Driver,
+    {
+        assert!(
+            !CURRENT.is_set(),
+            "Can not start a runtime inside a runtime"
+        );
+        let deadline = std::time::Instant::now() + timeout;
+        self.driver
+            .with(|| CURRENT.set(&self.context, || self.context.begin_shutdown()));
+        self.drain(deadline)
+    }
+
+    /// Run the tasks of a runtime shutting down until all of them complete
+    /// or `deadline` passes. Returns whether all of them completed.
+    fn drain(&mut self, deadline: std::time::Instant) -> bool
+    where
+        D: Driver,
+    {
+        let done = loop {
+            if self.context.tasks_alive() == 0 {
+                break true;
+            }
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                break false;
+            }
+            self.turn_until(deadline - now, |cx| cx.tasks_alive() == 0);
+        };
+        self.context.shutting_down.set(false);
+        done
+    }
+
+    /// Run `future` to completion. If `abort` is given, the future is woken
+    /// to be aborted through it once its deadline passes, or once the
+    /// runtime is idle if it aborts on idle.
+    fn run<F>(&mut self, future: F, abort: Option<&Abort>) -> F::Output
     where
         F: Future,
         D: Driver,
@@ -146,16 +686,29 @@ impl<D> Runtime<D> {
                 #[cfg(feature = "sync")]
                 let join = unsafe { spawn_without_static(future) };
                 #[cfg(not(feature = "sync"))]
-                let join = future;
+                let join = async {
+                    // Count the future as a task, as when it is spawned
+                    let counters = &self.context.counters;
+                    Counters::add(&counters.tasks_spawned, 1);
+                    let output = future.await;
+                    Counters::add(&counters.tasks_completed, 1);
+                    output
+                };
 
                 let mut join = std::pin::pin!(join);
+                let mut output = None;
+                let mut exit_deadline = None;
                 set_poll();
                 loop {
+                    // Park waiters are woken at most once per park
+                    let mut park_waiters_woken = false;
                     loop {
                         // Consume all tasks(with max round to prevent io starvation)
                         let mut max_round = self.context.tasks.len() * 2;
+                        let mut polled = 0usize;
                         while let Some(t) = self.context.tasks.pop() {
-                            t.run();
+                            self.context.run_task(t);
+                            polled += 1;
                             if max_round == 0 {
                                 // maybe there's a looping task
                                 break;
@@ -163,39 +716,283 @@ impl<D> Runtime<D> {
                                 max_round -= 1;
                             }
                         }
+                        driver_event!("tick", tasks = polled, queued = self.context.tasks.len());
 
                         // Check main future
-                        while should_poll() {
+                        while output.is_none() && should_poll() {
                             // check if ready
                             if let std::task::Poll::Ready(t) = join.as_mut().poll(cx) {
+                                output = Some(t);
+                                exit_deadline = self
+                                    .context
+                                    .exit_timeout
+                                    .map(|timeout| std::time::Instant::now() + timeout);
+                            }
+                        }
+                        if output.is_some() && self.context.may_exit(exit_deadline) {
+                            if let Some(t) = output.take() {
                                 return t;
                             }
                         }
+                        if let Some(abort) = abort {
+                            if output.is_none() && !abort.aborted.get() && abort.deadline_passed() {
+                                abort.abort();
+                                continue;
+                            }
+                        }
 
                         if self.context.tasks.is_empty() {
+                            // Give tasks waiting for the end of the tick a chance to run
+                            if !park_waiters_woken && self.context.wake_park_waiters() {
+                                park_waiters_woken = true;
+                                continue;
+                            }
+                            if let Some(abort) = abort {
+                                if abort.on_idle
+                                    && output.is_none()
+                                    && !abort.aborted.get()
+                                    && self.context.is_idle()
+                                {
+                                    abort.abort();
+                                    continue;
+                                }
+                            }
                             // No task to execute, we should wait for io blockingly
                             // Hot path
                             break;
                         }
 
                         // Cold path
+                        let queued = self.context.tasks.normal_len();
                         let _ = self.driver.submit();
+                        self.context.reorder_woken(queued);
                     }
 
                     // Wait and Process CQ(the error is ignored for not debug mode)
+                    let queued = self.context.tasks.normal_len();
+                    if let Some(spin) = self.context.spin_before_park {
+                        // Poll without sleeping, in case io completes soon
+                        let deadline = std::time::Instant::now() + spin;
+                        while self.context.tasks.is_empty()
+                            && !is_woken()
+                            && std::time::Instant::now() < deadline
+                        {
+                            let _ = self.driver.park_timeout(Duration::ZERO);
+                        }
+                        if !self.context.tasks.is_empty() || is_woken() {
+                            self.context.reorder_woken(queued);
+                            continue;
+                        }
+                    }
+                    Counters::add(&self.context.counters.parks, 1);
+                    // Wake up at the exit or abort deadline, if waiting for it
+                    let deadline =
+                        exit_deadline.or_else(|| abort.and_then(|abort| abort.deadline.get()));
+                    // Tasks waiting for the end of the tick since its park
+                    // waiters were woken must not wait for IO, only poll it
+                    let park = match deadline {
+                        _ if !self.context.park_waiters.borrow().is_empty() => {
+                            self.driver.park_timeout(Duration::ZERO)
+                        }
+                        Some(deadline) => self.driver.park_timeout(
+                            deadline.saturating_duration_since(std::time::Instant::now()),
+                        ),
+                        None => self.driver.park(),
+                    };
                     #[cfg(not(all(debug_assertions, feature = "debug")))]
-                    let _ = self.driver.park();
+                    let _ = park;
 
                     #[cfg(all(debug_assertions, feature = "debug"))]
-                    if let Err(e) = self.driver.park() {
+                    if let Err(e) = park {
                         trace!("park error: {:?}", e);
                     }
+                    self.context.reorder_woken(queued);
+                }
+            })
+        })
+    }
+
+    /// Spawn a task onto the runtime from outside of it, returning a
+    /// [`JoinHandle`] for it.
+    ///
+    /// The task is executed by later calls to [`turn`](Runtime::turn) or
+    /// [`block_on`](Runtime::block_on).
+    pub fn spawn<T>(&self, future: T) -> JoinHandle<T::Output>
+    where
+        T: Future + 'static,
+        T::Output: 'static,
+    {
+        let (task, join) = new_task(self.context.thread_id, future, LocalScheduler::default());
+        self.context.spawn_task(task, Priority::Normal);
+        join
+    }
+
+    /// Get a handle to spawn tasks on the runtime from other threads.
+    #[cfg(feature = "sync")]
+    pub fn handle(&self) -> RuntimeHandle {
+        RuntimeHandle {
+            thread_id: self.context.thread_id,
+        }
+    }
+
+    /// Register a fd owned by a foreign event loop from outside of the
+    /// runtime, calling `callback` every time it becomes readable. See
+    /// [`register_external_fd`](crate::io::register_external_fd).
+    #[cfg(unix)]
+    pub fn register_external_fd<F>(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: F,
+    ) -> std::io::Result<crate::io::ExternalFd>
+    where
+        F: FnMut() + 'static,
+        D: Driver,
+    {
+        self.driver.with(|| {
+            CURRENT.set(&self.context, || {
+                crate::io::register_external_fd(fd, callback)
+            })
+        })
+    }
+
+    /// Get the metrics of the runtime from outside of it. See
+    /// [`runtime_metrics`](crate::utils::runtime_metrics).
+    pub fn metrics(&self) -> crate::utils::RuntimeMetrics
+    where
+        D: Driver,
+    {
+        self.driver
+            .with(|| CURRENT.set(&self.context, crate::utils::RuntimeMetrics::current))
+    }
+
+    /// Get the report of the io_uring driver of the runtime from outside of
+    /// it, to log at startup. See [`uring_report`](crate::utils::uring_report).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub fn uring_report(&self) -> Option<crate::utils::UringReport>
+    where
+        D: Driver,
+    {
+        self.driver.with(crate::driver::uring_report)
+    }
+
+    /// Run one iteration of the event loop: execute the ready tasks, then
+    /// wait for io and timers for at most `max_time` and process their
+    /// events. It does not wait if tasks are still ready to run.
+    ///
+    /// Returns whether tasks are ready to run after the iteration, in which
+    /// case `turn` should be called again soon.
+    ///
+    /// This enables embedding the runtime in a loop owned by someone else,
+    /// e.g. a GUI or game loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+    ///     .enable_timer()
+    ///     .build()
+    ///     .unwrap();
+    /// let handle = rt.spawn(async {
+    ///     monoio::time::sleep(Duration::from_millis(1)).await;
+    /// });
+    /// while !handle.is_finished() {
+    ///     // Do the work of the host loop here.
+    ///     rt.turn(Duration::from_millis(10));
+    /// }
+    /// ```
+    pub fn turn(&mut self, max_time: Duration) -> bool
+    where
+        D: Driver,
+    {
+        self.turn_until(max_time, |_| false)
+    }
+
+    /// [`turn`](Runtime::turn), without parking if `done` returns `true`
+    /// once the ready tasks are run.
+    fn turn_until(&mut self, max_time: Duration, done: impl Fn(&Context) -> bool) -> bool
+    where
+        D: Driver,
+    {
+        assert!(
+            !CURRENT.is_set(),
+            "Can not start a runtime inside a runtime"
+        );
+
+        self.driver.with(|| {
+            CURRENT.set(&self.context, || {
+                // Consume all tasks(with max round to prevent io starvation)
+                let mut max_round = self.context.tasks.len() * 2;
+                let mut polled = 0usize;
+                while let Some(t) = self.context.tasks.pop() {
+                    self.context.run_task(t);
+                    polled += 1;
+                    if max_round == 0 {
+                        break;
+                    } else {
+                        max_round -= 1;
+                    }
                 }
+                driver_event!("tick", tasks = polled, queued = self.context.tasks.len());
+                if done(&self.context) {
+                    return false;
+                }
+
+                if self.context.tasks.is_empty() && !self.context.wake_park_waiters() {
+                    Counters::add(&self.context.counters.parks, 1);
+                    let _ = self.driver.park_timeout(max_time);
+                    self.context.reorder_woken(0);
+                } else {
+                    let _ = self.driver.submit();
+                }
+                !self.context.tasks.is_empty()
             })
         })
     }
 }
 
+/// Aborts the future of [`Runtime::block_on_until_idle`] or
+/// [`Runtime::block_on_with_shutdown`].
+#[derive(Default)]
+struct Abort {
+    on_idle: bool,
+    deadline: std::cell::Cell<Option<std::time::Instant>>,
+    aborted: std::cell::Cell<bool>,
+    waker: std::cell::Cell<Option<std::task::Waker>>,
+}
+
+impl Abort {
+    fn abort(&self) {
+        self.aborted.set(true);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn deadline_passed(&self) -> bool {
+        self.deadline
+            .get()
+            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    }
+
+    /// Poll `future` unless aborted, keeping the waker to abort it.
+    fn poll<F: Future>(
+        &self,
+        future: std::pin::Pin<&mut F>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<F::Output>> {
+        if self.aborted.get() {
+            return std::task::Poll::Ready(None);
+        }
+        if let std::task::Poll::Ready(out) = future.poll(cx) {
+            return std::task::Poll::Ready(Some(out));
+        }
+        self.waker.set(Some(cx.waker().clone()));
+        std::task::Poll::Pending
+    }
+}
+
 /// Fusion Runtime is a wrapper of io_uring driver or legacy driver based
 /// runtime.
 #[cfg(feature = "legacy")]
@@ -237,6 +1034,101 @@ where
             }
         }
     }
+
+    /// Block on `future` until it completes or the runtime is idle, see
+    /// [`Runtime::block_on_until_idle`].
+    pub fn block_on_until_idle<F>(&mut self, future: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.block_on_until_idle(future),
+            FusionRuntime::Legacy(inner) => inner.block_on_until_idle(future),
+        }
+    }
+
+    /// Block on `future` until it completes, or until `signal` does and the
+    /// runtime shuts down gracefully, see [`Runtime::block_on_with_shutdown`].
+    pub fn block_on_with_shutdown<F, S>(
+        &mut self,
+        future: F,
+        signal: S,
+        timeout: Duration,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.block_on_with_shutdown(future, signal, timeout),
+            FusionRuntime::Legacy(inner) => inner.block_on_with_shutdown(future, signal, timeout),
+        }
+    }
+
+    /// Shut the runtime down gracefully, see [`Runtime::shutdown_timeout`].
+    pub fn shutdown_timeout(self, timeout: Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.shutdown_timeout(timeout),
+            FusionRuntime::Legacy(inner) => inner.shutdown_timeout(timeout),
+        }
+    }
+
+    /// Spawn a task onto the runtime from outside of it, see
+    /// [`Runtime::spawn`].
+    pub fn spawn<T>(&self, future: T) -> JoinHandle<T::Output>
+    where
+        T: Future + 'static,
+        T::Output: 'static,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.spawn(future),
+            FusionRuntime::Legacy(inner) => inner.spawn(future),
+        }
+    }
+
+    /// Get a handle to spawn tasks on the runtime from other threads, see
+    /// [`Runtime::handle`].
+    #[cfg(feature = "sync")]
+    pub fn handle(&self) -> RuntimeHandle {
+        match self {
+            FusionRuntime::Uring(inner) => inner.handle(),
+            FusionRuntime::Legacy(inner) => inner.handle(),
+        }
+    }
+
+    /// Run one iteration of the event loop, see [`Runtime::turn`].
+    pub fn turn(&mut self, max_time: Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.turn(max_time),
+            FusionRuntime::Legacy(inner) => inner.turn(max_time),
+        }
+    }
+
+    /// Get the report of the io_uring driver, or `None` with the legacy
+    /// driver, see [`Runtime::uring_report`].
+    pub fn uring_report(&self) -> Option<crate::utils::UringReport> {
+        match self {
+            FusionRuntime::Uring(inner) => inner.uring_report(),
+            FusionRuntime::Legacy(_) => None,
+        }
+    }
+
+    /// Register a fd owned by a foreign event loop, see
+    /// [`Runtime::register_external_fd`].
+    #[cfg(unix)]
+    pub fn register_external_fd<F>(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: F,
+    ) -> std::io::Result<crate::io::ExternalFd>
+    where
+        F: FnMut() + 'static,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.register_external_fd(fd, callback),
+            FusionRuntime::Legacy(inner) => inner.register_external_fd(fd, callback),
+        }
+    }
 }
 
 #[cfg(all(feature = "legacy", not(all(target_os = "linux", feature = "iouring"))))]
@@ -253,23 +1145,188 @@ where
             FusionRuntime::Legacy(inner) => inner.block_on(future),
         }
     }
-}
 
-#[cfg(all(not(feature = "legacy"), all(target_os = "linux", feature = "iouring")))]
-impl<R> FusionRuntime<R>
-where
-    R: Driver,
-{
-    /// Block on
-    pub fn block_on<F>(&mut self, future: F) -> F::Output
+    /// Block on `future` until it completes or the runtime is idle, see
+    /// [`Runtime::block_on_until_idle`].
+    pub fn block_on_until_idle<F>(&mut self, future: F) -> Option<F::Output>
     where
         F: Future,
     {
         match self {
-            FusionRuntime::Uring(inner) => inner.block_on(future),
+            FusionRuntime::Legacy(inner) => inner.block_on_until_idle(future),
         }
     }
-}
+
+    /// Block on `future` until it completes, or until `signal` does and the
+    /// runtime shuts down gracefully, see [`Runtime::block_on_with_shutdown`].
+    pub fn block_on_with_shutdown<F, S>(
+        &mut self,
+        future: F,
+        signal: S,
+        timeout: Duration,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+    {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.block_on_with_shutdown(future, signal, timeout),
+        }
+    }
+
+    /// Shut the runtime down gracefully, see [`Runtime::shutdown_timeout`].
+    pub fn shutdown_timeout(self, timeout: Duration) -> bool {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.shutdown_timeout(timeout),
+        }
+    }
+
+    /// Spawn a task onto the runtime from outside of it, see
+    /// [`Runtime::spawn`].
+    pub fn spawn<T>(&self, future: T) -> JoinHandle<T::Output>
+    where
+        T: Future + 'static,
+        T::Output: 'static,
+    {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.spawn(future),
+        }
+    }
+
+    /// Get a handle to spawn tasks on the runtime from other threads, see
+    /// [`Runtime::handle`].
+    #[cfg(feature = "sync")]
+    pub fn handle(&self) -> RuntimeHandle {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.handle(),
+        }
+    }
+
+    /// Run one iteration of the event loop, see [`Runtime::turn`].
+    pub fn turn(&mut self, max_time: Duration) -> bool {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.turn(max_time),
+        }
+    }
+
+    /// Register a fd owned by a foreign event loop, see
+    /// [`Runtime::register_external_fd`].
+    #[cfg(unix)]
+    pub fn register_external_fd<F>(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: F,
+    ) -> std::io::Result<crate::io::ExternalFd>
+    where
+        F: FnMut() + 'static,
+    {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.register_external_fd(fd, callback),
+        }
+    }
+}
+
+#[cfg(all(not(feature = "legacy"), all(target_os = "linux", feature = "iouring")))]
+impl<R> FusionRuntime<R>
+where
+    R: Driver,
+{
+    /// Block on
+    pub fn block_on<F>(&mut self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.block_on(future),
+        }
+    }
+
+    /// Block on `future` until it completes or the runtime is idle, see
+    /// [`Runtime::block_on_until_idle`].
+    pub fn block_on_until_idle<F>(&mut self, future: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.block_on_until_idle(future),
+        }
+    }
+
+    /// Block on `future` until it completes, or until `signal` does and the
+    /// runtime shuts down gracefully, see [`Runtime::block_on_with_shutdown`].
+    pub fn block_on_with_shutdown<F, S>(
+        &mut self,
+        future: F,
+        signal: S,
+        timeout: Duration,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.block_on_with_shutdown(future, signal, timeout),
+        }
+    }
+
+    /// Shut the runtime down gracefully, see [`Runtime::shutdown_timeout`].
+    pub fn shutdown_timeout(self, timeout: Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.shutdown_timeout(timeout),
+        }
+    }
+
+    /// Spawn a task onto the runtime from outside of it, see
+    /// [`Runtime::spawn`].
+    pub fn spawn<T>(&self, future: T) -> JoinHandle<T::Output>
+    where
+        T: Future + 'static,
+        T::Output: 'static,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.spawn(future),
+        }
+    }
+
+    /// Get a handle to spawn tasks on the runtime from other threads, see
+    /// [`Runtime::handle`].
+    #[cfg(feature = "sync")]
+    pub fn handle(&self) -> RuntimeHandle {
+        match self {
+            FusionRuntime::Uring(inner) => inner.handle(),
+        }
+    }
+
+    /// Run one iteration of the event loop, see [`Runtime::turn`].
+    pub fn turn(&mut self, max_time: Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.turn(max_time),
+        }
+    }
+
+    /// Get the report of the io_uring driver, see [`Runtime::uring_report`].
+    pub fn uring_report(&self) -> Option<crate::utils::UringReport> {
+        match self {
+            FusionRuntime::Uring(inner) => inner.uring_report(),
+        }
+    }
+
+    /// Register a fd owned by a foreign event loop, see
+    /// [`Runtime::register_external_fd`].
+    #[cfg(unix)]
+    pub fn register_external_fd<F>(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: F,
+    ) -> std::io::Result<crate::io::ExternalFd>
+    where
+        F: FnMut() + 'static,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.register_external_fd(fd, callback),
+        }
+    }
+}
 
 // L -> Fusion<L, R>
 #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
@@ -339,6 +1396,280 @@ impl From<Runtime<TimeDriver<IoUringDriver>>> for FusionRuntime<TimeDriver<IoUri
     }
 }
 
+/// A runtime built with the io_uring driver if the kernel supports it, and
+/// with the legacy driver otherwise, see
+/// [`RuntimeBuilder::new_auto`](crate::RuntimeBuilder::new_auto).
+///
+/// Unlike [`FusionRuntime`], its type depends neither on the drivers enabled
+/// nor on the timer: the calls are dispatched to the driver dynamically, so
+/// it can be stored and passed around without generics.
+///
+/// # Examples
+///
+/// ```
+/// let mut rt = monoio::RuntimeBuilder::new_auto()
+///     .enable_timer()
+///     .build()
+///     .unwrap();
+/// let n = rt.block_on(async {
+///     monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+///     1
+/// });
+/// assert_eq!(n, 1);
+/// ```
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+pub struct AutoRuntime {
+    inner: Box<dyn ErasedRuntime>,
+    uring: bool,
+}
+
+/// The methods of [`Runtime`] which do not depend on its driver type.
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+trait ErasedRuntime {
+    fn context(&self) -> &Context;
+    fn block_on(&mut self, future: std::pin::Pin<&mut dyn Future<Output = ()>>);
+    fn block_on_until_idle(&mut self, future: std::pin::Pin<&mut dyn Future<Output = ()>>);
+    fn block_on_with_shutdown(
+        &mut self,
+        future: std::pin::Pin<&mut dyn Future<Output = ()>>,
+        signal: std::pin::Pin<&mut dyn Future<Output = ()>>,
+        timeout: Duration,
+    );
+    fn shutdown_timeout(self: Box<Self>, timeout: Duration) -> bool;
+    fn turn(&mut self, max_time: Duration) -> bool;
+    fn metrics(&self) -> crate::utils::RuntimeMetrics;
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_report(&self) -> Option<crate::utils::UringReport>;
+    #[cfg(unix)]
+    fn register_external_fd(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: Box<dyn FnMut()>,
+    ) -> std::io::Result<crate::io::ExternalFd>;
+}
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl<D: Driver> ErasedRuntime for Runtime<D> {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn block_on(&mut self, future: std::pin::Pin<&mut dyn Future<Output = ()>>) {
+        Runtime::block_on(self, future)
+    }
+
+    fn block_on_until_idle(&mut self, future: std::pin::Pin<&mut dyn Future<Output = ()>>) {
+        Runtime::block_on_until_idle(self, future);
+    }
+
+    fn block_on_with_shutdown(
+        &mut self,
+        future: std::pin::Pin<&mut dyn Future<Output = ()>>,
+        signal: std::pin::Pin<&mut dyn Future<Output = ()>>,
+        timeout: Duration,
+    ) {
+        Runtime::block_on_with_shutdown(self, future, signal, timeout);
+    }
+
+    fn shutdown_timeout(self: Box<Self>, timeout: Duration) -> bool {
+        Runtime::shutdown_timeout(*self, timeout)
+    }
+
+    fn turn(&mut self, max_time: Duration) -> bool {
+        Runtime::turn(self, max_time)
+    }
+
+    fn metrics(&self) -> crate::utils::RuntimeMetrics {
+        Runtime::metrics(self)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_report(&self) -> Option<crate::utils::UringReport> {
+        Runtime::uring_report(self)
+    }
+
+    #[cfg(unix)]
+    fn register_external_fd(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: Box<dyn FnMut()>,
+    ) -> std::io::Result<crate::io::ExternalFd> {
+        Runtime::register_external_fd(self, fd, callback)
+    }
+}
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl AutoRuntime {
+    fn new<D: Driver + 'static>(runtime: Runtime<D>, uring: bool) -> Self {
+        Self {
+            inner: Box::new(runtime),
+            uring,
+        }
+    }
+
+    /// Block on
+    pub fn block_on<F>(&mut self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        if self.uring {
+            info!("Monoio is running with io_uring driver");
+        } else {
+            info!("Monoio is running with legacy driver");
+        }
+        let mut output = None;
+        {
+            let future = std::pin::pin!(async {
+                output = Some(future.await);
+            });
+            self.inner.block_on(future);
+        }
+        output.expect("block_on returned before the future completed")
+    }
+
+    /// Block on `future` until it completes or the runtime is idle, see
+    /// [`Runtime::block_on_until_idle`].
+    pub fn block_on_until_idle<F>(&mut self, future: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        let mut output = None;
+        {
+            let future = std::pin::pin!(async {
+                output = Some(future.await);
+            });
+            self.inner.block_on_until_idle(future);
+        }
+        output
+    }
+
+    /// Block on `future` until it completes, or until `signal` does and the
+    /// runtime shuts down gracefully, see [`Runtime::block_on_with_shutdown`].
+    pub fn block_on_with_shutdown<F, S>(
+        &mut self,
+        future: F,
+        signal: S,
+        timeout: Duration,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+    {
+        let mut output = None;
+        {
+            let future = std::pin::pin!(async {
+                output = Some(future.await);
+            });
+            let signal = std::pin::pin!(async {
+                signal.await;
+            });
+            self.inner.block_on_with_shutdown(future, signal, timeout);
+        }
+        output
+    }
+
+    /// Shut the runtime down gracefully, see [`Runtime::shutdown_timeout`].
+    pub fn shutdown_timeout(self, timeout: Duration) -> bool {
+        self.inner.shutdown_timeout(timeout)
+    }
+
+    /// Spawn a task onto the runtime from outside of it, see
+    /// [`Runtime::spawn`].
+    pub fn spawn<T>(&self, future: T) -> JoinHandle<T::Output>
+    where
+        T: Future + 'static,
+        T::Output: 'static,
+    {
+        let context = self.inner.context();
+        let (task, join) = new_task(context.thread_id, future, LocalScheduler::default());
+        context.spawn_task(task, Priority::Normal);
+        join
+    }
+
+    /// Get a handle to spawn tasks on the runtime from other threads, see
+    /// [`Runtime::handle`].
+    #[cfg(feature = "sync")]
+    pub fn handle(&self) -> RuntimeHandle {
+        RuntimeHandle {
+            thread_id: self.inner.context().thread_id,
+        }
+    }
+
+    /// Run one iteration of the event loop, see [`Runtime::turn`].
+    pub fn turn(&mut self, max_time: Duration) -> bool {
+        self.inner.turn(max_time)
+    }
+
+    /// Get the metrics of the runtime from outside of it, see
+    /// [`Runtime::metrics`].
+    pub fn metrics(&self) -> crate::utils::RuntimeMetrics {
+        self.inner.metrics()
+    }
+
+    /// Returns whether the runtime was built with the io_uring driver.
+    pub fn is_uring(&self) -> bool {
+        self.uring
+    }
+
+    /// Get the report of the io_uring driver, or `None` with the legacy
+    /// driver, see [`Runtime::uring_report`].
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub fn uring_report(&self) -> Option<crate::utils::UringReport> {
+        self.inner.uring_report()
+    }
+
+    /// Register a fd owned by a foreign event loop, see
+    /// [`Runtime::register_external_fd`].
+    #[cfg(unix)]
+    pub fn register_external_fd<F>(
+        &self,
+        fd: std::os::unix::io::RawFd,
+        callback: F,
+    ) -> std::io::Result<crate::io::ExternalFd>
+    where
+        F: FnMut() + 'static,
+    {
+        self.inner.register_external_fd(fd, Box::new(callback))
+    }
+}
+
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl std::fmt::Debug for AutoRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AutoRuntime")
+            .field("uring", &self.uring)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
+impl<L: Driver + 'static, R: Driver + 'static> From<FusionRuntime<L, R>> for AutoRuntime {
+    fn from(r: FusionRuntime<L, R>) -> Self {
+        match r {
+            FusionRuntime::Uring(inner) => Self::new(inner, true),
+            FusionRuntime::Legacy(inner) => Self::new(inner, false),
+        }
+    }
+}
+
+#[cfg(all(feature = "legacy", not(all(target_os = "linux", feature = "iouring"))))]
+impl<R: Driver + 'static> From<FusionRuntime<R>> for AutoRuntime {
+    fn from(r: FusionRuntime<R>) -> Self {
+        match r {
+            FusionRuntime::Legacy(inner) => Self::new(inner, false),
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring", not(feature = "legacy")))]
+impl<L: Driver + 'static> From<FusionRuntime<L>> for AutoRuntime {
+    fn from(r: FusionRuntime<L>) -> Self {
+        match r {
+            FusionRuntime::Uring(inner) => Self::new(inner, true),
+        }
+    }
+}
+
 /// Spawns a new asynchronous task, returning a [`JoinHandle`] for it.
 ///
 /// Spawning a task enables the task to execute concurrently to other tasks.
@@ -366,6 +1697,39 @@ impl From<Runtime<TimeDriver<IoUringDriver>>> for FusionRuntime<TimeDriver<IoUri
 /// }
 /// ```
 pub fn spawn<T>(future: T) -> JoinHandle<T::Output>
+where
+    T: Future + 'static,
+    T::Output: 'static,
+{
+    spawn_with_priority(future, Priority::Normal)
+}
+
+/// Spawns a new asynchronous task with the given priority, returning a
+/// [`JoinHandle`](super::task::JoinHandle) for it.
+///
+/// Whenever they are ready to run, the tasks of [`Priority::High`] are polled
+/// before the ones of normal priority, so latency-sensitive work such as
+/// heartbeats is not queued behind bulk work of the same thread. They are not
+/// preempted though, and a busy high priority task delays all the others.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::Priority;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let bulk = monoio::spawn(async { "bulk" });
+///     let heartbeat = monoio::spawn_with_priority(async { "heartbeat" }, Priority::High);
+///     assert_eq!(heartbeat.await, "heartbeat");
+///     assert_eq!(bulk.await, "bulk");
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub fn spawn_with_priority<T>(future: T, priority: Priority) -> JoinHandle<T::Output>
 where
     T: Future + 'static,
     T::Output: 'static,
@@ -373,15 +1737,222 @@ where
     let (task, join) = new_task(
         crate::utils::thread_id::get_current_thread_id(),
         future,
-        LocalScheduler,
+        LocalScheduler::new(priority),
     );
 
-    CURRENT.with(|ctx| {
-        ctx.tasks.push(task);
-    });
+    CURRENT.with(|ctx| ctx.spawn_task(task, priority));
     join
 }
 
+/// Spawns a new asynchronous task which inherits the context of the caller,
+/// returning a [`JoinHandle`] for it.
+///
+/// It behaves like [`spawn`], except that the inheritable task-locals (see
+/// [`LocalKey::scope_inherited`]) and the `tracing` span of the caller are
+/// captured and re-entered in the child task. See [`with_context`] for
+/// details.
+///
+/// [`JoinHandle`]: super::task::JoinHandle
+/// [`LocalKey::scope_inherited`]: super::task::LocalKey::scope_inherited
+/// [`with_context`]: super::task::with_context
+///
+/// # Examples
+///
+/// ```
+/// monoio::task_local! {
+///     static REQUEST_ID: u64;
+/// }
+///
+/// #[monoio::main]
+/// async fn main() {
+///     REQUEST_ID
+///         .scope_inherited(42, async {
+///             let handle = monoio::spawn_with_context(async { REQUEST_ID.get() });
+///             assert_eq!(handle.await, 42);
+///         })
+///         .await;
+/// }
+/// ```
+pub fn spawn_with_context<T>(future: T) -> JoinHandle<T::Output>
+where
+    T: Future + 'static,
+    T::Output: 'static,
+{
+    spawn(crate::task::with_context(future))
+}
+
+/// Measures how long an operation takes to go through the driver of the
+/// current runtime and back to the caller.
+///
+/// A no-op is submitted to the io_uring (`IORING_OP_NOP`), and the time until
+/// the awaiting task is resumed with its completion is returned. It includes
+/// running the tasks ready before it, the submission and the reaping of the
+/// completion, so it tells how responsive the event loop is. The legacy
+/// driver has no such operation, it waits for the runtime to run out of ready
+/// tasks instead, see [`before_park`](crate::task::before_park).
+///
+/// Calling this periodically and exporting the latency to metrics makes a
+/// health check of the runtime thread: high latencies point at tasks hogging
+/// the thread, or at an overloaded ring.
+///
+/// # Examples
+///
+/// ```
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let latency = monoio::ping_driver().await?;
+///     println!("driver round trip: {latency:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub async fn ping_driver() -> std::io::Result<Duration> {
+    let start = std::time::Instant::now();
+    if crate::driver::op::is_legacy() {
+        crate::task::before_park().await;
+    }
+    crate::driver::op::Op::nop()?.await.meta.result?;
+    Ok(start.elapsed())
+}
+
+/// A handle to a runtime, which may be sent to other threads to spawn tasks
+/// on it.
+///
+/// The tasks are handed to the runtime through the channel its driver
+/// receives the wakers of other threads on, and spawned on it once it
+/// processes them, waking it up if needed. They run like any other task of
+/// the runtime, and must only be `Send` to get there.
+///
+/// # Examples
+///
+/// ```
+/// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///     .enable_timer()
+///     .build()
+///     .unwrap();
+/// let handle = rt.handle();
+/// let worker = std::thread::spawn(move || {
+///     let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///         .build()
+///         .unwrap();
+///     rt.block_on(async move { handle.spawn_on(async { 21 * 2 }).await })
+/// });
+/// rt.block_on(async {
+///     // Run until the task of the worker thread has been processed.
+///     while !worker.is_finished() {
+///         monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+///     }
+/// });
+/// assert_eq!(worker.join().unwrap(), Some(42));
+/// ```
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeHandle {
+    thread_id: usize,
+}
+
+#[cfg(feature = "sync")]
+impl RuntimeHandle {
+    /// Get the handle of the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn current() -> Self {
+        CURRENT.with(|ctx| Self {
+            thread_id: ctx.thread_id,
+        })
+    }
+
+    /// Spawn a task on the runtime of this handle, returning a
+    /// [`RemoteJoinHandle`] resolving to its output.
+    ///
+    /// It may be called from any thread, including the ones without a
+    /// runtime. If the runtime has been dropped, or drops the task before it
+    /// completes, the future is dropped and the handle resolves to `None`.
+    pub fn spawn_on<T>(&self, future: T) -> RemoteJoinHandle<T::Output>
+    where
+        T: Future + Send + 'static,
+        T::Output: Send + 'static,
+    {
+        use crate::driver::{
+            thread::{get_unpark_handle, get_waker_sender},
+            unpark::Unpark,
+        };
+
+        let (tx, rx) = flume::bounded(1);
+        let future = Box::pin(async move {
+            let _ = tx.send(future.await);
+        });
+        let waker = std::task::Waker::from(std::sync::Arc::new(RemoteSpawn(
+            std::sync::Mutex::new(Some(future)),
+        )));
+        if let Some(sender) = get_waker_sender(self.thread_id) {
+            if sender.send(waker).is_ok() {
+                if let Some(unpark) = get_unpark_handle(self.thread_id) {
+                    let _ = unpark.unpark();
+                }
+            }
+        }
+        RemoteJoinHandle {
+            rx: rx.into_recv_async(),
+        }
+    }
+}
+
+/// A task sent to a runtime, spawned by the runtime when waking it.
+#[cfg(feature = "sync")]
+struct RemoteSpawn(std::sync::Mutex<Option<std::pin::Pin<Box<dyn Future<Output = ()> + Send>>>>);
+
+#[cfg(feature = "sync")]
+impl std::task::Wake for RemoteSpawn {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &std::sync::Arc<Self>) {
+        // The driver wakes it on the thread of the runtime.
+        let future = self.0.lock().ok().and_then(|mut future| future.take());
+        if let Some(future) = future {
+            if CURRENT.is_set() {
+                spawn(future);
+            }
+        }
+    }
+}
+
+/// The output of a task spawned with [`RuntimeHandle::spawn_on`], which may
+/// be awaited on any thread.
+///
+/// It resolves to `None` if the task was dropped before it completed.
+/// Dropping the handle detaches the task.
+#[cfg(feature = "sync")]
+pub struct RemoteJoinHandle<T: 'static> {
+    rx: flume::r#async::RecvFut<'static, T>,
+}
+
+#[cfg(feature = "sync")]
+impl<T: 'static> Future for RemoteJoinHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        std::pin::Pin::new(&mut self.rx).poll(cx).map(Result::ok)
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<T: 'static> std::fmt::Debug for RemoteJoinHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteJoinHandle").finish_non_exhaustive()
+    }
+}
+
 #[cfg(feature = "sync")]
 unsafe fn spawn_without_static<T>(future: T) -> JoinHandle<T::Output>
 where
@@ -391,12 +1962,10 @@ where
     let (task, join) = new_task_holding(
         crate::utils::thread_id::get_current_thread_id(),
         future,
-        LocalScheduler,
+        LocalScheduler::default(),
     );
 
-    CURRENT.with(|ctx| {
-        ctx.tasks.push(task);
-    });
+    CURRENT.with(|ctx| ctx.spawn_task(task, Priority::Normal));
     join
 }
 
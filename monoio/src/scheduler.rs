@@ -1,22 +1,81 @@
 use std::{cell::UnsafeCell, collections::VecDeque, marker::PhantomData};
 
-use crate::task::{Schedule, Task};
+use crate::task::{Schedule, Task, TaskId};
 
-pub(crate) struct LocalScheduler;
+/// The scheduler of the tasks of a thread, keeping the priority of a task.
+#[derive(Default)]
+pub(crate) struct LocalScheduler {
+    priority: Priority,
+}
+
+impl LocalScheduler {
+    pub(crate) const fn new(priority: Priority) -> Self {
+        Self { priority }
+    }
+}
+
+/// The priority of a task, see [`spawn_with_priority`](crate::spawn_with_priority).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Priority {
+    /// Polled before the tasks of normal priority ready at the same time,
+    /// e.g. for heartbeats or control messages.
+    High,
+    /// The priority of the tasks spawned with [`spawn`](crate::spawn).
+    #[default]
+    Normal,
+}
+
+/// The order in which the tasks woken by one driver tick are run.
+///
+/// Tasks are woken in the order the driver reaps their completions or
+/// readiness events. With many connections completing in every tick, e.g.
+/// under batched or multishot completions, this order tends to repeat and
+/// the same connections keep being served first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WakeOrder {
+    /// Run the tasks in the order they were woken.
+    #[default]
+    Completion,
+    /// Shuffle the tasks woken by each tick.
+    Shuffle,
+    /// Rotate the tasks woken by each tick by one more position than the
+    /// previous tick, so each of them takes turns being run first.
+    RoundRobin,
+}
 
 impl Schedule for LocalScheduler {
     fn schedule(&self, task: Task<Self>) {
-        crate::runtime::CURRENT.with(|cx| cx.tasks.push(task));
+        crate::runtime::CURRENT.with(|cx| cx.tasks.push(task, self.priority));
     }
 
     fn yield_now(&self, task: Task<Self>) {
-        crate::runtime::CURRENT.with(|cx| cx.tasks.push_front(task));
+        crate::runtime::CURRENT.with(|cx| {
+            // A task out of budget goes behind the other ready tasks.
+            if cx.budget.take_exhausted() {
+                cx.tasks.push(task, self.priority);
+            } else {
+                cx.tasks.push_front(task, self.priority);
+            }
+        });
+    }
+
+    fn completed(&self, id: TaskId) {
+        if crate::runtime::CURRENT.is_set() {
+            crate::runtime::CURRENT.with(|cx| {
+                crate::runtime::Counters::add(&cx.counters.tasks_completed, 1);
+                if let Some(hooks) = &cx.task_hooks {
+                    hooks.completed(id);
+                }
+            });
+        }
     }
 }
 
 pub(crate) struct TaskQueue {
     // Local queue.
     queue: UnsafeCell<VecDeque<Task<LocalScheduler>>>,
+    // Tasks of high priority, run before the ones in `queue`.
+    high: UnsafeCell<VecDeque<Task<LocalScheduler>>>,
     // Make sure the type is `!Send` and `!Sync`.
     _marker: PhantomData<*const ()>,
 }
@@ -29,10 +88,7 @@ impl Default for TaskQueue {
 
 impl Drop for TaskQueue {
     fn drop(&mut self) {
-        unsafe {
-            let queue = &mut *self.queue.get();
-            while let Some(_task) = queue.pop_front() {}
-        }
+        while let Some(_task) = self.pop() {}
     }
 }
 
@@ -44,11 +100,17 @@ impl TaskQueue {
     pub(crate) fn new_with_capacity(capacity: usize) -> Self {
         Self {
             queue: UnsafeCell::new(VecDeque::with_capacity(capacity)),
+            high: UnsafeCell::new(VecDeque::new()),
             _marker: PhantomData,
         }
     }
 
     pub(crate) fn len(&self) -> usize {
+        unsafe { (*self.queue.get()).len() + (*self.high.get()).len() }
+    }
+
+    /// Number of tasks of normal priority, which are the ones reordered.
+    pub(crate) fn normal_len(&self) -> usize {
         unsafe { (*self.queue.get()).len() }
     }
 
@@ -56,19 +118,53 @@ impl TaskQueue {
         self.len() == 0
     }
 
-    pub(crate) fn push(&self, runnable: Task<LocalScheduler>) {
+    fn lane(&self, priority: Priority) -> *mut VecDeque<Task<LocalScheduler>> {
+        match priority {
+            Priority::High => self.high.get(),
+            Priority::Normal => self.queue.get(),
+        }
+    }
+
+    pub(crate) fn push(&self, runnable: Task<LocalScheduler>, priority: Priority) {
         unsafe {
-            (*self.queue.get()).push_back(runnable);
+            (*self.lane(priority)).push_back(runnable);
         }
     }
 
-    pub(crate) fn push_front(&self, runnable: Task<LocalScheduler>) {
+    pub(crate) fn push_front(&self, runnable: Task<LocalScheduler>, priority: Priority) {
         unsafe {
-            (*self.queue.get()).push_front(runnable);
+            (*self.lane(priority)).push_front(runnable);
         }
     }
 
     pub(crate) fn pop(&self) -> Option<Task<LocalScheduler>> {
-        unsafe { (*self.queue.get()).pop_front() }
+        unsafe {
+            (*self.high.get())
+                .pop_front()
+                .or_else(|| (*self.queue.get()).pop_front())
+        }
+    }
+
+    /// Reorder the tasks queued from position `from` on, `round` is the
+    /// number of times this has been called.
+    pub(crate) fn reorder(&self, from: usize, order: WakeOrder, round: usize) {
+        let queue = unsafe { &mut *self.queue.get() };
+        if queue.len() <= from + 1 {
+            return;
+        }
+        let woken = &mut queue.make_contiguous()[from..];
+        match order {
+            WakeOrder::Completion => (),
+            WakeOrder::Shuffle => {
+                for i in (1..woken.len()).rev() {
+                    let j = crate::utils::thread_rng_n(i as u32 + 1) as usize;
+                    woken.swap(i, j);
+                }
+            }
+            WakeOrder::RoundRobin => {
+                let len = woken.len();
+                woken.rotate_left(round % len);
+            }
+        }
     }
 }
@@ -0,0 +1,49 @@
+use std::{cell::OnceCell, fmt, rc::Rc};
+
+/// A value initialized once per runtime, on first access.
+///
+/// It is meant to be a `static`, see the [module documentation](super): each
+/// runtime has a value of its own, made by the same `init` function.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// use monoio::sync::Lazy;
+///
+/// static REQUESTS: Lazy<Cell<u64>> = Lazy::new(|| Cell::new(0));
+///
+/// #[monoio::main]
+/// async fn main() {
+///     REQUESTS.get().set(REQUESTS.get().get() + 1);
+///     assert_eq!(REQUESTS.get().get(), 1);
+/// }
+/// ```
+pub struct Lazy<T> {
+    init: fn() -> T,
+}
+
+impl<T: 'static> Lazy<T> {
+    /// Create a value initialized by `init` in each runtime.
+    pub const fn new(init: fn() -> T) -> Self {
+        Self { init }
+    }
+
+    /// Returns the value of the current runtime, initializing it if this is
+    /// its first access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime, or from `init`.
+    pub fn get(&'static self) -> Rc<T> {
+        let slot = super::runtime_slot(self as *const Self as usize, OnceCell::<Rc<T>>::new);
+        slot.get_or_init(|| Rc::new((self.init)())).clone()
+    }
+}
+
+impl<T> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,75 @@
+//! Synchronization primitives for the tasks of a runtime.
+//!
+//! The [`mpsc`] and [`oneshot`] channels connect the tasks of a runtime
+//! without atomic operations, and [`mpsc::shared`] the ones of different
+//! threads. [`Mutex`], [`RwLock`] and [`Semaphore`] are asynchronous locks
+//! for the tasks of a runtime, also without atomic operations, e.g. to share
+//! a connection pool. With the `sync` feature, [`watch`] publishes a value
+//! to the tasks of all the runtimes.
+//!
+//! # Per-core singletons
+//!
+//! With a runtime per core, state shared by the tasks of a core does not
+//! need to be shared with the other cores, and keeping it apart spares the
+//! atomics and locks of process-global state. [`OnceCell`] and [`Lazy`] are
+//! declared as `static`s like their std counterparts, but hold one value per
+//! runtime: each runtime initializes its own the first time one of its tasks
+//! accesses it, and drops it when the runtime is dropped. The values are
+//! handed out as [`Rc`](std::rc::Rc)s, and may be interior mutable through
+//! a [`RefCell`](std::cell::RefCell).
+//!
+//! Unlike a `thread_local!`, the value does not outlive the runtime, so it
+//! may hold IO objects bound to the runtime's driver, and a thread running
+//! runtimes one after the other gets a fresh value in each.
+//!
+//! ```
+//! use std::{cell::RefCell, collections::HashMap};
+//!
+//! use monoio::sync::Lazy;
+//!
+//! // The cache of each core, no lock needed.
+//! static CACHE: Lazy<RefCell<HashMap<String, String>>> = Lazy::new(Default::default);
+//!
+//! #[monoio::main]
+//! async fn main() {
+//!     CACHE.get().borrow_mut().insert("key".into(), "value".into());
+//!     assert_eq!(CACHE.get().borrow()["key"], "value");
+//! }
+//! ```
+
+mod lazy;
+pub mod mpsc;
+mod mutex;
+mod once;
+pub mod oneshot;
+mod rwlock;
+mod semaphore;
+#[cfg(feature = "sync")]
+pub mod watch;
+
+pub use lazy::Lazy;
+pub use mutex::{Mutex, MutexGuard};
+pub use once::OnceCell;
+pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use semaphore::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+
+use std::rc::Rc;
+
+// Returns the slot of the current runtime for the cell at `key`, creating it
+// with `new` if missing.
+fn runtime_slot<S: 'static>(key: usize, new: impl FnOnce() -> S) -> Rc<S> {
+    assert!(
+        crate::runtime::CURRENT.is_set(),
+        "runtime-local cells must be accessed within a monoio runtime"
+    );
+    crate::runtime::CURRENT.with(|ctx| {
+        let slot = ctx
+            .locals
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| Rc::new(new()))
+            .clone();
+        slot.downcast()
+            .unwrap_or_else(|_| unreachable!("runtime-local slot of another type"))
+    })
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::VecDeque,
+    task::{Context, Poll, Waker},
+};
+
+use super::{SendError, TryRecvError, TrySendError};
+
+/// The state of a bounded channel, behind a `RefCell` or a `Mutex`.
+///
+/// The wakers to wake are returned in a [`Woken`], to be woken once the
+/// state is released: waking or dropping a task may drop a sender of the
+/// channel.
+pub(super) struct Chan<T> {
+    queue: VecDeque<T>,
+    bound: usize,
+    senders: usize,
+    rx_closed: bool,
+    rx_waker: Option<Waker>,
+    // The senders waiting for room, in order, by the id of their send.
+    tx_waiters: VecDeque<(usize, Waker)>,
+    next_waiter: usize,
+}
+
+/// Wakers taken from a channel.
+#[derive(Default)]
+#[must_use]
+pub(super) struct Woken {
+    rx: Option<Waker>,
+    tx: Vec<Waker>,
+}
+
+impl Woken {
+    pub(super) fn wake(self) {
+        if let Some(waker) = self.rx {
+            waker.wake();
+        }
+        self.tx.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl<T> Chan<T> {
+    pub(super) fn new(bound: usize) -> Self {
+        assert!(bound > 0, "mpsc bound must be positive");
+        Self {
+            queue: VecDeque::new(),
+            bound,
+            senders: 1,
+            rx_closed: false,
+            rx_waker: None,
+            tx_waiters: VecDeque::new(),
+            next_waiter: 0,
+        }
+    }
+
+    pub(super) fn try_send(&mut self, value: T) -> (Result<(), TrySendError<T>>, Woken) {
+        if self.rx_closed {
+            return (Err(TrySendError::Closed(value)), Woken::default());
+        }
+        if self.queue.len() >= self.bound {
+            return (Err(TrySendError::Full(value)), Woken::default());
+        }
+        self.queue.push_back(value);
+        let woken = Woken {
+            rx: self.rx_waker.take(),
+            tx: Vec::new(),
+        };
+        (Ok(()), woken)
+    }
+
+    /// Send the value once there is room, `value` is taken when sent.
+    ///
+    /// `waiter` is the id of the send while it waits, it must be passed to
+    /// [`cancel_send`](Self::cancel_send) if the send is dropped waiting.
+    pub(super) fn poll_send(
+        &mut self,
+        cx: &mut Context<'_>,
+        value: &mut Option<T>,
+        waiter: &mut Option<usize>,
+    ) -> (Poll<Result<(), SendError<T>>>, Woken) {
+        let v = value.take().expect("send polled after completion");
+        let (ret, woken) = self.try_send(v);
+        let poll = match ret {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(TrySendError::Closed(v)) => Poll::Ready(Err(SendError(v))),
+            Err(TrySendError::Full(v)) => {
+                *value = Some(v);
+                let Some(id) = *waiter else {
+                    self.next_waiter = self.next_waiter.wrapping_add(1);
+                    *waiter = Some(self.next_waiter);
+                    self.tx_waiters
+                        .push_back((self.next_waiter, cx.waker().clone()));
+                    return (Poll::Pending, woken);
+                };
+                match self.tx_waiters.iter_mut().find(|(i, _)| *i == id) {
+                    Some((_, waker)) if !waker.will_wake(cx.waker()) => {
+                        waker.clone_from(cx.waker())
+                    }
+                    Some(_) => (),
+                    // Woken for room taken by another send, it keeps its turn.
+                    None => self.tx_waiters.push_front((id, cx.waker().clone())),
+                }
+                return (Poll::Pending, woken);
+            }
+        };
+        if let Some(id) = waiter.take() {
+            self.tx_waiters.retain(|(i, _)| *i != id);
+        }
+        (poll, woken)
+    }
+
+    /// Forget a send dropped while waiting, and hand the room it was woken
+    /// for to the next sender.
+    pub(super) fn cancel_send(&mut self, waiter: usize) -> Woken {
+        let len = self.tx_waiters.len();
+        self.tx_waiters.retain(|(i, _)| *i != waiter);
+        if self.tx_waiters.len() < len || self.queue.len() >= self.bound {
+            return Woken::default();
+        }
+        self.take_sender()
+    }
+
+    pub(super) fn try_recv(&mut self) -> (Result<T, TryRecvError>, Woken) {
+        match self.queue.pop_front() {
+            // Wake one sender for the room made, a sender dropped before
+            // sending passes it on.
+            Some(value) => (Ok(value), self.take_sender()),
+            None if self.senders == 0 || self.rx_closed => {
+                (Err(TryRecvError::Closed), Woken::default())
+            }
+            None => (Err(TryRecvError::Empty), Woken::default()),
+        }
+    }
+
+    pub(super) fn poll_recv(&mut self, cx: &mut Context<'_>) -> (Poll<Option<T>>, Woken) {
+        let (ret, woken) = self.try_recv();
+        let poll = match ret {
+            Ok(value) => Poll::Ready(Some(value)),
+            Err(TryRecvError::Closed) => Poll::Ready(None),
+            Err(TryRecvError::Empty) => {
+                self.rx_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        };
+        (poll, woken)
+    }
+
+    /// Close the receiving side, the values already sent may still be
+    /// received.
+    pub(super) fn close(&mut self) -> Woken {
+        self.rx_closed = true;
+        self.take_senders()
+    }
+
+    pub(super) fn is_closed(&self) -> bool {
+        self.rx_closed
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub(super) fn capacity(&self) -> usize {
+        self.bound - self.queue.len()
+    }
+
+    pub(super) fn add_sender(&mut self) {
+        self.senders += 1;
+    }
+
+    pub(super) fn drop_sender(&mut self) -> Woken {
+        self.senders -= 1;
+        Woken {
+            rx: (self.senders == 0).then(|| self.rx_waker.take()).flatten(),
+            tx: Vec::new(),
+        }
+    }
+
+    /// Close the channel for a dropped receiver, returning the values to
+    /// drop.
+    pub(super) fn drop_receiver(&mut self) -> (VecDeque<T>, Woken) {
+        let woken = self.close();
+        (std::mem::take(&mut self.queue), woken)
+    }
+
+    fn take_sender(&mut self) -> Woken {
+        Woken {
+            rx: None,
+            tx: self
+                .tx_waiters
+                .pop_front()
+                .map(|(_, w)| w)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn take_senders(&mut self) -> Woken {
+        Woken {
+            rx: None,
+            tx: self.tx_waiters.drain(..).map(|(_, w)| w).collect(),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Bounded multi-producer, single-consumer channels.
+//!
+//! [`channel`] connects the tasks of a runtime: it is `!Send`, and its state
+//! is a plain `RefCell` with no atomic operation nor lock. With the `sync`
+//! feature, [`shared::channel`] connects tasks of different threads, or
+//! threads without a runtime: waking a task of another runtime wakes its
+//! driver up, as any cross-thread wake does.
+//!
+//! Sending waits for room in the channel, so that a slow receiver pushes back
+//! on its producers. Receiving returns `None` once all the senders are
+//! dropped and the values sent before are received.
+//!
+//! # Examples
+//!
+//! ```
+//! use monoio::sync::mpsc;
+//!
+//! #[monoio::main]
+//! async fn main() {
+//!     let (tx, mut rx) = mpsc::channel(16);
+//!     for i in 0..4 {
+//!         let tx = tx.clone();
+//!         monoio::spawn(async move { tx.send(i).await.unwrap() });
+//!     }
+//!     drop(tx);
+//!
+//!     let mut sum = 0;
+//!     while let Some(i) = rx.recv().await {
+//!         sum += i;
+//!     }
+//!     assert_eq!(sum, 6);
+//! }
+//! ```
+
+mod chan;
+#[cfg(feature = "sync")]
+pub mod shared;
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::poll_fn,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use self::chan::Chan;
+
+/// Create a bounded channel for the tasks of a runtime, holding up to
+/// `bound` values.
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+pub fn channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    let chan = Rc::new(RefCell::new(Chan::new(bound)));
+    (Sender { chan: chan.clone() }, Receiver { chan })
+}
+
+/// The sending half of a [`channel`], which may be cloned.
+pub struct Sender<T> {
+    chan: Rc<RefCell<Chan<T>>>,
+}
+
+/// The receiving half of a [`channel`].
+///
+/// Dropping it closes the channel, and drops the values not received.
+pub struct Receiver<T> {
+    chan: Rc<RefCell<Chan<T>>>,
+}
+
+impl<T> Sender<T> {
+    /// Send a value, waiting for room in the channel.
+    ///
+    /// Returns the value back if the receiver is closed.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        let mut waiting = Waiting {
+            chan: &self.chan,
+            id: None,
+        };
+        poll_fn(|cx| {
+            let (poll, woken) = self
+                .chan
+                .borrow_mut()
+                .poll_send(cx, &mut value, &mut waiting.id);
+            woken.wake();
+            poll
+        })
+        .await
+    }
+
+    /// Send a value if there is room in the channel, without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let (ret, woken) = self.chan.borrow_mut().try_send(value);
+        woken.wake();
+        ret
+    }
+
+    /// Returns true if the receiver is closed or dropped.
+    pub fn is_closed(&self) -> bool {
+        self.chan.borrow().is_closed()
+    }
+
+    /// Returns the number of values which may be sent without waiting.
+    pub fn capacity(&self) -> usize {
+        self.chan.borrow().capacity()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.borrow_mut().add_sender();
+        Self {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let woken = self.chan.borrow_mut().drop_sender();
+        woken.wake();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The turn of a send waiting for room, given up when it is dropped.
+struct Waiting<'a, T> {
+    chan: &'a RefCell<Chan<T>>,
+    id: Option<usize>,
+}
+
+impl<T> Drop for Waiting<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            let woken = self.chan.borrow_mut().cancel_send(id);
+            woken.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receive a value, waiting for one to be sent.
+    ///
+    /// Returns `None` once the channel is empty and all the senders are
+    /// dropped, or the receiver is closed.
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Poll to receive a value, see [`recv`](Receiver::recv).
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let (poll, woken) = self.chan.borrow_mut().poll_recv(cx);
+        woken.wake();
+        poll
+    }
+
+    /// Receive a value if there is one, without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let (ret, woken) = self.chan.borrow_mut().try_recv();
+        woken.wake();
+        ret
+    }
+
+    /// Close the channel: sending fails from now on, while the values already
+    /// sent may still be received.
+    pub fn close(&mut self) {
+        let woken = self.chan.borrow_mut().close();
+        woken.wake();
+    }
+
+    /// Returns the number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.chan.borrow().len()
+    }
+
+    /// Returns true if the channel holds no value.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let (values, woken) = self.chan.borrow_mut().drop_receiver();
+        woken.wake();
+        drop(values);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// The error of sending to a closed channel, holding the value which could
+/// not be sent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// The error of [`try_send`](Sender::try_send), holding the value which
+/// could not be sent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+    /// The receiver is closed.
+    Closed(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value which could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(value) | TrySendError::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Closed(_) => f.write_str("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("sending on a full channel"),
+            TrySendError::Closed(_) => f.write_str("sending on a closed channel"),
+        }
+    }
+}
+
+impl<T> std::error::Error for TrySendError<T> {}
+
+/// The error of `try_recv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The channel is empty.
+    Empty,
+    /// The channel is empty, and all the senders are dropped or the receiver
+    /// is closed.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("receiving on an empty channel"),
+            TryRecvError::Closed => f.write_str("receiving on a closed channel"),
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
@@ -0,0 +1,190 @@
+//! A bounded channel between threads, see the [module documentation](super).
+//!
+//! # Examples
+//!
+//! ```
+//! use monoio::sync::mpsc::shared;
+//!
+//! #[monoio::main]
+//! async fn main() {
+//!     let (tx, mut rx) = shared::channel(16);
+//!     let producer = std::thread::spawn(move || {
+//!         // The sender may be awaited by any executor.
+//!         for i in 0..4 {
+//!             futures::executor::block_on(tx.send(i)).unwrap();
+//!         }
+//!     });
+//!
+//!     let mut received = Vec::new();
+//!     while let Some(i) = rx.recv().await {
+//!         received.push(i);
+//!     }
+//!     assert_eq!(received, [0, 1, 2, 3]);
+//!     producer.join().unwrap();
+//! }
+//! ```
+
+use std::{
+    fmt,
+    future::poll_fn,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+};
+
+use super::{chan::Chan, SendError, TryRecvError, TrySendError};
+
+/// Create a bounded channel between threads, holding up to `bound` values.
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+pub fn channel<T: Send>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    let chan = Arc::new(Mutex::new(Chan::new(bound)));
+    (Sender { chan: chan.clone() }, Receiver { chan })
+}
+
+/// The sending half of a [`channel`], which may be cloned and sent to other
+/// threads.
+pub struct Sender<T> {
+    chan: Arc<Mutex<Chan<T>>>,
+}
+
+/// The receiving half of a [`channel`].
+///
+/// Dropping it closes the channel, and drops the values not received.
+pub struct Receiver<T> {
+    chan: Arc<Mutex<Chan<T>>>,
+}
+
+// The state is consistent whenever the lock is released, even by a panic.
+fn lock<T>(chan: &Mutex<Chan<T>>) -> MutexGuard<'_, Chan<T>> {
+    chan.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> Sender<T> {
+    /// Send a value, waiting for room in the channel.
+    ///
+    /// Returns the value back if the receiver is closed.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        let mut waiting = Waiting {
+            chan: &self.chan,
+            id: None,
+        };
+        poll_fn(|cx| {
+            let (poll, woken) = lock(&self.chan).poll_send(cx, &mut value, &mut waiting.id);
+            woken.wake();
+            poll
+        })
+        .await
+    }
+
+    /// Send a value if there is room in the channel, without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let (ret, woken) = lock(&self.chan).try_send(value);
+        woken.wake();
+        ret
+    }
+
+    /// Returns true if the receiver is closed or dropped.
+    pub fn is_closed(&self) -> bool {
+        lock(&self.chan).is_closed()
+    }
+
+    /// Returns the number of values which may be sent without waiting.
+    pub fn capacity(&self) -> usize {
+        lock(&self.chan).capacity()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        lock(&self.chan).add_sender();
+        Self {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let woken = lock(&self.chan).drop_sender();
+        woken.wake();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The turn of a send waiting for room, given up when it is dropped.
+struct Waiting<'a, T> {
+    chan: &'a Mutex<Chan<T>>,
+    id: Option<usize>,
+}
+
+impl<T> Drop for Waiting<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            let woken = lock(self.chan).cancel_send(id);
+            woken.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receive a value, waiting for one to be sent.
+    ///
+    /// Returns `None` once the channel is empty and all the senders are
+    /// dropped, or the receiver is closed.
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Poll to receive a value, see [`recv`](Receiver::recv).
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let (poll, woken) = lock(&self.chan).poll_recv(cx);
+        woken.wake();
+        poll
+    }
+
+    /// Receive a value if there is one, without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let (ret, woken) = lock(&self.chan).try_recv();
+        woken.wake();
+        ret
+    }
+
+    /// Close the channel: sending fails from now on, while the values already
+    /// sent may still be received.
+    pub fn close(&mut self) {
+        let woken = lock(&self.chan).close();
+        woken.wake();
+    }
+
+    /// Returns the number of values in the channel.
+    pub fn len(&self) -> usize {
+        lock(&self.chan).len()
+    }
+
+    /// Returns true if the channel holds no value.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let (values, woken) = lock(&self.chan).drop_receiver();
+        woken.wake();
+        drop(values);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
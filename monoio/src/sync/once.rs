@@ -0,0 +1,186 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    future::{poll_fn, Future},
+    marker::PhantomData,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+/// A cell initialized once per runtime, possibly asynchronously.
+///
+/// It is meant to be a `static`, see the [module documentation](super): each
+/// runtime has a value of its own.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::sync::OnceCell;
+///
+/// static CONFIG: OnceCell<String> = OnceCell::new();
+///
+/// async fn load() -> String {
+///     "config".to_string()
+/// }
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let config = CONFIG.get_or_init(load).await;
+///     assert_eq!(*config, "config");
+/// }
+/// ```
+pub struct OnceCell<T> {
+    // Never zero-sized, so that each cell has an address of its own, which
+    // keys its values.
+    _key: u8,
+    _value: PhantomData<fn() -> T>,
+}
+
+struct Slot<T> {
+    value: std::cell::OnceCell<Rc<T>>,
+    initializing: Cell<bool>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Self {
+            value: std::cell::OnceCell::new(),
+            initializing: Cell::new(false),
+            waiters: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+// Ends an initialization, completed or abandoned, and wakes the tasks
+// waiting for it.
+struct Initializing<'a, T>(&'a Slot<T>);
+
+impl<T> Drop for Initializing<'_, T> {
+    fn drop(&mut self) {
+        self.0.initializing.set(false);
+        let waiters = std::mem::take(&mut *self.0.waiters.borrow_mut());
+        waiters.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl<T: 'static> OnceCell<T> {
+    /// Create a cell, empty in every runtime.
+    pub const fn new() -> Self {
+        Self {
+            _key: 0,
+            _value: PhantomData,
+        }
+    }
+
+    fn slot(&'static self) -> Rc<Slot<T>> {
+        super::runtime_slot(self as *const Self as usize, Slot::new)
+    }
+
+    /// Returns the value of the current runtime, if initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn get(&'static self) -> Option<Rc<T>> {
+        self.slot().value.get().cloned()
+    }
+
+    /// Returns `true` if the cell is initialized in the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn initialized(&'static self) -> bool {
+        self.slot().value.get().is_some()
+    }
+
+    /// Set the value of the current runtime, or give `value` back if the
+    /// cell is already initialized, or being initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn set(&'static self, value: T) -> Result<(), T> {
+        let slot = self.slot();
+        if slot.initializing.get() || slot.value.get().is_some() {
+            return Err(value);
+        }
+        let _ = slot.value.set(Rc::new(value));
+        Ok(())
+    }
+
+    /// Returns the value of the current runtime, initializing it with `init`
+    /// if the cell is empty.
+    ///
+    /// Only one task of a runtime initializes the cell, the others wait for
+    /// it. If it is canceled, one of them takes over.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub async fn get_or_init<F, Fut>(&'static self, init: F) -> Rc<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let res = self
+            .get_or_try_init(|| async { Ok::<_, std::convert::Infallible>(init().await) })
+            .await;
+        match res {
+            Ok(value) => value,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Returns the value of the current runtime, initializing it with `init`
+    /// if the cell is empty. If `init` fails, the cell is left empty and
+    /// another task waiting for it takes over.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub async fn get_or_try_init<F, Fut, E>(&'static self, init: F) -> Result<Rc<T>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let slot = self.slot();
+        loop {
+            if let Some(value) = slot.value.get() {
+                return Ok(value.clone());
+            }
+            if !slot.initializing.get() {
+                break;
+            }
+            let mut registered = false;
+            poll_fn(|cx| {
+                if registered && !slot.initializing.get() {
+                    return Poll::Ready(());
+                }
+                registered = true;
+                slot.waiters.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            })
+            .await;
+        }
+
+        slot.initializing.set(true);
+        let _initializing = Initializing(&slot);
+        let value = Rc::new(init().await?);
+        let _ = slot.value.set(value.clone());
+        Ok(value)
+    }
+}
+
+impl<T: 'static> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceCell").finish_non_exhaustive()
+    }
+}
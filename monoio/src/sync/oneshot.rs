@@ -0,0 +1,185 @@
+//! A channel sending a single value between the tasks of a runtime.
+//!
+//! # Examples
+//!
+//! ```
+//! use monoio::sync::oneshot;
+//!
+//! #[monoio::main]
+//! async fn main() {
+//!     let (tx, rx) = oneshot::channel();
+//!     monoio::spawn(async move {
+//!         let _ = tx.send("done");
+//!     });
+//!     assert_eq!(rx.await, Ok("done"));
+//! }
+//! ```
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// Create a oneshot channel.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Rc::new(RefCell::new(Inner {
+        value: None,
+        tx_dropped: false,
+        rx_closed: false,
+        rx_waker: None,
+    }));
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+struct Inner<T> {
+    value: Option<T>,
+    tx_dropped: bool,
+    rx_closed: bool,
+    rx_waker: Option<Waker>,
+}
+
+/// The sending half of a oneshot [`channel`].
+pub struct Sender<T> {
+    inner: Rc<RefCell<Inner<T>>>,
+}
+
+/// The receiving half of a oneshot [`channel`], a future resolving to the
+/// value sent.
+pub struct Receiver<T> {
+    inner: Rc<RefCell<Inner<T>>>,
+}
+
+impl<T> Sender<T> {
+    /// Send the value, or return it back if the receiver is closed or
+    /// dropped.
+    pub fn send(self, value: T) -> Result<(), T> {
+        let waker = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.rx_closed {
+                return Err(value);
+            }
+            inner.value = Some(value);
+            inner.rx_waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Returns true if the receiver is closed or dropped.
+    pub fn is_closed(&self) -> bool {
+        self.inner.borrow().rx_closed
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut inner = self.inner.borrow_mut();
+            inner.tx_dropped = true;
+            inner.rx_waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receive the value if it was sent, without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut inner = self.inner.borrow_mut();
+        match inner.value.take() {
+            Some(value) => Ok(value),
+            None if inner.tx_dropped || inner.rx_closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Close the channel, so that sending fails. A value sent before may
+    /// still be received.
+    pub fn close(&mut self) {
+        self.inner.borrow_mut().rx_closed = true;
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Ok(value)),
+            Err(TryRecvError::Closed) => Poll::Ready(Err(RecvError)),
+            Err(TryRecvError::Empty) => {
+                self.inner.borrow_mut().rx_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let value = {
+            let mut inner = self.inner.borrow_mut();
+            inner.rx_closed = true;
+            inner.value.take()
+        };
+        drop(value);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// The error of receiving from a oneshot channel whose sender was dropped
+/// without sending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("oneshot sender dropped without sending")
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// The error of [`Receiver::try_recv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No value was sent yet.
+    Empty,
+    /// No value was sent, and the sender is dropped or the receiver closed.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("oneshot value not sent yet"),
+            TryRecvError::Closed => f.write_str("oneshot channel closed"),
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
@@ -0,0 +1,319 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// A semaphore for the tasks of a runtime, with no atomic operation.
+///
+/// Permits are handed out in the order they are asked for: a task asking for
+/// many permits is not overtaken by the ones asking for fewer after it.
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use monoio::sync::Semaphore;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     // At most 2 connections at a time.
+///     let connections = Rc::new(Semaphore::new(2));
+///     let mut tasks = Vec::new();
+///     for i in 0..4 {
+///         let permit = connections.clone().acquire_owned().await;
+///         tasks.push(monoio::spawn(async move {
+///             let _permit = permit;
+///             i
+///         }));
+///     }
+///     for task in tasks {
+///         task.await;
+///     }
+///     assert_eq!(connections.available_permits(), 2);
+/// }
+/// ```
+pub struct Semaphore {
+    state: RefCell<State>,
+}
+
+struct State {
+    permits: usize,
+    waiters: VecDeque<Rc<Waiter>>,
+}
+
+struct Waiter {
+    needed: usize,
+    granted: Cell<bool>,
+    waker: Cell<Option<Waker>>,
+}
+
+impl State {
+    /// Grant the permits to the waiters in order, returning their wakers.
+    fn grant(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        while let Some(waiter) = self.waiters.front() {
+            if waiter.needed > self.permits {
+                break;
+            }
+            self.permits -= waiter.needed;
+            waiter.granted.set(true);
+            wakers.extend(waiter.waker.take());
+            self.waiters.pop_front();
+        }
+        wakers
+    }
+}
+
+impl Semaphore {
+    /// Create a semaphore with the given number of permits.
+    pub const fn new(permits: usize) -> Self {
+        Self {
+            state: RefCell::new(State {
+                permits,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the number of permits available.
+    pub fn available_permits(&self) -> usize {
+        self.state.borrow().permits
+    }
+
+    /// Add permits, waking the tasks waiting for them.
+    pub fn add_permits(&self, n: usize) {
+        let wakers = {
+            let mut state = self.state.borrow_mut();
+            state.permits += n;
+            state.grant()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Wait for a permit.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1).await
+    }
+
+    /// Wait for `n` permits.
+    pub async fn acquire_many(&self, n: usize) -> SemaphorePermit<'_> {
+        self.take(n).await;
+        SemaphorePermit {
+            semaphore: self,
+            permits: n,
+        }
+    }
+
+    /// Wait for a permit, which holds the semaphore.
+    pub async fn acquire_owned(self: Rc<Self>) -> OwnedSemaphorePermit {
+        self.acquire_many_owned(1).await
+    }
+
+    /// Wait for `n` permits, which hold the semaphore.
+    pub async fn acquire_many_owned(self: Rc<Self>, n: usize) -> OwnedSemaphorePermit {
+        self.take(n).await;
+        OwnedSemaphorePermit {
+            semaphore: self,
+            permits: n,
+        }
+    }
+
+    /// Take a permit if one is available and no task is waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Take `n` permits if they are available and no task is waiting.
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        self.try_take(n).then(|| SemaphorePermit {
+            semaphore: self,
+            permits: n,
+        })
+    }
+
+    /// Take a permit holding the semaphore, if one is available and no task
+    /// is waiting.
+    pub fn try_acquire_owned(self: Rc<Self>) -> Option<OwnedSemaphorePermit> {
+        self.try_take(1).then(|| OwnedSemaphorePermit {
+            semaphore: self,
+            permits: 1,
+        })
+    }
+
+    fn try_take(&self, n: usize) -> bool {
+        let mut state = self.state.borrow_mut();
+        if state.waiters.is_empty() && state.permits >= n {
+            state.permits -= n;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take(&self, n: usize) -> Acquire<'_> {
+        Acquire {
+            semaphore: self,
+            needed: n,
+            waiter: None,
+            done: false,
+        }
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.borrow();
+        f.debug_struct("Semaphore")
+            .field("permits", &state.permits)
+            .field("waiters", &state.waiters.len())
+            .finish()
+    }
+}
+
+/// Takes permits from a semaphore, in turn with the other waiters.
+struct Acquire<'a> {
+    semaphore: &'a Semaphore,
+    needed: usize,
+    waiter: Option<Rc<Waiter>>,
+    done: bool,
+}
+
+impl Future for Acquire<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        match &this.waiter {
+            Some(waiter) if waiter.granted.get() => {
+                this.done = true;
+                Poll::Ready(())
+            }
+            Some(waiter) => {
+                waiter.waker.set(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            None if this.semaphore.try_take(this.needed) => {
+                this.done = true;
+                Poll::Ready(())
+            }
+            None => {
+                let waiter = Rc::new(Waiter {
+                    needed: this.needed,
+                    granted: Cell::new(false),
+                    waker: Cell::new(Some(cx.waker().clone())),
+                });
+                this.semaphore
+                    .state
+                    .borrow_mut()
+                    .waiters
+                    .push_back(waiter.clone());
+                this.waiter = Some(waiter);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        let Some(waiter) = self.waiter.take() else {
+            return;
+        };
+        if self.done {
+            return;
+        }
+        let wakers = {
+            let mut state = self.semaphore.state.borrow_mut();
+            if waiter.granted.get() {
+                // Give back the permits granted but not taken.
+                state.permits += waiter.needed;
+            } else {
+                state.waiters.retain(|w| !Rc::ptr_eq(w, &waiter));
+            }
+            // The waiters behind may be served now.
+            state.grant()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// Permits of a [`Semaphore`], given back when dropped.
+#[must_use = "the permits are given back when dropped"]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Drop the permits without giving them back to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.add_permits(self.permits);
+        }
+    }
+}
+
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
+
+/// Permits of a [`Semaphore`] holding it, given back when dropped.
+#[must_use = "the permits are given back when dropped"]
+pub struct OwnedSemaphorePermit {
+    semaphore: Rc<Semaphore>,
+    permits: usize,
+}
+
+impl OwnedSemaphorePermit {
+    /// Returns the number of permits held.
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Returns the semaphore of the permits.
+    pub fn semaphore(&self) -> &Rc<Semaphore> {
+        &self.semaphore
+    }
+
+    /// Drop the permits without giving them back to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.add_permits(self.permits);
+        }
+    }
+}
+
+impl fmt::Debug for OwnedSemaphorePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
@@ -0,0 +1,280 @@
+//! A channel publishing the latest value to many receivers, e.g. a
+//! configuration reloaded at runtime.
+//!
+//! The sender and receivers may be on different threads: a receiver waiting
+//! for a change on a runtime is woken through its driver, which is unparked
+//! if it sleeps. Receivers only see the latest value, the ones published in
+//! between are skipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use monoio::sync::watch;
+//!
+//! #[derive(Clone, Debug, PartialEq)]
+//! struct Config {
+//!     max_connections: usize,
+//! }
+//!
+//! let (tx, rx) = watch::channel(Config {
+//!     max_connections: 16,
+//! });
+//! let worker = std::thread::spawn(move || {
+//!     let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+//!         .build()
+//!         .unwrap();
+//!     rt.block_on(async move {
+//!         let mut rx = rx;
+//!         while rx.borrow_and_update().max_connections != 64 {
+//!             rx.changed().await.unwrap();
+//!         }
+//!     });
+//! });
+//! tx.send(Config {
+//!     max_connections: 64,
+//! });
+//! worker.join().unwrap();
+//! ```
+
+use std::{
+    fmt,
+    future::poll_fn,
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Poll, Waker},
+};
+
+/// Create a watch channel holding `init`.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: init,
+        version: 0,
+        closed: false,
+        receivers: 1,
+        next_id: 1,
+        wakers: Default::default(),
+    }));
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver {
+            shared,
+            id: 0,
+            seen: 0,
+        },
+    )
+}
+
+struct Shared<T> {
+    value: T,
+    version: u64,
+    closed: bool,
+    receivers: usize,
+    next_id: usize,
+    // Wakers of the receivers waiting for a change, by receiver.
+    wakers: fxhash::FxHashMap<usize, Waker>,
+}
+
+impl<T> Shared<T> {
+    fn publish(&mut self) -> Vec<Waker> {
+        self.version += 1;
+        self.wakers.drain().map(|(_, waker)| waker).collect()
+    }
+}
+
+// Values are only replaced or modified in place under the lock.
+fn lock<T>(shared: &Mutex<Shared<T>>) -> MutexGuard<'_, Shared<T>> {
+    shared.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The sending half of a watch [`channel`].
+///
+/// Dropping it closes the channel: the receivers keep the last value, and
+/// stop waiting for changes.
+pub struct Sender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+/// A receiving half of a watch [`channel`], which may be cloned.
+pub struct Receiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    id: usize,
+    // Version of the value last seen.
+    seen: u64,
+}
+
+/// A reference to the value of a watch channel, holding its lock.
+///
+/// The sender waits for it to be dropped to publish a value, so it should
+/// not be held across an `.await`.
+pub struct Ref<'a, T> {
+    guard: MutexGuard<'a, Shared<T>>,
+}
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Sender<T> {
+    /// Publish a value, waking the receivers waiting for a change.
+    ///
+    /// The value is published even if there is no receiver, for the ones
+    /// [`subscribe`](Sender::subscribe)d later.
+    pub fn send(&self, value: T) {
+        self.send_modify(|current| *current = value);
+    }
+
+    /// Modify the value in place and publish it, see [`send`](Sender::send).
+    pub fn send_modify(&self, modify: impl FnOnce(&mut T)) {
+        let wakers = {
+            let mut shared = lock(&self.shared);
+            modify(&mut shared.value);
+            shared.publish()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Returns a reference to the current value.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref {
+            guard: lock(&self.shared),
+        }
+    }
+
+    /// Create a receiver, which has seen the current value.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut shared = lock(&self.shared);
+        Receiver::new(&self.shared, &mut shared)
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        lock(&self.shared).receivers
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut shared = lock(&self.shared);
+            shared.closed = true;
+            std::mem::take(&mut shared.wakers)
+        };
+        wakers.into_values().for_each(Waker::wake);
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Receiver<T> {
+    fn new(arc: &Arc<Mutex<Shared<T>>>, shared: &mut Shared<T>) -> Self {
+        let id = shared.next_id;
+        shared.next_id += 1;
+        shared.receivers += 1;
+        Self {
+            shared: arc.clone(),
+            id,
+            seen: shared.version,
+        }
+    }
+
+    /// Returns a reference to the current value, without marking it seen.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref {
+            guard: lock(&self.shared),
+        }
+    }
+
+    /// Returns a reference to the current value, and marks it seen.
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let guard = lock(&self.shared);
+        self.seen = guard.version;
+        Ref { guard }
+    }
+
+    /// Returns whether a value not seen yet was published.
+    ///
+    /// Fails if the sender is dropped.
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        let shared = lock(&self.shared);
+        if shared.closed {
+            return Err(RecvError);
+        }
+        Ok(shared.version != self.seen)
+    }
+
+    /// Wait for a value not seen yet to be published, and mark it seen.
+    ///
+    /// Returns at once if one was published since the value was last seen.
+    /// Fails if the sender is dropped.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        poll_fn(|cx| {
+            let mut shared = lock(&self.shared);
+            if shared.version != self.seen {
+                self.seen = shared.version;
+                shared.wakers.remove(&self.id);
+                return Poll::Ready(Ok(()));
+            }
+            if shared.closed {
+                return Poll::Ready(Err(RecvError));
+            }
+            shared.wakers.insert(self.id, cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        let mut shared = lock(&self.shared);
+        let mut receiver = Receiver::new(&self.shared, &mut shared);
+        receiver.seen = self.seen;
+        receiver
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut shared = lock(&self.shared);
+            shared.receivers -= 1;
+            shared.wakers.remove(&self.id)
+        };
+        drop(waker);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// The error of waiting for a change of a watch channel whose sender is
+/// dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("watch sender dropped")
+    }
+}
+
+impl std::error::Error for RecvError {}
@@ -0,0 +1,50 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::runtime::CURRENT;
+
+/// Waits until the runtime has run out of ready tasks and is about to park
+/// the driver, i.e. the end of the current event loop iteration.
+///
+/// This is useful to batch work issued by several tasks during one iteration,
+/// e.g. to flush buffered writes once instead of after each write. Waiting
+/// tasks are woken at most once per park. The driver does not block while
+/// tasks wait, it polls for IO without sleeping instead.
+///
+/// # Examples
+///
+/// ```
+/// #[monoio::main]
+/// async fn main() {
+///     monoio::task::before_park().await;
+/// }
+/// ```
+pub fn before_park() -> BeforePark {
+    BeforePark { round: None }
+}
+
+/// Future returned by [`before_park`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct BeforePark {
+    round: Option<u64>,
+}
+
+impl Future for BeforePark {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        CURRENT.with(|ctx| {
+            let current = ctx.park_round.get();
+            match self.round {
+                Some(round) if current > round => return Poll::Ready(()),
+                _ => self.round = Some(current),
+            }
+            ctx.park_waiters.borrow_mut().push(cx.waker().clone());
+            Poll::Pending
+        })
+    }
+}
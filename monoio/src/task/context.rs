@@ -0,0 +1,136 @@
+//! Context propagation from parent tasks to child tasks.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// A task-local key whose current value can be captured for a child task.
+pub(crate) trait Inherit {
+    fn capture(&'static self) -> Option<Box<dyn Captured>>;
+}
+
+/// A captured value which can be re-entered around a poll.
+pub(crate) trait Captured {
+    fn enter(&mut self, f: &mut dyn FnMut());
+}
+
+thread_local! {
+    /// Inheritable keys of the scopes being polled, outermost first.
+    static INHERITED: RefCell<Vec<&'static dyn Inherit>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a key as inheritable until dropped.
+pub(crate) struct InheritGuard(());
+
+impl InheritGuard {
+    pub(crate) fn push(key: &'static dyn Inherit) -> Self {
+        INHERITED.with(|i| i.borrow_mut().push(key));
+        Self(())
+    }
+}
+
+impl Drop for InheritGuard {
+    fn drop(&mut self) {
+        INHERITED.with(|i| i.borrow_mut().pop());
+    }
+}
+
+fn capture() -> Vec<Box<dyn Captured>> {
+    let mut captured = Vec::new();
+
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        if !span.is_none() {
+            captured.push(Box::new(CapturedSpan(span)) as Box<dyn Captured>);
+        }
+    }
+
+    INHERITED.with(|i| {
+        let keys = i.borrow();
+        for (idx, key) in keys.iter().enumerate() {
+            let addr = *key as *const dyn Inherit as *const ();
+            // Nested scopes of the same key share the innermost value.
+            if keys[..idx]
+                .iter()
+                .any(|k| *k as *const dyn Inherit as *const () == addr)
+            {
+                continue;
+            }
+            captured.extend(key.capture());
+        }
+    });
+    captured
+}
+
+#[cfg(feature = "tracing")]
+struct CapturedSpan(tracing::Span);
+
+#[cfg(feature = "tracing")]
+impl Captured for CapturedSpan {
+    fn enter(&mut self, f: &mut dyn FnMut()) {
+        self.0.in_scope(f)
+    }
+}
+
+fn enter_all(captured: &mut [Box<dyn Captured>], f: &mut dyn FnMut()) {
+    match captured.split_first_mut() {
+        Some((first, rest)) => first.enter(&mut || enter_all(rest, f)),
+        None => f(),
+    }
+}
+
+/// Wraps `future` so that it runs within the context of the caller.
+///
+/// The context is captured when this function is called and consists of:
+/// - the values of task-locals scoped with
+///   [`LocalKey::scope_inherited`](super::LocalKey::scope_inherited), including the current
+///   [`Deadline`](crate::time::Deadline);
+/// - the current `tracing` span, when the `tracing` feature is enabled.
+///
+/// The context is re-entered every time the returned future is polled.
+///
+/// [`spawn_with_context`](crate::spawn_with_context) is a shortcut for
+/// spawning such a future.
+pub fn with_context<F: Future>(future: F) -> WithContext<F> {
+    WithContext {
+        captured: capture(),
+        future,
+    }
+}
+
+pin_project! {
+    /// Future returned by [`with_context`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct WithContext<F> {
+        captured: Vec<Box<dyn Captured>>,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for WithContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        let mut future = me.future;
+        let mut res = Poll::Pending;
+        enter_all(me.captured, &mut || res = future.as_mut().poll(cx));
+        res
+    }
+}
+
+impl<F> fmt::Debug for WithContext<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithContext")
+            .field("captured", &self.captured.len())
+            .finish()
+    }
+}
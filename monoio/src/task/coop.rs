@@ -0,0 +1,150 @@
+//! Cooperative scheduling budget of the tasks.
+//!
+//! A task may only complete so many operations per poll: once its budget is
+//! spent, the next operation it polls returns `Pending` and the task is
+//! queued behind the other ready tasks. A loop over IO which is always ready
+//! does not starve the other tasks of the thread this way.
+
+use std::{
+    cell::Cell,
+    future::poll_fn,
+    task::{Context, Poll},
+};
+
+/// Default number of operations a task may complete per poll.
+pub(crate) const DEFAULT_BUDGET: u32 = 128;
+
+/// The budget of the task being polled, `None` when it is unconstrained.
+pub(crate) struct Budget {
+    initial: Option<u32>,
+    remaining: Cell<Option<u32>>,
+    exhausted: Cell<bool>,
+}
+
+impl Budget {
+    pub(crate) const fn new(initial: Option<u32>) -> Self {
+        Self {
+            initial,
+            remaining: Cell::new(None),
+            exhausted: Cell::new(false),
+        }
+    }
+
+    /// Run the poll of a task with a full budget.
+    pub(crate) fn with<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Reset<'a>(&'a Budget, Option<u32>);
+
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                self.0.remaining.set(self.1);
+            }
+        }
+
+        self.exhausted.set(false);
+        let _reset = Reset(self, self.remaining.replace(self.initial));
+        f()
+    }
+
+    /// Returns whether the task polled last has spent its budget, and was
+    /// woken to be polled again.
+    pub(crate) fn take_exhausted(&self) -> bool {
+        self.exhausted.replace(false)
+    }
+}
+
+/// Gives the budget back unless the operation completed.
+pub(crate) struct RestoreOnPending(Option<u32>);
+
+impl RestoreOnPending {
+    /// The operation completed, keep its unit of the budget spent.
+    #[inline]
+    pub(crate) fn made_progress(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for RestoreOnPending {
+    fn drop(&mut self) {
+        if let Some(remaining) = self.0 {
+            with_budget(|budget| budget.remaining.set(Some(remaining)));
+        }
+    }
+}
+
+fn with_budget<R>(f: impl FnOnce(&Budget) -> R) -> Option<R> {
+    crate::runtime::CURRENT
+        .is_set()
+        .then(|| crate::runtime::CURRENT.with(|cx| f(&cx.budget)))
+}
+
+/// Spend one unit of the budget of the current task for an operation, or
+/// wake the task and return `Pending` if it has none left.
+pub(crate) fn poll_proceed(cx: &mut Context<'_>) -> Poll<RestoreOnPending> {
+    let remaining = with_budget(|budget| match budget.remaining.get() {
+        Some(0) => {
+            budget.exhausted.set(true);
+            Err(())
+        }
+        Some(n) => {
+            budget.remaining.set(Some(n - 1));
+            Ok(Some(n))
+        }
+        None => Ok(None),
+    });
+    match remaining.unwrap_or(Ok(None)) {
+        Ok(restore) => Poll::Ready(RestoreOnPending(restore)),
+        Err(()) => {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Spend one unit of the budget of the current task, yielding to the other
+/// tasks if it has none left.
+///
+/// The IO operations of monoio spend the budget when they complete, see
+/// [`RuntimeBuilder::with_task_budget`](crate::RuntimeBuilder::with_task_budget).
+/// This is a checkpoint for loops which may not hit any, e.g. over data
+/// already buffered. It does nothing outside a task of the runtime.
+///
+/// # Examples
+///
+/// ```
+/// #[monoio::main]
+/// async fn main() {
+///     let mut sum = 0u64;
+///     for i in 0..10_000 {
+///         sum += i;
+///         monoio::task::consume_budget().await;
+///     }
+///     assert_eq!(sum, 49_995_000);
+/// }
+/// ```
+pub async fn consume_budget() {
+    let mut status = Poll::Pending;
+    poll_fn(move |cx| {
+        if status.is_ready() {
+            return status;
+        }
+        status = poll_proceed(cx).map(RestoreOnPending::made_progress);
+        status
+    })
+    .await
+}
+
+/// Let the other ready tasks run before the current task is polled again.
+pub(crate) async fn yield_now() {
+    let mut yielded = false;
+    poll_fn(move |cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        // Queued behind the other tasks, as if out of budget.
+        with_budget(|budget| budget.exhausted.set(true));
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
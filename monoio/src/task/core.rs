@@ -32,6 +32,8 @@ pub(crate) struct CoreStage<T: Future> {
 pub(crate) enum Stage<T: Future> {
     Running(T),
     Finished(T::Output),
+    // The task panicked, isolated by the panic policy of the runtime.
+    Panicked(Box<dyn std::any::Any + Send>),
     Consumed,
 }
 
@@ -125,7 +127,19 @@ impl<T: Future> CoreStage<T> {
         }
     }
 
-    /// Take the task output
+    /// Store the panic of the task
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure it is safe to mutate the `stage` field.
+    pub(crate) fn store_panic(&self, panic: Box<dyn std::any::Any + Send>) {
+        // Safety: the caller ensures mutual exclusion to the field.
+        unsafe {
+            self.set_stage(Stage::Panicked(panic));
+        }
+    }
+
+    /// Take the task output, resuming its panic if it panicked
     ///
     /// # Safety
     ///
@@ -137,13 +151,34 @@ impl<T: Future> CoreStage<T> {
             // Safety:: the caller ensures mutual exclusion to the field.
             match mem::replace(unsafe { &mut *ptr }, Stage::Consumed) {
                 Stage::Finished(output) => output,
+                Stage::Panicked(panic) => std::panic::resume_unwind(panic),
                 _ => panic!("JoinHandle polled after completion"),
             }
         })
     }
 
     unsafe fn set_stage(&self, stage: Stage<T>) {
-        self.with_mut(|ptr| *ptr = stage)
+        // Writes the new stage once the previous one is dropped, even if
+        // dropping it panics.
+        struct Write<T: Future> {
+            ptr: *mut Stage<T>,
+            stage: Option<Stage<T>>,
+        }
+
+        impl<T: Future> Drop for Write<T> {
+            fn drop(&mut self) {
+                unsafe { std::ptr::write(self.ptr, self.stage.take().unwrap_unchecked()) };
+            }
+        }
+
+        self.with_mut(|ptr| {
+            let _write = Write {
+                ptr,
+                stage: Some(stage),
+            };
+            // The future is pinned, it must be dropped in place.
+            std::ptr::drop_in_place(ptr);
+        })
     }
 }
 
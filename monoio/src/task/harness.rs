@@ -7,6 +7,7 @@ use std::{
 
 use super::utils::UnsafeCellExt;
 use crate::{
+    runtime::PanicPolicy,
     task::{
         core::{Cell, Core, CoreStage, Header, Trailer},
         state::Snapshot,
@@ -281,6 +282,9 @@ where
         // stage. We transition from running to complete.
 
         let snapshot = self.header().state.transition_to_complete();
+        self.core()
+            .scheduler
+            .completed(super::TaskId(self.cell.as_ptr() as usize));
 
         // We catch panics here in case dropping the future or waking the
         // JoinHandle panics.
@@ -410,41 +414,37 @@ enum PollFuture {
 /// Poll the future. If the future completes, the output is written to the
 /// stage field.
 fn poll_future<T: Future>(core: &CoreStage<T>, cx: Context<'_>) -> Poll<()> {
-    // CHIHAI: For efficiency we do not catch.
-
-    // Poll the future.
-    // let output = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-    //     struct Guard<'a, T: Future> {
-    //         core: &'a CoreStage<T>,
-    //     }
-    //     impl<'a, T: Future> Drop for Guard<'a, T> {
-    //         fn drop(&mut self) {
-    //             // If the future panics on poll, we drop it inside the panic
-    //             // guard.
-    //             self.core.drop_future_or_output();
-    //         }
-    //     }
-    //     let guard = Guard { core };
-    //     let res = guard.core.poll(cx);
-    //     mem::forget(guard);
-    //     res
-    // }));
-    let output = core.poll(cx);
-
-    // Prepare output for being placed in the core stage.
-    let output = match output {
-        // Ok(Poll::Pending) => return Poll::Pending,
-        // Ok(Poll::Ready(output)) => Ok(output),
-        // Err(panic) => Err(JoinError::panic(panic)),
-        Poll::Pending => return Poll::Pending,
-        Poll::Ready(output) => output,
-    };
-
-    // Catch and ignore panics if the future panics on drop.
-    // let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-    //     core.store_output(output);
-    // }));
-    core.store_output(output);
-
-    Poll::Ready(())
+    // CHIHAI: For efficiency we do not catch, unless the panic policy of the
+    // runtime asks to.
+    let policy = crate::runtime::panic_policy();
+    if policy == PanicPolicy::Unwind {
+        return match core.poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(output) => {
+                core.store_output(output);
+                Poll::Ready(())
+            }
+        };
+    }
+
+    let output = panic::catch_unwind(panic::AssertUnwindSafe(|| core.poll(cx)));
+    match output {
+        Ok(Poll::Pending) => Poll::Pending,
+        Ok(Poll::Ready(output)) => {
+            core.store_output(output);
+            Poll::Ready(())
+        }
+        Err(panic) => {
+            if policy == PanicPolicy::Abort {
+                std::process::abort();
+            }
+            // The future may have panicked on drop once ready, otherwise drop
+            // it inside the panic guard, it may panic as well.
+            let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                core.drop_future_or_output();
+            }));
+            core.store_panic(panic);
+            Poll::Ready(())
+        }
+    }
 }
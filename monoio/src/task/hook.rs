@@ -0,0 +1,116 @@
+use std::{cell::RefCell, sync::Arc, time::Duration};
+
+/// An identifier of a task, unique among the tasks alive in the process.
+///
+/// The identifier of a completed task may be given to a task spawned later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(pub(crate) usize);
+
+impl TaskId {
+    /// Returns the identifier as an integer.
+    #[inline]
+    pub fn as_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// Callbacks run by a runtime around the tasks it runs, registered with
+/// [`RuntimeBuilder::with_task_hook`](crate::RuntimeBuilder::with_task_hook).
+///
+/// They are called on the thread of the runtime, and every method does
+/// nothing by default. One hook can be shared by the runtimes of several
+/// threads by registering it in an [`Arc`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use monoio::task::{TaskHook, TaskId};
+///
+/// struct SlowPolls;
+///
+/// impl TaskHook for SlowPolls {
+///     fn on_poll(&self, id: TaskId, elapsed: Duration) {
+///         if elapsed > Duration::from_millis(10) {
+///             eprintln!("task {} blocked the thread for {elapsed:?}", id.as_u64());
+///         }
+///     }
+/// }
+///
+/// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///     .with_task_hook(SlowPolls)
+///     .build()
+///     .unwrap();
+/// rt.block_on(async {
+///     monoio::spawn(async {}).await;
+/// });
+/// ```
+pub trait TaskHook: Send + Sync + 'static {
+    /// Called when a task is spawned.
+    fn on_spawn(&self, _id: TaskId) {}
+
+    /// Called before a task is polled for the first time.
+    fn on_first_poll(&self, _id: TaskId) {}
+
+    /// Called after each poll of a task, with the time the poll took. The
+    /// poll completing the task is reported after
+    /// [`on_complete`](TaskHook::on_complete).
+    fn on_poll(&self, _id: TaskId, _elapsed: Duration) {}
+
+    /// Called when the future of a task has completed.
+    fn on_complete(&self, _id: TaskId) {}
+}
+
+impl<H: TaskHook + ?Sized> TaskHook for Arc<H> {
+    fn on_spawn(&self, id: TaskId) {
+        (**self).on_spawn(id)
+    }
+
+    fn on_first_poll(&self, id: TaskId) {
+        (**self).on_first_poll(id)
+    }
+
+    fn on_poll(&self, id: TaskId, elapsed: Duration) {
+        (**self).on_poll(id, elapsed)
+    }
+
+    fn on_complete(&self, id: TaskId) {
+        (**self).on_complete(id)
+    }
+}
+
+/// The task hook of a runtime, and the tasks it has not polled yet.
+pub(crate) struct TaskHooks {
+    hook: Arc<dyn TaskHook>,
+    unpolled: RefCell<fxhash::FxHashSet<TaskId>>,
+}
+
+impl TaskHooks {
+    #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+    pub(crate) fn new(hook: Arc<dyn TaskHook>) -> Self {
+        Self {
+            hook,
+            unpolled: Default::default(),
+        }
+    }
+
+    pub(crate) fn spawned(&self, id: TaskId) {
+        self.unpolled.borrow_mut().insert(id);
+        self.hook.on_spawn(id);
+    }
+
+    pub(crate) fn run(&self, task: super::Task<crate::scheduler::LocalScheduler>) {
+        let id = task.id();
+        if self.unpolled.borrow_mut().remove(&id) {
+            self.hook.on_first_poll(id);
+        }
+        let start = std::time::Instant::now();
+        task.run();
+        self.hook.on_poll(id, start.elapsed());
+    }
+
+    pub(crate) fn completed(&self, id: TaskId) {
+        self.hook.on_complete(id);
+    }
+}
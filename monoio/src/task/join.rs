@@ -25,6 +25,12 @@ impl<T> JoinHandle<T> {
         }
     }
 
+    /// Returns the identifier of the task, as given to its
+    /// [`TaskHook`](super::TaskHook).
+    pub fn id(&self) -> super::TaskId {
+        super::TaskId(self.raw.header() as *const _ as usize)
+    }
+
     /// Checks if the task associated with this `JoinHandle` has finished.
     pub fn is_finished(&self) -> bool {
         let state = self.raw.header().state.load();
@@ -0,0 +1,209 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use pin_project_lite::pin_project;
+
+use super::JoinHandle;
+
+/// A set of tasks spawned on the current runtime, awaited in the order they
+/// complete.
+///
+/// The tasks still in the set when it is dropped are aborted: their futures
+/// are dropped the next time they are polled, without being polled again.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::task::JoinSet;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let mut set = JoinSet::new();
+///     for i in 0..4 {
+///         set.spawn(async move { i * 2 });
+///     }
+///     let mut sum = 0;
+///     while let Some(n) = set.join_next().await {
+///         sum += n;
+///     }
+///     assert_eq!(sum, 12);
+/// }
+/// ```
+pub struct JoinSet<T> {
+    tasks: fxhash::FxHashMap<u64, Entry<T>>,
+    next_key: u64,
+    shared: Rc<Shared>,
+}
+
+struct Entry<T> {
+    handle: JoinHandle<Option<T>>,
+    abort: Rc<Abort>,
+}
+
+/// Tasks of the set which are done, and the waker of its owner.
+#[derive(Default)]
+struct Shared {
+    done: RefCell<VecDeque<u64>>,
+    waker: Cell<Option<Waker>>,
+}
+
+/// Abort flag of a task, and its waker.
+#[derive(Default)]
+struct Abort {
+    aborted: Cell<bool>,
+    waker: Cell<Option<Waker>>,
+}
+
+impl<T: 'static> JoinSet<T> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self {
+            tasks: Default::default(),
+            next_key: 0,
+            shared: Default::default(),
+        }
+    }
+
+    /// Returns the number of tasks in the set, including the completed ones
+    /// not joined yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns true if the set has no task.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Spawn a task on the current runtime and add it to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the monoio runtime.
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = T> + 'static,
+    {
+        let key = self.next_key;
+        self.next_key += 1;
+        let abort = Rc::new(Abort::default());
+        let handle = crate::spawn(Tracked {
+            future,
+            abort: abort.clone(),
+            _done: Done {
+                key,
+                shared: self.shared.clone(),
+            },
+        });
+        self.tasks.insert(key, Entry { handle, abort });
+    }
+
+    /// Wait for one of the tasks of the set to complete and return its
+    /// output, or `None` if the set is empty.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of a task which panicked, if the panic policy of the
+    /// runtime isolates it.
+    pub async fn join_next(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_join_next(cx)).await
+    }
+
+    /// Poll for one of the tasks of the set to complete, see
+    /// [`join_next`](JoinSet::join_next).
+    pub fn poll_join_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            if self.tasks.is_empty() {
+                return Poll::Ready(None);
+            }
+            let Some(key) = self.shared.done.borrow().front().copied() else {
+                self.shared.waker.set(Some(cx.waker().clone()));
+                return Poll::Pending;
+            };
+            let entry = self.tasks.get_mut(&key).expect("unknown task");
+            // The future is dropped before the task is marked complete.
+            let output = std::task::ready!(Pin::new(&mut entry.handle).poll(cx));
+            self.shared.done.borrow_mut().pop_front();
+            self.tasks.remove(&key);
+            if let Some(output) = output {
+                return Poll::Ready(Some(output));
+            }
+        }
+    }
+}
+
+impl<T: 'static> Default for JoinSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for JoinSet<T> {
+    fn drop(&mut self) {
+        for entry in self.tasks.values() {
+            entry.abort.aborted.set(true);
+            if let Some(waker) = entry.abort.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for JoinSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinSet")
+            .field("len", &self.tasks.len())
+            .finish()
+    }
+}
+
+pin_project! {
+    /// A task of a set, returning `None` if aborted.
+    struct Tracked<F> {
+        #[pin]
+        future: F,
+        abort: Rc<Abort>,
+        // Dropped with the future, once the task completes or is aborted.
+        _done: Done,
+    }
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if this.abort.aborted.get() {
+            return Poll::Ready(None);
+        }
+        match this.future.poll(cx) {
+            Poll::Ready(output) => Poll::Ready(Some(output)),
+            Poll::Pending => {
+                this.abort.waker.set(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Notifies the set that a task is done when dropped.
+struct Done {
+    key: u64,
+    shared: Rc<Shared>,
+}
+
+impl Drop for Done {
+    fn drop(&mut self) {
+        self.shared.done.borrow_mut().push_back(self.key);
+        if let Some(waker) = self.shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
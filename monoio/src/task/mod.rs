@@ -15,6 +15,24 @@ mod join;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::join::JoinHandle;
 
+mod join_set;
+pub use self::join_set::JoinSet;
+
+mod before_park;
+mod hook;
+pub use self::before_park::{before_park, BeforePark};
+pub(crate) use self::hook::TaskHooks;
+pub use self::hook::{TaskHook, TaskId};
+
+mod context;
+pub use self::context::{with_context, WithContext};
+
+pub(crate) mod coop;
+pub use self::coop::consume_budget;
+
+mod task_local;
+pub use self::task_local::{AccessError, LocalKey, TaskLocalFuture};
+
 mod raw;
 use self::raw::RawTask;
 
@@ -43,6 +61,10 @@ impl<S: 'static> Task<S> {
         self.raw.header()
     }
 
+    pub(crate) fn id(&self) -> TaskId {
+        TaskId(self.header() as *const Header as usize)
+    }
+
     pub(crate) fn run(self) {
         self.raw.poll();
     }
@@ -71,6 +93,8 @@ pub(crate) trait Schedule: Sized + 'static {
     fn yield_now(&self, task: Task<Self>) {
         self.schedule(task);
     }
+    /// Called once the task has completed.
+    fn completed(&self, _id: TaskId) {}
 }
 
 pub(crate) fn new_task<T, S>(
@@ -0,0 +1,456 @@
+//! Task-local storage.
+// Heavily borrowed from tokio.
+// Copyright (c) 2021 Tokio Contributors, licensed under the MIT license.
+
+use std::{
+    cell::RefCell,
+    error, fmt,
+    future::Future,
+    marker::PhantomPinned,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+use super::context::{Captured, Inherit, InheritGuard};
+
+/// Declares a new task-local key of type [`LocalKey`].
+///
+/// # Syntax
+///
+/// The macro wraps any number of static declarations and makes them local to
+/// the current task. Publicity and attributes for each static is preserved.
+/// For example:
+///
+/// ```
+/// monoio::task_local! {
+///     pub static ONE: u32;
+///
+///     #[allow(unused)]
+///     static TWO: f32;
+/// }
+/// # fn main() {}
+/// ```
+///
+/// See [`LocalKey`] documentation for more information.
+#[macro_export]
+macro_rules! task_local {
+    // empty (base case for the recursion)
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)*) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+        $crate::task_local!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inner {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $t:ty) => {
+        $(#[$attr])*
+        $vis static $name: $crate::task::LocalKey<$t> = {
+            ::std::thread_local! {
+                static __KEY: ::std::cell::RefCell<::std::option::Option<$t>> =
+                    const { ::std::cell::RefCell::new(::std::option::Option::None) };
+            }
+
+            $crate::task::LocalKey { inner: &__KEY }
+        };
+    };
+}
+
+/// A key for task-local data.
+///
+/// This type is generated by the [`task_local!`](crate::task_local) macro.
+///
+/// Unlike [`std::thread::LocalKey`], `monoio::task::LocalKey` will
+/// _not_ lazily initialize the value on first access. Instead, the
+/// value is first initialized when the future containing
+/// the task-local is first polled by a futures executor.
+///
+/// The value is bound to the future passed to [`LocalKey::scope`] and is
+/// carried across its `.await` points. Futures passed to
+/// [`spawn`](crate::spawn) do not inherit it; use
+/// [`LocalKey::scope_inherited`] together with
+/// [`spawn_with_context`](crate::spawn_with_context) to propagate it to child
+/// tasks.
+///
+/// # Examples
+///
+/// ```
+/// monoio::task_local! {
+///     static REQUEST_ID: u64;
+/// }
+///
+/// #[monoio::main]
+/// async fn main() {
+///     REQUEST_ID
+///         .scope(42, async move {
+///             assert_eq!(REQUEST_ID.get(), 42);
+///         })
+///         .await;
+///     assert!(REQUEST_ID.try_with(|_| ()).is_err());
+/// }
+/// ```
+pub struct LocalKey<T: 'static> {
+    #[doc(hidden)]
+    pub inner: &'static std::thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> LocalKey<T> {
+    /// Sets a value `T` as the task-local value for the future `F`.
+    ///
+    /// On completion of `scope`, the task-local will be dropped.
+    pub fn scope<F>(&'static self, value: T, f: F) -> TaskLocalFuture<T, F>
+    where
+        F: Future,
+    {
+        TaskLocalFuture {
+            local: self,
+            slot: Some(value),
+            future: Some(f),
+            inherit: None,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Sets a value `T` as the task-local value for the closure `F`.
+    ///
+    /// On completion of `sync_scope`, the task-local will be dropped.
+    pub fn sync_scope<F, R>(&'static self, value: T, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let mut value = Some(value);
+        match self.scope_inner(&mut value, None, f) {
+            Ok(res) => res,
+            Err(err) => err.panic(),
+        }
+    }
+
+    fn scope_inner<F, R>(
+        &'static self,
+        slot: &mut Option<T>,
+        inherit: Option<&'static dyn Inherit>,
+        f: F,
+    ) -> Result<R, ScopeInnerErr>
+    where
+        F: FnOnce() -> R,
+    {
+        struct Guard<'a, T: 'static> {
+            local: &'static LocalKey<T>,
+            slot: &'a mut Option<T>,
+        }
+
+        impl<T: 'static> Drop for Guard<'_, T> {
+            fn drop(&mut self) {
+                // This should not panic.
+                //
+                // We know that the RefCell was not borrowed before the call to
+                // `scope_inner`, so the only way for this to panic is if the
+                // closure has created but not destroyed a RefCell guard.
+                // However, we never give user-code access to the guards, so
+                // there's no way for user-code to forget to destroy a guard.
+                //
+                // The call to `with` also should not panic, since the
+                // thread-local wasn't destroyed when we first called
+                // `scope_inner`, and it shouldn't have gotten destroyed since
+                // then.
+                self.local.inner.with(|inner| {
+                    let mut ref_mut = inner.borrow_mut();
+                    mem::swap(self.slot, &mut *ref_mut);
+                });
+            }
+        }
+
+        self.inner.try_with(|inner| {
+            inner
+                .try_borrow_mut()
+                .map(|mut ref_mut| mem::swap(slot, &mut *ref_mut))
+        })??;
+
+        let guard = Guard { local: self, slot };
+        let inherit = inherit.map(InheritGuard::push);
+
+        let res = f();
+
+        drop(inherit);
+        drop(guard);
+
+        Ok(res)
+    }
+
+    /// Accesses the current task-local and runs the provided closure.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the task local doesn't have a value set.
+    #[track_caller]
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        match self.try_with(f) {
+            Ok(res) => res,
+            Err(_) => panic!("cannot access a task-local storage value without setting it first"),
+        }
+    }
+
+    /// Accesses the current task-local and runs the provided closure.
+    ///
+    /// If the task-local with the associated key is not present, this
+    /// method will return an `AccessError`. For a panicking variant,
+    /// see `with`.
+    pub fn try_with<F, R>(&'static self, f: F) -> Result<R, AccessError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        // If called after the thread-local storing the task-local is destroyed,
+        // then we are outside of a closure where the task-local is set.
+        //
+        // Therefore, it is correct to return an AccessError if `try_with`
+        // returns an error.
+        let try_with_res = self.inner.try_with(|v| {
+            // This call to `borrow` cannot panic because no user-defined code
+            // runs while a `borrow_mut` call is active.
+            v.borrow().as_ref().map(f)
+        });
+
+        match try_with_res {
+            Ok(Some(res)) => Ok(res),
+            Ok(None) | Err(_) => Err(AccessError { _private: () }),
+        }
+    }
+}
+
+impl<T: Clone + 'static> LocalKey<T> {
+    /// Like [`scope`](LocalKey::scope), but the value is also inherited by
+    /// tasks spawned with [`spawn_with_context`](crate::spawn_with_context)
+    /// from inside the future `F`.
+    ///
+    /// The child task gets a clone of the value which is current at the time
+    /// of spawning.
+    pub fn scope_inherited<F>(&'static self, value: T, f: F) -> TaskLocalFuture<T, F>
+    where
+        F: Future,
+    {
+        TaskLocalFuture {
+            local: self,
+            slot: Some(value),
+            future: Some(f),
+            inherit: Some(self),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Returns a copy of the task-local value
+    /// if the task-local value implements `Clone`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the task local doesn't have a value set.
+    #[track_caller]
+    pub fn get(&'static self) -> T {
+        self.with(|v| v.clone())
+    }
+}
+
+impl<T: 'static> fmt::Debug for LocalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalKey { .. }")
+    }
+}
+
+pin_project! {
+    /// A future that sets a value `T` of a task local for the future `F` during
+    /// its execution.
+    ///
+    /// The value of the task-local must be `'static` and will be dropped on the
+    /// completion of the future.
+    ///
+    /// Created by the function [`LocalKey::scope`](self::LocalKey::scope).
+    pub struct TaskLocalFuture<T, F>
+    where
+        T: 'static,
+    {
+        local: &'static LocalKey<T>,
+        slot: Option<T>,
+        #[pin]
+        future: Option<F>,
+        inherit: Option<&'static dyn Inherit>,
+        #[pin]
+        _pinned: PhantomPinned,
+    }
+
+    impl<T: 'static, F> PinnedDrop for TaskLocalFuture<T, F> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            if mem::needs_drop::<F>() && this.future.is_some() {
+                // Drop the future while the task-local is set, if possible.
+                // Otherwise the future is dropped normally when the `Option<F>`
+                // field drops.
+                let mut future = this.future;
+                let _ = this.local.scope_inner(this.slot, *this.inherit, || {
+                    future.set(None);
+                });
+            }
+        }
+    }
+}
+
+impl<T: 'static, F> TaskLocalFuture<T, F> {
+    /// Returns the value stored in the task local by this `TaskLocalFuture`.
+    ///
+    /// The function returns `None` if the future is currently being polled,
+    /// which can only happen from inside the future itself.
+    pub fn take_value(self: Pin<&mut Self>) -> Option<T> {
+        let this = self.project();
+        this.slot.take()
+    }
+
+    /// Consumes this `TaskLocalFuture`, returning the underlying future. It is
+    /// `!Unpin`, so it cannot have been polled.
+    pub(crate) fn into_inner(mut self) -> F {
+        self.future
+            .take()
+            .expect("`TaskLocalFuture` moved after being polled")
+    }
+}
+
+impl<T: 'static, F: Future> Future for TaskLocalFuture<T, F> {
+    type Output = F::Output;
+
+    #[track_caller]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut future_opt = this.future;
+
+        let res = this.local.scope_inner(this.slot, *this.inherit, || {
+            match future_opt.as_mut().as_pin_mut() {
+                Some(fut) => {
+                    let res = fut.poll(cx);
+                    if res.is_ready() {
+                        future_opt.set(None);
+                    }
+                    Some(res)
+                }
+                None => None,
+            }
+        });
+
+        match res {
+            Ok(Some(res)) => res,
+            Ok(None) => panic!("`TaskLocalFuture` polled after completion"),
+            Err(err) => err.panic(),
+        }
+    }
+}
+
+impl<T: 'static, F> fmt::Debug for TaskLocalFuture<T, F>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Format the Option without Some.
+        struct TransparentOption<'a, T> {
+            value: &'a Option<T>,
+        }
+        impl<T: fmt::Debug> fmt::Debug for TransparentOption<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.value.as_ref() {
+                    Some(value) => value.fmt(f),
+                    // Hitting the None branch should not be possible.
+                    None => f.pad("<missing>"),
+                }
+            }
+        }
+
+        f.debug_struct("TaskLocalFuture")
+            .field("value", &TransparentOption { value: &self.slot })
+            .finish()
+    }
+}
+
+/// An error returned by [`LocalKey::try_with`](method@LocalKey::try_with).
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct AccessError {
+    _private: (),
+}
+
+impl fmt::Debug for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessError").finish()
+    }
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt("task-local value not set", f)
+    }
+}
+
+impl error::Error for AccessError {}
+
+impl<T: Clone + 'static> Inherit for LocalKey<T> {
+    fn capture(&'static self) -> Option<Box<dyn Captured>> {
+        let slot = self.try_with(T::clone).ok()?;
+        Some(Box::new(CapturedValue {
+            local: self,
+            slot: Some(slot),
+        }))
+    }
+}
+
+/// A task-local value captured for a child task.
+struct CapturedValue<T: 'static> {
+    local: &'static LocalKey<T>,
+    slot: Option<T>,
+}
+
+impl<T: Clone + 'static> Captured for CapturedValue<T> {
+    fn enter(&mut self, f: &mut dyn FnMut()) {
+        // Keep it inheritable so the grandchildren see it too.
+        if let Err(err) = self.local.scope_inner(&mut self.slot, Some(self.local), f) {
+            err.panic()
+        }
+    }
+}
+
+enum ScopeInnerErr {
+    BorrowError,
+    AccessError,
+}
+
+impl ScopeInnerErr {
+    #[track_caller]
+    fn panic(&self) -> ! {
+        match self {
+            Self::BorrowError => {
+                panic!("cannot enter a task-local scope while the task-local storage is borrowed")
+            }
+            Self::AccessError => panic!(
+                "cannot enter a task-local scope during or after destruction of the underlying \
+                 thread-local"
+            ),
+        }
+    }
+}
+
+impl From<std::cell::BorrowMutError> for ScopeInnerErr {
+    fn from(_: std::cell::BorrowMutError) -> Self {
+        Self::BorrowError
+    }
+}
+
+impl From<std::thread::AccessError> for ScopeInnerErr {
+    fn from(_: std::thread::AccessError) -> Self {
+        Self::AccessError
+    }
+}
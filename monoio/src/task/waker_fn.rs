@@ -41,6 +41,12 @@ pub(crate) fn should_poll() -> bool {
     SHOULD_POLL.replace(false)
 }
 
+/// Returns whether the main future was woken, without clearing the flag.
+#[inline]
+pub(crate) fn is_woken() -> bool {
+    SHOULD_POLL.get()
+}
+
 #[inline]
 pub(crate) fn set_poll() {
     SHOULD_POLL.set(true);
@@ -0,0 +1,163 @@
+//! Deadline propagation across nested calls.
+//!
+//! See [`Deadline`] documentation for more details.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+use crate::{
+    task::TaskLocalFuture,
+    time::{error::Elapsed, sleep_until, Duration, Instant, Sleep},
+};
+
+crate::task_local! {
+    static CURRENT: Instant;
+}
+
+/// A point in time by which a request must be finished.
+///
+/// A deadline is attached to a future with [`Deadline::scope`]. While that
+/// future (and everything it awaits) runs, [`Deadline::current`] returns the
+/// deadline, so nested calls can inherit it without passing it through every
+/// function signature, e.g. to forward it to downstream services or to bail
+/// out early with [`Deadline::check`].
+///
+/// Nested scopes can only tighten the deadline: the effective deadline of a
+/// scope is the earliest of its own and the one inherited from the caller.
+///
+/// The deadline is bound to the scoped future, not to the task: futures
+/// passed to [`spawn`](crate::spawn) do not inherit it, while the ones passed
+/// to [`spawn_with_context`](crate::spawn_with_context) do.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::time::{Deadline, Duration};
+///
+/// async fn call_backend() -> std::io::Result<()> {
+///     // Short-circuit if the request has already run out of time.
+///     Deadline::check()?;
+///     let _remaining = Deadline::current().map(|d| d.remaining());
+///     Ok(())
+/// }
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let res = Deadline::after(Duration::from_millis(100))
+///         .scope(call_backend())
+///         .await;
+///     assert!(matches!(res, Ok(Ok(()))));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// Create a deadline at the given instant.
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    /// Create a deadline `duration` from now.
+    pub fn after(duration: Duration) -> Self {
+        Self(
+            Instant::now()
+                .checked_add(duration)
+                .unwrap_or_else(Instant::far_future),
+        )
+    }
+
+    /// Returns the deadline inherited by the current scope, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|d| Self(*d)).ok()
+    }
+
+    /// Returns an error if the inherited deadline has been reached.
+    pub fn check() -> Result<(), Elapsed> {
+        match Self::current() {
+            Some(d) if d.is_elapsed() => Err(Elapsed::new()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the instant of the deadline.
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    /// Returns the time left before the deadline, or zero if it has passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    /// Returns `true` if the deadline has passed.
+    pub fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.0
+    }
+
+    /// Run `future` with this deadline.
+    ///
+    /// The deadline is visible through [`Deadline::current`] inside the future
+    /// and is also enforced: if the future does not complete in time, it is
+    /// canceled and an error is returned.
+    ///
+    /// The timer must be enabled on the runtime.
+    pub fn scope<F: Future>(self, future: F) -> DeadlineScope<F> {
+        let deadline = match Self::current() {
+            Some(inherited) => self.min(inherited),
+            None => self,
+        };
+        DeadlineScope {
+            value: CURRENT.scope_inherited(deadline.0, future),
+            delay: sleep_until(deadline.0),
+            deadline,
+        }
+    }
+}
+
+pin_project! {
+    /// Future returned by [`Deadline::scope`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    #[derive(Debug)]
+    pub struct DeadlineScope<F> {
+        #[pin]
+        value: TaskLocalFuture<Instant, F>,
+        #[pin]
+        delay: Sleep,
+        deadline: Deadline,
+    }
+}
+
+impl<F> DeadlineScope<F> {
+    /// Returns the effective deadline of this scope.
+    pub fn deadline(&self) -> Deadline {
+        self.deadline
+    }
+
+    /// Consumes this scope, returning the underlying future.
+    pub fn into_inner(self) -> F {
+        self.value.into_inner()
+    }
+}
+
+impl<F: Future> Future for DeadlineScope<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+
+        if let Poll::Ready(v) = me.value.poll(cx) {
+            return Poll::Ready(Ok(v));
+        }
+
+        match me.delay.poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed::new())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
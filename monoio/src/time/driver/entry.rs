@@ -61,7 +61,10 @@ use std::{
 };
 
 use super::Handle;
-use crate::{time::Instant, utils::linked_list};
+use crate::{
+    time::Instant,
+    utils::{cache_padded::CachePadded, linked_list},
+};
 
 type TimerResult = Result<(), crate::time::error::Error>;
 
@@ -573,8 +576,3 @@ impl Drop for TimerEntry {
         unsafe { Pin::new_unchecked(self) }.as_mut().cancel()
     }
 }
-
-#[cfg_attr(target_arch = "x86_64", repr(align(128)))]
-#[cfg_attr(not(target_arch = "x86_64"), repr(align(64)))]
-#[derive(Debug, Default)]
-struct CachePadded<T>(T);
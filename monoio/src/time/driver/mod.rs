@@ -215,6 +215,16 @@ where
 }
 
 impl Handle {
+    /// Returns whether a timer is registered and has not fired yet.
+    pub(crate) fn has_pending_timers(&self) -> bool {
+        self.get()
+            .state
+            .borrow()
+            .wheel
+            .next_expiration_time()
+            .is_some()
+    }
+
     /// Runs timer related logic, and returns the next wakeup time
     pub(self) fn process(&self) {
         let now = self.time_source().now();
@@ -236,7 +246,7 @@ impl Handle {
         }
         while let Some(entry) = state.wheel.poll(now) {
             if let Some(waker) = unsafe { entry.fire(Ok(())) } {
-                waker.wake();
+                crate::runtime::isolate_wake(|| waker.wake());
             }
         }
         state.elapsed = state.wheel.elapsed();
@@ -302,7 +312,7 @@ impl Handle {
         // Wake the waker; this is needed because we might reset _after_ a poll,
         // and otherwise the task won't be awoken to poll again.
         if let Some(waker) = waker {
-            waker.wake();
+            crate::runtime::isolate_wake(|| waker.wake());
         }
     }
 }
@@ -103,6 +103,10 @@ pin_project! {
     /// use it with [`select!`] or by calling `poll`, you have to pin it first.
     /// If you use it with `.await`, this does not apply.
     ///
+    /// The timer entry is stored inline, so a `Sleep` does not allocate unless
+    /// it is boxed to be pinned. A pinned `Sleep` can be reused for another
+    /// deadline with [`reset`](Sleep::reset).
+    ///
     /// # Examples
     ///
     /// Wait 100ms and print "100 ms have elapsed".
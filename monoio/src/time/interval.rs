@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    io,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -114,7 +115,7 @@ pub fn interval_at(start: Instant, period: Duration) -> Interval {
     assert!(period > Duration::new(0, 0), "`period` must be non-zero.");
 
     Interval {
-        delay: Box::pin(sleep_until(start)),
+        delay: Delay::Sleep(Box::pin(sleep_until(start))),
         period,
         missed_tick_behavior: Default::default(),
     }
@@ -350,7 +351,8 @@ impl Default for MissedTickBehavior {
     }
 }
 
-/// Interval returned by [`interval`] and [`interval_at`]
+/// Interval returned by [`interval`], [`interval_at`] and
+/// [`Interval::system_clock`]
 ///
 /// This type allows you to wait on a sequence of instants with a certain
 /// duration between each instant. Unlike calling [`sleep`] in a loop, this lets
@@ -358,7 +360,7 @@ impl Default for MissedTickBehavior {
 #[derive(Debug)]
 pub struct Interval {
     /// Future that completes the next time the `Interval` yields a value.
-    delay: Pin<Box<Sleep>>,
+    delay: Delay,
 
     /// The duration between values yielded by `Interval`.
     period: Duration,
@@ -367,7 +369,55 @@ pub struct Interval {
     missed_tick_behavior: MissedTickBehavior,
 }
 
+#[derive(Debug)]
+enum Delay {
+    Sleep(Pin<Box<Sleep>>),
+    #[cfg(target_os = "linux")]
+    SystemClock(super::timerfd::SystemClock),
+}
+
 impl Interval {
+    /// Creates a new `Interval` backed by a `CLOCK_REALTIME` timerfd, which
+    /// ticks on the multiples of `period` since the UNIX epoch, i.e. aligned to
+    /// the wall clock (e.g. every full minute for a period of 60s). The first
+    /// tick completes at the next multiple.
+    ///
+    /// Unlike [`interval`], it follows wall clock changes and does not require
+    /// the timer to be enabled. The returned instants are the times the ticks
+    /// are observed. With [`MissedTickBehavior::Delay`], the alignment is lost
+    /// after a missed tick.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use monoio::time::Interval;
+    ///
+    /// #[monoio::main]
+    /// async fn main() {
+    ///     let mut interval = Interval::system_clock(Duration::from_secs(60)).unwrap();
+    ///     loop {
+    ///         interval.tick().await;
+    ///         // report the metrics of the minute
+    ///     }
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn system_clock(period: Duration) -> io::Result<Interval> {
+        assert!(period > Duration::new(0, 0), "`period` must be non-zero.");
+
+        Ok(Interval {
+            delay: Delay::SystemClock(super::timerfd::SystemClock::new(period)?),
+            period,
+            missed_tick_behavior: Default::default(),
+        })
+    }
+
     /// Completes when the next instant in the interval has been reached.
     ///
     /// # Examples
@@ -388,10 +438,39 @@ impl Interval {
     ///     // approximately 20ms have elapsed.
     /// }
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// An interval of [`Interval::system_clock`] panics if its timerfd fails,
+    /// see [`try_tick`](Interval::try_tick).
     pub async fn tick(&mut self) -> Instant {
         poll_fn(|cx| self.poll_tick(cx)).await
     }
 
+    /// Completes when the next instant in the interval has been reached, like
+    /// [`tick`](Interval::tick), or with the error of the timerfd of an
+    /// interval of [`Interval::system_clock`], e.g. when its read is canceled
+    /// by a graceful shutdown. The other intervals never fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use monoio::time::Interval;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut interval = Interval::system_clock(Duration::from_secs(60))?;
+    ///     loop {
+    ///         interval.try_tick().await?;
+    ///     }
+    /// }
+    /// ```
+    pub async fn try_tick(&mut self) -> io::Result<Instant> {
+        poll_fn(|cx| self.poll_try_tick(cx)).await
+    }
+
     /// Poll for the next instant in the interval to be reached.
     ///
     /// This method can return the following values:
@@ -404,12 +483,34 @@ impl Interval {
     /// calls to `poll_tick`, only the [`Waker`](std::task::Waker) from the
     /// [`Context`] passed to the most recent call is scheduled to receive a
     /// wakeup.
+    ///
+    /// # Panics
+    ///
+    /// An interval of [`Interval::system_clock`] panics if its timerfd fails,
+    /// see [`poll_try_tick`](Interval::poll_try_tick).
     pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        self.poll_try_tick(cx).map(|res| match res {
+            Ok(instant) => instant,
+            Err(e) => panic!("system clock interval failed: {e}"),
+        })
+    }
+
+    /// Poll for the next instant in the interval to be reached, like
+    /// [`poll_tick`](Interval::poll_tick), or for the error of the timerfd of
+    /// an interval of [`Interval::system_clock`], see
+    /// [`try_tick`](Interval::try_tick).
+    pub fn poll_try_tick(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Instant>> {
+        let delay = match &mut self.delay {
+            Delay::Sleep(delay) => delay,
+            #[cfg(target_os = "linux")]
+            Delay::SystemClock(clock) => return clock.poll_tick(cx, self.missed_tick_behavior),
+        };
+
         // Wait for the delay to be done
-        ready!(Pin::new(&mut self.delay).poll(cx));
+        ready!(Pin::new(&mut *delay).poll(cx));
 
         // Get the time when we were scheduled to tick
-        let timeout = self.delay.deadline();
+        let timeout = delay.deadline();
 
         let now = Instant::now();
 
@@ -427,10 +528,10 @@ impl Interval {
             timeout + self.period
         };
 
-        self.delay.as_mut().reset(next);
+        delay.as_mut().reset(next);
 
         // Return the time when we were scheduled to tick
-        Poll::Ready(timeout)
+        Poll::Ready(Ok(timeout))
     }
 
     /// Returns the [`MissedTickBehavior`] strategy currently being used.
@@ -6,12 +6,22 @@
 //! * [`Sleep`] is a future that does no work and completes at a specific [`Instant`] in time.
 //!
 //! * [`Interval`] is a stream yielding a value at a fixed period. It is initialized with a
-//!   [`Duration`] and repeatedly yields each time the duration elapses.
+//!   [`Duration`] and repeatedly yields each time the duration elapses. On Linux,
+//!   [`Interval::system_clock`] ticks aligned to the wall clock instead.
 //!
 //! * [`Timeout`]: Wraps a future or stream, setting an upper bound to the amount of time it is
 //!   allowed to execute. If the future or stream does not complete in time, then it is canceled and
 //!   an error is returned.
 //!
+//! * [`Deadline`]: Attaches a deadline to a future so that nested calls can inherit it through
+//!   [`Deadline::current`] and short-circuit once it has passed.
+//!
+//! * [`RateLimiter`] is a token bucket whose [`acquire`](RateLimiter::acquire) waits until enough
+//!   permits are available.
+//!
+//! * [`retry`]: Runs a fallible operation until it succeeds, sleeping with exponential backoff
+//!   between attempts.
+//!
 //! These types are sufficient for handling a large number of scenarios
 //! involving time.
 //!
@@ -99,13 +109,24 @@ pub use driver::{
 
 pub mod error;
 
+mod deadline;
+pub use deadline::{Deadline, DeadlineScope};
+
 mod instant;
 pub use self::instant::Instant;
 
 mod interval;
 pub use interval::{interval, interval_at, Interval, MissedTickBehavior};
 
+mod rate_limiter;
+pub use rate_limiter::RateLimiter;
+
+mod retry;
+pub use retry::{retry, RetryError, RetryPolicy};
+
 mod timeout;
+#[cfg(target_os = "linux")]
+mod timerfd;
 // Re-export for convenience
 #[doc(no_inline)]
 pub use std::time::Duration;
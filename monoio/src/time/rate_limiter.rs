@@ -0,0 +1,160 @@
+//! Token bucket based rate limiter.
+//!
+//! See [`RateLimiter`] documentation for more details.
+
+use std::cell::Cell;
+
+use crate::time::{sleep_until, Duration, Instant};
+
+/// A token bucket rate limiter.
+///
+/// The bucket is refilled with `permits` tokens every `interval` and holds at
+/// most `burst` tokens (equal to `permits` by default). It starts full.
+///
+/// The limiter is designed for a single thread: it uses no atomics, and is
+/// shared across tasks by reference (e.g. wrapped in an `Rc`). Waiting is
+/// driven by the timer, so the timer must be enabled on the runtime.
+///
+/// Waiters are not queued: when tokens become available, whichever pending
+/// task is polled first takes them.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::time::{Duration, RateLimiter};
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     // 100 requests per second, allowing bursts of 10.
+///     let limiter = RateLimiter::new(100, Duration::from_secs(1)).burst(10);
+///     for _ in 0..20 {
+///         limiter.acquire(1).await;
+///         // send request
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    permits: u64,
+    interval: Duration,
+    burst: u64,
+    tokens: Cell<u64>,
+    last_refill: Cell<Instant>,
+}
+
+impl RateLimiter {
+    /// Create a RateLimiter granting `permits` tokens per `interval`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `permits` or `interval` is zero.
+    pub fn new(permits: u32, interval: Duration) -> Self {
+        assert!(permits > 0, "`permits` must be non-zero.");
+        assert!(
+            interval > Duration::new(0, 0),
+            "`interval` must be non-zero."
+        );
+        Self {
+            permits: permits as u64,
+            interval,
+            burst: permits as u64,
+            tokens: Cell::new(permits as u64),
+            last_refill: Cell::new(Instant::now()),
+        }
+    }
+
+    /// Specify the bucket capacity. The bucket is filled up to it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `burst` is zero.
+    #[must_use]
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "`burst` must be non-zero.");
+        self.burst = burst as u64;
+        self.tokens.set(burst as u64);
+        self
+    }
+
+    /// Returns the number of tokens currently available.
+    pub fn available(&self) -> u64 {
+        self.refill(Instant::now());
+        self.tokens.get()
+    }
+
+    /// Try to take `n` tokens without waiting. Returns whether they were
+    /// taken.
+    pub fn try_acquire(&self, n: u32) -> bool {
+        self.refill(Instant::now());
+        let tokens = self.tokens.get();
+        if tokens >= n as u64 {
+            self.tokens.set(tokens - n as u64);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wait until `n` tokens are available and take them.
+    ///
+    /// # Cancelation
+    ///
+    /// Cancelling is done by dropping the future. No token is taken in this
+    /// case.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `n` is greater than the bucket capacity, since
+    /// it could never be satisfied.
+    pub async fn acquire(&self, n: u32) {
+        assert!(
+            n as u64 <= self.burst,
+            "acquiring more tokens than the bucket capacity"
+        );
+        loop {
+            let now = Instant::now();
+            self.refill(now);
+            let tokens = self.tokens.get();
+            if tokens >= n as u64 {
+                self.tokens.set(tokens - n as u64);
+                return;
+            }
+            sleep_until(self.refill_deadline(n as u64 - tokens)).await;
+        }
+    }
+
+    fn nanos_for(&self, tokens: u64) -> u128 {
+        // Round up to make sure the tokens are available at the deadline.
+        let interval = self.interval.as_nanos();
+        (tokens as u128 * interval).div_ceil(self.permits as u128)
+    }
+
+    fn refill_deadline(&self, missing: u64) -> Instant {
+        let nanos = self.nanos_for(missing).min(u64::MAX as u128) as u64;
+        self.last_refill
+            .get()
+            .checked_add(Duration::from_nanos(nanos))
+            .unwrap_or_else(Instant::far_future)
+    }
+
+    fn refill(&self, now: Instant) {
+        let last = self.last_refill.get();
+        let elapsed = now.saturating_duration_since(last).as_nanos();
+        let added = elapsed * self.permits as u128 / self.interval.as_nanos();
+        if added == 0 {
+            return;
+        }
+
+        let tokens = self.tokens.get() as u128 + added;
+        if tokens >= self.burst as u128 {
+            self.tokens.set(self.burst);
+            self.last_refill.set(now);
+        } else {
+            self.tokens.set(tokens as u64);
+            // Only consume the time accounted for the added tokens so that
+            // fractions are not lost between refills.
+            let used = (added * self.interval.as_nanos() / self.permits as u128) as u64;
+            self.last_refill.set(last + Duration::from_nanos(used));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Retry a fallible operation with exponential backoff.
+//!
+//! See [`retry`] documentation for more details.
+
+use std::{error, fmt, future::Future};
+
+use crate::{
+    time::{sleep, Duration},
+    utils::thread_rng_n,
+};
+
+/// Policy controlling how [`retry`] schedules attempts.
+///
+/// The delay before the `n`-th retry is `initial_backoff * multiplier^(n-1)`,
+/// capped at `max_backoff`. With jitter enabled, the actual delay is picked
+/// uniformly from `[delay / 2, delay]` to avoid many clients retrying in
+/// lockstep.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+    /// Max attempts including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay is multiplied by after each failed attempt.
+    pub multiplier: u32,
+    /// Whether to randomize the delay.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Create a default RetryPolicy: 3 attempts, backoff starting at 100ms
+    /// and doubling up to 10s, with jitter.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2,
+            jitter: true,
+        }
+    }
+
+    /// Specify max attempts, including the first one.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `max_attempts` is zero.
+    #[must_use]
+    #[inline]
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        assert!(max_attempts > 0, "`max_attempts` must be non-zero.");
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Specify the delay before the first retry.
+    #[must_use]
+    #[inline]
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Specify the upper bound of the delay between two attempts.
+    #[must_use]
+    #[inline]
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Specify the backoff multiplier. A multiplier of 1 means constant
+    /// backoff.
+    #[must_use]
+    #[inline]
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enable or disable jitter.
+    #[must_use]
+    #[inline]
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the delay (before jitter) to wait after `failures` failed
+    /// attempts.
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let exp = failures.saturating_sub(1);
+        let factor = self.multiplier.checked_pow(exp).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    fn jittered_backoff(&self, failures: u32) -> Duration {
+        let delay = self.backoff(failures);
+        if !self.jitter {
+            return delay;
+        }
+        let half_ms = (delay.as_millis() / 2).min(u32::MAX as u128) as u32;
+        if half_ms == 0 {
+            return delay;
+        }
+        delay - Duration::from_millis(thread_rng_n(half_ms + 1) as u64)
+    }
+}
+
+/// Error returned by [`retry`] when all attempts failed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RetryError<E> {
+    attempts: u32,
+    last_error: E,
+}
+
+impl<E> RetryError<E> {
+    /// Returns how many attempts have been made.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the error of the last attempt.
+    pub fn last_error(&self) -> &E {
+        &self.last_error
+    }
+
+    /// Consumes this error, returning the error of the last attempt.
+    pub fn into_inner(self) -> E {
+        self.last_error
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "operation failed after {} attempts: {}",
+            self.attempts, self.last_error
+        )
+    }
+}
+
+impl<E: error::Error + 'static> error::Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.last_error)
+    }
+}
+
+impl From<RetryError<std::io::Error>> for std::io::Error {
+    fn from(err: RetryError<std::io::Error>) -> std::io::Error {
+        err.last_error
+    }
+}
+
+/// Run the future produced by `op` until it succeeds or `policy.max_attempts`
+/// is reached, sleeping with exponential backoff between attempts.
+///
+/// A new future is created by calling `op` for every attempt. If all attempts
+/// fail, a [`RetryError`] carrying the last error is returned.
+///
+/// The timer must be enabled on the runtime.
+///
+/// # Cancelation
+///
+/// Cancelling a retry is done by dropping the future.
+///
+/// # Examples
+///
+/// ```
+/// use monoio::time::{retry, Duration, RetryPolicy};
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let policy = RetryPolicy::new()
+///         .max_attempts(5)
+///         .initial_backoff(Duration::from_millis(1));
+///     let mut tried = 0;
+///     let res = retry(policy, || {
+///         tried += 1;
+///         let ok = tried == 3;
+///         async move { if ok { Ok(tried) } else { Err("not yet") } }
+///     })
+///     .await;
+///     assert_eq!(res.unwrap(), 3);
+/// }
+/// ```
+pub async fn retry<F, Fut, T, E>(policy: RetryPolicy, mut op: F) -> Result<T, RetryError<E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match op().await {
+            Ok(v) => return Ok(v),
+            Err(e) if attempts >= policy.max_attempts => {
+                return Err(RetryError {
+                    attempts,
+                    last_error: e,
+                })
+            }
+            Err(_) => sleep(policy.jittered_backoff(attempts)).await,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! timerfd backed interval ticking on the wall clock.
+
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{Instant, MissedTickBehavior};
+use crate::driver::{
+    op::{read::Read, Op},
+    shared_fd::SharedFd,
+};
+
+/// A `CLOCK_REALTIME` timerfd expiring on the multiples of `period` since the
+/// UNIX epoch.
+pub(crate) struct SystemClock {
+    fd: SharedFd,
+    period: Duration,
+    // Pending read of the expiration count
+    op: Option<Op<Read<Box<[u8; 8]>>>>,
+    // Buffer of the last read, reused by the next one
+    buf: Option<Box<[u8; 8]>>,
+    // Missed ticks to yield immediately(with burst behavior)
+    missed: u64,
+}
+
+impl SystemClock {
+    pub(crate) fn new(period: Duration) -> io::Result<Self> {
+        let flags = if crate::driver::op::is_legacy() {
+            libc::TFD_CLOEXEC | libc::TFD_NONBLOCK
+        } else {
+            libc::TFD_CLOEXEC
+        };
+        let fd = crate::syscall!(timerfd_create@RAW(libc::CLOCK_REALTIME, flags))?;
+        let fd = SharedFd::new::<false>(fd)?;
+
+        // Align the first expiration to the next multiple of period.
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let period_nanos = period.as_nanos();
+        let first = (now / period_nanos + 1) * period_nanos;
+        let first = Duration::new(
+            (first / 1_000_000_000) as u64,
+            (first % 1_000_000_000) as u32,
+        );
+
+        let this = Self {
+            fd,
+            period,
+            op: None,
+            buf: None,
+            missed: 0,
+        };
+        this.arm(first, libc::TFD_TIMER_ABSTIME)?;
+        Ok(this)
+    }
+
+    fn arm(&self, value: Duration, flags: libc::c_int) -> io::Result<()> {
+        let spec = libc::itimerspec {
+            it_interval: timespec(self.period),
+            it_value: timespec(value),
+        };
+        crate::syscall!(timerfd_settime@RAW(
+            self.fd.raw_fd(),
+            flags,
+            &spec,
+            std::ptr::null_mut()
+        ))?;
+        Ok(())
+    }
+
+    pub(crate) fn poll_tick(
+        &mut self,
+        cx: &mut Context<'_>,
+        behavior: MissedTickBehavior,
+    ) -> Poll<io::Result<Instant>> {
+        if self.missed > 0 {
+            self.missed -= 1;
+            return Poll::Ready(Ok(Instant::now()));
+        }
+
+        let op = match &mut self.op {
+            Some(op) => op,
+            None => {
+                let buf = self.buf.take().unwrap_or_else(|| Box::new([0; 8]));
+                self.op.insert(Op::read(self.fd.clone(), buf)?)
+            }
+        };
+        let completion = ready!(Pin::new(op).poll(cx));
+        self.op = None;
+        self.buf = Some(completion.data.buf);
+        completion.meta.result?;
+        let expirations = u64::from_ne_bytes(**self.buf.as_ref().unwrap());
+
+        if expirations > 1 {
+            match behavior {
+                MissedTickBehavior::Burst => self.missed = expirations - 1,
+                MissedTickBehavior::Delay => {
+                    // Restart the period from now, the wall clock alignment is lost.
+                    self.arm(self.period, 0)?;
+                }
+                MissedTickBehavior::Skip => (),
+            }
+        }
+        Poll::Ready(Ok(Instant::now()))
+    }
+}
+
+fn timespec(duration: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: duration.as_secs() as _,
+        tv_nsec: duration.subsec_nanos() as _,
+    }
+}
+
+impl std::fmt::Debug for SystemClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SystemClock")
+            .field("fd", &self.fd)
+            .field("period", &self.period)
+            .finish()
+    }
+}
@@ -0,0 +1,148 @@
+//! A token to cancel a tree of tasks and of their IO from one place.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    rc::{Rc, Weak},
+};
+
+use crate::io::{CancelHandle, CancellationRegion, Entered};
+
+/// A token signaling cancellation to the tasks and IO operations of, e.g.,
+/// one connection, and to the tokens derived from it.
+///
+/// Once the token is [canceled](CancellationToken::cancel):
+/// - [`cancelled`](CancellationToken::cancelled) resolves, to break out of a
+///   [`select!`](crate::select) or of a loop;
+/// - the futures run with
+///   [`run_until_cancelled`](CancellationToken::run_until_cancelled) are
+///   dropped, which cancels the operations they have in flight;
+/// - the `cancelable_*` operations given one of its
+///   [`handle`](CancellationToken::handle)s complete with an `ECANCELED`
+///   error and give their buffers back;
+/// - its [child tokens](CancellationToken::child_token) are canceled too.
+///
+/// Canceling a child token does not cancel its parent. Clones of a token
+/// refer to the same token. The token is local to the thread of the runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{AsyncReadRent, CancelableAsyncReadRent},
+///     net::TcpStream,
+///     utils::CancellationToken,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let token = CancellationToken::new();
+///     let mut a = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut b = TcpStream::connect("127.0.0.1:8081").await?;
+///
+///     let child = token.child_token();
+///     let task_a = monoio::spawn(async move {
+///         monoio::select! {
+///             _ = child.cancelled() => None,
+///             r = a.read(vec![0; 1024]) => Some(r),
+///         }
+///     });
+///     let handle = token.handle();
+///     let task_b = monoio::spawn(async move { b.cancelable_read(vec![0; 1024], handle).await });
+///
+///     // Tear the whole connection down.
+///     token.cancel();
+///     assert!(task_a.await.is_none());
+///     let (res, _buf) = task_b.await;
+///     assert!(res.is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    node: Rc<Node>,
+}
+
+#[derive(Default)]
+struct Node {
+    region: CancellationRegion,
+    // Tokens derived from this one, the dropped ones are pruned when a child
+    // is added.
+    children: RefCell<Vec<Weak<Node>>>,
+}
+
+impl Node {
+    fn cancel(&self) {
+        if self.region.is_canceled() {
+            return;
+        }
+        self.region.cancel();
+        let children = std::mem::take(&mut *self.children.borrow_mut());
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Create a new token.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a token canceled with this one, which may also be canceled on
+    /// its own. It is created canceled if this token is.
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            let mut children = self.node.children.borrow_mut();
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Rc::downgrade(&child.node));
+        }
+        child
+    }
+
+    /// Cancel the token and its children. Canceling it again does nothing.
+    #[inline]
+    pub fn cancel(&self) {
+        self.node.cancel();
+    }
+
+    /// Returns `true` if the token is canceled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.node.region.is_canceled()
+    }
+
+    /// Wait for the token to be canceled.
+    pub async fn cancelled(&self) {
+        self.node.region.enter(std::future::pending::<()>()).await;
+    }
+
+    /// Run `future` until the token is canceled. It resolves to `None` if the
+    /// token is canceled before the future completes, and the future is then
+    /// dropped.
+    #[inline]
+    pub fn run_until_cancelled<F: Future>(&self, future: F) -> Entered<F> {
+        self.node.region.enter(future)
+    }
+
+    /// Create a [`CancelHandle`] for `cancelable_*` operations, they are
+    /// canceled with the token.
+    #[inline]
+    pub fn handle(&self) -> CancelHandle {
+        self.node.region.handle()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
@@ -0,0 +1,15 @@
+/// Returns the number of fds the current runtime closed when they were
+/// dropped, rather than with the `close` method of their type.
+///
+/// These fds are closed in the background by the io_uring driver, and
+/// synchronously by the legacy one. This includes the sockets dropped by
+/// monoio itself, e.g. when connecting fails. See
+/// [`RuntimeBuilder::warn_on_drop_close`](crate::RuntimeBuilder::warn_on_drop_close)
+/// to find out where they come from.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub fn closed_on_drop() -> u64 {
+    crate::runtime::CURRENT.with(|ctx| ctx.closed_on_drop.get())
+}
@@ -0,0 +1,74 @@
+//! Statistics of completions processed by the io_uring driver.
+
+/// Statistics of the completions processed per driver tick.
+///
+/// Only ticks which processed at least one completion are counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompletionStats {
+    pub(crate) ticks: u64,
+    pub(crate) cqes: u64,
+    pub(crate) max_per_tick: usize,
+    pub(crate) capped_ticks: u64,
+}
+
+impl CompletionStats {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn record(&mut self, cqes: usize, capped: bool) {
+        if cqes == 0 {
+            return;
+        }
+        self.ticks += 1;
+        self.cqes += cqes as u64;
+        self.max_per_tick = self.max_per_tick.max(cqes);
+        if capped {
+            self.capped_ticks += 1;
+        }
+    }
+
+    /// Number of ticks which processed completions.
+    #[inline]
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Total number of processed completions.
+    #[inline]
+    pub fn cqes(&self) -> u64 {
+        self.cqes
+    }
+
+    /// Maximum number of completions processed in one tick.
+    #[inline]
+    pub fn max_per_tick(&self) -> usize {
+        self.max_per_tick
+    }
+
+    /// Average number of completions processed per tick.
+    #[inline]
+    pub fn avg_per_tick(&self) -> f64 {
+        if self.ticks == 0 {
+            0.0
+        } else {
+            self.cqes as f64 / self.ticks as f64
+        }
+    }
+
+    /// Number of ticks which stopped at the limit set by
+    /// [`RuntimeBuilder::with_max_cqes_per_tick`](crate::RuntimeBuilder::with_max_cqes_per_tick),
+    /// leaving completions for the next tick.
+    #[inline]
+    pub fn capped_ticks(&self) -> u64 {
+        self.capped_ticks
+    }
+}
+
+/// Get the completion statistics of the current io_uring driver.
+///
+/// Returns `None` if not running with the io_uring driver.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub fn completion_stats() -> Option<CompletionStats> {
+    crate::driver::completion_stats()
+}
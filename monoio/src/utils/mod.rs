@@ -1,16 +1,37 @@
 //! Common utils
 
 pub(crate) mod box_into_inner;
+pub(crate) mod cache_padded;
 pub(crate) mod linked_list;
+#[cfg(target_os = "linux")]
+pub(crate) mod numa;
 #[allow(dead_code)]
 pub(crate) mod slab;
 #[allow(dead_code)]
 pub(crate) mod thread_id;
 pub(crate) mod uring_detect;
 
+mod cancellation_token;
+#[cfg(unix)]
+mod closed_on_drop;
+mod completion_stats;
+#[cfg(all(unix, feature = "poll-io"))]
+mod poll_io_stats;
 mod rand;
+mod runtime_metrics;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod uring_report;
+pub use cancellation_token::CancellationToken;
+#[cfg(unix)]
+pub use closed_on_drop::closed_on_drop;
+pub use completion_stats::{completion_stats, CompletionStats};
+#[cfg(all(unix, feature = "poll-io"))]
+pub use poll_io_stats::{poll_io_stats, PollIoStats};
 pub use rand::thread_rng_n;
+pub use runtime_metrics::{runtime_metrics, RuntimeMetrics};
 pub use uring_detect::detect_uring;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use uring_report::{uring_report, UringFeatures, UringReport};
 
 pub use crate::driver::op::is_legacy;
 
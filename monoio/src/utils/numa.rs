@@ -0,0 +1,54 @@
+//! NUMA memory policy helpers.
+
+use std::io;
+
+const MPOL_PREFERRED: libc::c_long = 1;
+
+// Number of nodes the node masks can address, the kernel default of
+// CONFIG_NODES_SHIFT=10.
+const MAX_NODES: usize = 1024;
+const MASK_WORDS: usize = MAX_NODES / libc::c_ulong::BITS as usize;
+
+fn node_mask(node: usize) -> io::Result<[libc::c_ulong; MASK_WORDS]> {
+    if node >= MAX_NODES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "numa node out of range",
+        ));
+    }
+    let bits = libc::c_ulong::BITS as usize;
+    let mut mask = [0; MASK_WORDS];
+    mask[node / bits] = 1 << (node % bits);
+    Ok(mask)
+}
+
+/// Prefer allocating the memory of the current thread on `node`. The policy
+/// stays in place for the lifetime of the thread.
+#[cfg(any(feature = "iouring", feature = "legacy"))]
+pub(crate) fn set_preferred_node(node: usize) -> io::Result<()> {
+    let mask = node_mask(node)?;
+    // The kernel reads maxnode - 1 bits of the mask.
+    crate::syscall!(syscall @RAW (
+        libc::SYS_set_mempolicy,
+        MPOL_PREFERRED,
+        mask.as_ptr(),
+        MAX_NODES + 1,
+    ))?;
+    Ok(())
+}
+
+/// Prefer allocating the pages of the mapping at `ptr` on `node`, whichever
+/// thread faults them in.
+pub(crate) fn bind_to_node(ptr: *mut u8, len: usize, node: usize) -> io::Result<()> {
+    let mask = node_mask(node)?;
+    crate::syscall!(syscall @RAW (
+        libc::SYS_mbind,
+        ptr,
+        len,
+        MPOL_PREFERRED,
+        mask.as_ptr(),
+        MAX_NODES + 1,
+        0,
+    ))?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Statistics of fd registrations of the `poll-io` poller.
+
+/// Statistics of the fds registered with the poller of `poll-io` when they
+/// are converted between completion and poll mode.
+///
+/// Converting a fd back to completion mode defers its deregistration to the
+/// next park, so a conversion back to poll mode before that reuses the
+/// registration without any syscall.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PollIoStats {
+    pub(crate) registered: u64,
+    pub(crate) deregistered: u64,
+    pub(crate) reused: u64,
+    pub(crate) flushes: u64,
+}
+
+impl PollIoStats {
+    /// Number of fds registered with the poller.
+    #[inline]
+    pub fn registered(&self) -> u64 {
+        self.registered
+    }
+
+    /// Number of fds deregistered from the poller.
+    #[inline]
+    pub fn deregistered(&self) -> u64 {
+        self.deregistered
+    }
+
+    /// Number of conversions to poll mode which reused a pending
+    /// registration.
+    #[inline]
+    pub fn reused(&self) -> u64 {
+        self.reused
+    }
+
+    /// Number of parks which flushed deferred deregistrations.
+    #[inline]
+    pub fn flushes(&self) -> u64 {
+        self.flushes
+    }
+}
+
+/// Get the `poll-io` registration statistics of the current io_uring driver.
+///
+/// Returns `None` if not running with the io_uring driver.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub fn poll_io_stats() -> Option<PollIoStats> {
+    crate::driver::poll_io_stats()
+}
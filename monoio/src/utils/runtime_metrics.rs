@@ -0,0 +1,115 @@
+//! Metrics of a runtime thread.
+
+use super::CompletionStats;
+
+/// A snapshot of the counters of a runtime, to export the health of its
+/// thread.
+///
+/// The counters start at zero when the runtime is built, and only grow,
+/// except for the tasks and the operations currently alive.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RuntimeMetrics {
+    pub(crate) tasks_spawned: u64,
+    pub(crate) tasks_completed: u64,
+    pub(crate) tasks_polled: u64,
+    pub(crate) parks: u64,
+    pub(crate) ops: Option<(usize, u64)>,
+    pub(crate) completion_stats: Option<CompletionStats>,
+}
+
+impl RuntimeMetrics {
+    pub(crate) fn current() -> Self {
+        let counters = crate::runtime::CURRENT.with(|ctx| {
+            let counters = &ctx.counters;
+            (
+                counters.tasks_spawned.get(),
+                counters.tasks_completed.get(),
+                counters.tasks_polled.get(),
+                counters.parks.get(),
+            )
+        });
+        Self {
+            tasks_spawned: counters.0,
+            tasks_completed: counters.1,
+            tasks_polled: counters.2,
+            parks: counters.3,
+            ops: crate::driver::op_counts(),
+            completion_stats: crate::driver::completion_stats(),
+        }
+    }
+
+    /// Number of tasks spawned and not completed yet. The future run by
+    /// [`block_on`](crate::Runtime::block_on) counts as a task.
+    #[inline]
+    pub fn tasks_alive(&self) -> u64 {
+        self.tasks_spawned - self.tasks_completed
+    }
+
+    /// Number of tasks spawned.
+    #[inline]
+    pub fn tasks_spawned(&self) -> u64 {
+        self.tasks_spawned
+    }
+
+    /// Number of times tasks have been polled.
+    #[inline]
+    pub fn tasks_polled(&self) -> u64 {
+        self.tasks_polled
+    }
+
+    /// Number of times the runtime parked the driver to wait for events,
+    /// having no task ready to run.
+    #[inline]
+    pub fn parks(&self) -> u64 {
+        self.parks
+    }
+
+    /// Number of io_uring operations in flight, including the ones whose
+    /// future was dropped before they completed.
+    ///
+    /// Returns `None` if not running with the io_uring driver.
+    #[inline]
+    pub fn ops_in_flight(&self) -> Option<usize> {
+        self.ops.map(|(in_flight, _)| in_flight)
+    }
+
+    /// Number of io_uring operations submitted.
+    ///
+    /// Returns `None` if not running with the io_uring driver.
+    #[inline]
+    pub fn ops_submitted(&self) -> Option<u64> {
+        self.ops.map(|(_, submitted)| submitted)
+    }
+
+    /// Statistics of the completions processed per tick, see
+    /// [`completion_stats`](super::completion_stats).
+    ///
+    /// Returns `None` if not running with the io_uring driver.
+    #[inline]
+    pub fn completion_stats(&self) -> Option<CompletionStats> {
+        self.completion_stats
+    }
+}
+
+/// Get the metrics of the current runtime.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+///
+/// # Examples
+///
+/// ```
+/// #[monoio::main]
+/// async fn main() {
+///     let metrics = monoio::utils::runtime_metrics();
+///     println!(
+///         "{} tasks alive, {} parks",
+///         metrics.tasks_alive(),
+///         metrics.parks()
+///     );
+/// }
+/// ```
+pub fn runtime_metrics() -> RuntimeMetrics {
+    RuntimeMetrics::current()
+}
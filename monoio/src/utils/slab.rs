@@ -43,6 +43,15 @@ impl<T> Slab<T> {
         })
     }
 
+    /// Keys of the occupied entries.
+    pub(crate) fn keys(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pages.iter().flatten().flat_map(|page| {
+            (0..page.initialized)
+                .filter(|&slot| page.get(slot).is_some())
+                .map(|slot| slot + page.prev_len)
+        })
+    }
+
     pub(crate) fn get(&mut self, key: usize) -> Option<Ref<'_, T>> {
         let page_id = get_page_id(key);
         // here we make 2 mut ref so we must make it safe.
@@ -356,6 +365,23 @@ mod tests {
         assert_eq!(slab.len(), 0);
     }
 
+    #[test]
+    fn keys_of_occupied() {
+        let mut slab = Slab::default();
+        let keys: Vec<usize> = (0..100).map(|i| slab.insert(i)).collect();
+        for key in keys.iter().filter(|&&key| key % 3 != 0) {
+            slab.remove(*key);
+        }
+        let left: Vec<usize> = slab.keys().collect();
+        assert_eq!(
+            left,
+            keys.into_iter()
+                .filter(|key| key % 3 == 0)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(left.len(), slab.len());
+    }
+
     #[test]
     fn insert_get_remove_many() {
         let mut slab = Slab::new();
@@ -13,6 +13,10 @@ macro_rules! err_to_false {
 }
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 fn detect_uring_inner() -> bool {
+    // Miri does not emulate io_uring, fall back to the legacy driver under it.
+    if cfg!(miri) {
+        return false;
+    }
     let val = std::env::var("MONOIO_FORCE_LEGACY_DRIVER");
     match val {
         Ok(v) if matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes") => {
@@ -0,0 +1,283 @@
+//! Capabilities of the host and of the io_uring instance of a runtime.
+
+use std::fmt;
+
+/// Capabilities of the host and of the io_uring instance of a runtime,
+/// gathered once when the runtime is built.
+///
+/// They explain performance differences across hosts: which features and
+/// opcodes the kernel supports, how large the queues are, and how much
+/// memory may be locked for registered buffers. Its [`Display`](fmt::Display)
+/// implementation is a single line meant to be logged at startup. With the
+/// `tracing` feature, the report is also emitted as an info event with the
+/// target `monoio::driver` when the runtime is built.
+///
+/// # Examples
+///
+/// ```
+/// let rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///     .build()
+///     .unwrap();
+/// if let Some(report) = rt.uring_report() {
+///     println!("{report}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UringReport {
+    pub(crate) kernel_release: String,
+    pub(crate) features: UringFeatures,
+    pub(crate) opcodes: Option<[u64; 4]>,
+    pub(crate) memlock_limit: Option<u64>,
+    pub(crate) sq_entries: u32,
+    pub(crate) cq_entries: u32,
+}
+
+/// The setup flags and features of an io_uring instance, see
+/// `io_uring_setup(2)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UringFeatures {
+    /// The ring is polled by a kernel thread (`IORING_SETUP_SQPOLL`).
+    pub sqpoll: bool,
+    /// Completions are busy-polled (`IORING_SETUP_IOPOLL`).
+    pub iopoll: bool,
+    /// Only one thread submits to the ring (`IORING_SETUP_SINGLE_ISSUER`).
+    pub single_issuer: bool,
+    /// `IORING_FEAT_SINGLE_MMAP`
+    pub single_mmap: bool,
+    /// Completions are never dropped on CQ overflow (`IORING_FEAT_NODROP`).
+    pub nodrop: bool,
+    /// `IORING_FEAT_SUBMIT_STABLE`
+    pub submit_stable: bool,
+    /// `IORING_FEAT_RW_CUR_POS`
+    pub rw_cur_pos: bool,
+    /// `IORING_FEAT_CUR_PERSONALITY`
+    pub cur_personality: bool,
+    /// Sockets are polled internally instead of by a worker thread
+    /// (`IORING_FEAT_FAST_POLL`).
+    pub fast_poll: bool,
+    /// `IORING_FEAT_POLL_32BITS`
+    pub poll_32bits: bool,
+    /// `IORING_FEAT_SQPOLL_NONFIXED`
+    pub sqpoll_nonfixed: bool,
+    /// Waits may have a timeout without a timeout op (`IORING_FEAT_EXT_ARG`).
+    pub ext_arg: bool,
+    /// `IORING_FEAT_NATIVE_WORKERS`
+    pub native_workers: bool,
+    /// `IORING_FEAT_RSRC_TAGS`
+    pub resource_tagging: bool,
+    /// `IORING_FEAT_CQE_SKIP`
+    pub skip_cqe_on_success: bool,
+    /// `IORING_FEAT_LINKED_FILE`
+    pub linked_file: bool,
+}
+
+impl UringFeatures {
+    fn new(params: &io_uring::Parameters) -> Self {
+        Self {
+            sqpoll: params.is_setup_sqpoll(),
+            iopoll: params.is_setup_iopoll(),
+            single_issuer: params.is_setup_single_issuer(),
+            single_mmap: params.is_feature_single_mmap(),
+            nodrop: params.is_feature_nodrop(),
+            submit_stable: params.is_feature_submit_stable(),
+            rw_cur_pos: params.is_feature_rw_cur_pos(),
+            cur_personality: params.is_feature_cur_personality(),
+            fast_poll: params.is_feature_fast_poll(),
+            poll_32bits: params.is_feature_poll_32bits(),
+            sqpoll_nonfixed: params.is_feature_sqpoll_nonfixed(),
+            ext_arg: params.is_feature_ext_arg(),
+            native_workers: params.is_feature_native_workers(),
+            resource_tagging: params.is_feature_resource_tagging(),
+            skip_cqe_on_success: params.is_feature_skip_cqe_on_success(),
+            linked_file: params.is_feature_linked_file(),
+        }
+    }
+
+    fn names(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.sqpoll, "sqpoll"),
+            (self.iopoll, "iopoll"),
+            (self.single_issuer, "single_issuer"),
+            (self.single_mmap, "single_mmap"),
+            (self.nodrop, "nodrop"),
+            (self.submit_stable, "submit_stable"),
+            (self.rw_cur_pos, "rw_cur_pos"),
+            (self.cur_personality, "cur_personality"),
+            (self.fast_poll, "fast_poll"),
+            (self.poll_32bits, "poll_32bits"),
+            (self.sqpoll_nonfixed, "sqpoll_nonfixed"),
+            (self.ext_arg, "ext_arg"),
+            (self.native_workers, "native_workers"),
+            (self.resource_tagging, "resource_tagging"),
+            (self.skip_cqe_on_success, "skip_cqe_on_success"),
+            (self.linked_file, "linked_file"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+    }
+}
+
+impl UringReport {
+    pub(crate) fn new(uring: &io_uring::IoUring) -> Self {
+        let params = uring.params();
+        let mut probe = io_uring::Probe::new();
+        let opcodes = uring.submitter().register_probe(&mut probe).ok().map(|_| {
+            let mut opcodes = [0; 4];
+            for op in 0..=u8::MAX {
+                if probe.is_supported(op) {
+                    opcodes[op as usize / 64] |= 1 << (op % 64);
+                }
+            }
+            opcodes
+        });
+        Self {
+            kernel_release: kernel_release(),
+            features: UringFeatures::new(params),
+            opcodes,
+            memlock_limit: memlock_limit(),
+            sq_entries: params.sq_entries(),
+            cq_entries: params.cq_entries(),
+        }
+    }
+
+    /// The release of the running kernel, as reported by `uname(2)`, or an
+    /// empty string if it is unknown.
+    #[inline]
+    pub fn kernel_release(&self) -> &str {
+        &self.kernel_release
+    }
+
+    /// The major, minor and patch numbers of the running kernel, if its
+    /// release starts with them.
+    pub fn kernel_version(&self) -> Option<(u32, u32, u32)> {
+        let mut numbers = self
+            .kernel_release
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|n| n.parse().ok());
+        let major = numbers.next()??;
+        let minor = numbers.next()??;
+        // The patch number is omitted by some releases, e.g. `6.1-rc1`.
+        let patch = numbers.next().flatten().unwrap_or(0);
+        Some((major, minor, patch))
+    }
+
+    /// The setup flags and features of the ring.
+    #[inline]
+    pub fn features(&self) -> UringFeatures {
+        self.features
+    }
+
+    /// Returns whether the kernel supports the opcode, e.g.
+    /// `io_uring::opcode::Accept::CODE`.
+    ///
+    /// Returns `None` if the kernel cannot be probed for its opcodes, which
+    /// requires Linux 5.6.
+    pub fn is_opcode_supported(&self, opcode: u8) -> Option<bool> {
+        let opcodes = self.opcodes.as_ref()?;
+        Some(opcodes[opcode as usize / 64] & (1 << (opcode % 64)) != 0)
+    }
+
+    /// The opcodes supported by the kernel, empty if it cannot be probed.
+    pub fn supported_opcodes(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|&op| self.is_opcode_supported(op) == Some(true))
+    }
+
+    /// The soft limit on the memory that may be locked, which registered
+    /// buffers count against on kernels older than 5.12, or `None` if it is
+    /// unlimited.
+    #[inline]
+    pub fn memlock_limit(&self) -> Option<u64> {
+        self.memlock_limit
+    }
+
+    /// Number of entries of the submission queue.
+    #[inline]
+    pub fn sq_entries(&self) -> u32 {
+        self.sq_entries
+    }
+
+    /// Number of entries of the completion queue.
+    #[inline]
+    pub fn cq_entries(&self) -> u32 {
+        self.cq_entries
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn emit(&self) {
+        tracing::info!(
+            target: "monoio::driver",
+            kernel = self.kernel_release(),
+            sq_entries = self.sq_entries,
+            cq_entries = self.cq_entries,
+            memlock_limit = self.memlock_limit,
+            features = %Features(&self.features),
+            opcodes = self.supported_opcodes().count(),
+            "uring report",
+        );
+    }
+}
+
+impl fmt::Display for UringReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "kernel={} sq_entries={} cq_entries={} memlock_limit=",
+            self.kernel_release, self.sq_entries, self.cq_entries
+        )?;
+        match self.memlock_limit {
+            Some(limit) => write!(f, "{limit}")?,
+            None => f.write_str("unlimited")?,
+        }
+        write!(f, " features={}", Features(&self.features))?;
+        match self.opcodes {
+            Some(_) => write!(f, " opcodes={}", self.supported_opcodes().count()),
+            None => f.write_str(" opcodes=unknown"),
+        }
+    }
+}
+
+/// Comma separated names of the set features.
+struct Features<'a>(&'a UringFeatures);
+
+impl fmt::Display for Features<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, name) in self.0.names().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+fn kernel_release() -> String {
+    let mut uts = std::mem::MaybeUninit::<libc::utsname>::zeroed();
+    if unsafe { libc::uname(uts.as_mut_ptr()) } != 0 {
+        return String::new();
+    }
+    let uts = unsafe { uts.assume_init() };
+    let release = unsafe { std::ffi::CStr::from_ptr(uts.release.as_ptr()) };
+    release.to_string_lossy().into_owned()
+}
+
+fn memlock_limit() -> Option<u64> {
+    let mut limit = std::mem::MaybeUninit::<libc::rlimit>::zeroed();
+    if unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, limit.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let limit = unsafe { limit.assume_init() };
+    (limit.rlim_cur != libc::RLIM_INFINITY).then_some(limit.rlim_cur)
+}
+
+/// Get the report of the current io_uring driver.
+///
+/// Returns `None` if not running with the io_uring driver.
+///
+/// # Panics
+///
+/// Panics if called outside the monoio runtime.
+pub fn uring_report() -> Option<UringReport> {
+    crate::driver::uring_report()
+}
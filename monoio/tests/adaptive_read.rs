@@ -0,0 +1,78 @@
+use std::io;
+
+use monoio::{
+    buf::AdaptiveReadSize,
+    io::{AsyncBufRead, AsyncReadRentExt, AsyncWriteRentExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+#[test]
+fn adaptive_read_size() {
+    let mut sizer = AdaptiveReadSize::new();
+    assert_eq!(sizer.next_capacity(), 2048);
+
+    // Full reads grow the capacity quickly, up to the upper bound.
+    sizer.record(2048);
+    assert_eq!(sizer.next_capacity(), 32 * 1024);
+    sizer.record(32 * 1024);
+    assert_eq!(sizer.next_capacity(), 64 * 1024);
+
+    // Small reads shrink it one step every other read.
+    sizer.record(10);
+    assert_eq!(sizer.next_capacity(), 64 * 1024);
+    sizer.record(10);
+    assert_eq!(sizer.next_capacity(), 32 * 1024);
+    for _ in 0..128 {
+        sizer.record(10);
+    }
+    assert_eq!(sizer.next_capacity(), 64);
+
+    let sizer = AdaptiveReadSize::with_bounds(100, 100, 1000).unwrap();
+    assert_eq!(sizer.next_capacity(), 112);
+    assert_eq!(
+        AdaptiveReadSize::with_bounds(100, 10, 1000)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[monoio::test_all]
+async fn buf_reader_adaptive_capacity() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = monoio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(vec![1; 16 * 1024]).await.0.unwrap();
+        for _ in 0..8 {
+            let (res, _) = stream.read_exact(vec![0; 1]).await;
+            res.unwrap();
+            stream.write_all(&b"x"[..]).await.0.unwrap();
+        }
+    });
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let sizer = AdaptiveReadSize::with_bounds(64, 64, 8 * 1024).unwrap();
+    let mut reader = BufReader::with_adaptive_capacity(sizer, stream);
+    assert_eq!(reader.capacity(), 64);
+
+    let mut remaining = 16 * 1024;
+    while remaining > 0 {
+        let n = reader.fill_buf().await.unwrap().len();
+        reader.consume(n);
+        remaining -= n;
+    }
+    let busy = reader.capacity();
+    assert!(busy > 64);
+
+    for _ in 0..8 {
+        reader.get_mut().write_all(&b"?"[..]).await.0.unwrap();
+        let n = reader.fill_buf().await.unwrap().len();
+        assert_eq!(n, 1);
+        reader.consume(n);
+    }
+    // The buffer is resized before the next read.
+    client.await;
+    assert_eq!(reader.fill_buf().await.unwrap().len(), 0);
+    assert!(reader.capacity() < busy);
+}
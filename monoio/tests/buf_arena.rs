@@ -0,0 +1,68 @@
+#![cfg(unix)]
+
+use std::io;
+
+use monoio::{
+    buf::BufArena,
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[test]
+fn arena_recycles_buffers() {
+    let arena = BufArena::new(4096, 2).unwrap();
+    assert_eq!(arena.available(), 2);
+
+    let a = arena.get().unwrap();
+    let b = arena.get().unwrap();
+    assert_eq!((a.buf_index(), b.buf_index()), (0, 1));
+    assert_eq!(a.capacity(), 4096);
+    assert!(a.is_empty());
+    assert!(arena.get().is_none());
+
+    drop(a);
+    assert_eq!(arena.available(), 1);
+    assert_eq!(arena.get().unwrap().buf_index(), 0);
+
+    assert_eq!(
+        BufArena::new(4096, 0).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[monoio::test_all]
+async fn arena_buf_io() {
+    let arena = BufArena::new(4096, 4).unwrap();
+    match arena.register() {
+        Ok(()) => {
+            assert!(arena.is_registered());
+            assert_eq!(
+                arena.register().unwrap_err().kind(),
+                io::ErrorKind::AlreadyExists
+            );
+        }
+        Err(e) if monoio::utils::is_legacy() => assert_eq!(e.kind(), io::ErrorKind::Unsupported),
+        Err(e) => panic!("register failed: {e}"),
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        stream.write_all(b"hello").await.0.unwrap();
+    });
+
+    let mut out = arena.get().unwrap();
+    out.set_len(5);
+    out.copy_from_slice(b"world");
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let (res, buf) = stream.read(arena.get().unwrap()).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(&buf[..], b"hello");
+    assert_eq!(&out[..], b"world");
+    server.await;
+
+    drop((buf, out));
+    assert_eq!(arena.available(), 4);
+}
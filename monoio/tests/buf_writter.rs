@@ -1,5 +1,5 @@
 use monoio::{
-    io::{AsyncReadRent, AsyncWriteRent, BufReader, BufWriter, Splitable},
+    io::{AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt, BufReader, BufWriter, Splitable},
     net::{TcpListener, TcpStream},
 };
 
@@ -29,3 +29,132 @@ async fn ensure_buf_writter_write_properly() {
     assert!(size.is_ok());
     assert_eq!(s, b"123");
 }
+
+#[monoio::test_all]
+async fn buf_writer_watermark() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    let client = monoio::spawn(async move {
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let mut buf_w = BufWriter::with_capacity(16, stream).with_watermark(4);
+        assert!(buf_w.write(b"12").await.0.is_ok());
+        assert_eq!(buf_w.buffer(), b"12");
+        // Exceeding the watermark writes out the buffered data first.
+        assert!(buf_w.write(b"345").await.0.is_ok());
+        assert_eq!(buf_w.buffer(), b"345");
+        // Writes larger than the watermark bypass the buffer.
+        assert!(buf_w.write(b"67890").await.0.is_ok());
+        assert!(buf_w.buffer().is_empty());
+        assert!(buf_w.flush().await.is_ok());
+        buf_w
+    });
+
+    let (mut stream, _) = srv.accept().await.unwrap();
+    let mut received = Vec::new();
+    while received.len() < 10 {
+        let (res, buf) = stream.read(Vec::with_capacity(16)).await;
+        assert!(res.unwrap() > 0);
+        received.extend_from_slice(&buf);
+    }
+    assert_eq!(received, b"1234567890");
+    drop(client.await);
+}
+
+#[monoio::test_all]
+async fn buf_writer_flush_on_park() {
+    use std::{cell::Cell, rc::Rc};
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let ticked = Rc::new(Cell::new(false));
+
+    let ticked_clone = ticked.clone();
+    monoio::spawn(async move {
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let mut buf_w = BufWriter::new(stream).with_auto_flush(true);
+        assert!(buf_w.write(b"ping").await.0.is_ok());
+        assert_eq!(buf_w.buffer(), b"ping");
+        // Reading waits for the end of the tick, flushes, then reads.
+        let ready = monoio::spawn(async move { ticked_clone.set(true) });
+        let (res, buf) = buf_w.read(Vec::with_capacity(8)).await;
+        assert_eq!(res.unwrap(), 4);
+        assert_eq!(buf, b"pong");
+        ready.await;
+    });
+
+    let (mut stream, _) = srv.accept().await.unwrap();
+    let (res, buf) = stream.read(Vec::with_capacity(8)).await;
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(buf, b"ping");
+    assert!(ticked.get());
+    assert!(stream.write_all(b"pong").await.0.is_ok());
+}
+
+#[monoio::test_all]
+async fn before_park_rearmed_in_the_same_tick() {
+    // The second wait starts once the waiters of the tick were woken, the
+    // runtime must not block in the driver with it pending.
+    monoio::spawn(async {
+        monoio::task::before_park().await;
+        monoio::task::before_park().await;
+    })
+    .await;
+}
+
+/// Accepts at most `limit` bytes, then fails.
+struct Limited {
+    written: Vec<u8>,
+    limit: usize,
+}
+
+impl AsyncWriteRent for Limited {
+    async fn write<T: monoio::buf::IoBuf>(&mut self, buf: T) -> monoio::BufResult<usize, T> {
+        let left = self.limit - self.written.len();
+        if left == 0 {
+            return (Err(std::io::ErrorKind::BrokenPipe.into()), buf);
+        }
+        let n = left.min(buf.bytes_init());
+        self.written.extend_from_slice(&buf.as_slice()[..n]);
+        (Ok(n), buf)
+    }
+
+    async fn writev<T: monoio::buf::IoVecBuf>(&mut self, buf: T) -> monoio::BufResult<usize, T> {
+        (Ok(0), buf)
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[monoio::test_all]
+async fn buf_writer_into_inner() {
+    let mut buf_w = BufWriter::new(Vec::new());
+    assert!(buf_w.write(b"hello").await.0.is_ok());
+    assert!(buf_w.get_ref().is_empty());
+    let Ok(inner) = buf_w.into_inner().await else {
+        panic!("failed to flush");
+    };
+    assert_eq!(inner, b"hello");
+
+    let mut buf_w = BufWriter::new(Limited {
+        written: Vec::new(),
+        limit: 3,
+    });
+    assert!(buf_w.write(b"hello").await.0.is_ok());
+    let Err((err, buf_w)) = buf_w.into_inner().await else {
+        panic!("flushed past the limit");
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+    // Only the data which could not be written is left.
+    assert_eq!(buf_w.get_ref().written, b"hel");
+    assert_eq!(buf_w.buffer(), b"lo");
+    let (inner, buffered) = buf_w.into_parts();
+    assert_eq!(inner.written, b"hel");
+    assert_eq!(buffered, b"lo");
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRent, CancelableAsyncReadRent, CancellationRegion},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn region_cancels_entered_futures() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let mut peers = Vec::new();
+        for _ in 0..2 {
+            peers.push(listener.accept().await.unwrap().0);
+        }
+        peers
+    });
+    let mut a = TcpStream::connect(addr).await.unwrap();
+    let mut b = TcpStream::connect(addr).await.unwrap();
+    let peers = server.await;
+
+    let region = CancellationRegion::new();
+    let r = region.clone();
+    let read_a = monoio::spawn(async move { r.enter(a.read(vec![0; 16])).await });
+    let handle = region.handle();
+    let read_b = monoio::spawn(async move { b.cancelable_read(vec![0; 16], handle).await });
+    let r = region.clone();
+    let sleep =
+        monoio::spawn(async move { r.enter(monoio::time::sleep(Duration::from_secs(60))).await });
+
+    // Let all of them start waiting.
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!region.is_canceled());
+    region.cancel();
+    assert!(region.is_canceled());
+
+    assert!(read_a.await.is_none());
+    // The buffer is given back.
+    let (res, buf) = read_b.await;
+    assert!(res.is_err());
+    assert_eq!(buf.len(), 16);
+    assert!(sleep.await.is_none());
+
+    // A canceled region does not poll new futures.
+    assert!(region.enter(async { unreachable!() }).await.is_none());
+    region.cancel();
+    drop(peers);
+}
+
+#[monoio::test_all]
+async fn region_completes_futures() {
+    let region = CancellationRegion::new();
+    assert_eq!(region.enter(async { 1 }).await, Some(1));
+    assert_eq!(region.enter(async { 2 }).await, Some(2));
+    assert!(!region.is_canceled());
+}
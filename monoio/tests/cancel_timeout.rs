@@ -0,0 +1,52 @@
+use std::{io, time::Duration};
+
+use monoio::{
+    io::{AsyncWriteRentExt, CancelableAsyncReadRent, CancelableAsyncReadRentExt, Canceller},
+    net::{TcpListener, TcpStream},
+};
+
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn read_with_timeout() {
+    let (mut client, mut server) = pair().await;
+    let canceller = Canceller::new();
+    let handle = canceller.handle().with_timeout(Duration::from_millis(50));
+    assert_eq!(handle.timeout(), Some(Duration::from_millis(50)));
+
+    let (res, buf) = client.cancelable_read(vec![0; 16], handle.clone()).await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    assert_eq!(buf.capacity(), 16);
+
+    // The stream is still usable after a timeout, and a read completing in
+    // time is not affected.
+    server.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = client.cancelable_read(buf, handle.clone()).await;
+    assert_eq!(&buf[..res.unwrap()], b"hello");
+
+    // The timeout applies to each read of the extension methods.
+    server.write_all(b"hel").await.0.unwrap();
+    let (res, buf) = client.cancelable_read_exact(vec![0; 5], handle).await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    assert_eq!(buf.capacity(), 5);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn cancel_with_timeout() {
+    let (mut client, _server) = pair().await;
+    let canceller = Canceller::new();
+    let handle = canceller.handle().with_timeout(Duration::from_secs(10));
+    let read = monoio::spawn(async move { client.cancelable_read(vec![0; 16], handle).await });
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    canceller.cancel();
+
+    // Canceling the handle is not reported as a timeout.
+    let (res, _) = read.await;
+    assert_ne!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+}
@@ -0,0 +1,103 @@
+use monoio::{
+    io::{
+        cancelable_copy, cancelable_copy_bidirectional, AsyncReadRent, AsyncReadRentExt,
+        AsyncWriteRent, AsyncWriteRentExt, Canceller,
+    },
+    net::{TcpListener, TcpStream},
+};
+
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[monoio::test_all]
+async fn copy_until_closed() {
+    let (mut src, mut from) = pair().await;
+    let (mut to, mut dst) = pair().await;
+
+    src.write_all(b"hello world").await.0.unwrap();
+    src.shutdown().await.unwrap();
+    let canceller = Canceller::new();
+    let (res, transferred) = cancelable_copy(&mut from, &mut to, canceller.handle()).await;
+    res.unwrap();
+    assert_eq!(transferred, 11);
+
+    let (res, buf) = dst.read_exact(vec![0; 11]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello world");
+}
+
+#[monoio::test_all]
+async fn copy_canceled() {
+    let (mut src, mut from) = pair().await;
+    let (mut to, mut dst) = pair().await;
+
+    let canceller = Canceller::new();
+    let handle = canceller.handle();
+    let copy = monoio::spawn(async move { cancelable_copy(&mut from, &mut to, handle).await });
+    src.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = dst.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+
+    // The copy is waiting for more data, and stops at once.
+    let _canceller = canceller.cancel();
+    let (res, transferred) = copy.await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ECANCELED));
+    assert_eq!(transferred, 5);
+}
+
+#[monoio::test_all]
+async fn copy_bidirectional() {
+    let (mut client, mut proxy_in) = pair().await;
+    let (mut proxy_out, mut server) = pair().await;
+
+    let canceller = Canceller::new();
+    let handle = canceller.handle();
+    let proxy = monoio::spawn(async move {
+        cancelable_copy_bidirectional(&mut proxy_in, &mut proxy_out, handle).await
+    });
+
+    client.write_all(b"ping").await.0.unwrap();
+    let (res, buf) = server.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+    server.write_all(b"pong!").await.0.unwrap();
+    let (res, buf) = client.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"pong!");
+
+    // Closing a side shuts the other down.
+    client.shutdown().await.unwrap();
+    let (res, _) = server.read(Vec::with_capacity(4)).await;
+    assert_eq!(res.unwrap(), 0);
+    server.shutdown().await.unwrap();
+
+    let (res, transferred) = proxy.await;
+    res.unwrap();
+    assert_eq!(transferred, (4, 5));
+}
+
+#[monoio::test_all]
+async fn copy_bidirectional_canceled() {
+    let (mut client, mut proxy_in) = pair().await;
+    let (mut proxy_out, mut server) = pair().await;
+
+    let canceller = Canceller::new();
+    let handle = canceller.handle();
+    let proxy = monoio::spawn(async move {
+        cancelable_copy_bidirectional(&mut proxy_in, &mut proxy_out, handle).await
+    });
+    client.write_all(b"abc").await.0.unwrap();
+    let (res, _) = server.read_exact(vec![0; 3]).await;
+    res.unwrap();
+
+    let _canceller = canceller.cancel();
+    let (res, transferred) = proxy.await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ECANCELED));
+    assert_eq!(transferred, (3, 0));
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRent, CancelableAsyncReadRent},
+    net::{TcpListener, TcpStream},
+    utils::CancellationToken,
+};
+
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[monoio::test_all]
+async fn cancel_children() {
+    let token = CancellationToken::new();
+    let child = token.child_token();
+    let grandchild = child.child_token();
+    let other = token.child_token();
+
+    child.cancel();
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+    assert!(!token.is_cancelled());
+    assert!(!other.is_cancelled());
+
+    token.cancel();
+    assert!(other.is_cancelled());
+    assert!(token.child_token().is_cancelled());
+    // Canceling again does nothing.
+    token.cancel();
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn cancelled_resolves() {
+    let token = CancellationToken::new();
+    let child = token.child_token();
+    let waiting = monoio::spawn(async move {
+        child.cancelled().await;
+        1
+    });
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiting.is_finished());
+    token.cancel();
+    assert_eq!(waiting.await, 1);
+    // Resolves at once once canceled.
+    token.cancelled().await;
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn select_and_run_until_cancelled() {
+    let token = CancellationToken::new();
+    let t = token.clone();
+    let selecting = monoio::spawn(async move {
+        monoio::select! {
+            _ = t.cancelled() => None,
+            _ = monoio::time::sleep(Duration::from_secs(60)) => Some(()),
+        }
+    });
+    let t = token.child_token();
+    let running = monoio::spawn(async move {
+        t.run_until_cancelled(monoio::time::sleep(Duration::from_secs(60)))
+            .await
+    });
+    assert_eq!(token.run_until_cancelled(async { 2 }).await, Some(2));
+    token.cancel();
+    assert_eq!(selecting.await, None);
+    assert_eq!(running.await, None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn tear_down_io() {
+    let (mut a, _peer_a) = pair().await;
+    let (mut b, _peer_b) = pair().await;
+    let token = CancellationToken::new();
+
+    let child = token.child_token();
+    let read_a = monoio::spawn(async move { child.run_until_cancelled(a.read(vec![0; 16])).await });
+    let handle = token.handle();
+    let read_b = monoio::spawn(async move { b.cancelable_read(vec![0; 16], handle).await });
+    // Let both reads start waiting.
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    token.cancel();
+    assert!(read_a.await.is_none());
+    let (res, buf) = read_b.await;
+    assert!(res.is_err());
+    assert_eq!(buf.len(), 16);
+}
@@ -0,0 +1,51 @@
+#![cfg(target_os = "linux")]
+use std::{os::unix::io::AsRawFd, process::Command};
+
+use monoio::{
+    fs::File,
+    io::set_cloexec,
+    net::{udp::UdpSocket, unix::new_pipe, TcpListener, TcpStream, UnixStream},
+};
+
+// Whether a child process inherits `fd`.
+fn inherited(fd: &impl AsRawFd) -> bool {
+    let fd = fd.as_raw_fd();
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("test -e /proc/self/fd/{fd}"))
+        .status()
+        .unwrap()
+        .success()
+}
+
+#[monoio::test_all]
+async fn fds_are_cloexec() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (accepted, _) = listener.accept().await.unwrap();
+    let udp = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let (unix_a, unix_b) = UnixStream::pair().unwrap();
+    let (pipe_r, pipe_w) = new_pipe().unwrap();
+    let file = File::open("Cargo.toml").await.unwrap();
+
+    assert!(!inherited(&listener));
+    assert!(!inherited(&client));
+    assert!(!inherited(&accepted));
+    assert!(!inherited(&udp));
+    assert!(!inherited(&unix_a));
+    assert!(!inherited(&unix_b));
+    assert!(!inherited(&pipe_r));
+    assert!(!inherited(&pipe_w));
+    assert!(!inherited(&file));
+}
+
+#[monoio::test_all]
+async fn opt_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    set_cloexec(&listener, false).unwrap();
+    assert!(inherited(&listener));
+    set_cloexec(&listener, true).unwrap();
+    assert!(!inherited(&listener));
+}
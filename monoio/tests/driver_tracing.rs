@@ -0,0 +1,99 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// Collects the names of the driver events.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<String>>>);
+
+struct Message<'a>(&'a mut Option<String>);
+
+impl Visit for Message<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            *self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "monoio::driver"
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = None;
+        event.record(&mut Message(&mut message));
+        self.0.lock().unwrap().extend(message);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+fn events_of(run: impl FnOnce()) -> Vec<String> {
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), run);
+    let events = collector.0.lock().unwrap().clone();
+    events
+}
+
+async fn work() {
+    monoio::spawn(async {}).await;
+    monoio::time::sleep(Duration::from_millis(1)).await;
+}
+
+fn assert_events(events: &[String]) {
+    for name in ["tick", "park", "complete"] {
+        assert!(
+            events.iter().any(|e| e == name),
+            "no {name} event in {events:?}"
+        );
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn uring_events() {
+    let events = events_of(|| {
+        monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .enable_timer()
+            .build()
+            .unwrap()
+            .block_on(work())
+    });
+    assert_events(&events);
+    // Emitted once when the driver is built.
+    assert_eq!(events.iter().filter(|e| *e == "uring report").count(), 1);
+    assert!(events.iter().any(|e| e == "submit"), "{events:?}");
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_events() {
+    assert_events(&events_of(|| {
+        monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+            .enable_timer()
+            .build()
+            .unwrap()
+            .block_on(work())
+    }));
+}
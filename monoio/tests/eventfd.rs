@@ -0,0 +1,46 @@
+#![cfg(target_os = "linux")]
+
+use std::{rc::Rc, time::Duration};
+
+use monoio::io::EventFd;
+
+#[monoio::test_all]
+async fn eventfd_counter() {
+    let efd = EventFd::new(1).unwrap();
+    efd.write(2).await.unwrap();
+    efd.write(3).await.unwrap();
+    assert_eq!(efd.read().await.unwrap(), 6);
+}
+
+#[monoio::test_all]
+async fn eventfd_semaphore() {
+    let efd = EventFd::semaphore(2).unwrap();
+    assert_eq!(efd.read().await.unwrap(), 1);
+    assert_eq!(efd.read().await.unwrap(), 1);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn eventfd_wait() {
+    let efd = Rc::new(EventFd::new(0).unwrap());
+    let reader = {
+        let efd = efd.clone();
+        monoio::spawn(async move { efd.read().await.unwrap() })
+    };
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!reader.is_finished());
+    efd.write(42).await.unwrap();
+    assert_eq!(reader.await, 42);
+}
+
+#[monoio::test_all]
+async fn eventfd_foreign_thread() {
+    use std::os::unix::io::AsRawFd;
+
+    let efd = EventFd::new(0).unwrap();
+    let fd = efd.as_raw_fd();
+    std::thread::spawn(move || {
+        let value = 7_u64.to_ne_bytes();
+        unsafe { libc::write(fd, value.as_ptr() as _, 8) };
+    });
+    assert_eq!(efd.read().await.unwrap(), 7);
+}
@@ -0,0 +1,79 @@
+#![cfg(unix)]
+
+use std::{
+    cell::Cell,
+    io::{Read, Write},
+    os::unix::{io::AsRawFd, net::UnixStream},
+    rc::Rc,
+    time::Duration,
+};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn external_fd_callback() {
+    let (mut tx, rx) = UnixStream::pair().unwrap();
+    rx.set_nonblocking(true).unwrap();
+
+    let count = Rc::new(Cell::new(0));
+    let callback = {
+        let count = count.clone();
+        let mut rx = rx.try_clone().unwrap();
+        move || {
+            let mut buf = [0; 16];
+            while let Ok(n) = rx.read(&mut buf) {
+                count.set(count.get() + n);
+            }
+        }
+    };
+    let handle = monoio::io::register_external_fd(rx.as_raw_fd(), callback).unwrap();
+    assert_eq!(handle.fd(), rx.as_raw_fd());
+
+    tx.write_all(b"hello").unwrap();
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(count.get(), 5);
+
+    tx.write_all(b"world").unwrap();
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(count.get(), 10);
+
+    // Deregistered fds are not polled any more and stay open.
+    drop(handle);
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    tx.write_all(b"!").unwrap();
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(count.get(), 10);
+    let mut buf = [0; 1];
+    assert_eq!((&rx).read(&mut buf).unwrap(), 1);
+}
+
+#[test]
+fn runtime_register_external_fd() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+
+    let (mut tx, rx) = UnixStream::pair().unwrap();
+    rx.set_nonblocking(true).unwrap();
+
+    let count = Rc::new(Cell::new(0));
+    let callback = {
+        let count = count.clone();
+        let mut rx = rx.try_clone().unwrap();
+        move || {
+            let mut buf = [0; 16];
+            while let Ok(n) = rx.read(&mut buf) {
+                count.set(count.get() + n);
+            }
+        }
+    };
+    let _handle = rt.register_external_fd(rx.as_raw_fd(), callback).unwrap();
+
+    tx.write_all(b"ping").unwrap();
+    for _ in 0..10 {
+        rt.turn(Duration::from_millis(10));
+        if count.get() == 4 {
+            break;
+        }
+    }
+    assert_eq!(count.get(), 4);
+}
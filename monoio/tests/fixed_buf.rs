@@ -0,0 +1,75 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::io::Write;
+
+use monoio::{
+    buf::{BufArena, FixedBuf},
+    fs::File,
+    io::AsyncReadRentExt,
+    net::{TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+#[test]
+fn registered_buffers() {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"hello world").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_registered_buffers(2, 4096)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let a = FixedBuf::get().unwrap();
+        let b = FixedBuf::get().unwrap();
+        assert_eq!((a.buf_index(), b.buf_index()), (0, 1));
+        assert_eq!(a.capacity(), 4096);
+        assert!(FixedBuf::get().is_none());
+        drop(b);
+
+        let file = File::open(tmp.path()).await.unwrap();
+        let (res, mut buf) = file.read_fixed_at(a, 6).await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(&buf[..], b"world");
+
+        let file = File::create(tmp.path()).await.unwrap();
+        buf.copy_from_slice(b"fixed");
+        let (res, buf) = file.write_fixed_at(buf, 0).await;
+        assert_eq!(res.unwrap(), 5);
+        drop(buf);
+        assert_eq!(std::fs::read(tmp.path()).unwrap(), b"fixed");
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = monoio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let mut buf = FixedBuf::get().unwrap();
+            buf.set_len(4);
+            buf.copy_from_slice(b"ping");
+            let (res, _) = stream.write_fixed(buf).await;
+            assert_eq!(res.unwrap(), 4);
+        });
+        let (mut stream, _) = listener.accept().await.unwrap();
+        client.await;
+        let (res, buf) = stream.read_fixed(FixedBuf::get().unwrap()).await;
+        assert_eq!(res.unwrap(), 4);
+        assert_eq!(&buf[..], b"ping");
+        drop(buf);
+        let (res, _) = stream.read_exact(vec![0; 1]).await;
+        assert!(res.is_err());
+    });
+}
+
+#[test]
+fn no_registered_buffers() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+    rt.block_on(async {
+        assert!(FixedBuf::get().is_none());
+        // An arena not registered does not give fixed buffers.
+        let arena = BufArena::new(4096, 1).unwrap();
+        assert!(FixedBuf::try_from(arena.get().unwrap()).is_err());
+        arena.register().unwrap();
+        assert!(FixedBuf::try_from(arena.get().unwrap()).is_ok());
+    });
+    assert!(FixedBuf::get().is_none());
+}
@@ -0,0 +1,97 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+
+use monoio::fs::{File, OpenOptions};
+use tempfile::tempdir;
+
+#[monoio::test_all]
+async fn copy_file() {
+    let dir = tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    let contents: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+    std::fs::write(&from, &contents).unwrap();
+    std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o640)).unwrap();
+    // An existing destination is truncated.
+    std::fs::write(&to, vec![1; 300_000]).unwrap();
+
+    let copied = monoio::fs::copy(&from, &to).await.unwrap();
+    assert_eq!(copied, contents.len() as u64);
+    assert_eq!(std::fs::read(&to).unwrap(), contents);
+    let mode = std::fs::metadata(&to).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+}
+
+#[monoio::test_all]
+async fn copy_missing_or_directory() {
+    let dir = tempdir().unwrap();
+    let to = dir.path().join("to");
+    let err = monoio::fs::copy(dir.path().join("missing"), &to)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(monoio::fs::copy(dir.path(), &to).await.is_err());
+}
+
+#[monoio::test_all]
+async fn copy_range() {
+    let dir = tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, b"hello world").unwrap();
+    std::fs::write(&to, b"0123456789").unwrap();
+
+    let src = File::open(&from).await.unwrap();
+    let dst = OpenOptions::new().write(true).open(&to).await.unwrap();
+    assert_eq!(src.copy_range_to(&dst, 6, 2, 3).await.unwrap(), 3);
+    assert_eq!(std::fs::read(&to).unwrap(), b"01wor56789");
+
+    // The copy stops at the end of the source.
+    assert_eq!(src.copy_range_to(&dst, 6, 8, 100).await.unwrap(), 5);
+    assert_eq!(std::fs::read(&to).unwrap(), b"01wor567world");
+    assert_eq!(src.copy_range_to(&dst, 20, 0, 100).await.unwrap(), 0);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn copy_from_procfs() {
+    // Files of procfs report a size of zero, and the kernel does not copy
+    // them, so they are read and written instead.
+    let dir = tempdir().unwrap();
+    let to = dir.path().join("to");
+    let copied = monoio::fs::copy("/proc/self/mountinfo", &to).await.unwrap();
+    assert!(copied > 0);
+    assert_eq!(std::fs::metadata(&to).unwrap().len(), copied);
+}
+
+#[monoio::test_all]
+async fn copy_lets_other_tasks_run() {
+    use std::{cell::Cell, rc::Rc};
+
+    let dir = tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, vec![7; 4 << 20]).unwrap();
+    let src = File::open(&from).await.unwrap();
+    let dst = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .open(&to)
+        .await
+        .unwrap();
+
+    let ran = Rc::new(Cell::new(false));
+    let copy = monoio::spawn({
+        let ran = ran.clone();
+        async move {
+            let copied = src.copy_range_to(&dst, 0, 0, u64::MAX).await;
+            (copied, ran.get())
+        }
+    });
+    monoio::spawn(async move { ran.set(true) });
+    let (copied, ran_meanwhile) = copy.await;
+    assert_eq!(copied.unwrap(), 4 << 20);
+    // A large copy does not hold up the other tasks until it completes.
+    assert!(ran_meanwhile);
+}
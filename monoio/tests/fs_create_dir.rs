@@ -135,3 +135,72 @@ async fn create_directory_with_permission_issue() {
 
     assert!(path.exists());
 }
+
+#[monoio::test_all]
+async fn create_dir_all_over_file() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("file");
+    std::fs::write(&path, b"").unwrap();
+
+    let err = fs::create_dir_all(&path).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+}
+
+#[monoio::test_all]
+async fn create_with_parent_mode() {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("a/b/c");
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .parent_mode(0o750)
+        .mode(0o700)
+        .create(&path)
+        .await
+        .unwrap();
+
+    let mode = |p: &std::path::Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(&temp_dir.path().join("a")), 0o750);
+    assert_eq!(mode(&temp_dir.path().join("a/b")), 0o750);
+    assert_eq!(mode(&path), 0o700);
+}
+
+#[monoio::test_all]
+async fn create_with_owner() {
+    use std::os::unix::fs::MetadataExt;
+
+    let temp_dir = tempdir().unwrap();
+    let parent = std::fs::metadata(temp_dir.path()).unwrap();
+    let path = temp_dir.path().join("a/b");
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .owner(Some(parent.uid()), Some(parent.gid()))
+        .create(&path)
+        .await
+        .unwrap();
+
+    let meta = std::fs::metadata(&path).unwrap();
+    assert_eq!((meta.uid(), meta.gid()), (parent.uid(), parent.gid()));
+}
+
+#[monoio::test_all]
+async fn create_with_owner_not_permitted() {
+    // Only root may give a directory to another user.
+    if unsafe { libc::geteuid() } == 0 {
+        return;
+    }
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("a");
+
+    let err = fs::DirBuilder::new()
+        .owner(Some(0), None)
+        .create(&path)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    // The directory is not left behind with the wrong owner.
+    assert!(!path.exists());
+}
@@ -0,0 +1,104 @@
+#![cfg(unix)]
+
+use monoio::fs::{Dir, OpenOptions};
+
+#[monoio::test_all]
+async fn open_and_create_at() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dir = Dir::open(temp_dir.path()).await.unwrap();
+
+    let file = dir.create_at("hello.txt").await.unwrap();
+    let (res, _) = file.write_all_at(&b"hello"[..], 0).await;
+    res.unwrap();
+    file.close().await.unwrap();
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("hello.txt")).unwrap(),
+        b"hello"
+    );
+
+    let file = dir
+        .open_at("hello.txt", OpenOptions::new().read(true))
+        .await
+        .unwrap();
+    let (res, buf) = file.read_at(vec![0; 5], 0).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+
+    let meta = dir.metadata_at("hello.txt").await.unwrap();
+    assert!(meta.is_file());
+    assert_eq!(meta.len(), 5);
+
+    let err = dir.metadata_at("missing").await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn open_dir_at() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+    std::fs::write(temp_dir.path().join("sub/a"), b"a").unwrap();
+    std::fs::write(temp_dir.path().join("file"), b"").unwrap();
+
+    let dir = Dir::open(temp_dir.path()).await.unwrap();
+    let sub = dir.open_dir_at("sub").await.unwrap();
+    assert_eq!(sub.metadata_at("a").await.unwrap().len(), 1);
+    assert!(dir.metadata_at("sub").await.unwrap().is_dir());
+
+    assert!(Dir::open(temp_dir.path().join("file")).await.is_err());
+}
+
+#[monoio::test_all]
+async fn stays_on_renamed_dir() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(temp_dir.path().join("old")).unwrap();
+
+    let dir = Dir::open(temp_dir.path().join("old")).await.unwrap();
+    std::fs::rename(temp_dir.path().join("old"), temp_dir.path().join("new")).unwrap();
+    dir.create_at("a").await.unwrap();
+    assert!(temp_dir.path().join("new/a").exists());
+}
+
+#[monoio::test_all]
+async fn symlink_metadata_at() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("target"), b"abc").unwrap();
+    std::os::unix::fs::symlink("target", temp_dir.path().join("link")).unwrap();
+
+    let dir = Dir::open(temp_dir.path()).await.unwrap();
+    assert!(dir.symlink_metadata_at("link").await.unwrap().is_symlink());
+    assert_eq!(dir.metadata_at("link").await.unwrap().len(), 3);
+}
+
+#[cfg(feature = "unlinkat")]
+#[monoio::test_all]
+async fn unlink_at() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("file"), b"").unwrap();
+    std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+
+    let dir = Dir::open(temp_dir.path()).await.unwrap();
+    dir.unlink_at("file").await.unwrap();
+    assert!(!temp_dir.path().join("file").exists());
+
+    assert!(dir.unlink_at("sub").await.is_err());
+    dir.remove_dir_at("sub").await.unwrap();
+    assert!(!temp_dir.path().join("sub").exists());
+}
+
+#[cfg(feature = "renameat")]
+#[monoio::test_all]
+async fn rename_at() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(temp_dir.path().join("a")).unwrap();
+    std::fs::create_dir(temp_dir.path().join("b")).unwrap();
+    std::fs::write(temp_dir.path().join("a/file"), b"x").unwrap();
+
+    let a = Dir::open(temp_dir.path().join("a")).await.unwrap();
+    let b = Dir::open(temp_dir.path().join("b")).await.unwrap();
+    a.rename_at("file", &b, "moved").await.unwrap();
+    assert!(!temp_dir.path().join("a/file").exists());
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("b/moved")).unwrap(),
+        b"x"
+    );
+}
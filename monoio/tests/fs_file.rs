@@ -110,6 +110,66 @@ async fn read_file_all() {
     assert_eq!(res, HELLO);
 }
 
+#[monoio::test_all]
+async fn read_to_end() {
+    let mut tempfile = tempfile();
+    let contents: Vec<u8> = (0..10000).map(|i| i as u8).collect();
+    tempfile.write_all(&contents).unwrap();
+    tempfile.as_file_mut().sync_data().unwrap();
+
+    let mut file = File::open(tempfile.path()).await.unwrap();
+    let (res, buf) = file.read_to_end(b"prefix".to_vec()).await;
+    assert_eq!(res.unwrap(), contents.len());
+    assert_eq!(&buf[..6], b"prefix");
+    assert_eq!(&buf[6..], &contents[..]);
+
+    // Reading at EOF appends nothing.
+    let (res, buf) = file.read_to_end(buf).await;
+    assert_eq!(res.unwrap(), 0);
+    assert_eq!(buf.len(), contents.len() + 6);
+
+    // Exact capacity does not need to grow.
+    let mut file = File::open(tempfile.path()).await.unwrap();
+    let (res, buf) = file.read_to_end(Vec::with_capacity(contents.len())).await;
+    assert_eq!(res.unwrap(), contents.len());
+    assert_eq!(buf.capacity(), contents.len());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn read_zero_sized_proc_file() {
+    let status = monoio::fs::read_to_string("/proc/self/status")
+        .await
+        .unwrap();
+    assert!(status.contains("Name:"));
+}
+
+#[monoio::test_all]
+async fn read_write_string() {
+    let tempfile = tempfile();
+
+    monoio::fs::write_str(tempfile.path(), "hello")
+        .await
+        .unwrap();
+    assert_eq!(
+        monoio::fs::read_to_string(tempfile.path()).await.unwrap(),
+        "hello"
+    );
+
+    let (res, _) = monoio::fs::write(tempfile.path(), String::from("world")).await;
+    res.unwrap();
+    assert_eq!(
+        monoio::fs::read_to_string(tempfile.path()).await.unwrap(),
+        "world"
+    );
+
+    std::fs::write(tempfile.path(), [0xff, 0xfe]).unwrap();
+    let err = monoio::fs::read_to_string(tempfile.path())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
 #[monoio::test_all]
 async fn basic_write() {
     let tempfile = tempfile();
@@ -178,6 +238,57 @@ async fn basic_write_all_at() {
     assert_eq!(file, HELLO);
 }
 
+#[cfg(unix)]
+#[monoio::test_all]
+async fn vectored_at() {
+    let tempfile = tempfile();
+    let mut file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+
+    let bufs = VecBuf::from(vec![b"hello".to_vec(), b" world".to_vec()]);
+    let (res, _) = file.writev_at(bufs, 3).await;
+    assert_eq!(res.unwrap(), 11);
+    assert_eq!(
+        std::fs::read(tempfile.path()).unwrap(),
+        b"\0\0\0hello world"
+    );
+
+    let (res, bufs) = file.readv_at(VecBuf::from(vec![vec![0; 3]; 2]), 5).await;
+    assert_eq!(res.unwrap(), 6);
+    let bufs: Vec<Vec<u8>> = bufs.into();
+    assert_eq!(bufs, [b"llo".to_vec(), b" wo".to_vec()]);
+
+    // The position of the file is not used.
+    let (res, buf) = file.read(vec![0; 3]).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(buf, b"\0\0\0");
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn vectored_at_with_flags() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+
+    let bufs = VecBuf::from(vec![HELLO.to_vec()]);
+    let (res, bufs) = file.writev_at_with_flags(bufs, 0, libc::RWF_DSYNC).await;
+    assert_eq!(res.unwrap(), HELLO.len());
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), HELLO);
+
+    // Unknown flags are rejected by the kernel.
+    let (res, _) = file.writev_at_with_flags(bufs, 0, 1 << 30).await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EOPNOTSUPP));
+    let file = File::open(tempfile.path()).await.unwrap();
+    let (res, _) = file
+        .readv_at_with_flags(VecBuf::from(vec![vec![0; 4]]), 0, 1 << 30)
+        .await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EOPNOTSUPP));
+}
+
 #[monoio::test(driver = "uring")]
 async fn cancel_read_at() {
     let mut tempfile = tempfile();
@@ -209,6 +320,17 @@ async fn explicit_close() {
     assert_invalid_fd(fd, tempfile.as_file().metadata().unwrap());
 }
 
+#[cfg(unix)]
+#[monoio::test_all]
+async fn close_error() {
+    // Not an open fd, without racing with fds opened by other tests.
+    let std = unsafe { std::fs::File::from_raw_fd(i32::MAX) };
+    let file = File::from_std(std).unwrap();
+
+    let err = file.close().await.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+}
+
 #[monoio::test_all]
 async fn drop_open() {
     let tempfile = tempfile();
@@ -315,6 +437,58 @@ async fn file_from_std() {
     read_hello(&file, 0).await;
 }
 
+#[cfg(unix)]
+#[monoio::test_all]
+async fn file_into_std() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_at(HELLO, 0).await.0.unwrap();
+    let fd = file.as_raw_fd();
+
+    let mut std_file = file.try_into_std().unwrap();
+    assert_eq!(std_file.as_raw_fd(), fd);
+    std_file.write_all(b"!").unwrap();
+    drop(std_file);
+
+    let file = File::from(std::os::fd::OwnedFd::from(
+        std::fs::File::open(tempfile.path()).unwrap(),
+    ));
+    let mut buf = HELLO.to_vec();
+    buf[0] = b'!';
+    let (res, read) = file.read_at(vec![0; 64], 0).await;
+    assert_eq!(&read[..res.unwrap()], &buf[..]);
+
+    let std_file = file.into_std().unwrap();
+    assert_eq!(std_file.metadata().unwrap().len(), HELLO.len() as u64);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn file_into_std_in_flight() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fifo");
+    let c_path = std::ffi::CString::new(path.to_str().unwrap()).unwrap();
+    assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+    monoio::start::<monoio::IoUringDriver, _>(async move {
+        let mut reader = monoio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .await
+            .unwrap();
+        // the canceled read stays in flight until the kernel completes it
+        poll_once(reader.read(vec![0; 8])).await;
+
+        let fd = reader.as_raw_fd();
+        let reader = reader.try_into_std().unwrap_err();
+        let std_file = reader.into_std().unwrap();
+        assert_ne!(std_file.as_raw_fd(), fd);
+        use std::os::unix::fs::FileTypeExt;
+        assert!(std_file.metadata().unwrap().file_type().is_fifo());
+    });
+}
+
 #[monoio::test_all]
 async fn flush_and_shutdown() {
     let tempfile = tempfile();
@@ -326,3 +500,85 @@ async fn flush_and_shutdown() {
     let res = file.shutdown().await;
     assert!(matches!(res, Ok(())));
 }
+
+#[monoio::test_all]
+async fn seek_moves_cursor() {
+    use monoio::io::{AsyncReadRentExt, AsyncSeek, AsyncWriteRentExt};
+
+    let tempfile = tempfile();
+    let mut file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+    file.write_all(HELLO).await.0.unwrap();
+    assert_eq!(file.stream_position().await.unwrap(), HELLO.len() as u64);
+
+    assert_eq!(file.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+    let (res, buf) = file.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"world");
+    assert_eq!(file.seek(SeekFrom::Current(-5)).await.unwrap(), 6);
+    assert_eq!(file.seek(SeekFrom::End(-3)).await.unwrap(), 11);
+    assert!(file.seek(SeekFrom::Current(-20)).await.is_err());
+
+    // Positional IO leaves the cursor alone.
+    read_hello(&file, 0).await;
+    assert_eq!(file.stream_position().await.unwrap(), 11);
+
+    file.rewind().await.unwrap();
+    let (res, buf) = file.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+}
+
+#[monoio::test_all]
+async fn buf_reader_seek() {
+    use monoio::io::{AsyncBufRead, AsyncReadRentExt, AsyncSeek, BufReader};
+
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+    tempfile.as_file_mut().sync_data().unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    let mut reader = BufReader::with_capacity(8, file);
+    assert_eq!(reader.fill_buf().await.unwrap(), b"hello wo");
+    reader.consume(2);
+
+    // Relative seeks start from the data returned next.
+    assert_eq!(reader.seek(SeekFrom::Current(4)).await.unwrap(), 6);
+    assert!(reader.buffer().is_empty());
+    let (res, buf) = reader.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"world");
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn set_len() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_all_at(HELLO, 0).await.0.unwrap();
+
+    file.set_len(5).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 5);
+    file.set_len(8).await.unwrap();
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"hello\0\0\0");
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn allocate() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+
+    file.allocate(0, 4096, 0).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 4096);
+
+    // Preallocating past the end keeps the size.
+    file.allocate(4096, 4096, libc::FALLOC_FL_KEEP_SIZE)
+        .await
+        .unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 4096);
+}
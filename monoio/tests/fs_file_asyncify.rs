@@ -104,3 +104,31 @@ fn non_blocking_read_file_all() {
         assert_eq!(res, HELLO);
     })
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn set_len_and_allocate_on_thread_pool() {
+    create_runtime().block_on(async {
+        let tempfile = tempfile();
+        let file = File::create(tempfile.path()).await.unwrap();
+
+        file.allocate(0, 4096, 0).await.unwrap();
+        assert_eq!(file.metadata().await.unwrap().len(), 4096);
+        file.set_len(10).await.unwrap();
+        assert_eq!(file.metadata().await.unwrap().len(), 10);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_on_thread_pool() {
+    create_runtime().block_on(async {
+        let from = tempfile();
+        let to = tempfile();
+        std::fs::write(from.path(), HELLO).unwrap();
+
+        let copied = monoio::fs::copy(from.path(), to.path()).await.unwrap();
+        assert_eq!(copied, HELLO.len() as u64);
+        assert_eq!(std::fs::read(to.path()).unwrap(), HELLO);
+    });
+}
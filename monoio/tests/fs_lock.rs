@@ -0,0 +1,132 @@
+#![cfg(unix)]
+
+use monoio::fs::File;
+use tempfile::NamedTempFile;
+
+#[monoio::test_all]
+async fn try_lock_exclusive() {
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    assert!(a.try_lock().unwrap());
+    // locking again through the same file is a no-op
+    assert!(a.try_lock().unwrap());
+    assert!(!b.try_lock().unwrap());
+    assert!(!b.try_lock_shared().unwrap());
+
+    a.unlock().unwrap();
+    assert!(b.try_lock().unwrap());
+}
+
+#[monoio::test_all]
+async fn try_lock_shared() {
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    assert!(a.try_lock_shared().unwrap());
+    assert!(b.try_lock_shared().unwrap());
+    assert!(!b.try_lock().unwrap());
+
+    a.unlock().unwrap();
+    assert!(b.try_lock().unwrap());
+}
+
+#[monoio::test_all]
+async fn close_releases_lock() {
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    assert!(a.try_lock().unwrap());
+    a.close().await.unwrap();
+    assert!(b.try_lock().unwrap());
+}
+
+#[monoio::test_all]
+async fn lock_uncontended() {
+    // does not need a thread pool
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    a.lock_shared().await.unwrap();
+    b.lock_shared().await.unwrap();
+    a.unlock().unwrap();
+    b.unlock().unwrap();
+    a.lock_exclusive().await.unwrap();
+    assert!(!b.try_lock_shared().unwrap());
+}
+
+#[cfg(all(feature = "sync", feature = "legacy"))]
+#[test]
+fn lock_contended() {
+    use std::time::Duration;
+
+    use monoio::{blocking::DefaultThreadPool, LegacyDriver, RuntimeBuilder};
+
+    let mut rt = RuntimeBuilder::<LegacyDriver>::new()
+        .enable_timer()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let tempfile = NamedTempFile::new().unwrap();
+        let a = File::open(tempfile.path()).await.unwrap();
+        let b = File::open(tempfile.path()).await.unwrap();
+
+        a.lock_exclusive().await.unwrap();
+        let waiter = monoio::spawn(async move {
+            b.lock_exclusive().await.unwrap();
+            b
+        });
+        monoio::time::sleep(Duration::from_millis(50)).await;
+        let c = File::open(tempfile.path()).await.unwrap();
+        assert!(!c.try_lock_shared().unwrap());
+
+        a.unlock().unwrap();
+        let b = waiter.await;
+        assert!(!c.try_lock_shared().unwrap());
+        b.unlock().unwrap();
+        assert!(c.try_lock_shared().unwrap());
+    });
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn lock_contended_without_thread_pool() {
+    use std::time::Duration;
+
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    a.lock_exclusive().await.unwrap();
+    let waiter = monoio::spawn(async move {
+        b.lock_shared().await.unwrap();
+        b
+    });
+    // The waiter does not block the runtime thread.
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!waiter.is_finished());
+
+    a.unlock().unwrap();
+    let b = waiter.await;
+    let c = File::open(tempfile.path()).await.unwrap();
+    assert!(!c.try_lock().unwrap());
+    b.unlock().unwrap();
+    assert!(c.try_lock().unwrap());
+}
+
+#[monoio::test_all]
+async fn lock_contended_without_timer() {
+    let tempfile = NamedTempFile::new().unwrap();
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    a.lock_exclusive().await.unwrap();
+    let err = b.lock_shared().await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+    a.unlock().unwrap();
+    b.lock_shared().await.unwrap();
+}
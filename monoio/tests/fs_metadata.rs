@@ -80,3 +80,49 @@ async fn symlink_metadata() {
 
     assert_eq!(m_meta.is_symlink(), std_meta.is_symlink());
 }
+
+#[monoio::test_all]
+async fn exists_and_kind() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("file");
+    std::fs::write(&file, b"x").unwrap();
+    let missing = dir.path().join("missing");
+    let broken = dir.path().join("broken");
+    std::os::unix::fs::symlink(&missing, &broken).unwrap();
+
+    assert!(monoio::fs::try_exists(&file).await.unwrap());
+    assert!(monoio::fs::try_exists(dir.path()).await.unwrap());
+    assert!(!monoio::fs::try_exists(&missing).await.unwrap());
+    assert!(!monoio::fs::try_exists(&broken).await.unwrap());
+    assert!(monoio::fs::exists(&file).await);
+    assert!(!monoio::fs::exists(&missing).await);
+
+    assert!(monoio::fs::is_file(&file).await);
+    assert!(!monoio::fs::is_file(dir.path()).await);
+    assert!(!monoio::fs::is_file(&missing).await);
+    assert!(monoio::fs::is_dir(dir.path()).await);
+    assert!(!monoio::fs::is_dir(&file).await);
+    assert!(!monoio::fs::is_dir(&missing).await);
+}
+
+#[monoio::test_all]
+async fn file_metadata_matches_path_metadata() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    let file = monoio::fs::File::create(&path).await.unwrap();
+    file.write_all_at(&b"hello"[..], 0).await.0.unwrap();
+
+    let f_meta = file.metadata().await.unwrap();
+    let p_meta = monoio::fs::metadata(&path).await.unwrap();
+    let std_meta = std::fs::metadata(&path).unwrap();
+
+    assert_eq!(f_meta.len(), 5);
+    assert_eq!(f_meta.ino(), std_meta.ino());
+    assert_eq!(f_meta.ino(), p_meta.ino());
+    assert_eq!(f_meta.dev(), std_meta.dev());
+    assert!(f_meta.file_type().is_file());
+    assert_eq!(f_meta.permissions().mode(), std_meta.permissions().mode());
+    assert_eq!(f_meta.modified().unwrap(), std_meta.modified().unwrap());
+}
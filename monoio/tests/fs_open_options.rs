@@ -0,0 +1,45 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+use monoio::fs::OpenOptions;
+
+#[monoio::test_all]
+async fn open_with_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .await
+        .unwrap();
+    file.close().await.unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[monoio::test_all]
+async fn open_with_custom_flags() {
+    use std::os::fd::AsRawFd;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+
+    // access mode bits in custom flags are ignored
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .custom_flags(libc::O_SYNC | libc::O_NONBLOCK | libc::O_RDWR)
+        .open(&path)
+        .await
+        .unwrap();
+
+    let flags = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETFL) };
+    assert_eq!(flags & libc::O_SYNC, libc::O_SYNC);
+    assert_eq!(flags & libc::O_NONBLOCK, libc::O_NONBLOCK);
+    assert_eq!(flags & libc::O_ACCMODE, libc::O_WRONLY);
+}
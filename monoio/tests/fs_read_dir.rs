@@ -0,0 +1,121 @@
+#![cfg(unix)]
+
+use std::{collections::BTreeSet, io};
+
+use monoio::{fs::read_dir, io::stream::Stream};
+
+async fn names(path: &std::path::Path) -> BTreeSet<String> {
+    let mut entries = read_dir(path).await.unwrap();
+    let mut names = BTreeSet::new();
+    while let Some(entry) = entries.next().await {
+        let entry = entry.unwrap();
+        assert_eq!(entry.path(), path.join(entry.file_name()));
+        assert!(names.insert(entry.file_name().to_str().unwrap().to_string()));
+    }
+    // The stream stays exhausted.
+    assert!(entries.next().await.is_none());
+    names
+}
+
+#[monoio::test_all]
+async fn read_dir_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(dir.path().join("file"), b"hello").unwrap();
+    std::os::unix::fs::symlink(dir.path().join("file"), dir.path().join("link")).unwrap();
+
+    let mut entries = read_dir(dir.path()).await.unwrap();
+    let mut kinds = Vec::new();
+    while let Some(entry) = entries.next().await {
+        let entry = entry.unwrap();
+        let file_type = entry.file_type();
+        let metadata = entry.metadata().await.unwrap();
+        assert_eq!(metadata.file_type().is_dir(), file_type.is_dir());
+        kinds.push((
+            entry.file_name().to_str().unwrap().to_string(),
+            file_type.is_dir(),
+            file_type.is_file(),
+            file_type.is_symlink(),
+        ));
+        if file_type.is_file() {
+            assert_eq!(metadata.len(), 5);
+        }
+    }
+    kinds.sort();
+    assert_eq!(
+        kinds,
+        [
+            ("file".to_string(), false, true, false),
+            ("link".to_string(), false, false, true),
+            ("sub".to_string(), true, false, false),
+        ]
+    );
+}
+
+#[monoio::test_all]
+async fn read_dir_many_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let expected: BTreeSet<_> = (0..200).map(|i| format!("f{i}")).collect();
+    for name in &expected {
+        std::fs::write(dir.path().join(name), b"").unwrap();
+    }
+    assert_eq!(names(dir.path()).await, expected);
+
+    let empty = tempfile::tempdir().unwrap();
+    assert!(names(empty.path()).await.is_empty());
+}
+
+#[monoio::test_all]
+async fn read_dir_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = read_dir(dir.path().join("missing")).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    std::fs::write(dir.path().join("file"), b"").unwrap();
+    assert!(read_dir(dir.path().join("file")).await.is_err());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn read_dir_on_thread_pool() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .attach_thread_pool(Box::new(monoio::blocking::DefaultThreadPool::new(2)))
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        let expected: BTreeSet<_> = (0..100).map(|i| format!("f{i}")).collect();
+        for name in &expected {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        assert_eq!(names(dir.path()).await, expected);
+    });
+}
+
+#[monoio::test_all]
+async fn read_dir_lets_other_tasks_run() {
+    use std::{cell::Cell, rc::Rc};
+
+    let dir = tempfile::tempdir().unwrap();
+    for i in 0..200 {
+        std::fs::write(dir.path().join(i.to_string()), b"").unwrap();
+    }
+    let path = dir.path().to_path_buf();
+    let ran = Rc::new(Cell::new(false));
+    let list = monoio::spawn({
+        let ran = ran.clone();
+        async move {
+            let mut entries = read_dir(path).await.unwrap();
+            let mut n = 0;
+            while let Some(entry) = entries.next().await {
+                entry.unwrap();
+                n += 1;
+            }
+            (n, ran.get())
+        }
+    });
+    monoio::spawn(async move { ran.set(true) });
+    let (n, ran_meanwhile) = list.await;
+    assert_eq!(n, 200);
+    assert!(ran_meanwhile);
+}
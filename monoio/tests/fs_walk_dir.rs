@@ -0,0 +1,203 @@
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+
+use monoio::{
+    fs::{glob, walk_dir, GlobPattern, WalkEntry},
+    io::stream::Stream,
+};
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("a/b")).unwrap();
+    std::fs::create_dir(root.join("c")).unwrap();
+    std::fs::write(root.join("x.log"), b"").unwrap();
+    std::fs::write(root.join("a/y.log"), b"").unwrap();
+    std::fs::write(root.join("a/b/z.log"), b"").unwrap();
+    std::fs::write(root.join("a/b/z.txt"), b"").unwrap();
+    std::os::unix::fs::symlink(root.join("a"), root.join("c/link")).unwrap();
+    dir
+}
+
+async fn collect(mut stream: impl Stream<Item = std::io::Result<WalkEntry>>) -> Vec<WalkEntry> {
+    let mut entries = Vec::new();
+    while let Some(entry) = stream.next().await {
+        entries.push(entry.unwrap());
+    }
+    entries
+}
+
+fn relative(root: &Path, entries: &[WalkEntry]) -> Vec<(String, usize)> {
+    entries
+        .iter()
+        .map(|e| {
+            let path = e.path().strip_prefix(root).unwrap();
+            (path.to_str().unwrap().to_string(), e.depth())
+        })
+        .collect()
+}
+
+#[monoio::test_all]
+async fn walk_all() {
+    let dir = tree();
+    let root = dir.path();
+    let entries = collect(walk_dir(root).sort_by_file_name()).await;
+
+    assert_eq!(
+        relative(root, &entries),
+        [
+            ("", 0),
+            ("a", 1),
+            ("a/b", 2),
+            ("a/b/z.log", 3),
+            ("a/b/z.txt", 3),
+            ("a/y.log", 2),
+            ("c", 1),
+            ("c/link", 2),
+            ("x.log", 1),
+        ]
+        .map(|(p, d)| (p.to_string(), d))
+    );
+    let link = entries.iter().find(|e| e.file_name() == "link").unwrap();
+    assert!(link.path_is_symlink());
+    assert!(link.file_type().is_symlink());
+    assert!(link.metadata().await.unwrap().file_type().is_symlink());
+}
+
+#[monoio::test_all]
+async fn walk_depth() {
+    let dir = tree();
+    let root = dir.path();
+    let entries = collect(walk_dir(root).min_depth(1).max_depth(1).sort_by_file_name()).await;
+
+    assert_eq!(
+        relative(root, &entries),
+        [("a", 1), ("c", 1), ("x.log", 1)].map(|(p, d)| (p.to_string(), d))
+    );
+}
+
+#[monoio::test_all]
+async fn walk_follow_links() {
+    let dir = tree();
+    let root = dir.path();
+    let entries = collect(
+        walk_dir(root.join("c"))
+            .follow_links(true)
+            .sort_by_file_name(),
+    )
+    .await;
+
+    assert_eq!(
+        relative(root, &entries),
+        [
+            ("c", 0),
+            ("c/link", 1),
+            ("c/link/b", 2),
+            ("c/link/b/z.log", 3),
+            ("c/link/b/z.txt", 3),
+            ("c/link/y.log", 2),
+        ]
+        .map(|(p, d)| (p.to_string(), d))
+    );
+    assert!(entries[1].path_is_symlink());
+    assert!(entries[1].file_type().is_dir());
+}
+
+#[monoio::test_all]
+async fn walk_follow_links_loop() {
+    let dir = tempfile::tempdir().unwrap();
+    std::os::unix::fs::symlink(dir.path(), dir.path().join("loop")).unwrap();
+
+    let mut walk = walk_dir(dir.path()).follow_links(true);
+    assert_eq!(walk.next().await.unwrap().unwrap().depth(), 0);
+    assert_eq!(walk.next().await.unwrap().unwrap().file_name(), "loop");
+    assert!(walk.next().await.unwrap().is_err());
+    assert!(walk.next().await.is_none());
+}
+
+#[monoio::test_all]
+async fn walk_missing_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut walk = walk_dir(dir.path().join("missing"));
+    let err = walk.next().await.unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(walk.next().await.is_none());
+}
+
+#[monoio::test_all]
+async fn glob_paths() {
+    let dir = tree();
+    let root = dir.path();
+    let paths = |entries: Vec<WalkEntry>| -> Vec<PathBuf> {
+        entries.into_iter().map(|e| e.into_path()).collect()
+    };
+
+    let pattern = format!("{}/**/*.log", root.display());
+    let entries = collect(glob(&pattern).unwrap().sort_by_file_name()).await;
+    assert_eq!(
+        paths(entries),
+        [
+            root.join("a/b/z.log"),
+            root.join("a/y.log"),
+            root.join("x.log")
+        ]
+    );
+
+    let pattern = format!("{}/a/*/z.*", root.display());
+    let entries = collect(glob(&pattern).unwrap().sort_by_file_name()).await;
+    assert_eq!(
+        paths(entries),
+        [root.join("a/b/z.log"), root.join("a/b/z.txt")]
+    );
+
+    let pattern = format!("{}/missing/*", root.display());
+    assert!(collect(glob(&pattern).unwrap()).await.is_empty());
+}
+
+#[test]
+fn glob_pattern() {
+    let p = GlobPattern::new("*.log").unwrap();
+    assert!(p.matches("a.log"));
+    assert!(p.matches("./.log"));
+    assert!(!p.matches("a/a.log"));
+
+    let p = GlobPattern::new("a?c/[b-d]x[!0-9]").unwrap();
+    assert!(p.matches("abc/cxy"));
+    assert!(!p.matches("ac/cxy"));
+    assert!(!p.matches("abc/axy"));
+    assert!(!p.matches("abc/cx1"));
+
+    let p = GlobPattern::new("[]]**").unwrap();
+    assert!(p.matches("]"));
+    assert!(p.matches("]abc"));
+
+    let p = GlobPattern::new("/**").unwrap();
+    assert!(p.matches("/"));
+    assert!(p.matches("/a/b"));
+    assert!(!p.matches("a/b"));
+
+    let p = GlobPattern::new("a/**/b").unwrap();
+    assert!(p.matches("a/b"));
+    assert!(p.matches("a/x/y/b"));
+    assert!(!p.matches("a/x/y/c"));
+
+    assert_eq!(
+        GlobPattern::new("[ab").unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+}
+
+#[cfg(all(feature = "sync", feature = "legacy"))]
+#[test]
+fn walk_on_thread_pool() {
+    use monoio::{blocking::DefaultThreadPool, LegacyDriver, RuntimeBuilder};
+
+    let dir = tree();
+    let mut rt = RuntimeBuilder::<LegacyDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    let entries = rt.block_on(collect(walk_dir(dir.path())));
+    assert_eq!(entries.len(), 9);
+}
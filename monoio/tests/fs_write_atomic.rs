@@ -0,0 +1,47 @@
+#![cfg(all(unix, feature = "renameat", feature = "unlinkat"))]
+
+#[monoio::test_all]
+async fn write_atomic_creates_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("state");
+
+    let (res, _) = monoio::fs::write_atomic(&path, b"hello".to_vec()).await;
+    res.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+}
+
+#[monoio::test_all]
+async fn write_atomic_replaces_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("state");
+    std::fs::write(&path, b"a much longer old content").unwrap();
+    let old = std::fs::File::open(&path).unwrap();
+
+    let (res, _) = monoio::fs::write_atomic(&path, b"new".to_vec()).await;
+    res.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"new");
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    // the file is replaced rather than rewritten in place
+    assert_eq!(
+        std::io::read_to_string(old).unwrap(),
+        "a much longer old content"
+    );
+}
+
+#[monoio::test_all]
+async fn write_atomic_without_file_name() {
+    let (res, _) = monoio::fs::write_atomic("/", b"x".to_vec()).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[monoio::test_all]
+async fn write_atomic_missing_parent() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("missing/state");
+
+    let (res, _) = monoio::fs::write_atomic(&path, b"x".to_vec()).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
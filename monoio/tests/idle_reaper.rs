@@ -0,0 +1,46 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::net::IdleReaper;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn reaps_idle_connections() {
+    let reaper =
+        IdleReaper::new(Duration::from_millis(100)).with_resolution(Duration::from_millis(10));
+    let closed = Rc::new(Cell::new(0));
+
+    let idle = reaper.register({
+        let closed = closed.clone();
+        move || closed.set(closed.get() + 1)
+    });
+    let active = reaper.register(|| panic!("active connection reaped"));
+    assert_eq!(reaper.len(), 2);
+
+    for _ in 0..20 {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        active.touch();
+    }
+    assert!(idle.is_reaped());
+    assert!(!active.is_reaped());
+    assert_eq!(closed.get(), 1);
+    assert_eq!(reaper.len(), 1);
+
+    drop(active);
+    assert!(reaper.is_empty());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn restarts_after_emptied() {
+    let reaper =
+        IdleReaper::new(Duration::from_millis(20)).with_resolution(Duration::from_millis(5));
+    for _ in 0..2 {
+        let closed = Rc::new(Cell::new(false));
+        let guard = reaper.register({
+            let closed = closed.clone();
+            move || closed.set(true)
+        });
+        monoio::time::sleep(Duration::from_millis(60)).await;
+        assert!(closed.get());
+        assert!(guard.is_reaped());
+        assert!(reaper.is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::Write;
+
+use monoio::{
+    fs::File,
+    io::{submit_batch, Batch},
+};
+use tempfile::NamedTempFile;
+
+fn tempfile(contents: &[u8]) -> NamedTempFile {
+    let mut tempfile = NamedTempFile::new().unwrap();
+    tempfile.write_all(contents).unwrap();
+    tempfile
+}
+
+#[monoio::test_all]
+async fn batch_outputs_in_order() {
+    let contents: Vec<u8> = (0..64).collect();
+    let tempfile = tempfile(&contents);
+    let file = File::open(tempfile.path()).await.unwrap();
+
+    let reads: Batch<_> = (0..8).map(|i| file.read_at(vec![0; 8], i * 8)).collect();
+    assert_eq!(reads.len(), 8);
+    for (i, (res, buf)) in reads.submit().await.into_iter().enumerate() {
+        assert_eq!(res.unwrap(), 8);
+        assert_eq!(buf, &contents[i * 8..i * 8 + 8]);
+    }
+
+    let sizes = submit_batch([4, 2].map(|len| file.read_at(vec![0; len], 0))).await;
+    assert_eq!(sizes[0].1, &contents[..4]);
+    assert_eq!(sizes[1].1, &contents[..2]);
+
+    assert!(Batch::<std::future::Ready<()>>::new()
+        .submit()
+        .await
+        .is_empty());
+}
+
+#[monoio::test_all]
+async fn linked_batch_runs_in_order() {
+    let tempfile = tempfile(b"");
+    let file = File::create(tempfile.path()).await.unwrap();
+
+    let writes = (0..4u8).map(|i| file.write_at(vec![i; 4], 0));
+    let res = writes.collect::<Batch<_>>().linked().submit().await;
+    assert!(res.into_iter().all(|(res, _)| res.unwrap() == 4));
+    // The last write wins.
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), [3; 4]);
+}
+
+#[monoio::test(driver = "uring")]
+async fn linked_batch_cancels_on_error() {
+    use std::os::unix::io::FromRawFd;
+
+    let tempfile = tempfile(b"hello");
+    let file = File::open(tempfile.path()).await.unwrap();
+    // Not an open fd, reading it fails with EBADF.
+    let bad = File::from_std(unsafe { std::fs::File::from_raw_fd(i32::MAX) }).unwrap();
+
+    let mut batch = Batch::with_capacity(2).linked();
+    batch.push(bad.read_at(vec![0; 5], 0));
+    batch.push(file.read_at(vec![0; 5], 0));
+    let res = batch.submit().await;
+    assert_eq!(
+        res[0].0.as_ref().unwrap_err().raw_os_error(),
+        Some(libc::EBADF)
+    );
+    assert_eq!(
+        res[1].0.as_ref().unwrap_err().raw_os_error(),
+        Some(libc::ECANCELED)
+    );
+
+    // Operations submitted after the batch are not part of its chain.
+    let (res, buf) = file.read_at(vec![0; 5], 0).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn linked_batch_larger_than_submission_queue() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .with_entries(256)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let tempfile = tempfile(b"hello");
+        let file = File::open(tempfile.path()).await.unwrap();
+
+        // The operations and the nop ending the chain fill the ring.
+        let syncs = (0..255).map(|_| file.sync_data());
+        let res = syncs.collect::<Batch<_>>().linked().submit().await;
+        assert!(res.iter().all(Result::is_ok));
+
+        // None of the batch is submitted if the ring can not hold it.
+        let syncs = (0..256).map(|_| file.sync_data());
+        let res = syncs.collect::<Batch<_>>().linked().submit().await;
+        assert!(res.iter().all(Result::is_err));
+
+        // Futures pushing more operations than expected fill the ring during
+        // the batch, the operations left fail instead of splitting the chain.
+        let syncs = (0..200).map(|_| async { monoio::join!(file.sync_data(), file.sync_data()) });
+        let res = syncs.collect::<Batch<_>>().linked().submit().await;
+        let res: Vec<_> = res.into_iter().flat_map(|(a, b)| [a, b]).collect();
+        assert!(res[..255].iter().all(Result::is_ok));
+        assert!(res[255..].iter().all(Result::is_err));
+    });
+}
@@ -0,0 +1,105 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::{fs::File, io::with_drain};
+use tempfile::NamedTempFile;
+
+#[monoio::test_all]
+async fn drain_runs_future() {
+    let tempfile = NamedTempFile::new().unwrap();
+    let file = File::create(tempfile.path()).await.unwrap();
+
+    let (data, commit) = monoio::join!(
+        file.write_at(b"data".to_vec(), 0),
+        with_drain(file.write_at(b"commit".to_vec(), 4)),
+    );
+    assert_eq!(data.0.unwrap(), 4);
+    assert_eq!(commit.0.unwrap(), 6);
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"datacommit");
+}
+
+// Writes to `file` behind a barrier, checking it waits for `pending` to
+// complete, which `complete` makes it do.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+async fn assert_barrier(
+    file: File,
+    pending: impl std::future::Future + 'static,
+    complete: impl FnOnce(),
+) {
+    let pending = monoio::spawn(pending);
+    // Let the pending op be submitted, and the driver park.
+    monoio::time::sleep(Duration::from_millis(5)).await;
+
+    let done = Rc::new(Cell::new(false));
+    let drained = monoio::spawn({
+        let done = done.clone();
+        async move {
+            let (res, _) = with_drain(file.write_at(b"commit".to_vec(), 0)).await;
+            done.set(true);
+            res.unwrap()
+        }
+    });
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!done.get());
+
+    complete();
+    pending.await;
+    assert_eq!(drained.await, 6);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn drain_waits_for_prior_ops() {
+    use monoio::{io::AsyncReadRent, net::TcpStream};
+
+    let tempfile = NamedTempFile::new().unwrap();
+    let file = File::create(tempfile.path()).await.unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let mut client = TcpStream::from_std(client).unwrap();
+    let (server, _) = listener.accept().unwrap();
+
+    let read = async move {
+        let (res, _) = client.read(vec![0; 8]).await;
+        assert_eq!(res.unwrap(), 5);
+    };
+    assert_barrier(file, read, || {
+        use std::io::Write;
+        (&server).write_all(b"hello").unwrap();
+    })
+    .await;
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"commit");
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn drain_on_fs_ring() {
+    use std::{ffi::CString, io::Write, os::unix::ffi::OsStrExt};
+
+    use monoio::io::AsyncReadRent;
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .with_fs_ring(io_uring::IoUring::builder(), 64)
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let dir = tempfile::tempdir().unwrap();
+        let fifo = dir.path().join("fifo");
+        let path = CString::new(fifo.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(path.as_ptr(), 0o600) }, 0);
+        let writer = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&fifo)
+            .unwrap();
+        // Reads of the fifo go to the file ring, and wait for the writer.
+        let mut reader = File::open(&fifo).await.unwrap();
+        let read = async move {
+            let (res, _) = reader.read(vec![0; 8]).await;
+            assert_eq!(res.unwrap(), 5);
+        };
+
+        let file = File::create(dir.path().join("data")).await.unwrap();
+        assert_barrier(file, read, || (&writer).write_all(b"hello").unwrap()).await;
+    });
+}
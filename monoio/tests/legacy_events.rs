@@ -0,0 +1,47 @@
+#![cfg(feature = "legacy")]
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    LegacyDriver, RuntimeBuilder,
+};
+
+#[test]
+fn small_events_capacity() {
+    const CONNS: usize = 32;
+
+    let mut rt = RuntimeBuilder::<LegacyDriver>::new()
+        .with_events_capacity(2)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = srv.local_addr().unwrap();
+
+        let server = monoio::spawn(async move {
+            for _ in 0..CONNS {
+                let (mut stream, _) = srv.accept().await.unwrap();
+                monoio::spawn(async move {
+                    let (res, buf) = stream.read_exact(vec![0; 4]).await;
+                    res.unwrap();
+                    stream.write_all(buf).await.0.unwrap();
+                });
+            }
+        });
+
+        // Connect everything first, so many fds become ready at once.
+        let mut streams = Vec::new();
+        for _ in 0..CONNS {
+            streams.push(TcpStream::connect(addr).await.unwrap());
+        }
+        for stream in &mut streams {
+            stream.write_all(b"ping").await.0.unwrap();
+        }
+        for stream in &mut streams {
+            let (res, buf) = stream.read_exact(vec![0; 4]).await;
+            res.unwrap();
+            assert_eq!(&buf, b"ping");
+        }
+        server.await;
+    });
+}
@@ -0,0 +1,39 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+#[monoio::test(driver = ["uring", "legacy"], timer_enabled = true)]
+async fn first_available_driver() {
+    monoio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[monoio::test(driver = ["legacy", "fusion"])]
+async fn driver_list_test_returns_result() -> std::io::Result<()> {
+    let listener = monoio::net::TcpListener::bind("127.0.0.1:0")?;
+    listener.local_addr()?;
+    Ok(())
+}
+
+#[monoio::main(driver = ["uring", "legacy"])]
+async fn answer() -> u32 {
+    monoio::spawn(async { 42 }).await
+}
+
+#[test]
+fn driver_list_main_returns_value() {
+    assert_eq!(answer(), 42);
+}
+
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+#[monoio::main(driver = ["uring", "legacy"], threads = 3)]
+async fn on_threads() {
+    STARTED.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn driver_list_on_threads() {
+    on_threads();
+    assert_eq!(STARTED.load(Ordering::Relaxed), 3);
+}
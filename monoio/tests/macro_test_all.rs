@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn every_driver() {
+    monoio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[monoio::test_all(exclude = ["uring", "iocp"], timer_enabled = true)]
+async fn only_legacy() {
+    monoio::time::sleep(Duration::from_millis(1)).await;
+}
+
+// Would collide with the excluded variant.
+#[test]
+fn uring_only_legacy() {}
+
+#[monoio::test_all(exclude = "legacy")]
+async fn without_legacy() {}
+
+#[test]
+fn legacy_without_legacy() {}
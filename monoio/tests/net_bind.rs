@@ -0,0 +1,77 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{udp::UdpSocket, ListenerOpts, TcpListener, TcpStream},
+};
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("[::]:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+async fn accept_one(listener: &TcpListener, addr: SocketAddr) {
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (mut peer, _) = listener.accept().await.unwrap();
+    client.write_all(&b"ping"[..]).await.0.unwrap();
+    let (res, buf) = peer.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+}
+
+#[monoio::test_all]
+async fn tcp_bind_all() {
+    let port = free_port();
+    let addrs = [
+        SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)),
+        SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+    ];
+    // Without IPV6_V6ONLY the second one would conflict with the first.
+    let opts = ListenerOpts::new().reuse_port(false).reuse_addr(false);
+    let listeners = TcpListener::bind_all_with_config(&addrs[..], &opts).unwrap();
+    assert_eq!(listeners.len(), 2);
+    assert_eq!(listeners[0].local_addr().unwrap(), addrs[0]);
+    assert_eq!(listeners[1].local_addr().unwrap(), addrs[1]);
+
+    accept_one(&listeners[0], (Ipv4Addr::LOCALHOST, port).into()).await;
+    accept_one(&listeners[1], (Ipv6Addr::LOCALHOST, port).into()).await;
+
+    assert!(TcpListener::bind_all(&[][..] as &[SocketAddr]).is_err());
+}
+
+#[monoio::test_all]
+async fn tcp_dual_stack() {
+    let listener = TcpListener::dual_stack(0).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    accept_one(&listener, (Ipv4Addr::LOCALHOST, port).into()).await;
+    accept_one(&listener, (Ipv6Addr::LOCALHOST, port).into()).await;
+}
+
+#[monoio::test_all]
+async fn udp_bind_all_and_dual_stack() {
+    let port = free_port();
+    let sockets = UdpSocket::bind_all(
+        &[
+            SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+            SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+        ][..],
+    )
+    .unwrap();
+    assert_eq!(sockets.len(), 2);
+
+    let socket = UdpSocket::dual_stack(0).unwrap();
+    let port = socket.local_addr().unwrap().port();
+    for client in sockets {
+        let target = match client.local_addr().unwrap() {
+            SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+            SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+        };
+        client.send_to("ping", target).await.0.unwrap();
+        let (res, buf) = socket.recv_from(vec![0; 8]).await;
+        res.unwrap();
+        assert_eq!(buf, b"ping");
+    }
+}
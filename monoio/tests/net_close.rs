@@ -0,0 +1,100 @@
+use monoio::{
+    io::AsyncReadRent,
+    net::{udp::UdpSocket, TcpListener, TcpStream},
+    utils::closed_on_drop,
+};
+
+async fn pair(listener: &TcpListener) -> (TcpStream, TcpStream) {
+    let client = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[monoio::test_all]
+async fn close_is_not_counted() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let (client, mut server) = pair(&listener).await;
+    let before = closed_on_drop();
+
+    client.close().await.unwrap();
+    // The peer sees the connection closed.
+    let (res, _) = server.read(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), 0);
+    server.close().await.unwrap();
+    listener.close().await.unwrap();
+    UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .close()
+        .await
+        .unwrap();
+    assert_eq!(closed_on_drop(), before);
+}
+
+#[monoio::test_all]
+async fn drop_is_counted() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let (client, server) = pair(&listener).await;
+    let before = closed_on_drop();
+
+    drop(client);
+    drop(server);
+    drop(listener);
+    assert_eq!(closed_on_drop(), before + 3);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn close_unix() {
+    use monoio::net::{UnixDatagram, UnixStream};
+
+    let before = closed_on_drop();
+    let (a, b) = UnixStream::pair().unwrap();
+    a.close().await.unwrap();
+    b.close().await.unwrap();
+    let (a, b) = UnixDatagram::pair().unwrap();
+    a.close().await.unwrap();
+    b.close().await.unwrap();
+    assert_eq!(closed_on_drop(), before);
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn warn_on_drop_close() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .warn_on_drop_close()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        drop(UdpSocket::bind("127.0.0.1:0").unwrap());
+        assert_eq!(closed_on_drop(), 1);
+    });
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn on_drop_close() {
+    use std::{
+        os::fd::AsRawFd,
+        sync::{
+            atomic::{AtomicI32, Ordering},
+            Arc,
+        },
+    };
+
+    let closed = Arc::new(AtomicI32::new(-1));
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .on_drop_close({
+            let closed = closed.clone();
+            move |fd| closed.store(fd, Ordering::Relaxed)
+        })
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let fd = socket.as_raw_fd();
+        drop(socket);
+        assert_eq!(closed.load(Ordering::Relaxed), fd);
+    });
+}
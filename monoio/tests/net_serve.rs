@@ -0,0 +1,84 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use monoio::{
+    io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt},
+    net::{serve, ShutdownSignal, TcpListener, TcpStream},
+};
+
+async fn echo(mut stream: TcpStream) {
+    let mut buf = vec![0; 64];
+    loop {
+        let (res, b) = stream.read(buf).await;
+        if !matches!(res, Ok(n) if n > 0) {
+            return;
+        }
+        let (res, b) = stream.write_all(b).await;
+        if res.is_err() {
+            return;
+        }
+        buf = b;
+        buf.clear();
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn serve_cancels_after_grace_period() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let signal = ShutdownSignal::new().with_grace_period(Duration::from_millis(50));
+    let served = Rc::new(Cell::new(0));
+
+    let s = served.clone();
+    let server = monoio::spawn(serve(
+        listener,
+        move |stream, _| {
+            s.set(s.get() + 1);
+            echo(stream)
+        },
+        signal.clone(),
+    ));
+
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(&b"ping"[..]).await.0.unwrap();
+    let (res, buf) = client.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+
+    // The idle client keeps its connection open past the grace period.
+    let start = Instant::now();
+    signal.shutdown();
+    server.await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(served.get(), 1);
+
+    // The connection was canceled and the listener closed.
+    let (res, _) = client.read(vec![0; 4]).await;
+    assert_eq!(res.unwrap(), 0);
+    assert!(TcpStream::connect(addr).await.is_err());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn serve_waits_for_connections() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let signal = ShutdownSignal::new();
+    let server = monoio::spawn(serve(listener, |stream, _| echo(stream), signal.clone()));
+
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(&b"ping"[..]).await.0.unwrap();
+    let (res, _) = client.read_exact(vec![0; 4]).await;
+    res.unwrap();
+
+    signal.shutdown();
+    assert!(signal.is_shutdown());
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!server.is_finished());
+
+    // Closing the last connection lets the server return.
+    drop(client);
+    server.await.unwrap();
+}
@@ -0,0 +1,51 @@
+#![cfg(target_os = "linux")]
+
+use std::io;
+
+use monoio::{buf::BufArena, RuntimeBuilder};
+
+// Whether the kernel lets us set memory policies, it may lack NUMA support.
+fn numa_supported() -> bool {
+    BufArena::new_on_node(4096, 1, 0).is_ok()
+}
+
+#[test]
+fn numa_node_runtime() {
+    if !numa_supported() {
+        return;
+    }
+    // The memory policy outlives the runtime, keep it off the test threads.
+    std::thread::spawn(|| {
+        let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+            .numa_node(0)
+            .enable_timer()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let arena = BufArena::new(4096, 2).unwrap();
+            let mut buf = arena.get().unwrap();
+            buf.set_len(4);
+            buf.copy_from_slice(b"numa");
+            assert_eq!(&buf[..], b"numa");
+            monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+        });
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn numa_node_invalid() {
+    assert_eq!(
+        BufArena::new_on_node(4096, 1, 1 << 20).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    std::thread::spawn(|| {
+        assert!(RuntimeBuilder::<monoio::FusionDriver>::new()
+            .numa_node(1 << 20)
+            .build()
+            .is_err());
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,89 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Wake, Waker},
+    time::Duration,
+};
+
+use monoio::{Buildable, Driver, PanicPolicy, RuntimeBuilder};
+
+fn block_on<D, F>(builder: RuntimeBuilder<D>, policy: PanicPolicy, future: F) -> F::Output
+where
+    D: Buildable + Driver,
+    F: Future,
+{
+    D::build(builder.with_panic_policy(policy))
+        .unwrap()
+        .block_on(future)
+}
+
+fn legacy() -> RuntimeBuilder<monoio::time::TimeDriver<monoio::LegacyDriver>> {
+    RuntimeBuilder::<monoio::LegacyDriver>::new().enable_timer()
+}
+
+async fn isolated_task() {
+    let bad = monoio::spawn(async {
+        monoio::time::sleep(Duration::from_millis(1)).await;
+        panic!("task panic");
+    });
+    let good = monoio::spawn(async {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        1
+    });
+    assert_eq!(good.await, 1);
+    assert!(bad.is_finished());
+
+    // Awaiting the panicked task resumes the panic, in the awaiting task.
+    let joiner = monoio::spawn(bad);
+    monoio::time::sleep(Duration::from_millis(1)).await;
+    assert!(joiner.is_finished());
+}
+
+struct PanicWaker;
+
+impl Wake for PanicWaker {
+    fn wake(self: Arc<Self>) {
+        panic!("waker panic");
+    }
+}
+
+async fn isolated_waker() {
+    // Leave a timer registered with a panicking waker, the driver invokes it
+    // when it fires.
+    let waker = Waker::from(Arc::new(PanicWaker));
+    let mut sleep = Box::pin(monoio::time::sleep(Duration::from_millis(1)));
+    assert!(sleep
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert!(pin!(sleep)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready());
+}
+
+#[test]
+fn isolate_legacy() {
+    block_on(legacy(), PanicPolicy::Isolate, isolated_task());
+    block_on(legacy(), PanicPolicy::Isolate, isolated_waker());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn isolate_uring() {
+    let uring = || RuntimeBuilder::<monoio::IoUringDriver>::new().enable_timer();
+    block_on(uring(), PanicPolicy::Isolate, isolated_task());
+    block_on(uring(), PanicPolicy::Isolate, isolated_waker());
+}
+
+#[test]
+fn unwind_by_default() {
+    let res = std::panic::catch_unwind(|| {
+        block_on(legacy(), PanicPolicy::default(), async {
+            monoio::spawn(async { panic!("task panic") });
+            monoio::time::sleep(Duration::from_millis(10)).await;
+        })
+    });
+    assert!(res.is_err());
+}
@@ -0,0 +1,33 @@
+use std::time::{Duration, Instant};
+
+#[monoio::test_all]
+async fn ping_driver() {
+    let latency = monoio::ping_driver().await.unwrap();
+    assert!(latency < Duration::from_secs(1));
+}
+
+#[monoio::test_all]
+async fn ping_driver_waits_for_ready_tasks() {
+    // A task hogging the thread delays the ping.
+    monoio::spawn(async {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(20) {}
+    });
+    let latency = monoio::ping_driver().await.unwrap();
+    assert!(latency >= Duration::from_millis(20));
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn ping_driver_iopoll() {
+    let Ok(mut rt) = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .with_iopoll()
+        .build()
+    else {
+        // IOPOLL is not supported by this kernel.
+        return;
+    };
+    rt.block_on(async {
+        monoio::ping_driver().await.unwrap();
+    });
+}
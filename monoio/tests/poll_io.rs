@@ -0,0 +1,44 @@
+#![cfg(all(target_os = "linux", feature = "iouring", feature = "poll-io"))]
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt, IntoCompIo, IntoPollIo},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn convert_reuses_registration() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        let (res, _) = stream.write_all(buf).await;
+        res.unwrap();
+    });
+
+    let before = monoio::utils::poll_io_stats();
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    for _ in 0..8 {
+        stream = stream.into_poll_io().unwrap().into_comp_io().unwrap();
+    }
+    let (res, _) = stream.write_all(b"hello").await;
+    res.unwrap();
+    let (res, buf) = stream.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    server.await;
+
+    // Only the io_uring driver has a separate poller.
+    if let Some(before) = before {
+        let stats = monoio::utils::poll_io_stats().unwrap();
+        assert_eq!(stats.registered() - before.registered(), 1);
+        assert_eq!(stats.reused() - before.reused(), 7);
+    }
+
+    // Closing the fd drops the registration right away.
+    drop(stream);
+    if let Some(stats) = monoio::utils::poll_io_stats() {
+        assert_eq!(stats.registered(), stats.deregistered());
+    }
+}
@@ -0,0 +1,49 @@
+use std::{future::poll_fn, task::Poll};
+
+use monoio::{
+    io::AsyncWriteRentExt,
+    net::{udp::UdpSocket, TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn tcp_poll_ready() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    poll_fn(|cx| server.poll_write_ready(cx)).await.unwrap();
+    // Nothing to read yet, the wait is kept for the next poll.
+    assert!(poll_fn(|cx| Poll::Ready(server.poll_read_ready(cx).is_pending())).await);
+
+    let (res, _) = client.write_all(b"hello").await;
+    res.unwrap();
+    poll_fn(|cx| server.poll_read_ready(cx)).await.unwrap();
+    server.close().await.unwrap();
+}
+
+#[monoio::test_all]
+async fn unix_poll_ready() {
+    let (mut a, b) = monoio::net::UnixStream::pair().unwrap();
+    poll_fn(|cx| b.poll_write_ready(cx)).await.unwrap();
+    assert!(poll_fn(|cx| Poll::Ready(b.poll_read_ready(cx).is_pending())).await);
+
+    let (res, _) = a.write_all(b"hello").await;
+    res.unwrap();
+    poll_fn(|cx| b.poll_read_ready(cx)).await.unwrap();
+}
+
+#[monoio::test_all]
+async fn udp_poll_ready() {
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+    poll_fn(|cx| b.poll_write_ready(cx)).await.unwrap();
+    assert!(poll_fn(|cx| Poll::Ready(b.poll_read_ready(cx).is_pending())).await);
+
+    let (res, _) = a.send_to(b"hello", b.local_addr().unwrap()).await;
+    res.unwrap();
+    poll_fn(|cx| b.poll_read_ready(cx)).await.unwrap();
+    let (res, buf) = b.recv_from(vec![0; 5]).await;
+    assert_eq!(res.unwrap().0, 5);
+    assert_eq!(buf, b"hello");
+}
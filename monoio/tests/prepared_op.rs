@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn prepared_recv_echo() {
+    const ITER: usize = 64;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    monoio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut recv = stream.prepare_recv(Vec::with_capacity(64));
+        loop {
+            let n = recv.resubmit().await.unwrap();
+            if n == 0 {
+                break;
+            }
+            let data = recv.get_ref().unwrap().clone();
+            assert_eq!(data.len(), n);
+            stream.write_all(data).await.0.unwrap();
+        }
+        assert!(recv.into_inner().unwrap().capacity() >= 64);
+    });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    for i in 0..ITER {
+        let msg = format!("message {i}");
+        stream.write_all(msg.clone().into_bytes()).await.0.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; msg.len()]).await;
+        res.unwrap();
+        assert_eq!(buf, msg.as_bytes());
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn prepared_recv_dropped_in_flight() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let _cli = TcpStream::connect(addr).await.unwrap();
+    let (stream, _) = listener.accept().await.unwrap();
+
+    let mut recv = stream.prepare_recv(vec![0; 16]);
+    monoio::select! {
+        _ = recv.resubmit() => panic!("unexpected data"),
+        _ = monoio::time::sleep(Duration::from_millis(10)) => {}
+    }
+    assert!(recv.get_ref().is_none());
+    assert!(recv.resubmit().await.is_err());
+}
@@ -0,0 +1,38 @@
+#![cfg(target_os = "linux")]
+// The children are reaped through their pidfds.
+#![allow(clippy::zombie_processes)]
+use std::{os::unix::process::ExitStatusExt, process::Command, time::Duration};
+
+use monoio::process::{pidfd_open, PidFd};
+
+#[monoio::test_all]
+async fn wait_exit_code() {
+    let child = Command::new("sh").args(["-c", "exit 3"]).spawn().unwrap();
+    let pidfd = PidFd::from_child(&child).unwrap();
+    assert_eq!(pidfd.pid(), child.id());
+    let status = pidfd.wait().await.unwrap();
+    assert_eq!(status.code(), Some(3));
+    // Reaped already.
+    assert!(pidfd.try_wait().is_err());
+}
+
+#[monoio::test_all]
+async fn kill() {
+    let child = Command::new("sleep").arg("10").spawn().unwrap();
+    let pidfd = pidfd_open(child.id()).unwrap();
+    assert!(pidfd.try_wait().unwrap().is_none());
+    pidfd.kill().unwrap();
+    pidfd.exited().await.unwrap();
+    let status = pidfd.wait().await.unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn exited_waits() {
+    let child = Command::new("sleep").arg("0.2").spawn().unwrap();
+    let pidfd = PidFd::from_child(&child).unwrap();
+    let res = monoio::time::timeout(Duration::from_millis(50), pidfd.exited()).await;
+    assert!(res.is_err());
+    pidfd.exited().await.unwrap();
+    assert!(pidfd.wait().await.unwrap().success());
+}
@@ -0,0 +1,38 @@
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn read_append() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = monoio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&b"hello"[..]).await.0.unwrap();
+        let (res, _) = stream.read_exact(vec![0; 1]).await;
+        res.unwrap();
+        stream.write_all(&b" world"[..]).await.0.unwrap();
+    });
+    let (mut stream, _) = listener.accept().await.unwrap();
+
+    // An empty Vec gets capacity instead of looking like EOF.
+    let (res, buf) = stream.read_append(Vec::new()).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+
+    stream.write_all(&b"?"[..]).await.0.unwrap();
+    let mut total = buf.len();
+    let mut buf = buf;
+    while total < 11 {
+        let (res, b) = stream.read_append(buf).await;
+        total += res.unwrap();
+        buf = b;
+    }
+    assert_eq!(buf, b"hello world");
+
+    client.await;
+    let (res, buf) = stream.read_append(buf).await;
+    assert_eq!(res.unwrap(), 0);
+    assert_eq!(buf, b"hello world");
+}
@@ -0,0 +1,104 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use monoio::{
+    buf::BufRing,
+    io::{stream::Stream, AsyncWriteRentExt},
+    net::{udp::UdpSocket, TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+#[test]
+fn tcp_recv_multishot() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+    rt.block_on(async {
+        let ring = BufRing::new(4, 8).unwrap();
+        assert_eq!((ring.entries(), ring.buf_size()), (4, 8));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (mut conn, _) = listener.accept().await.unwrap();
+
+        let mut received = conn.recv_multishot(&ring);
+        let mut data = Vec::new();
+        client.write_all(&b"hello multishot"[..]).await.0.unwrap();
+        while data.len() < 15 {
+            let buf = received.next().await.unwrap().unwrap();
+            assert!(buf.len() <= 8);
+            data.extend_from_slice(&buf);
+        }
+        assert_eq!(data, b"hello multishot");
+
+        // Dropped buffers go back to the ring, so it never runs dry.
+        for _ in 0..16 {
+            client.write_all(&b"ping"[..]).await.0.unwrap();
+            let buf = received.next().await.unwrap().unwrap();
+            assert_eq!(&buf[..], b"ping");
+        }
+
+        drop(client);
+        assert!(received.next().await.is_none());
+    });
+}
+
+#[test]
+fn udp_recv_multishot() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+    rt.block_on(async {
+        let ring = BufRing::new(8, 64).unwrap();
+        let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+        a.connect(b.local_addr().unwrap()).await.unwrap();
+        b.connect(a.local_addr().unwrap()).await.unwrap();
+
+        let mut received = b.recv_multishot(&ring);
+        for msg in [&b"one"[..], b"two", b"three"] {
+            a.send(msg).await.0.unwrap();
+        }
+        for msg in [&b"one"[..], b"two", b"three"] {
+            let buf = received.next().await.unwrap().unwrap();
+            assert_eq!(&buf[..], msg);
+        }
+    });
+}
+
+#[test]
+fn buf_ring_invalid_size() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+    rt.block_on(async {
+        let err = BufRing::new(3, 64).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(BufRing::new(4, 0).is_err());
+    });
+}
+
+#[test]
+fn dropped_recv_multishot_recycles_buffers() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let ring = BufRing::new(8, 4).unwrap();
+        assert_eq!(ring.available(), 8);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (mut conn, _) = listener.accept().await.unwrap();
+
+        for _ in 0..4 {
+            let mut received = conn.recv_multishot(&ring);
+            client.write_all(&b"ping"[..]).await.0.unwrap();
+            let buf = received.next().await.unwrap().unwrap();
+            assert_eq!(&buf[..], b"ping");
+            drop(buf);
+            // Dropped with data pending, which the kernel may still receive
+            // into buffers of the ring before the recv is canceled.
+            client.write_all(&b"pong pong"[..]).await.0.unwrap();
+            monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+            drop(received);
+            monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+            assert_eq!(ring.available(), 8);
+        }
+    });
+}
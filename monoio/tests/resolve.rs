@@ -0,0 +1,75 @@
+use std::{
+    future::Future,
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    pin::Pin,
+};
+
+use monoio::net::{lookup_host, set_resolver, Resolver, TcpListener, TcpStream};
+
+struct Local;
+
+impl Resolver for Local {
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + 'a>> {
+        Box::pin(async move {
+            match host {
+                "service.test" => Ok(vec![SocketAddr::from((Ipv4Addr::LOCALHOST, port))]),
+                _ => Err(io::ErrorKind::NotFound.into()),
+            }
+        })
+    }
+}
+
+#[monoio::test_all]
+async fn addresses_skip_the_resolver() {
+    let addr: SocketAddr = "127.0.0.1:80".parse().unwrap();
+    assert_eq!(lookup_host(addr).await.unwrap(), [addr]);
+    assert_eq!(lookup_host("127.0.0.1:80").await.unwrap(), [addr]);
+    assert_eq!(lookup_host(("127.0.0.1", 80)).await.unwrap(), [addr]);
+    assert_eq!(lookup_host(&[addr][..]).await.unwrap(), [addr]);
+    assert_eq!(
+        lookup_host("no-port").await.unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[monoio::test_all]
+async fn default_resolver() {
+    let addrs = lookup_host(("localhost", 80)).await.unwrap();
+    assert!(addrs
+        .iter()
+        .all(|addr| addr.ip().is_loopback() && addr.port() == 80));
+}
+
+#[monoio::test_all]
+async fn custom_resolver() {
+    set_resolver(Local);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let stream = TcpStream::connect(("service.test", port)).await.unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), listener.local_addr().unwrap());
+    let err = TcpStream::connect(format!("unknown.test:{port}"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn default_resolver_on_thread_pool() {
+    use monoio::blocking::DefaultThreadPool;
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let addrs = lookup_host("localhost:80").await.unwrap();
+        assert!(!addrs.is_empty());
+    });
+}
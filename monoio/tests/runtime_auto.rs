@@ -0,0 +1,59 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::{AutoRuntime, RuntimeBuilder};
+
+fn build(timer: bool) -> AutoRuntime {
+    let builder = RuntimeBuilder::new_auto();
+    if timer {
+        builder.enable_timer().build().unwrap()
+    } else {
+        builder.build().unwrap()
+    }
+}
+
+#[test]
+fn block_on_with_and_without_timer() {
+    let mut rt = build(false);
+    assert_eq!(rt.block_on(async { monoio::spawn(async { 1 }).await }), 1);
+
+    let mut rt = build(true);
+    let n = rt.block_on(async {
+        monoio::time::sleep(Duration::from_millis(1)).await;
+        2
+    });
+    assert_eq!(n, 2);
+}
+
+#[test]
+fn driver_matches_detection() {
+    let rt = build(false);
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    assert_eq!(rt.is_uring(), rt.uring_report().is_some());
+    #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+    assert!(!rt.is_uring());
+}
+
+#[test]
+fn spawn_and_turn() {
+    let mut rt = build(true);
+    let done = Rc::new(Cell::new(false));
+    let flag = done.clone();
+    let handle = rt.spawn(async move {
+        monoio::time::sleep(Duration::from_millis(1)).await;
+        flag.set(true);
+    });
+    while !handle.is_finished() {
+        rt.turn(Duration::from_millis(10));
+    }
+    assert!(done.get());
+    assert!(rt.metrics().tasks_spawned() >= 1);
+}
+
+#[test]
+fn start_auto() {
+    let n = monoio::start_auto(async {
+        monoio::time::sleep(Duration::from_millis(1)).await;
+        monoio::spawn(async { 3 }).await
+    });
+    assert_eq!(n, 3);
+}
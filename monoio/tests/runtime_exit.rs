@@ -0,0 +1,104 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::{ExitPolicy, FusionDriver, RuntimeBuilder};
+
+struct Dropped(Rc<Cell<bool>>);
+
+impl Drop for Dropped {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+fn spawn_background(done: &Rc<Cell<bool>>) {
+    let done = done.clone();
+    monoio::spawn(async move {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        done.set(true);
+    });
+}
+
+#[test]
+fn exit_with_main_future() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(false));
+    rt.block_on(async { spawn_background(&done) });
+    assert!(!done.get());
+}
+
+#[test]
+fn exit_with_all_tasks() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .with_exit_policy(ExitPolicy::AllTasks)
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(false));
+    assert_eq!(
+        rt.block_on(async {
+            spawn_background(&done);
+            1
+        }),
+        1
+    );
+    assert!(done.get());
+}
+
+#[test]
+fn until_idle_completes() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let out = rt.block_on_until_idle(async {
+        // Waiting on a timer, and on a task, is not idle.
+        monoio::time::sleep(Duration::from_millis(5)).await;
+        let (tx, rx) = monoio::sync::oneshot::channel();
+        monoio::spawn(async move {
+            monoio::time::sleep(Duration::from_millis(5)).await;
+            let _ = tx.send(2);
+        });
+        rx.await.unwrap()
+    });
+    assert_eq!(out, Some(2));
+}
+
+#[test]
+fn until_idle_aborts() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let dropped = Rc::new(Cell::new(false));
+    let guard = Dropped(dropped.clone());
+    let out = rt.block_on_until_idle(async move {
+        let _guard = guard;
+        let (_tx, rx) = monoio::sync::oneshot::channel::<u32>();
+        // The sender is kept, nothing can complete the receiver.
+        rx.await
+    });
+    assert!(out.is_none());
+    assert!(dropped.get());
+
+    // The runtime is still usable.
+    assert_eq!(rt.block_on(async { 3 }), 3);
+}
+
+#[test]
+fn until_idle_waits_for_tasks() {
+    let mut rt = RuntimeBuilder::new_auto()
+        .enable_timer()
+        .with_exit_policy(ExitPolicy::AllTasks)
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(false));
+    let out = rt.block_on_until_idle(async {
+        spawn_background(&done);
+        std::future::pending::<()>().await
+    });
+    assert!(out.is_none());
+    assert!(done.get());
+}
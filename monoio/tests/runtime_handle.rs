@@ -0,0 +1,63 @@
+#![cfg(feature = "sync")]
+
+use std::time::Duration;
+
+use monoio::RuntimeBuilder;
+
+#[test]
+fn spawn_on_from_other_thread() {
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let handle = rt.handle();
+    let runtime_thread = std::thread::current().id();
+
+    let sender = std::thread::spawn(move || {
+        let tasks: Vec<_> = (0..4)
+            .map(|i| handle.spawn_on(async move { (i, std::thread::current().id()) }))
+            .collect();
+        tasks
+            .into_iter()
+            .map(futures::executor::block_on)
+            .collect::<Vec<_>>()
+    });
+    rt.block_on(async {
+        while !sender.is_finished() {
+            monoio::time::sleep(Duration::from_millis(1)).await;
+        }
+    });
+
+    let outputs = sender.join().unwrap();
+    assert_eq!(
+        outputs,
+        (0..4)
+            .map(|i| Some((i, runtime_thread)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn spawn_on_current() {
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    let output = rt.block_on(async {
+        let handle = monoio::RuntimeHandle::current();
+        handle.spawn_on(async { 42 }).await
+    });
+    assert_eq!(output, Some(42));
+}
+
+#[test]
+fn spawn_on_dropped_runtime() {
+    let rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    let handle = rt.handle();
+    drop(rt);
+    assert_eq!(
+        futures::executor::block_on(handle.spawn_on(async { 1 })),
+        None
+    );
+}
@@ -0,0 +1,68 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use monoio::{FusionDriver, RuntimeBuilder};
+
+fn spawn_holding(guard: monoio::KeepAlive, ms: u64, done: &Rc<Cell<u32>>) {
+    let done = done.clone();
+    monoio::spawn(async move {
+        let _guard = guard;
+        monoio::time::sleep(Duration::from_millis(ms)).await;
+        done.set(done.get() + 1);
+    });
+}
+
+#[test]
+fn wait_for_guards() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(0));
+    let n = rt.block_on(async {
+        let guard = monoio::keep_alive();
+        spawn_holding(guard.clone(), 5, &done);
+        spawn_holding(guard, 10, &done);
+        // A task without a guard is not waited for.
+        let done = done.clone();
+        monoio::spawn(async move {
+            monoio::time::sleep(Duration::from_secs(60)).await;
+            done.set(100);
+        });
+        1
+    });
+    assert_eq!(n, 1);
+    assert_eq!(done.get(), 2);
+}
+
+#[test]
+fn exit_timeout() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .with_exit_timeout(Duration::from_millis(20))
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(0));
+    let begin = Instant::now();
+    rt.block_on(async {
+        spawn_holding(monoio::keep_alive(), 5, &done);
+        spawn_holding(monoio::keep_alive(), 60_000, &done);
+    });
+    assert!(begin.elapsed() < Duration::from_secs(30));
+    assert!(begin.elapsed() >= Duration::from_millis(20));
+    assert_eq!(done.get(), 1);
+}
+
+#[test]
+fn guard_dropped_by_future() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new().build().unwrap();
+    let n = rt.block_on(async {
+        let guard = monoio::keep_alive();
+        assert_eq!(format!("{guard:?}"), "KeepAlive { guards: 1 }");
+        2
+    });
+    assert_eq!(n, 2);
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use monoio::utils::runtime_metrics;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn task_counters() {
+    let before = runtime_metrics();
+    // The test itself runs as a task
+    assert!(before.tasks_alive() >= 1);
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| monoio::spawn(monoio::time::sleep(Duration::from_millis(10))))
+        .collect();
+    let spawned = runtime_metrics();
+    assert_eq!(spawned.tasks_spawned(), before.tasks_spawned() + 8);
+    assert_eq!(spawned.tasks_alive(), before.tasks_alive() + 8);
+
+    for task in tasks {
+        task.await;
+    }
+    let after = runtime_metrics();
+    assert_eq!(after.tasks_alive(), before.tasks_alive());
+    assert!(after.tasks_polled() >= before.tasks_polled() + 16);
+    assert!(after.parks() > before.parks());
+}
+
+#[monoio::test_all]
+async fn op_counters() {
+    let before = runtime_metrics();
+    let file = monoio::fs::File::open("Cargo.toml").await.unwrap();
+    let (res, _) = file.read_at(vec![0; 16], 0).await;
+    assert_eq!(res.unwrap(), 16);
+
+    let after = runtime_metrics();
+    if monoio::utils::is_legacy() {
+        assert_eq!(after.ops_in_flight(), None);
+        assert_eq!(after.ops_submitted(), None);
+        assert_eq!(after.completion_stats(), None);
+    } else {
+        assert!(after.ops_submitted().unwrap() >= before.ops_submitted().unwrap() + 2);
+        assert!(after.completion_stats().unwrap().cqes() > 0);
+        let read = file.read_at(vec![0; 16], 0);
+        let mut read = std::pin::pin!(read);
+        assert!(futures::poll!(read.as_mut()).is_pending());
+        assert!(runtime_metrics().ops_in_flight().unwrap() > after.ops_in_flight().unwrap());
+        read.await.0.unwrap();
+    }
+}
+
+#[test]
+fn metrics_from_outside() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    assert_eq!(rt.metrics().tasks_alive(), 0);
+    let handle = rt.spawn(async {});
+    assert_eq!(rt.metrics().tasks_alive(), 1);
+    while !handle.is_finished() {
+        rt.turn(Duration::from_millis(10));
+    }
+    let metrics = rt.metrics();
+    assert_eq!(metrics.tasks_alive(), 0);
+    assert_eq!(metrics.tasks_spawned(), 1);
+    assert_eq!(metrics.tasks_polled(), 1);
+    rt.block_on(async {});
+    assert_eq!(rt.metrics().tasks_spawned(), 2);
+    assert_eq!(rt.metrics().tasks_alive(), 0);
+}
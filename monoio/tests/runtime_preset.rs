@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    Profile,
+};
+
+const PROFILES: [Profile; 3] = [Profile::LowLatency, Profile::Balanced, Profile::Throughput];
+
+async fn echo() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let (res, buf) = conn.read(vec![0; 16]).await;
+        let n = res.unwrap();
+        conn.write_all(buf[..n].to_vec()).await.0.unwrap();
+    });
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(b"ping").await.0.unwrap();
+    let (res, buf) = client.read(vec![0; 16]).await;
+    assert_eq!(&buf[..res.unwrap()], b"ping");
+    server.await;
+    monoio::time::sleep(Duration::from_millis(2)).await;
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_presets() {
+    for profile in PROFILES {
+        monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+            .preset(profile)
+            .enable_timer()
+            .build()
+            .unwrap()
+            .block_on(echo());
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn uring_presets() {
+    for profile in PROFILES {
+        monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .preset(profile)
+            .enable_timer()
+            .build()
+            .unwrap()
+            .block_on(echo());
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn default_builder_is_unaffected() {
+    echo().await;
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn spin_before_park() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .with_spin_before_park(Duration::from_millis(5))
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // Sleeps end whether they expire while spinning or after parking.
+        let begin = Instant::now();
+        monoio::time::sleep(Duration::from_millis(2)).await;
+        monoio::time::sleep(Duration::from_millis(20)).await;
+        assert!(begin.elapsed() >= Duration::from_millis(22));
+        echo().await;
+    });
+}
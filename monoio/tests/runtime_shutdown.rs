@@ -0,0 +1,140 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    AutoRuntime, FusionDriver, RuntimeBuilder,
+};
+
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+fn runtime() -> AutoRuntime {
+    RuntimeBuilder::new_auto().enable_timer().build().unwrap()
+}
+
+#[test]
+fn shutdown_cancels_reads() {
+    let mut rt = runtime();
+    let canceled = Rc::new(Cell::new(false));
+    let flag = canceled.clone();
+    rt.block_on(async move {
+        let (mut stream, peer) = pair().await;
+        monoio::spawn(async move {
+            let _peer = peer;
+            let (res, _buf) = stream.read(vec![0; 16]).await;
+            flag.set(res.unwrap_err().raw_os_error() == Some(libc::ECANCELED));
+        });
+        // Let the read start waiting.
+        monoio::time::sleep(Duration::from_millis(10)).await;
+    });
+    assert!(rt.shutdown_timeout(Duration::from_secs(5)));
+    assert!(canceled.get());
+}
+
+#[test]
+fn shutdown_deadline() {
+    let rt = RuntimeBuilder::<FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let done = Rc::new(Cell::new(false));
+    let flag = done.clone();
+    rt.spawn(async move {
+        // Timers are not canceled.
+        monoio::time::sleep(Duration::from_secs(60)).await;
+        flag.set(true);
+    });
+    let begin = Instant::now();
+    assert!(!rt.shutdown_timeout(Duration::from_millis(20)));
+    assert!(begin.elapsed() >= Duration::from_millis(20));
+    assert!(begin.elapsed() < Duration::from_secs(30));
+    assert!(!done.get());
+}
+
+#[test]
+fn shutdown_runs_spawned_tasks() {
+    let rt = runtime();
+    let joined = Rc::new(Cell::new(false));
+    let flag = joined.clone();
+    rt.spawn(async move {
+        monoio::time::sleep(Duration::from_millis(5)).await;
+        // Spawned and awaited while the runtime shuts down.
+        let n = monoio::spawn(async {
+            monoio::time::sleep(Duration::from_millis(5)).await;
+            2
+        })
+        .await;
+        flag.set(n == 2);
+    });
+    assert!(rt.shutdown_timeout(Duration::from_secs(5)));
+    assert!(joined.get());
+}
+
+#[test]
+fn signal_runs_spawned_tasks() {
+    let mut rt = runtime();
+    let out = rt.block_on_with_shutdown(
+        async {
+            // Woken by the shutdown, then finishing its work in a task.
+            let (mut stream, _peer) = pair().await;
+            let (res, _buf) = stream.read(vec![0; 16]).await;
+            assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ECANCELED));
+            monoio::spawn(async { 3 }).await
+        },
+        async { monoio::time::sleep(Duration::from_millis(20)).await },
+        Duration::from_secs(5),
+    );
+    assert_eq!(out, Some(3));
+}
+
+#[test]
+fn future_completes_before_signal() {
+    let mut rt = runtime();
+    let out = rt.block_on_with_shutdown(
+        async { 1 },
+        std::future::pending::<()>(),
+        Duration::from_secs(1),
+    );
+    assert_eq!(out, Some(1));
+    // The runtime still accepts spawns.
+    assert_eq!(rt.block_on(async { monoio::spawn(async { 2 }).await }), 2);
+}
+
+#[test]
+fn graceful_shutdown() {
+    let mut rt = runtime();
+    let flushed = Rc::new(Cell::new(false));
+    let flag = flushed.clone();
+    let (tx, rx) = monoio::sync::oneshot::channel::<()>();
+    let out = rt.block_on_with_shutdown(
+        async move {
+            let (mut client, mut server) = pair().await;
+            // A background task finishing its work once the shutdown begins.
+            let (ready_tx, ready_rx) = monoio::sync::oneshot::channel::<()>();
+            monoio::spawn(async move {
+                let _ = ready_rx.await;
+                server.write_all(b"bye").await.0.unwrap();
+                flag.set(true);
+            });
+            let _ = tx.send(());
+            // The read waiting for the peer is canceled.
+            let (res, _buf) = client.read(vec![0; 16]).await;
+            let _ = ready_tx.send(());
+            res.unwrap_err().raw_os_error()
+        },
+        rx,
+        Duration::from_secs(5),
+    );
+    assert_eq!(out, Some(Some(libc::ECANCELED)));
+    assert!(flushed.get());
+}
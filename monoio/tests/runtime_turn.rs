@@ -0,0 +1,67 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn turn_runs_spawned_tasks() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+
+    let count = Rc::new(Cell::new(0));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let count = count.clone();
+            rt.spawn(async move { count.set(count.get() + 1) })
+        })
+        .collect();
+
+    rt.turn(Duration::ZERO);
+    assert_eq!(count.get(), 4);
+    assert!(handles.iter().all(|h| h.is_finished()));
+}
+
+#[test]
+fn turn_waits_at_most_max_time() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+
+    let handle = rt.spawn(async {
+        monoio::time::sleep(Duration::from_millis(50)).await;
+    });
+
+    let begin = Instant::now();
+    assert!(!rt.turn(Duration::from_millis(5)));
+    assert!(begin.elapsed() < Duration::from_millis(50));
+    assert!(!handle.is_finished());
+
+    while !handle.is_finished() {
+        rt.turn(Duration::from_millis(10));
+    }
+    assert!(begin.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn turn_reports_ready_tasks() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+
+    // A task that keeps waking itself is always ready.
+    let stop = Rc::new(Cell::new(false));
+    let stop_clone = stop.clone();
+    let handle = rt.spawn(async move {
+        while !stop_clone.get() {
+            monoio::task::before_park().await;
+        }
+    });
+    assert!(rt.turn(Duration::from_secs(1)));
+    stop.set(true);
+    while rt.turn(Duration::ZERO) {}
+    assert!(handle.is_finished());
+}
@@ -0,0 +1,131 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use monoio::sync::{Mutex, RwLock, Semaphore};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn mutex_held_across_await() {
+    let mutex = Rc::new(Mutex::new(Vec::new()));
+    let mut tasks = Vec::new();
+    for i in 0..3 {
+        let mutex = mutex.clone();
+        tasks.push(monoio::spawn(async move {
+            let mut guard = mutex.lock().await;
+            guard.push(i);
+            monoio::time::sleep(Duration::from_millis(5)).await;
+            guard.push(i);
+        }));
+    }
+    for task in tasks {
+        task.await;
+    }
+    // Each task held the lock for both of its pushes, in turn.
+    assert_eq!(*mutex.lock().await, [0, 0, 1, 1, 2, 2]);
+}
+
+#[monoio::test_all]
+async fn mutex_try_lock() {
+    let mut mutex = Mutex::new(1);
+    {
+        let guard = mutex.try_lock().unwrap();
+        assert!(mutex.try_lock().is_none());
+        assert_eq!(*guard, 1);
+    }
+    *mutex.get_mut() = 2;
+    assert_eq!(*mutex.try_lock().unwrap(), 2);
+    assert_eq!(mutex.into_inner(), 2);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn rwlock_writer_not_starved() {
+    let lock = Rc::new(RwLock::new(0));
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let reader = lock.read().await;
+
+    let writer = {
+        let (lock, order) = (lock.clone(), order.clone());
+        monoio::spawn(async move {
+            *lock.write().await += 1;
+            order.borrow_mut().push("write");
+        })
+    };
+    monoio::time::sleep(Duration::from_millis(5)).await;
+    // A reader arriving after the writer waits for it.
+    assert!(lock.try_read().is_none());
+    let late_reader = {
+        let (lock, order) = (lock.clone(), order.clone());
+        monoio::spawn(async move {
+            assert_eq!(*lock.read().await, 1);
+            order.borrow_mut().push("read");
+        })
+    };
+    monoio::time::sleep(Duration::from_millis(5)).await;
+    assert!(order.borrow().is_empty());
+
+    drop(reader);
+    writer.await;
+    late_reader.await;
+    assert_eq!(*order.borrow(), ["write", "read"]);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn semaphore_fifo() {
+    let semaphore = Rc::new(Semaphore::new(2));
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let held = semaphore.acquire_many(2).await;
+
+    let mut tasks = Vec::new();
+    for (name, n) in [("two", 2), ("one", 1)] {
+        let (semaphore, order) = (semaphore.clone(), order.clone());
+        tasks.push(monoio::spawn(async move {
+            let _permit = semaphore.acquire_many(n).await;
+            order.borrow_mut().push(name);
+        }));
+        monoio::time::sleep(Duration::from_millis(5)).await;
+    }
+    // The task asking for one permit does not overtake the one before.
+    drop(held);
+    for task in tasks {
+        task.await;
+    }
+    assert_eq!(*order.borrow(), ["two", "one"]);
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn semaphore_canceled_acquire() {
+    let semaphore = Rc::new(Semaphore::new(1));
+    let held = semaphore.clone().acquire_owned().await;
+
+    // A waiter which gives up does not hold back the ones behind it.
+    let big = {
+        let semaphore = semaphore.clone();
+        monoio::spawn(async move {
+            monoio::time::timeout(Duration::from_millis(5), semaphore.acquire_many(2))
+                .await
+                .is_err()
+        })
+    };
+    monoio::time::sleep(Duration::from_millis(1)).await;
+    let small = {
+        let semaphore = semaphore.clone();
+        monoio::spawn(async move { semaphore.acquire().await.num_permits() })
+    };
+    assert!(big.await);
+    drop(held);
+    assert_eq!(small.await, 1);
+
+    assert!(semaphore.try_acquire().unwrap().num_permits() == 1);
+    semaphore.try_acquire().unwrap().forget();
+    assert_eq!(semaphore.available_permits(), 0);
+    semaphore.add_permits(1);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[monoio::test_all]
+async fn semaphore_failed_try_acquire() {
+    let semaphore = Rc::new(Semaphore::new(1));
+    let _held = semaphore.try_acquire().unwrap();
+    assert!(semaphore.try_acquire_many(1).is_none());
+    assert!(semaphore.clone().try_acquire_owned().is_none());
+    assert_eq!(semaphore.available_permits(), 0);
+}
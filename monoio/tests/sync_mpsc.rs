@@ -0,0 +1,221 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+use monoio::sync::mpsc::{self, TryRecvError, TrySendError};
+
+#[monoio::test_all]
+async fn recv_in_order_until_senders_dropped() {
+    let (tx, mut rx) = mpsc::channel(4);
+    let tx2 = tx.clone();
+    tx.send(1).await.unwrap();
+    tx2.send(2).await.unwrap();
+    assert_eq!(rx.len(), 2);
+    drop(tx);
+    assert_eq!(rx.recv().await, Some(1));
+    tx2.send(3).await.unwrap();
+    drop(tx2);
+    assert_eq!(rx.recv().await, Some(2));
+    assert_eq!(rx.recv().await, Some(3));
+    assert_eq!(rx.recv().await, None);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn send_waits_for_room() {
+    let (tx, mut rx) = mpsc::channel(2);
+    let sent = Rc::new(Cell::new(0));
+    let producer = {
+        let sent = sent.clone();
+        monoio::spawn(async move {
+            for i in 0..5 {
+                tx.send(i).await.unwrap();
+                sent.set(sent.get() + 1);
+            }
+        })
+    };
+
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(sent.get(), 2);
+    assert!(matches!(rx.try_recv(), Ok(0)));
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(sent.get(), 3);
+
+    let mut received = vec![0];
+    while let Some(i) = rx.recv().await {
+        received.push(i);
+    }
+    assert_eq!(received, [0, 1, 2, 3, 4]);
+    producer.await;
+}
+
+#[monoio::test_all]
+async fn try_send_and_close() {
+    let (tx, mut rx) = mpsc::channel(1);
+    assert_eq!(tx.capacity(), 1);
+    tx.try_send(1).unwrap();
+    assert_eq!(tx.capacity(), 0);
+    assert!(matches!(tx.try_send(2), Err(TrySendError::Full(2))));
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    tx.try_send(3).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(4).await.unwrap_err().0, 4);
+    // Values sent before closing are still received.
+    assert_eq!(rx.recv().await, Some(3));
+    assert_eq!(rx.recv().await, None);
+}
+
+#[derive(Default)]
+struct CountWake(AtomicUsize);
+
+impl Wake for CountWake {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn count_waker() -> (Arc<CountWake>, Waker) {
+    let count = Arc::new(CountWake::default());
+    (count.clone(), Waker::from(count))
+}
+
+#[test]
+fn recv_wakes_one_sender() {
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(0).unwrap();
+    let (count1, waker1) = count_waker();
+    let (count2, waker2) = count_waker();
+    let mut send1 = pin!(tx.send(1));
+    let mut send2 = pin!(tx.send(2));
+    // Polling again does not queue the sender twice.
+    for _ in 0..3 {
+        assert!(send1
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker1))
+            .is_pending());
+    }
+    assert!(send2
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker2))
+        .is_pending());
+
+    assert_eq!(rx.try_recv(), Ok(0));
+    assert_eq!(count1.0.load(Ordering::Relaxed), 1);
+    assert_eq!(count2.0.load(Ordering::Relaxed), 0);
+    let cx = &mut Context::from_waker(&waker1);
+    assert!(matches!(send1.as_mut().poll(cx), Poll::Ready(Ok(()))));
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(count1.0.load(Ordering::Relaxed), 1);
+    assert_eq!(count2.0.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn dropped_sender_passes_room_on() {
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(0).unwrap();
+    let (count1, waker1) = count_waker();
+    let (count2, waker2) = count_waker();
+    let mut send1 = Box::pin(tx.send(1));
+    let mut send2 = pin!(tx.send(2));
+    assert!(send1
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker1))
+        .is_pending());
+    assert!(send2
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker2))
+        .is_pending());
+
+    assert_eq!(rx.try_recv(), Ok(0));
+    assert_eq!(count1.0.load(Ordering::Relaxed), 1);
+    drop(send1);
+    assert_eq!(count2.0.load(Ordering::Relaxed), 1);
+    let cx = &mut Context::from_waker(&waker2);
+    assert!(matches!(send2.as_mut().poll(cx), Poll::Ready(Ok(()))));
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[monoio::test_all]
+async fn dropped_receiver_drops_values() {
+    let value = Rc::new(());
+    let (tx, rx) = mpsc::channel(2);
+    tx.send(value.clone()).await.unwrap();
+    assert_eq!(Rc::strong_count(&value), 2);
+    drop(rx);
+    assert_eq!(Rc::strong_count(&value), 1);
+    assert!(tx.is_closed());
+    assert!(tx.send(value).await.is_err());
+}
+
+#[cfg(feature = "sync")]
+#[monoio::test_all]
+async fn shared_from_other_threads() {
+    use monoio::sync::mpsc::shared;
+
+    let (tx, mut rx) = shared::channel(2);
+    let producers: Vec<_> = (0..4)
+        .map(|t| {
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                for i in 0..8 {
+                    futures::executor::block_on(tx.send(t * 8 + i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut received = Vec::new();
+    while let Some(i) = rx.recv().await {
+        received.push(i);
+    }
+    received.sort_unstable();
+    assert_eq!(received, (0..32).collect::<Vec<_>>());
+    for producer in producers {
+        producer.join().unwrap();
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn shared_between_runtimes() {
+    use monoio::sync::mpsc::shared;
+
+    let (tx, mut rx) = shared::channel(1);
+    let producer = std::thread::spawn(move || {
+        let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            for i in 0..16 {
+                tx.send(i).await.unwrap();
+            }
+        });
+    });
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    let received = rt.block_on(async move {
+        let mut received = Vec::new();
+        while let Some(i) = rx.recv().await {
+            received.push(i);
+        }
+        received
+    });
+    assert_eq!(received, (0..16).collect::<Vec<_>>());
+    producer.join().unwrap();
+}
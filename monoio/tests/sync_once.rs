@@ -0,0 +1,89 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use monoio::sync::{Lazy, OnceCell};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn init_once() {
+    static CELL: OnceCell<u32> = OnceCell::new();
+    assert!(CELL.get().is_none());
+    assert!(!CELL.initialized());
+
+    let calls = Rc::new(Cell::new(0));
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let calls = calls.clone();
+            monoio::spawn(async move {
+                CELL.get_or_init(|| async move {
+                    monoio::time::sleep(Duration::from_millis(20)).await;
+                    calls.set(calls.get() + 1);
+                    7
+                })
+                .await
+            })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(*task.await, 7);
+    }
+    assert_eq!(calls.get(), 1);
+    assert_eq!(CELL.get().as_deref(), Some(&7));
+    assert_eq!(CELL.set(8), Err(8));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn failed_init_is_retried() {
+    static CELL: OnceCell<u32> = OnceCell::new();
+    let failing = monoio::spawn(CELL.get_or_try_init(|| async {
+        monoio::time::sleep(Duration::from_millis(20)).await;
+        Err("failed")
+    }));
+    // Waits for the first initialization, then takes over.
+    monoio::time::sleep(Duration::from_millis(5)).await;
+    let waiting = monoio::spawn(CELL.get_or_try_init(|| async { Ok::<_, &str>(1) }));
+    assert_eq!(failing.await, Err("failed"));
+    assert_eq!(*waiting.await.unwrap(), 1);
+}
+
+#[monoio::test_all]
+async fn set() {
+    static CELL: OnceCell<String> = OnceCell::new();
+    CELL.set("value".to_string()).unwrap();
+    assert!(CELL.initialized());
+    assert_eq!(
+        *CELL.get_or_init(|| async { unreachable!() }).await,
+        "value"
+    );
+}
+
+#[monoio::test_all]
+async fn lazy() {
+    static LAZY: Lazy<Cell<u32>> = Lazy::new(|| Cell::new(1));
+    LAZY.get().set(LAZY.get().get() + 1);
+    assert_eq!(LAZY.get().get(), 2);
+}
+
+// Each runtime has values of its own, also one after the other on a thread.
+#[test]
+fn per_runtime() {
+    static INITS: AtomicUsize = AtomicUsize::new(0);
+    static LAZY: Lazy<Cell<usize>> = Lazy::new(|| Cell::new(INITS.fetch_add(1, Ordering::SeqCst)));
+    static CELL: OnceCell<usize> = OnceCell::new();
+
+    let run = || {
+        monoio::start::<monoio::LegacyDriver, _>(async {
+            assert!(CELL.get().is_none());
+            CELL.set(LAZY.get().get()).unwrap();
+            *CELL.get().unwrap()
+        })
+    };
+    let threads: Vec<_> = (0..2).map(|_| std::thread::spawn(run)).collect();
+    let mut values: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    values.push(run());
+    values.sort();
+    assert_eq!(values, [0, 1, 2]);
+}
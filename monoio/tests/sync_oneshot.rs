@@ -0,0 +1,30 @@
+use monoio::sync::oneshot::{self, RecvError, TryRecvError};
+
+#[monoio::test_all]
+async fn send_and_recv() {
+    let (tx, rx) = oneshot::channel();
+    let sender = monoio::spawn(async move { tx.send(42) });
+    assert_eq!(rx.await, Ok(42));
+    assert_eq!(sender.await, Ok(()));
+}
+
+#[monoio::test_all]
+async fn sender_dropped() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    monoio::spawn(async move { drop(tx) });
+    assert_eq!(rx.await, Err(RecvError));
+}
+
+#[monoio::test_all]
+async fn try_recv_and_close() {
+    let (tx, mut rx) = oneshot::channel();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    rx.close();
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(1), Err(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+
+    let (tx, rx) = oneshot::channel();
+    drop(rx);
+    assert_eq!(tx.send(2), Err(2));
+}
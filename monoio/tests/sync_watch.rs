@@ -0,0 +1,74 @@
+#![cfg(feature = "sync")]
+
+use std::time::Duration;
+
+use monoio::sync::watch::{self, RecvError};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn changed_sees_latest() {
+    let (tx, mut rx) = watch::channel(0);
+    assert_eq!(rx.has_changed(), Ok(false));
+    tx.send(1);
+    tx.send(2);
+    assert_eq!(rx.has_changed(), Ok(true));
+    // Changes in between are skipped.
+    rx.changed().await.unwrap();
+    assert_eq!(*rx.borrow(), 2);
+    assert_eq!(rx.has_changed(), Ok(false));
+
+    let waiter = {
+        let mut rx = rx.clone();
+        monoio::spawn(async move {
+            rx.changed().await.unwrap();
+            *rx.borrow()
+        })
+    };
+    monoio::time::sleep(Duration::from_millis(5)).await;
+    tx.send_modify(|value| *value += 1);
+    assert_eq!(waiter.await, 3);
+
+    let mut late = tx.subscribe();
+    assert_eq!(late.has_changed(), Ok(false));
+    assert_eq!(tx.receiver_count(), 2);
+    drop(tx);
+    assert_eq!(late.changed().await, Err(RecvError));
+    // The last value is kept.
+    assert_eq!(*late.borrow_and_update(), 3);
+}
+
+#[test]
+fn wakes_other_runtimes() {
+    let (tx, rx) = watch::channel(0u32);
+    let workers: Vec<_> = (0..3)
+        .map(|_| {
+            let mut rx = rx.clone();
+            std::thread::spawn(move || {
+                let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+                    .build()
+                    .unwrap();
+                rt.block_on(async move {
+                    let mut seen = Vec::new();
+                    while rx.changed().await.is_ok() {
+                        seen.push(*rx.borrow_and_update());
+                    }
+                    seen
+                })
+            })
+        })
+        .collect();
+    drop(rx);
+
+    for i in 1..=3 {
+        // Let the workers go to sleep, waiting for the change.
+        std::thread::sleep(Duration::from_millis(20));
+        tx.send(i);
+    }
+    // The workers still see the last value once the sender is dropped.
+    drop(tx);
+    for worker in workers {
+        let seen = worker.join().unwrap();
+        // Values may be skipped, but not the last one.
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(seen.last(), Some(&3));
+    }
+}
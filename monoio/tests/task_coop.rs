@@ -0,0 +1,94 @@
+use std::{cell::Cell, rc::Rc};
+
+use monoio::{net::udp::UdpSocket, task::consume_budget, Buildable, Driver, RuntimeBuilder};
+
+/// Loops until another task runs, returning the number of iterations.
+async fn greedy_until_other_runs() -> usize {
+    let ran = Rc::new(Cell::new(false));
+    let greedy = monoio::spawn({
+        let ran = ran.clone();
+        async move {
+            let mut iterations = 0;
+            while !ran.get() {
+                consume_budget().await;
+                iterations += 1;
+            }
+            iterations
+        }
+    });
+    monoio::spawn(async move { ran.set(true) });
+    greedy.await
+}
+
+#[monoio::test_all]
+async fn consume_budget_yields() {
+    // The checkpoint out of budget completes once the task is polled again.
+    let iterations = greedy_until_other_runs().await;
+    assert_eq!(iterations, 128 + 1);
+}
+
+#[test]
+fn configured_budget() {
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_task_budget(Some(4))
+        .build()
+        .unwrap();
+    let iterations = rt.block_on(greedy_until_other_runs());
+    assert_eq!(iterations, 4 + 1);
+}
+
+#[monoio::test_all]
+async fn consume_budget_outside_task() {
+    // The main future is not budgeted.
+    for _ in 0..1000 {
+        consume_budget().await;
+    }
+}
+
+fn ready_io_yields<D: Buildable + Driver>() {
+    const DATAGRAMS: usize = 64;
+
+    let builder = RuntimeBuilder::<D>::new().with_task_budget(Some(8));
+    let mut rt = Buildable::build(builder).unwrap();
+    rt.block_on(async {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = receiver.local_addr().unwrap();
+        for _ in 0..DATAGRAMS {
+            let (res, _) = sender.send_to(vec![0u8; 8], addr).await;
+            res.unwrap();
+        }
+
+        let greedy = monoio::spawn(async move {
+            let received = Rc::new(Cell::new(0));
+            let mut buf = vec![0u8; 8];
+            let mut other = None;
+            for _ in 0..DATAGRAMS {
+                let (res, b) = receiver.recv_from(buf).await;
+                res.unwrap();
+                buf = b;
+                received.set(received.get() + 1);
+                // Spawned once the datagrams are known to be ready.
+                other.get_or_insert_with(|| {
+                    let received = received.clone();
+                    monoio::spawn(async move { received.get() })
+                });
+            }
+            other.unwrap().await
+        });
+        // The other task ran before all the datagrams were received.
+        assert!(greedy.await < DATAGRAMS);
+    })
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn uring_ready_io_yields() {
+    ready_io_yields::<monoio::IoUringDriver>();
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_ready_io_yields() {
+    ready_io_yields::<monoio::LegacyDriver>();
+}
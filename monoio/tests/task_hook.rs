@@ -0,0 +1,80 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use monoio::{
+    task::{TaskHook, TaskId},
+    RuntimeBuilder,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Event {
+    Spawn(TaskId),
+    FirstPoll(TaskId),
+    Poll(TaskId),
+    Complete(TaskId),
+}
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Event>>);
+
+impl TaskHook for Recorder {
+    fn on_spawn(&self, id: TaskId) {
+        self.0.lock().unwrap().push(Event::Spawn(id));
+    }
+
+    fn on_first_poll(&self, id: TaskId) {
+        self.0.lock().unwrap().push(Event::FirstPoll(id));
+    }
+
+    fn on_poll(&self, id: TaskId, elapsed: Duration) {
+        assert!(elapsed < Duration::from_secs(5));
+        self.0.lock().unwrap().push(Event::Poll(id));
+    }
+
+    fn on_complete(&self, id: TaskId) {
+        self.0.lock().unwrap().push(Event::Complete(id));
+    }
+}
+
+fn events_of(recorder: &Recorder, id: TaskId) -> Vec<Event> {
+    let mut events = recorder.0.lock().unwrap();
+    let (of_task, others) = events.drain(..).partition(|event| {
+        matches!(event, Event::Spawn(i) | Event::FirstPoll(i) | Event::Poll(i) | Event::Complete(i) if *i == id)
+    });
+    *events = others;
+    of_task
+}
+
+#[test]
+fn hook_events() {
+    let recorder = Arc::new(Recorder::default());
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_task_hook(recorder.clone())
+        .enable_timer()
+        .build()
+        .unwrap();
+    let id = rt.block_on(async {
+        let handle = monoio::spawn(async {
+            monoio::time::sleep(Duration::from_millis(1)).await;
+        });
+        let id = handle.id();
+        handle.await;
+        id
+    });
+
+    use Event::*;
+    assert_eq!(
+        events_of(&recorder, id),
+        [Spawn(id), FirstPoll(id), Poll(id), Complete(id), Poll(id)]
+    );
+}
+
+#[test]
+fn runtime_without_hook() {
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    assert_eq!(rt.block_on(async { monoio::spawn(async { 1 }).await }), 1);
+}
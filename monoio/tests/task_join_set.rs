@@ -0,0 +1,58 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::task::JoinSet;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn join_in_completion_order() {
+    let mut set = JoinSet::new();
+    assert!(set.is_empty());
+    for ms in [30, 10, 20] {
+        set.spawn(async move {
+            monoio::time::sleep(Duration::from_millis(ms)).await;
+            ms
+        });
+    }
+    assert_eq!(set.len(), 3);
+
+    let mut order = Vec::new();
+    while let Some(ms) = set.join_next().await {
+        order.push(ms);
+    }
+    assert_eq!(order, [10, 20, 30]);
+    assert!(set.is_empty());
+    assert_eq!(set.join_next().await, None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn abort_on_drop() {
+    struct Dropped(Rc<Cell<usize>>);
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(0));
+    let finished = Rc::new(Cell::new(0));
+    let mut set = JoinSet::new();
+    for i in 0..4 {
+        let guard = Dropped(dropped.clone());
+        let finished = finished.clone();
+        set.spawn(async move {
+            let _guard = guard;
+            // The first task runs before the set is dropped.
+            if i > 0 {
+                monoio::time::sleep(Duration::from_secs(60)).await;
+            }
+            finished.set(finished.get() + 1);
+        });
+    }
+    set.join_next().await.unwrap();
+    assert_eq!(set.len(), 3);
+    drop(set);
+
+    // Let the aborted tasks be dropped.
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(dropped.get(), 4);
+    assert_eq!(finished.get(), 1);
+}
@@ -0,0 +1,105 @@
+use std::{cell::RefCell, time::Duration};
+
+monoio::task_local! {
+    static REQ_ID: u32;
+    static TRACE: RefCell<Vec<&'static str>>;
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn scope_across_await() {
+    assert!(REQ_ID.try_with(|_| ()).is_err());
+    let res = REQ_ID
+        .scope(1, async {
+            assert_eq!(REQ_ID.get(), 1);
+            monoio::time::sleep(Duration::from_millis(1)).await;
+            assert_eq!(REQ_ID.get(), 1);
+
+            // Nested scope shadows the outer value.
+            REQ_ID
+                .scope(2, async {
+                    monoio::time::sleep(Duration::from_millis(1)).await;
+                    REQ_ID.get()
+                })
+                .await
+        })
+        .await;
+    assert_eq!(res, 2);
+    assert!(REQ_ID.try_with(|_| ()).is_err());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn scope_per_task() {
+    let (tx, rx) = local_sync::oneshot::channel();
+    let a = monoio::spawn(REQ_ID.scope(1, async move {
+        rx.await.unwrap();
+        REQ_ID.get()
+    }));
+    let b = monoio::spawn(REQ_ID.scope(2, async move {
+        tx.send(()).unwrap();
+        monoio::time::sleep(Duration::from_millis(1)).await;
+        REQ_ID.get()
+    }));
+    assert_eq!(a.await, 1);
+    assert_eq!(b.await, 2);
+}
+
+#[test]
+fn sync_scope() {
+    TRACE.sync_scope(RefCell::new(Vec::new()), || {
+        TRACE.with(|t| t.borrow_mut().push("a"));
+        TRACE.with(|t| t.borrow_mut().push("b"));
+        assert_eq!(TRACE.with(|t| t.borrow().clone()), ["a", "b"]);
+    });
+}
+
+#[test]
+#[should_panic]
+fn access_without_scope() {
+    REQ_ID.with(|_| ());
+}
+
+#[monoio::test_all]
+async fn spawn_with_context_inherits() {
+    REQ_ID
+        .scope_inherited(7, async {
+            // Plain spawn does not inherit.
+            let plain = monoio::spawn(async { REQ_ID.try_with(|v| *v).ok() });
+            assert_eq!(plain.await, None);
+
+            let child = monoio::spawn_with_context(async {
+                let id = REQ_ID.get();
+                // Grandchildren inherit too.
+                let grandchild = monoio::spawn_with_context(async { REQ_ID.get() });
+                (id, grandchild.await)
+            });
+            assert_eq!(child.await, (7, 7));
+
+            // The innermost value is captured.
+            let nested = REQ_ID
+                .scope_inherited(8, async {
+                    monoio::spawn_with_context(async { REQ_ID.get() }).await
+                })
+                .await;
+            assert_eq!(nested, 8);
+        })
+        .await;
+
+    // Non-inheritable scopes are not captured.
+    let res = REQ_ID
+        .scope(9, async {
+            monoio::spawn_with_context(async { REQ_ID.try_with(|v| *v).ok() }).await
+        })
+        .await;
+    assert_eq!(res, None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn spawn_with_context_inherits_deadline() {
+    use monoio::time::Deadline;
+
+    let deadline = Deadline::after(Duration::from_secs(10));
+    let res = deadline
+        .scope(async { monoio::spawn_with_context(async { Deadline::current() }).await })
+        .await;
+    assert_eq!(res, Ok(Some(deadline)));
+}
@@ -0,0 +1,70 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use monoio::Priority;
+
+#[monoio::test_all]
+async fn high_priority_runs_first() {
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let mut handles = Vec::new();
+    for i in 0..3 {
+        let order = order.clone();
+        handles.push(monoio::spawn(async move { order.borrow_mut().push(i) }));
+    }
+    let high = {
+        let order = order.clone();
+        monoio::spawn_with_priority(async move { order.borrow_mut().push(99) }, Priority::High)
+    };
+
+    high.await;
+    for handle in handles {
+        handle.await;
+    }
+    assert_eq!(*order.borrow(), [99, 0, 1, 2]);
+}
+
+#[monoio::test_all]
+async fn woken_high_priority_runs_first() {
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let waker: Rc<RefCell<Option<Waker>>> = Rc::default();
+    let ready = Rc::new(Cell::new(false));
+
+    let high = {
+        let (order, waker, ready) = (order.clone(), waker.clone(), ready.clone());
+        monoio::spawn_with_priority(
+            async move {
+                poll_fn(|cx| {
+                    if ready.get() {
+                        return Poll::Ready(());
+                    }
+                    *waker.borrow_mut() = Some(cx.waker().clone());
+                    Poll::Pending
+                })
+                .await;
+                order.borrow_mut().push("high");
+            },
+            Priority::High,
+        )
+    };
+    let mut handles = Vec::new();
+    for name in ["a", "b", "c"] {
+        let (order, waker, ready) = (order.clone(), waker.clone(), ready.clone());
+        handles.push(monoio::spawn(async move {
+            order.borrow_mut().push(name);
+            if name == "a" {
+                ready.set(true);
+                waker.borrow_mut().take().unwrap().wake();
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.await;
+    }
+    high.await;
+    assert_eq!(*order.borrow(), ["a", "high", "b", "c"]);
+}
@@ -1,6 +1,6 @@
 use std::net::{IpAddr, SocketAddr};
 
-use monoio::net::{TcpListener, TcpStream};
+use monoio::net::{AcceptAddrBuf, TcpListener, TcpStream};
 
 macro_rules! test_accept {
     ($(($ident:ident, $target:expr),)*) => {
@@ -29,3 +29,69 @@ test_accept! {
     (str_port_tuple, ("127.0.0.1", 0)),
     (ip_port_tuple, ("127.0.0.1".parse::<IpAddr>().unwrap(), 0)),
 }
+
+#[monoio::test_all]
+async fn accept_with_addr_buf() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut addr_buf = AcceptAddrBuf::new();
+    for _ in 0..3 {
+        let cli = TcpStream::connect(&addr).await.unwrap();
+        let (res, buf) = listener.accept_with_addr_buf(addr_buf).await;
+        addr_buf = buf;
+        let (srv, peer) = res.unwrap();
+        assert_eq!(peer, cli.local_addr().unwrap());
+        assert_eq!(srv.peer_addr().unwrap(), peer);
+    }
+}
+
+#[monoio::test_all]
+async fn accept_reuses_addr_buf() {
+    let listener = TcpListener::bind("[::1]:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    for _ in 0..3 {
+        let cli = TcpStream::connect(&addr).await.unwrap();
+        let (_, peer) = listener.accept().await.unwrap();
+        assert_eq!(peer, cli.local_addr().unwrap());
+    }
+}
+
+#[monoio::test_all]
+async fn accept_multishot() {
+    use monoio::io::stream::Stream;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut incoming = listener.accept_multishot();
+
+    let mut clients = Vec::new();
+    for _ in 0..3 {
+        clients.push(TcpStream::connect(addr).await.unwrap());
+    }
+    for client in &clients {
+        let (stream, peer) = incoming.next().await.unwrap().unwrap();
+        assert_eq!(peer, client.local_addr().unwrap());
+        assert_eq!(stream.local_addr().unwrap(), addr);
+    }
+
+    // The listener accepts one by one again once the stream is dropped.
+    drop(incoming);
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (_, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, client.local_addr().unwrap());
+}
+
+#[monoio::test_all]
+async fn accept_addronly() {
+    use monoio::io::AsyncReadRent;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut cli = TcpStream::connect(&addr).await.unwrap();
+    let peer = listener.accept_addronly().await.unwrap();
+    assert_eq!(peer, cli.local_addr().unwrap());
+
+    // The connection was closed by the listener.
+    let (res, _) = cli.read(vec![0; 8]).await;
+    assert!(matches!(res, Ok(0) | Err(_)));
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn closed_on_peer_shutdown() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (mut conn, _) = listener.accept().await.unwrap();
+
+    // Pending data does not complete it.
+    client.write_all(&b"hello"[..]).await.0.unwrap();
+    monoio::select! {
+        _ = conn.closed() => panic!("unexpected close"),
+        _ = monoio::time::sleep(Duration::from_millis(50)) => {}
+    }
+
+    drop(client);
+    conn.closed().await.unwrap();
+
+    // The data sent before closing is still there.
+    let (res, buf) = conn.read(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+    let (res, _) = conn.read(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap(), 0);
+}
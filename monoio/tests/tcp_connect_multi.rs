@@ -0,0 +1,63 @@
+use std::{io, net::SocketAddr, time::Duration};
+
+use monoio::net::{ConnectError, TcpConnectOpts, TcpListener, TcpStream};
+
+// An address nothing listens on. The std listener is closed synchronously,
+// unlike the ones of the runtime.
+fn closed_addr() -> SocketAddr {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+fn connect_errors(e: &io::Error) -> &[(SocketAddr, io::Error)] {
+    e.get_ref()
+        .and_then(|e| e.downcast_ref::<ConnectError>())
+        .unwrap()
+        .errors()
+}
+
+#[monoio::test_all]
+async fn tries_every_address() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect(&[closed_addr(), addr][..])
+        .await
+        .unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+
+    let closed = [closed_addr(), closed_addr()];
+    let err = TcpStream::connect(&closed[..]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    let errors = connect_errors(&err);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, closed[0]);
+    assert_eq!(errors[1].0, closed[1]);
+
+    // A single address keeps its own error.
+    let err = TcpStream::connect(closed_addr()).await.unwrap_err();
+    assert!(err.get_ref().is_none());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn happy_eyeballs() {
+    let opts = TcpConnectOpts::default().happy_eyeballs(Duration::from_secs(10));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // A failed attempt starts the next one without waiting for the delay.
+    let stream = monoio::time::timeout(
+        Duration::from_secs(5),
+        TcpStream::connect_with_config(&[closed_addr(), closed_addr(), addr][..], &opts),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+
+    let err = TcpStream::connect_with_config(&[closed_addr(), closed_addr()][..], &opts)
+        .await
+        .unwrap_err();
+    assert_eq!(connect_errors(&err).len(), 2);
+}
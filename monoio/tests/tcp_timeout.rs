@@ -0,0 +1,83 @@
+use std::{future::Future, io, net::SocketAddr, pin::Pin, time::Duration};
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt},
+    net::{set_resolver, Resolver, TcpConnectOpts, TcpListener, TcpStream},
+};
+
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn read_timeout() {
+    let (mut client, mut server) = pair().await;
+    assert_eq!(client.read_timeout(), None);
+    client.set_read_timeout(Some(Duration::from_millis(50)));
+    assert_eq!(client.read_timeout(), Some(Duration::from_millis(50)));
+
+    let (res, buf) = client.read(vec![0; 16]).await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    assert_eq!(buf.capacity(), 16);
+
+    // The stream is still usable after a timeout.
+    server.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = client.read(buf).await;
+    assert_eq!(&buf[..res.unwrap()], b"hello");
+
+    client.set_read_timeout(None);
+    assert_eq!(client.read_timeout(), None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn write_timeout() {
+    let (mut client, _server) = pair().await;
+    client.set_write_timeout(Some(Duration::from_millis(50)));
+    // The peer never reads, so the socket buffers fill up.
+    let mut buf = vec![0; 1 << 20];
+    for _ in 0..256 {
+        let (res, b) = client.write(buf).await;
+        buf = b;
+        match res {
+            Ok(_) => continue,
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::TimedOut);
+                assert_eq!(buf.len(), 1 << 20);
+                return;
+            }
+        }
+    }
+    panic!("write never timed out");
+}
+
+struct Never;
+
+impl Resolver for Never {
+    fn resolve<'a>(
+        &'a self,
+        _host: &'a str,
+        _port: u16,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + 'a>> {
+        Box::pin(std::future::pending())
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn connect_timeout() {
+    set_resolver(Never);
+    let opts = TcpConnectOpts::default().timeout(Duration::from_millis(50));
+    let err = TcpStream::connect_with_config("never.invalid:80", &opts)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+    // A connection established in time is not affected.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect_with_config(addr, &opts).await.unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+}
@@ -0,0 +1,74 @@
+use monoio::time::{sleep, Deadline, Duration};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn inherit_deadline() {
+    assert!(Deadline::current().is_none());
+
+    let outer = Deadline::after(Duration::from_secs(10));
+    let res = outer
+        .scope(async move {
+            assert_eq!(Deadline::current(), Some(outer));
+
+            // A looser nested deadline does not extend the inherited one.
+            let inner = Deadline::after(Duration::from_secs(20))
+                .scope(async { Deadline::current() })
+                .await;
+            assert_eq!(inner, Ok(Some(outer)));
+
+            // A tighter one does.
+            let tight = Deadline::after(Duration::from_secs(1));
+            let inner = tight.scope(async { Deadline::current() }).await;
+            assert_eq!(inner, Ok(Some(tight)));
+
+            assert_eq!(Deadline::current(), Some(outer));
+            assert!(Deadline::check().is_ok());
+        })
+        .await;
+    assert!(res.is_ok());
+    assert!(Deadline::current().is_none());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn deadline_elapsed() {
+    let res = Deadline::after(Duration::from_millis(20))
+        .scope(sleep(Duration::from_secs(10)))
+        .await;
+    assert!(res.is_err());
+
+    let res = Deadline::after(Duration::from_millis(10))
+        .scope(async {
+            let d = Deadline::current().unwrap();
+            while !d.is_elapsed() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Deadline::check()
+        })
+        .await;
+    assert!(matches!(res, Ok(Err(_))));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn deadline_not_leaked_to_other_tasks() {
+    let (tx, rx) = local_sync::oneshot::channel();
+    let observer = monoio::spawn(async move {
+        rx.await.unwrap();
+        Deadline::current()
+    });
+    let res = Deadline::after(Duration::from_secs(10))
+        .scope(async move {
+            tx.send(()).unwrap();
+            sleep(Duration::from_millis(10)).await;
+        })
+        .await;
+    assert!(res.is_ok());
+    assert_eq!(observer.await, None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn deadline_into_inner() {
+    let scope = Deadline::after(Duration::from_millis(1)).scope(async { Deadline::current() });
+    // The unwrapped future runs without the deadline.
+    let future = scope.into_inner();
+    sleep(Duration::from_millis(5)).await;
+    assert_eq!(future.await, None);
+}
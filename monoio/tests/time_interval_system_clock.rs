@@ -0,0 +1,55 @@
+#![cfg(target_os = "linux")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use monoio::time::{Instant, Interval, MissedTickBehavior};
+
+fn wall_clock_offset(period: Duration) -> Duration {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    Duration::from_nanos((now.as_nanos() % period.as_nanos()) as u64)
+}
+
+#[monoio::test_all]
+async fn system_clock_aligned() {
+    let period = Duration::from_millis(50);
+    let mut interval = Interval::system_clock(period).unwrap();
+    assert_eq!(interval.period(), period);
+
+    for _ in 0..3 {
+        interval.tick().await;
+        assert!(wall_clock_offset(period) < Duration::from_millis(20));
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn system_clock_missed_ticks() {
+    let period = Duration::from_millis(20);
+    let mut interval = Interval::system_clock(period).unwrap();
+    interval.tick().await;
+
+    // Burst yields the missed ticks immediately.
+    monoio::time::sleep(Duration::from_millis(70)).await;
+    interval.tick().await;
+    let begin = Instant::now();
+    interval.tick().await;
+    interval.tick().await;
+    assert!(begin.elapsed() < Duration::from_millis(10));
+
+    // Skip drops them.
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval.tick().await;
+    monoio::time::sleep(Duration::from_millis(70)).await;
+    interval.tick().await;
+    let begin = Instant::now();
+    interval.tick().await;
+    assert!(begin.elapsed() >= Duration::from_millis(1));
+}
+
+#[monoio::test_all]
+async fn system_clock_try_tick() {
+    let period = Duration::from_millis(20);
+    let mut interval = Interval::system_clock(period).unwrap();
+    for _ in 0..2 {
+        interval.try_tick().await.unwrap();
+    }
+}
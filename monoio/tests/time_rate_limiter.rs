@@ -0,0 +1,45 @@
+use std::rc::Rc;
+
+use monoio::time::{Duration, Instant, RateLimiter};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn burst_then_throttle() {
+    let limiter = RateLimiter::new(10, Duration::from_millis(100)).burst(5);
+    assert_eq!(limiter.available(), 5);
+    for _ in 0..5 {
+        assert!(limiter.try_acquire(1));
+    }
+    assert!(!limiter.try_acquire(1));
+
+    // 10 permits per 100ms means 5 more tokens takes ~50ms.
+    let begin = Instant::now();
+    limiter.acquire(5).await;
+    let elapsed = begin.elapsed();
+    assert!(elapsed >= Duration::from_millis(45), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(200), "{elapsed:?}");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn shared_across_tasks() {
+    let limiter = Rc::new(RateLimiter::new(1, Duration::from_millis(20)));
+    let begin = Instant::now();
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let limiter = limiter.clone();
+            monoio::spawn(async move { limiter.acquire(1).await })
+        })
+        .collect();
+    for h in handles {
+        h.await;
+    }
+    // The first token is available immediately, the others take 20ms each.
+    assert!(begin.elapsed() >= Duration::from_millis(40));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+#[should_panic]
+async fn acquire_over_capacity() {
+    RateLimiter::new(1, Duration::from_millis(1))
+        .acquire(2)
+        .await;
+}
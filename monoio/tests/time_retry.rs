@@ -0,0 +1,55 @@
+use std::{cell::Cell, rc::Rc};
+
+use monoio::time::{retry, Duration, Instant, RetryPolicy};
+
+#[test]
+fn backoff_grows_and_caps() {
+    let policy = RetryPolicy::new()
+        .initial_backoff(Duration::from_millis(10))
+        .max_backoff(Duration::from_millis(50))
+        .multiplier(2);
+    assert_eq!(policy.backoff(1), Duration::from_millis(10));
+    assert_eq!(policy.backoff(2), Duration::from_millis(20));
+    assert_eq!(policy.backoff(3), Duration::from_millis(40));
+    assert_eq!(policy.backoff(4), Duration::from_millis(50));
+    assert_eq!(policy.backoff(100), Duration::from_millis(50));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_until_success() {
+    let policy = RetryPolicy::new()
+        .max_attempts(5)
+        .initial_backoff(Duration::from_millis(1));
+    let count = Rc::new(Cell::new(0));
+    let res: Result<u32, _> = retry(policy, || {
+        let count = count.clone();
+        async move {
+            count.set(count.get() + 1);
+            if count.get() < 3 {
+                Err(count.get())
+            } else {
+                Ok(count.get())
+            }
+        }
+    })
+    .await;
+    assert_eq!(res, Ok(3));
+    assert_eq!(count.get(), 3);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_exhausted() {
+    let policy = RetryPolicy::new()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(20))
+        .jitter(false);
+    let begin = Instant::now();
+    let err = retry(policy, || async { Err::<(), _>("boom") })
+        .await
+        .unwrap_err();
+    // 20ms + 40ms of backoff
+    assert!(begin.elapsed() >= Duration::from_millis(60));
+    assert_eq!(err.attempts(), 3);
+    assert_eq!(*err.last_error(), "boom");
+    assert_eq!(err.into_inner(), "boom");
+}
@@ -96,3 +96,95 @@ async fn cancel_recv_from() {
         }
     }
 }
+
+#[monoio::test_all]
+async fn peek_from() {
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+
+    active.connect(passive_addr).await.unwrap();
+    active.send("hello").await.0.unwrap();
+
+    assert_eq!(passive.peek_sender().await.unwrap(), active_addr);
+    let (res, buf) = passive.peek_from(Vec::with_capacity(20)).await;
+    assert_eq!(res.unwrap(), (5, active_addr));
+    assert_eq!(buf, b"hello");
+
+    // Peeking leaves the datagram in the queue.
+    let (res, buf) = passive.recv_from(Vec::with_capacity(20)).await;
+    assert_eq!(res.unwrap(), (5, active_addr));
+    assert_eq!(buf, b"hello");
+}
+
+#[monoio::test_all]
+async fn stream_and_sink() {
+    use monoio::io::{sink::Sink, stream::Stream};
+
+    let mut server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let server_addr = server.local_addr().unwrap();
+    let mut client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let client_addr = client.local_addr().unwrap();
+
+    Sink::send(&mut client, (&b"ping"[..], server_addr))
+        .await
+        .unwrap();
+    let (buf, addr) = server.next().await.unwrap().unwrap();
+    assert_eq!((&buf[..], addr), (&b"ping"[..], client_addr));
+
+    // Echo the received buffer back as is.
+    Sink::send(&mut server, (buf, addr)).await.unwrap();
+    let (buf, addr) = client.next().await.unwrap().unwrap();
+    assert_eq!((&buf[..], addr), (&b"ping"[..], server_addr));
+    assert_eq!(buf.into_vec(), b"ping");
+}
+
+#[monoio::test_all]
+async fn vectored() {
+    use monoio::buf::VecBuf;
+
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b_addr = b.local_addr().unwrap();
+
+    let buf = VecBuf::from(vec![b"hello ".to_vec(), b"vectored".to_vec()]);
+    let (res, _) = a.send_to_vectored(buf, b_addr).await;
+    assert_eq!(res.unwrap(), 14);
+
+    let (res, buf) = b
+        .recv_from_vectored(VecBuf::from(vec![vec![0; 4], vec![0; 16]]))
+        .await;
+    let (n, addr) = res.unwrap();
+    assert_eq!(n, 14);
+    assert_eq!(addr, a.local_addr().unwrap());
+    let bufs: Vec<Vec<u8>> = buf.into();
+    assert_eq!(bufs[0], b"hell");
+    assert_eq!(&bufs[1][..10], b"o vectored");
+}
+
+#[monoio::test_all]
+async fn socket_options() {
+    use std::net::Ipv4Addr;
+
+    let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+    socket.set_broadcast(true).unwrap();
+    assert!(socket.broadcast().unwrap());
+    socket.set_ttl(42).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 42);
+    socket.set_multicast_ttl_v4(3).unwrap();
+    assert_eq!(socket.multicast_ttl_v4().unwrap(), 3);
+    socket.set_multicast_loop_v4(false).unwrap();
+    assert!(!socket.multicast_loop_v4().unwrap());
+
+    let group = Ipv4Addr::new(239, 255, 0, 1);
+    // Hosts without a multicast route cannot join.
+    if socket
+        .join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+        .is_ok()
+    {
+        socket
+            .leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+            .unwrap();
+    }
+}
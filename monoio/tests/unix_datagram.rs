@@ -43,3 +43,25 @@ async fn addr_type() -> std::io::Result<()> {
     assert_eq!(_res.unwrap().1.as_pathname(), Some(sock_path1.as_path()));
     Ok(())
 }
+
+#[monoio::test_all]
+async fn stream_and_sink() -> std::io::Result<()> {
+    use monoio::io::{sink::Sink, stream::Stream};
+
+    let dir = tempfile::Builder::new()
+        .prefix("monoio-unix-datagram-tests")
+        .tempdir()
+        .unwrap();
+    let mut dgram1 = UnixDatagram::bind(dir.path().join("dgram_sink1.sock"))?;
+    let mut dgram2 = UnixDatagram::bind(dir.path().join("dgram_sink2.sock"))?;
+
+    Sink::send(&mut dgram1, (&b"hello"[..], dgram2.local_addr()?)).await?;
+    let (buf, addr) = dgram2.next().await.unwrap()?;
+    assert_eq!(&buf[..], b"hello");
+    assert_eq!(addr.as_pathname(), dgram1.local_addr()?.as_pathname());
+
+    Sink::send(&mut dgram2, (buf, addr)).await?;
+    let (buf, _) = dgram1.next().await.unwrap()?;
+    assert_eq!(&buf[..], b"hello");
+    Ok(())
+}
@@ -0,0 +1,90 @@
+#![cfg(unix)]
+use std::{
+    fs::File,
+    io::{Read, Seek, Write},
+    os::unix::io::AsRawFd,
+};
+
+use monoio::net::{UnixDatagram, UnixStream};
+
+fn temp_file(content: &[u8]) -> File {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(content).unwrap();
+    file.rewind().unwrap();
+    file
+}
+
+#[monoio::test_all]
+async fn stream_pass_fds() {
+    let (a, b) = UnixStream::pair().unwrap();
+    let first = temp_file(b"first");
+    let second = temp_file(b"second");
+
+    let (res, _) = a
+        .send_with_fds(&b"fds"[..], &[first.as_raw_fd(), second.as_raw_fd()])
+        .await;
+    assert_eq!(res.unwrap(), 3);
+
+    let (res, buf) = b.recv_with_fds(vec![0; 16], 4).await;
+    let (n, fds) = res.unwrap();
+    assert_eq!(&buf[..n], b"fds");
+    assert_eq!(fds.len(), 2);
+
+    let mut content = String::new();
+    File::from(fds.into_iter().nth(1).unwrap())
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, "second");
+
+    // Plain data comes without descriptors.
+    a.send_with_fds(&b"none"[..], &[]).await.0.unwrap();
+    let (res, _) = b.recv_with_fds(vec![0; 16], 4).await;
+    assert!(res.unwrap().1.is_empty());
+}
+
+#[monoio::test_all]
+async fn datagram_pass_fds() {
+    let (a, b) = UnixDatagram::pair().unwrap();
+    let file = temp_file(b"datagram");
+
+    a.send_with_fds(&b"x"[..], &[file.as_raw_fd()], None)
+        .await
+        .0
+        .unwrap();
+    let (res, _) = b.recv_with_fds(vec![0; 16], 1).await;
+    let (n, _, fds) = res.unwrap();
+    assert_eq!(n, 1);
+
+    let mut content = String::new();
+    File::from(fds.into_iter().next().unwrap())
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, "datagram");
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn dropped_recv_closes_fds() {
+    use std::{os::unix::io::OwnedFd, time::Duration};
+
+    let (a, b) = UnixStream::pair().unwrap();
+    let (mut reader, writer) = std::io::pipe().unwrap();
+    let mut recv = Box::pin(b.recv_with_fds(vec![0; 16], 1));
+    assert!(futures::poll!(recv.as_mut()).is_pending());
+
+    let writer = OwnedFd::from(writer);
+    a.send_with_fds(&b"x"[..], &[writer.as_raw_fd()])
+        .await
+        .0
+        .unwrap();
+    drop(writer);
+    // The recv completes, and is dropped before its result is taken.
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    drop(recv);
+
+    // The write end received is closed, so the pipe is at its end rather
+    // than empty.
+    unsafe { libc::fcntl(reader.as_raw_fd(), libc::F_SETFL, libc::O_NONBLOCK) };
+    let mut buf = [0; 1];
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+}
@@ -0,0 +1,93 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::time::Duration;
+
+use monoio::{
+    fs::{File, OpenOptions},
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+#[test]
+fn fs_ring_mixed_io() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_fs_ring(io_uring::IoUring::builder(), 64)
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = monoio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (res, buf) = stream.read_exact(vec![0; 5]).await;
+            res.unwrap();
+            stream.write_all(buf).await.0.unwrap();
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .await
+            .unwrap();
+        let (res, _) = file.write_all_at(&b"hello"[..], 0).await;
+        res.unwrap();
+        file.sync_all().await.unwrap();
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let (res, buf) = file.read_exact_at(vec![0; 5], 0).await;
+        res.unwrap();
+        stream.write_all(buf).await.0.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        assert_eq!(buf, b"hello");
+        server.await;
+
+        assert_eq!(file.metadata().await.unwrap().len(), 5);
+        file.close().await.unwrap();
+
+        monoio::time::sleep(Duration::from_millis(1)).await;
+    });
+}
+
+#[test]
+fn fs_ring_routes_file_ops() {
+    let mut urb = io_uring::IoUring::builder();
+    urb.setup_iopoll();
+    let Ok(mut rt) = RuntimeBuilder::<IoUringDriver>::new()
+        .with_fs_ring(urb, 64)
+        .build()
+    else {
+        // IOPOLL is not supported by this kernel.
+        return;
+    };
+    rt.block_on(async {
+        // Ops the IOPOLL ring can not poll stay on the main ring.
+        let mut tempfile = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut tempfile, b"hello").unwrap();
+        let file = File::open(tempfile.path()).await.unwrap();
+        assert_eq!(file.metadata().await.unwrap().len(), 5);
+
+        // Buffered reads may not be polled by the filesystem.
+        let (res, buf) = file.read_exact_at(vec![0; 5], 0).await;
+        match res {
+            Ok(()) => assert_eq!(buf, b"hello"),
+            Err(e) => assert!(
+                matches!(e.raw_os_error(), Some(libc::EOPNOTSUPP | libc::EINVAL)),
+                "{e}"
+            ),
+        }
+        file.close().await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = monoio::spawn(async move { listener.accept().await.unwrap() });
+        TcpStream::connect(addr).await.unwrap();
+        server.await;
+    });
+}
@@ -0,0 +1,32 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{io, time::Duration};
+
+use monoio::{fs::File, IoUringDriver, RuntimeBuilder};
+
+#[test]
+fn iopoll_rejects_non_polled_ops() {
+    let Ok(mut rt) = RuntimeBuilder::<IoUringDriver>::new()
+        .with_iopoll()
+        .enable_timer()
+        .build()
+    else {
+        // IOPOLL is not supported by this kernel.
+        return;
+    };
+    rt.block_on(async {
+        let tempfile = tempfile::NamedTempFile::new().unwrap();
+
+        let err = File::open(tempfile.path()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+
+        // Files opened elsewhere are usable and closed with a syscall.
+        let file = File::from_std(std::fs::File::open(tempfile.path()).unwrap()).unwrap();
+        let err = file.sync_all().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        file.close().await.unwrap();
+
+        // The parked runtime busy-polls, timers still fire.
+        monoio::time::sleep(Duration::from_millis(10)).await;
+    });
+}
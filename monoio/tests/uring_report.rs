@@ -0,0 +1,33 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use monoio::{utils::uring_report, IoUringDriver, RuntimeBuilder};
+
+#[test]
+fn report_of_built_runtime() {
+    let rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(256)
+        .build()
+        .unwrap();
+    let report = rt.uring_report().unwrap();
+    assert_eq!(report.sq_entries(), 256);
+    assert!(report.cq_entries() >= report.sq_entries());
+    assert!(report.kernel_version().unwrap() >= (5, 6, 0));
+    assert_eq!(
+        report.is_opcode_supported(io_uring::opcode::Accept::CODE),
+        Some(true)
+    );
+    assert!(report.supported_opcodes().count() > 0);
+    assert!(report.to_string().starts_with("kernel="));
+}
+
+#[monoio::test(driver = "uring")]
+async fn report_of_current_driver() {
+    let report = uring_report().unwrap();
+    assert!(!report.kernel_release().is_empty());
+}
+
+#[cfg(feature = "legacy")]
+#[monoio::test(driver = "legacy")]
+async fn no_report_with_legacy_driver() {
+    assert!(uring_report().is_none());
+}
@@ -0,0 +1,89 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+async fn echo() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let (res, buf) = stream.read(vec![0; 16]).await;
+        let n = res.unwrap();
+        stream.write_all(buf[..n].to_vec()).await.0.unwrap();
+    });
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = client.read(vec![0; 16]).await;
+    assert_eq!(&buf[..res.unwrap()], b"hello");
+    server.await;
+    monoio::time::sleep(Duration::from_millis(1)).await;
+}
+
+fn run(builder: RuntimeBuilder<IoUringDriver>) {
+    let Ok(mut rt) = builder.enable_timer().build() else {
+        // The flag is not supported by this kernel.
+        return;
+    };
+    rt.block_on(echo());
+}
+
+#[test]
+fn sqpoll() {
+    run(RuntimeBuilder::<IoUringDriver>::new().with_sqpoll(Duration::from_millis(10)));
+}
+
+#[test]
+fn sqpoll_idle() {
+    let Ok(mut rt) = RuntimeBuilder::<IoUringDriver>::new()
+        .with_sqpoll(Duration::from_millis(1))
+        .enable_timer()
+        .build()
+    else {
+        return;
+    };
+    rt.block_on(async {
+        // The kernel thread goes to sleep and is woken by the next
+        // submission.
+        monoio::time::sleep(Duration::from_millis(20)).await;
+        echo().await;
+    });
+}
+
+#[test]
+fn sqpoll_cpu() {
+    run(RuntimeBuilder::<IoUringDriver>::new()
+        .with_sqpoll(Duration::from_millis(10))
+        .with_sqpoll_cpu(0));
+}
+
+#[test]
+fn sqpoll_cpu_requires_sqpoll() {
+    assert!(RuntimeBuilder::<IoUringDriver>::new()
+        .with_sqpoll_cpu(0)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn taskrun_flags() {
+    run(RuntimeBuilder::<IoUringDriver>::new().with_coop_taskrun());
+    run(RuntimeBuilder::<IoUringDriver>::new().with_single_issuer());
+    run(RuntimeBuilder::<IoUringDriver>::new().with_defer_taskrun());
+    run(RuntimeBuilder::<IoUringDriver>::new()
+        .with_coop_taskrun()
+        .with_defer_taskrun());
+}
+
+#[test]
+fn defer_taskrun_on_thread() {
+    // The ring is created and used by the same thread.
+    std::thread::spawn(|| run(RuntimeBuilder::<IoUringDriver>::new().with_defer_taskrun()))
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,79 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::time::Duration;
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+async fn ping_pong() {
+    const ITER: usize = 128;
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    let client = monoio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        for _ in 0..ITER {
+            assert!(stream.write_all(b"ping").await.0.is_ok());
+            let (res, buf) = stream.read_exact(vec![0; 4]).await;
+            assert!(res.is_ok());
+            assert_eq!(&buf, b"pong");
+        }
+    });
+
+    let (mut stream, _) = srv.accept().await.unwrap();
+    for _ in 0..ITER {
+        let (res, buf) = stream.read_exact(vec![0; 4]).await;
+        assert!(res.is_ok());
+        assert_eq!(&buf, b"ping");
+        assert!(stream.write_all(b"pong").await.0.is_ok());
+    }
+    client.await;
+}
+
+#[test]
+fn submit_threshold() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_submit_threshold(1)
+        .build()
+        .unwrap();
+    rt.block_on(ping_pong());
+}
+
+#[test]
+fn submit_interval() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_submit_interval(Duration::from_micros(50))
+        .build()
+        .unwrap();
+    rt.block_on(ping_pong());
+}
+
+#[test]
+fn max_cqes_per_tick() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_max_cqes_per_tick(4)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = std::rc::Rc::new(monoio::fs::File::open("Cargo.toml").await.unwrap());
+        let tasks: Vec<_> = (0..64)
+            .map(|_| {
+                let file = file.clone();
+                monoio::spawn(async move { file.read_at(vec![0; 16], 0).await.0.unwrap() })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await, 16);
+        }
+
+        let stats = monoio::utils::completion_stats().unwrap();
+        assert!(stats.cqes() >= 64);
+        assert!(stats.max_per_tick() <= 4);
+        assert!(stats.capped_ticks() > 0);
+        assert!(stats.avg_per_tick() <= 4.0);
+    });
+}
@@ -0,0 +1,63 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{cell::RefCell, rc::Rc};
+
+use monoio::{IoUringDriver, RuntimeBuilder, WakeOrder};
+
+const TASKS: usize = 4;
+const ROUNDS: usize = 8;
+
+// Runs tasks whose ops complete in the same driver ticks, and returns the
+// order they ran in.
+fn run_rounds(order: WakeOrder) -> Vec<usize> {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_wake_order(order)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let handles: Vec<_> = (0..TASKS)
+            .map(|id| {
+                let log = log.clone();
+                monoio::spawn(async move {
+                    for _ in 0..ROUNDS {
+                        monoio::ping_driver().await.unwrap();
+                        log.borrow_mut().push(id);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await;
+        }
+        log.take()
+    })
+}
+
+fn counts(log: &[usize]) -> Vec<usize> {
+    (0..TASKS)
+        .map(|id| log.iter().filter(|&&i| i == id).count())
+        .collect()
+}
+
+#[test]
+fn wake_order_completion() {
+    let log = run_rounds(WakeOrder::Completion);
+    // The tasks keep running in the order they first submitted.
+    for round in log.chunks(TASKS) {
+        assert_eq!(round, [0, 1, 2, 3]);
+    }
+}
+
+#[test]
+fn wake_order_round_robin() {
+    let log = run_rounds(WakeOrder::RoundRobin);
+    assert_eq!(counts(&log), [ROUNDS; TASKS]);
+    assert!(log.chunks(TASKS).any(|round| round[0] != 0));
+}
+
+#[test]
+fn wake_order_shuffle() {
+    let log = run_rounds(WakeOrder::Shuffle);
+    assert_eq!(counts(&log), [ROUNDS; TASKS]);
+}
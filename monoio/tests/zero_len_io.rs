@@ -0,0 +1,37 @@
+#![cfg(not(feature = "lint-zero-len-io"))]
+
+use monoio::{
+    fs::File,
+    io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn zero_len_read_completes_at_once() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (mut peer, _) = listener.accept().await.unwrap();
+
+    // Nothing was sent and the peer is open, the read still returns.
+    let (res, buf) = client.read(Vec::new()).await;
+    assert_eq!(res.unwrap(), 0);
+    assert!(buf.is_empty());
+
+    peer.write_all(&b"data"[..]).await.0.unwrap();
+    let (res, buf) = client.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"data");
+}
+
+#[monoio::test_all]
+async fn zero_len_file_read() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(tempfile.path(), b"data").unwrap();
+    let file = File::open(tempfile.path()).await.unwrap();
+    let (res, _) = file.read_at(Vec::new(), 0).await;
+    assert_eq!(res.unwrap(), 0);
+    let (res, buf) = file.read_at(Vec::with_capacity(4), 0).await;
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(buf, b"data");
+}